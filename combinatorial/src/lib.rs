@@ -1,3 +1,12 @@
+//! Iterators over the combinations and permutations of a slice, yielding
+//! vectors of references into it.
+//!
+//! Every iterator counts the items it has left, so `size_hint` is exact and
+//! `len` works. When the count does not fit in a `usize`, `size_hint` is
+//! `(0, None)` so collecting does not try to reserve room for it, and `len`
+//! panics.
+
+use std::convert::TryFrom;
 use std::slice;
 
 #[derive(Debug)]
@@ -17,6 +26,166 @@ fn add_with_carry(indexes: &mut [usize], base: usize) -> (usize, usize) {
     (0, 0)
 }
 
+// Like `add_with_carry`, but with each index counting in a base of its own.
+// Returns false when the counter wraps round to zero.
+fn add_with_mixed_carry(indexes: &mut [usize], bases: &[usize]) -> bool {
+    for (index, &base) in indexes.iter_mut().zip(bases) {
+        *index = (*index + 1) % base;
+
+        if *index != 0 {
+            return true;
+        }
+    }
+
+    false
+}
+
+// Number of ways to choose `k` of `n` items, or `None` if it does not fit in a
+// usize
+fn binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+
+    // Every partial product is itself a binomial no larger than the result
+    let mut result: usize = 1;
+    for i in 0..k.min(n - k) {
+        let next = result as u128 * (n - i) as u128 / (i as u128 + 1);
+        result = usize::try_from(next).ok()?;
+    }
+
+    Some(result)
+}
+
+// Number of ordered selections of `k` of `n` items, n! / (n - k)!
+fn falling_factorial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+
+    (n - k + 1..=n).try_fold(1usize, |product, factor| product.checked_mul(factor))
+}
+
+// Number of combinations of `ascending.len()` items of `0..n` that come at or
+// after `ascending`, a strictly increasing combination, in lexicographic order
+fn lexicographic_remaining(
+    n: usize,
+    ascending: impl ExactSizeIterator<Item = usize>,
+) -> Option<usize> {
+    let size = ascending.len();
+    ascending.enumerate().try_fold(1usize, |count, (i, index)| {
+        count.checked_add(binomial(n - 1 - index, size - i)?)
+    })
+}
+
+// Position of `ascending`, a strictly increasing combination of items of
+// `0..n`, in lexicographic order, or `None` if it does not fit in a usize
+fn lexicographic_rank(n: usize, ascending: &[usize]) -> Option<usize> {
+    let mut rank: usize = 0;
+    let mut next = 0;
+    for (i, &index) in ascending.iter().enumerate() {
+        // Every combination with a smaller item in this place comes first
+        for smaller in next..index {
+            rank = rank.checked_add(binomial(n - 1 - smaller, ascending.len() - 1 - i)?)?;
+        }
+        next = index + 1;
+    }
+
+    Some(rank)
+}
+
+// Combination of `size` items of `0..n` at position `rank` in lexicographic
+// order, in increasing order, or `None` if there are not that many
+fn lexicographic_unrank(n: usize, size: usize, mut rank: usize) -> Option<Vec<usize>> {
+    let mut combination = Vec::with_capacity(size);
+    let mut next = 0;
+    for i in 0..size {
+        // Skip past the combinations with too small an item in this place
+        loop {
+            if next >= n {
+                return None;
+            }
+            match binomial(n - 1 - next, size - 1 - i) {
+                Some(count) if count <= rank => {
+                    rank -= count;
+                    next += 1;
+                }
+                _ => break,
+            }
+        }
+        combination.push(next);
+        next += 1;
+    }
+
+    if rank == 0 {
+        Some(combination)
+    } else {
+        None
+    }
+}
+
+// Number of distinct sequences of `length` items drawn from groups of equal
+// items with `counts` items each, or `None` if it does not fit in a usize
+fn multiset_permutations(counts: &[usize], length: usize) -> Option<usize> {
+    // Without equal items this is only the number of arrangements
+    if counts.iter().all(|&count| count <= 1) {
+        return falling_factorial(counts.iter().sum(), length);
+    }
+
+    // Sequences of each length using the groups seen so far. A group's items
+    // can take any of the places of a longer sequence.
+    let mut sequences = vec![Some(0usize); length + 1];
+    sequences[0] = Some(1);
+    for &count in counts {
+        let mut next = vec![Some(0usize); length + 1];
+        for (total, next) in next.iter_mut().enumerate() {
+            for taken in 0..=count.min(total) {
+                let ways = sequences[total - taken]
+                    .and_then(|sequences| sequences.checked_mul(binomial(total, taken)?));
+                *next = next.and_then(|sum| sum.checked_add(ways?));
+            }
+        }
+        sequences = next;
+    }
+
+    sequences[length]
+}
+
+fn exact_size_hint(remaining: Option<usize>) -> (usize, Option<usize>) {
+    match remaining {
+        Some(remaining) => (remaining, Some(remaining)),
+        // Any lower bound this large would only make `collect` fail to
+        // reserve it up front
+        None => (0, None),
+    }
+}
+
+fn subtract_with_borrow(indexes: &mut [usize], base: usize) {
+    for index in indexes.iter_mut() {
+        if *index > 0 {
+            *index -= 1;
+            return;
+        }
+        *index = base - 1;
+    }
+}
+
+// Values a base-n counter takes from `indexes` to its last value, inclusive,
+// or `None` if they do not fit in a usize
+fn count_to_last(indexes: &[usize], base: usize) -> Option<usize> {
+    let mut weight = Some(1usize);
+    let mut count: usize = 1;
+    for &index in indexes {
+        let digit = base - 1 - index;
+        if digit > 0 {
+            count = count.checked_add(digit.checked_mul(weight?)?)?;
+        }
+        weight = weight.and_then(|weight| weight.checked_mul(base));
+    }
+
+    Some(count)
+}
+
 fn swizzle<'a, T>(source: &'a [T], indexes: &[usize]) -> Vec<&'a T> {
     let mut result = Vec::new();
 
@@ -27,11 +196,54 @@ fn swizzle<'a, T>(source: &'a [T], indexes: &[usize]) -> Vec<&'a T> {
     result
 }
 
+/// Adapter yielding each item of a combinatorial iterator as a `Vec<T>` of
+/// cloned elements, made by the `cloned` method of each iterator. Elements
+/// are cloned as each item is yielded, so the source is never copied.
+pub struct ClonedItems<I> {
+    iter: I,
+}
+
+impl<'a, T, I> std::iter::Iterator for ClonedItems<I>
+where
+    T: Clone + 'a,
+    I: Iterator<Item = std::vec::Vec<&'a T>>,
+{
+    type Item = std::vec::Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|items| items.into_iter().cloned().collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T, I> ExactSizeIterator for ClonedItems<I>
+where
+    T: Clone + 'a,
+    I: ExactSizeIterator<Item = std::vec::Vec<&'a T>>,
+{
+}
+
 /// Generates General Permutations from a given slice. (Unordered with Duplicates)
+///
+/// The positions of the elements in each item count in base n with the first
+/// position least significant, so from `new` they come in lexicographic order
+/// of the reversed positions: `[0, 0], [1, 0], [0, 1], [1, 1]`. From
+/// `new_lexicographic` the first position is the most significant, and the
+/// positions come in plain lexicographic order: `[0, 0], [0, 1], [1, 0],
+/// [1, 1]`.
 pub struct GeneralPermutationsIterator<'a, T> {
     source: &'a [T],
     indexes: std::vec::Vec<usize>,
-    modified: bool,
+    // Counter of the last permutation not yet yielded from the back
+    back: std::vec::Vec<usize>,
+    consumed: bool,
+    // Whether each item reads the counter from its most significant index
+    lexicographic: bool,
 }
 
 impl<'a, T> GeneralPermutationsIterator<'a, T> {
@@ -40,39 +252,168 @@ impl<'a, T> GeneralPermutationsIterator<'a, T> {
         source: &'a [T],
         output_size: usize,
     ) -> Result<GeneralPermutationsIterator<'a, T>, CombinatorialError> {
-        if source.len() == 0 && output_size > 0 {
+        if source.is_empty() && output_size > 0 {
             return Err(CombinatorialError::OutputSizeTooLarge);
         }
 
         Ok(GeneralPermutationsIterator {
             source,
             indexes: vec![0; output_size],
-            modified: false,
+            back: vec![source.len().saturating_sub(1); output_size],
+            consumed: false,
+            lexicographic: false,
         })
     }
+
+    /// Like `new`, but with the first position of each item the most
+    /// significant, so the positions come in lexicographic order.
+    pub fn new_lexicographic(
+        source: &'a [T],
+        output_size: usize,
+    ) -> Result<GeneralPermutationsIterator<'a, T>, CombinatorialError> {
+        let mut iter = GeneralPermutationsIterator::new(source, output_size)?;
+        iter.lexicographic = true;
+
+        Ok(iter)
+    }
+
+    /// Yields owned clones of the elements instead of references to them.
+    pub fn cloned(self) -> ClonedItems<Self>
+    where
+        T: Clone,
+    {
+        ClonedItems { iter: self }
+    }
+
+    // Permutations left to yield, from the front counter to the back one
+    fn remaining(&self) -> Option<usize> {
+        if self.consumed {
+            return Some(0);
+        }
+
+        let base = self.source.len();
+        let from_front = count_to_last(&self.indexes, base)?;
+        Some(from_front - count_to_last(&self.back, base)? + 1)
+    }
+
+    /// Moves to the permutation `rank` places after the first, so that it is
+    /// yielded next. The indexes are a base-n counter, so this only converts
+    /// `rank` to base n. A rank past
+    /// the last permutation, or past one already yielded from the back,
+    /// leaves the iterator exhausted.
+    pub fn seek(&mut self, rank: usize) {
+        let base = self.source.len();
+        let mut rest = rank;
+        let mut indexes = vec![0; self.indexes.len()];
+        for index in indexes.iter_mut() {
+            *index = rest % base;
+            rest /= base;
+        }
+
+        if rest > 0 || indexes.iter().rev().gt(self.back.iter().rev()) {
+            self.consumed = true;
+        } else {
+            self.indexes = indexes;
+            self.consumed = false;
+        }
+    }
+
+    // Position of the next permutation, or `None` once exhausted
+    fn rank(&self) -> Option<usize> {
+        if self.consumed {
+            return None;
+        }
+
+        let base = self.source.len();
+        let mut weight = Some(1usize);
+        let mut rank: usize = 0;
+        for &index in &self.indexes {
+            if index > 0 {
+                rank = rank.checked_add(index.checked_mul(weight?)?)?;
+            }
+            weight = weight.and_then(|weight| weight.checked_mul(base));
+        }
+
+        Some(rank)
+    }
+
+    // Elements at the positions a counter stands for
+    fn permutation(&self, indexes: &[usize]) -> Vec<&'a T> {
+        if self.lexicographic {
+            indexes
+                .iter()
+                .rev()
+                .map(|&index| &self.source[index])
+                .collect()
+        } else {
+            swizzle(self.source, indexes)
+        }
+    }
 }
 
 impl<'a, T> std::iter::Iterator for GeneralPermutationsIterator<'a, T> {
     type Item = std::vec::Vec<&'a T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.indexes.iter().all(|&x| x == 0) && self.modified {
+        if self.consumed {
             None
         } else {
-            let result = swizzle(self.source, &self.indexes);
+            let result = self.permutation(&self.indexes);
 
-            add_with_carry(&mut self.indexes, self.source.len());
-            self.modified = true;
+            if self.indexes == self.back {
+                self.consumed = true;
+            } else {
+                add_with_carry(&mut self.indexes, self.source.len());
+            }
 
             Some(result)
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact_size_hint(self.remaining())
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.rank().and_then(|rank| rank.checked_add(n)) {
+            Some(rank) => self.seek(rank),
+            None => self.consumed = true,
+        }
+
+        self.next()
+    }
 }
 
+impl<'a, T> DoubleEndedIterator for GeneralPermutationsIterator<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.consumed {
+            None
+        } else {
+            let result = self.permutation(&self.back);
+
+            if self.indexes == self.back {
+                self.consumed = true;
+            } else {
+                subtract_with_borrow(&mut self.back, self.source.len());
+            }
+
+            Some(result)
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for GeneralPermutationsIterator<'a, T> {}
+
 /// Generates Simple Combinations from a given slice. (Ordered without Duplicates)
+///
+/// The positions of the elements in each item increase, and the items come in
+/// reverse lexicographic order of them, starting from the last `output_size`
+/// elements: `[2, 3], [1, 3], [1, 2], [0, 3], [0, 2], [0, 1]`.
 pub struct SimpleCombinationsIterator<'a, T> {
     source: &'a [T],
     skip: std::vec::Vec<usize>,
+    // Skip holes of the last combination not yet yielded from the back
+    back: std::vec::Vec<usize>,
     consumed: bool,
 }
 
@@ -88,17 +429,28 @@ impl<'a, T> SimpleCombinationsIterator<'a, T> {
 
         let delta = source.len() - output_size;
         let mut skip = Vec::new();
+        let mut back = Vec::new();
         for i in 0..delta {
             skip.push(delta - i - 1);
+            back.push(source.len() - i - 1);
         }
 
         Ok(SimpleCombinationsIterator {
             source,
             skip,
+            back,
             consumed: false,
         })
     }
 
+    /// Yields owned clones of the elements instead of references to them.
+    pub fn cloned(self) -> ClonedItems<Self>
+    where
+        T: Clone,
+    {
+        ClonedItems { iter: self }
+    }
+
     fn increment_skip_hole(&mut self) -> Option<(usize, usize)> {
         for (i, index) in self.skip.iter_mut().enumerate() {
             if *index == self.source.len() - 1 - i {
@@ -111,8 +463,61 @@ impl<'a, T> SimpleCombinationsIterator<'a, T> {
         None
     }
 
-    fn generate_result(&self) -> std::vec::Vec<&'a T> {
-        let mut skip_iter = self.skip.iter().rev().peekable();
+    // Moves the back skip holes to the combination before them
+    fn decrement_skip_hole(&mut self) {
+        let n = self.source.len();
+        for i in 0..self.back.len() {
+            let lowest = self.back.get(i + 1).map_or(0, |&next| next + 1);
+            if self.back[i] > lowest {
+                self.back[i] -= 1;
+                for (j, index) in self.back.iter_mut().take(i).enumerate() {
+                    *index = n - 1 - j;
+                }
+                return;
+            }
+        }
+    }
+
+    // Combinations left to yield, from the front skip holes to the back ones.
+    // The skip holes run through the combinations of
+    // `source.len() - output_size` holes in lexicographic order.
+    fn remaining(&self) -> Option<usize> {
+        if self.consumed {
+            return Some(0);
+        }
+
+        let n = self.source.len();
+        let from_front = lexicographic_remaining(n, self.skip.iter().rev().copied())?;
+        Some(from_front - lexicographic_remaining(n, self.back.iter().rev().copied())? + 1)
+    }
+
+    /// Moves to the combination `rank` places after the first, so that it is
+    /// yielded next, working out the skip holes from the combinatorial number
+    /// system. A rank past the last combination, or past one already yielded
+    /// from the back, leaves the iterator exhausted.
+    pub fn seek(&mut self, rank: usize) {
+        let holes = self.skip.len();
+        match lexicographic_unrank(self.source.len(), holes, rank) {
+            Some(ascending) if ascending.iter().le(self.back.iter().rev()) => {
+                self.skip = ascending.into_iter().rev().collect();
+                self.consumed = false;
+            }
+            _ => self.consumed = true,
+        }
+    }
+
+    // Position of the next combination, or `None` once exhausted
+    fn rank(&self) -> Option<usize> {
+        if self.consumed {
+            return None;
+        }
+
+        let ascending: Vec<usize> = self.skip.iter().rev().copied().collect();
+        lexicographic_rank(self.source.len(), &ascending)
+    }
+
+    fn generate_result(&self, skip: &[usize]) -> std::vec::Vec<&'a T> {
+        let mut skip_iter = skip.iter().rev().peekable();
         self.source
             .iter()
             .enumerate()
@@ -135,23 +540,153 @@ impl<'a, T> std::iter::Iterator for SimpleCombinationsIterator<'a, T> {
         if self.consumed {
             None
         } else {
-            let result = self.generate_result();
+            let result = self.generate_result(&self.skip);
 
-            match self.increment_skip_hole() {
-                Some((i, skip)) => {
-                    for (j, index) in self.skip.iter_mut().take(i).enumerate() {
-                        *index = skip + (i - j);
+            if self.skip == self.back {
+                self.consumed = true;
+            } else {
+                match self.increment_skip_hole() {
+                    Some((i, skip)) => {
+                        for (j, index) in self.skip.iter_mut().take(i).enumerate() {
+                            *index = skip + (i - j);
+                        }
                     }
+                    None => self.consumed = true,
                 }
-                None => self.consumed = true,
             }
 
             Some(result)
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact_size_hint(self.remaining())
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.rank().and_then(|rank| rank.checked_add(n)) {
+            Some(rank) => self.seek(rank),
+            None => self.consumed = true,
+        }
+
+        self.next()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for SimpleCombinationsIterator<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.consumed {
+            None
+        } else {
+            let result = self.generate_result(&self.back);
+
+            if self.skip == self.back {
+                self.consumed = true;
+            } else {
+                self.decrement_skip_hole();
+            }
+
+            Some(result)
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for SimpleCombinationsIterator<'a, T> {}
+
+// Sequence of groups of equal items, stepped through in lexicographic order,
+// with each group used at most as many times as it has items
+struct GroupPermutation {
+    // Items of each group not used by the current permutation
+    available: std::vec::Vec<usize>,
+    // Group of each item of the current permutation
+    groups: std::vec::Vec<usize>,
+    consumed: bool,
+}
+
+impl GroupPermutation {
+    // The first permutation of `output_size` items, which must be no more
+    // than the items available
+    fn new(available: std::vec::Vec<usize>, output_size: usize) -> GroupPermutation {
+        let mut permutation = GroupPermutation {
+            available,
+            groups: Vec::with_capacity(output_size),
+            consumed: false,
+        };
+        permutation.fill_smallest(output_size);
+
+        permutation
+    }
+
+    // Appends the smallest available groups until the permutation is
+    // `output_size` long
+    fn fill_smallest(&mut self, output_size: usize) {
+        let mut group = 0;
+        while self.groups.len() < output_size {
+            if self.available[group] > 0 {
+                self.available[group] -= 1;
+                self.groups.push(group);
+            } else {
+                group += 1;
+            }
+        }
+    }
+
+    // Moves to the next permutation: the last place that can take a larger
+    // available group takes the smallest one, and the places after it the
+    // smallest groups left. After the last permutation this is consumed.
+    fn increment(&mut self) {
+        let output_size = self.groups.len();
+        while let Some(group) = self.groups.pop() {
+            self.available[group] += 1;
+
+            let larger = (group + 1..self.available.len()).find(|&g| self.available[g] > 0);
+            if let Some(larger) = larger {
+                self.available[larger] -= 1;
+                self.groups.push(larger);
+                self.fill_smallest(output_size);
+                return;
+            }
+        }
+
+        self.consumed = true;
+    }
+
+    // Permutations left to yield: the current one, and for each place every
+    // permutation sharing the places before it with a larger group there
+    fn remaining(&self) -> Option<usize> {
+        if self.consumed {
+            return Some(0);
+        }
+
+        let mut available = self.available.clone();
+        for &group in &self.groups {
+            available[group] += 1;
+        }
+
+        let mut remaining: usize = 1;
+        for (i, &group) in self.groups.iter().enumerate() {
+            for larger in group + 1..available.len() {
+                if available[larger] > 0 {
+                    available[larger] -= 1;
+                    let rest = multiset_permutations(&available, self.groups.len() - i - 1);
+                    available[larger] += 1;
+                    remaining = remaining.checked_add(rest?)?;
+                }
+            }
+            available[group] -= 1;
+        }
+
+        Some(remaining)
+    }
 }
 
 /// Generates Simple Permutations from a given slice. (Unordered without Duplicates)
+///
+/// From `new`, each combination of `SimpleCombinationsIterator` is yielded in
+/// turn with its permutations in the order of Heap's algorithm, which makes
+/// each item from the last with one swap but follows no simpler order. From
+/// `new_lexicographic`, the positions of the elements in each item come in
+/// strict lexicographic order instead, at the cost of more work per item.
 pub struct SimplePermutationsIterator<'a, T> {
     source: &'a [T],
     seed_iter: SimpleCombinationsIterator<'a, usize>,
@@ -159,6 +694,9 @@ pub struct SimplePermutationsIterator<'a, T> {
     indexes: Vec<usize>,
     counters: Vec<usize>,
     current_output: usize,
+    // Positions of the current permutation when in lexicographic order, each
+    // position a group of one
+    lexicographic: Option<GroupPermutation>,
 }
 
 impl<'a, T> SimplePermutationsIterator<'a, T> {
@@ -186,9 +724,59 @@ impl<'a, T> SimplePermutationsIterator<'a, T> {
             indexes: vec![0; output_size],
             counters: vec![0; output_size],
             current_output: output_size,
+            lexicographic: None,
         })
     }
 
+    /// Like `new`, but yields the permutations in lexicographic order of the
+    /// positions of their elements, so for a sorted source in sorted order.
+    pub fn new_lexicographic(
+        source: &'a [T],
+        output_size: usize,
+    ) -> Result<SimplePermutationsIterator<'a, T>, CombinatorialError> {
+        let mut iter = SimplePermutationsIterator::new(source, output_size)?;
+        iter.lexicographic = Some(GroupPermutation::new(vec![1; source.len()], output_size));
+
+        Ok(iter)
+    }
+
+    /// Yields owned clones of the elements instead of references to them.
+    pub fn cloned(self) -> ClonedItems<Self>
+    where
+        T: Clone,
+    {
+        ClonedItems { iter: self }
+    }
+
+    // Permutations left to yield, of the current seed and of the seeds to come
+    fn remaining(&self) -> Option<usize> {
+        if let Some(permutation) = &self.lexicographic {
+            return permutation.remaining();
+        }
+
+        let output_size = self.counters.len();
+        let per_seed = falling_factorial(output_size, output_size)?;
+
+        // Between permutations of a seed the counters hold, in the factorial
+        // number system, how many have been yielded after the first
+        let in_seed = if self.current_output < output_size {
+            let yielded = self.counters.iter().enumerate().skip(1).try_fold(
+                1usize,
+                |yielded, (i, &counter)| {
+                    yielded.checked_add(counter.checked_mul(falling_factorial(i, i)?)?)
+                },
+            )?;
+            per_seed - yielded
+        } else {
+            0
+        };
+
+        self.seed_iter
+            .remaining()?
+            .checked_mul(per_seed)?
+            .checked_add(in_seed)
+    }
+
     fn try_gen_permutation(&mut self) -> Option<std::vec::Vec<&'a T>> {
         if self.counters[self.current_output] < self.current_output {
             // Swizzle indexes to transition permutation
@@ -232,6 +820,16 @@ impl<'a, T> std::iter::Iterator for SimplePermutationsIterator<'a, T> {
     type Item = std::vec::Vec<&'a T>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(permutation) = &mut self.lexicographic {
+            if permutation.consumed {
+                return None;
+            }
+
+            let result = swizzle(self.source, &permutation.groups);
+            permutation.increment();
+            return Some(result);
+        }
+
         while self.current_output < self.counters.len() {
             if let Some(result) = self.try_gen_permutation() {
                 return Some(result);
@@ -239,9 +837,102 @@ impl<'a, T> std::iter::Iterator for SimplePermutationsIterator<'a, T> {
         }
         self.try_next_seed()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact_size_hint(self.remaining())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for SimplePermutationsIterator<'a, T> {}
+
+/// Generates the distinct Simple Permutations of a given slice, treating
+/// equal elements as the same. (Unordered without Duplicates)
+///
+/// Permutations come in lexicographic order, with each value ranked by where
+/// it first appears in the source, so for a sorted source this is plain
+/// lexicographic order. Each item refers to the first of its equal elements.
+pub struct DistinctPermutationsIterator<'a, T> {
+    source: &'a [T],
+    // Index of the first element of each group of equal elements, in order of
+    // first appearance
+    representatives: std::vec::Vec<usize>,
+    permutation: GroupPermutation,
+}
+
+impl<'a, T: Eq> DistinctPermutationsIterator<'a, T> {
+    #[warn(clippy::new_ret_no_self)]
+    pub fn new(
+        source: &'a [T],
+        output_size: usize,
+    ) -> Result<DistinctPermutationsIterator<'a, T>, CombinatorialError> {
+        if output_size > source.len() {
+            return Err(CombinatorialError::OutputSizeTooLarge);
+        }
+
+        let mut representatives: Vec<usize> = Vec::new();
+        let mut available = Vec::new();
+        for (i, item) in source.iter().enumerate() {
+            match representatives
+                .iter()
+                .position(|&first| source[first] == *item)
+            {
+                Some(group) => available[group] += 1,
+                None => {
+                    representatives.push(i);
+                    available.push(1);
+                }
+            }
+        }
+
+        Ok(DistinctPermutationsIterator {
+            source,
+            representatives,
+            permutation: GroupPermutation::new(available, output_size),
+        })
+    }
+}
+
+impl<'a, T> DistinctPermutationsIterator<'a, T> {
+    /// Yields owned clones of the elements instead of references to them.
+    pub fn cloned(self) -> ClonedItems<Self>
+    where
+        T: Clone,
+    {
+        ClonedItems { iter: self }
+    }
+}
+
+impl<'a, T> std::iter::Iterator for DistinctPermutationsIterator<'a, T> {
+    type Item = std::vec::Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.permutation.consumed {
+            None
+        } else {
+            let result = self
+                .permutation
+                .groups
+                .iter()
+                .map(|&group| &self.source[self.representatives[group]])
+                .collect();
+            self.permutation.increment();
+
+            Some(result)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact_size_hint(self.permutation.remaining())
+    }
 }
 
+impl<'a, T> ExactSizeIterator for DistinctPermutationsIterator<'a, T> {}
+
 /// Generates General Combinations from a given slice. (Ordered with Duplicates)
+///
+/// The positions of the elements in each item never increase, and the items
+/// come in lexicographic order of the reversed positions: `[0, 0], [1, 0],
+/// [2, 0], [1, 1], [2, 1], [2, 2]`.
 pub struct GeneralCombinationsIterator<'a, T> {
     source: &'a [T],
     indexes: std::vec::Vec<usize>,
@@ -254,7 +945,7 @@ impl<'a, T> GeneralCombinationsIterator<'a, T> {
         source: &'a [T],
         output_size: usize,
     ) -> Result<GeneralCombinationsIterator<'a, T>, CombinatorialError> {
-        if source.len() == 0 && output_size > 0 {
+        if source.is_empty() && output_size > 0 {
             return Err(CombinatorialError::OutputSizeTooLarge);
         }
 
@@ -264,6 +955,34 @@ impl<'a, T> GeneralCombinationsIterator<'a, T> {
             modified: false,
         })
     }
+
+    /// Yields owned clones of the elements instead of references to them.
+    pub fn cloned(self) -> ClonedItems<Self>
+    where
+        T: Clone,
+    {
+        ClonedItems { iter: self }
+    }
+
+    // Combinations left to yield. Read from the last index to the first the
+    // indexes never decrease, so adding each one's position turns them into
+    // a combination of `source.len() + output_size - 1` items without
+    // duplicates, in lexicographic order.
+    fn remaining(&self) -> Option<usize> {
+        if self.modified && self.indexes.iter().all(|&x| x == 0) {
+            return Some(0);
+        }
+
+        let items = (self.source.len() + self.indexes.len()).saturating_sub(1);
+        lexicographic_remaining(
+            items,
+            self.indexes
+                .iter()
+                .rev()
+                .enumerate()
+                .map(|(position, &index)| index + position),
+        )
+    }
 }
 
 impl<'a, T> std::iter::Iterator for GeneralCombinationsIterator<'a, T> {
@@ -285,12 +1004,103 @@ impl<'a, T> std::iter::Iterator for GeneralCombinationsIterator<'a, T> {
             Some(result)
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact_size_hint(self.remaining())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for GeneralCombinationsIterator<'a, T> {}
+
+/// Generates the Cartesian Product of several slices, drawing one element
+/// from each in the order the slices are given.
+///
+/// The indexes count like those of `GeneralPermutationsIterator`, each in
+/// the base of its own slice, with the first slice changing fastest. The
+/// product of no slices is a single empty Vec, and the product with an
+/// empty slice is empty.
+pub struct CartesianProductIterator<'a, T> {
+    sources: std::vec::Vec<&'a [T]>,
+    bases: std::vec::Vec<usize>,
+    indexes: std::vec::Vec<usize>,
+    consumed: bool,
 }
 
-/// Generates all Subsets from a given slice.
+impl<'a, T> CartesianProductIterator<'a, T> {
+    #[warn(clippy::new_ret_no_self)]
+    pub fn new(sources: &[&'a [T]]) -> CartesianProductIterator<'a, T> {
+        CartesianProductIterator {
+            sources: sources.to_vec(),
+            bases: sources.iter().map(|source| source.len()).collect(),
+            indexes: vec![0; sources.len()],
+            consumed: sources.iter().any(|source| source.is_empty()),
+        }
+    }
+
+    /// Yields owned clones of the elements instead of references to them.
+    pub fn cloned(self) -> ClonedItems<Self>
+    where
+        T: Clone,
+    {
+        ClonedItems { iter: self }
+    }
+
+    // Tuples left to yield, counting down the mixed-radix counter to its last
+    // value
+    fn remaining(&self) -> Option<usize> {
+        if self.consumed {
+            return Some(0);
+        }
+
+        let mut weight = Some(1usize);
+        let mut remaining: usize = 1;
+        for (&index, &base) in self.indexes.iter().zip(&self.bases) {
+            let digit = base - 1 - index;
+            if digit > 0 {
+                remaining = remaining.checked_add(digit.checked_mul(weight?)?)?;
+            }
+            weight = weight.and_then(|weight| weight.checked_mul(base));
+        }
+
+        Some(remaining)
+    }
+}
+
+impl<'a, T> std::iter::Iterator for CartesianProductIterator<'a, T> {
+    type Item = std::vec::Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.consumed {
+            None
+        } else {
+            let result = self
+                .sources
+                .iter()
+                .zip(&self.indexes)
+                .map(|(source, &index)| &source[index])
+                .collect();
+
+            if !add_with_mixed_carry(&mut self.indexes, &self.bases) {
+                self.consumed = true;
+            }
+
+            Some(result)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact_size_hint(self.remaining())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for CartesianProductIterator<'a, T> {}
+
+/// Generates all Subsets from a given slice, or those with a range of sizes, in
+/// order of size. The subsets of each size come in the order of
+/// `SimpleCombinationsIterator`.
 pub struct SubsetIterator<'a, T> {
     source: &'a [T],
-    combinations_iter: SimpleCombinationsIterator<'a, T>,
+    combinations_iter: Option<SimpleCombinationsIterator<'a, T>>,
     sizes: std::ops::RangeInclusive<usize>,
 }
 
@@ -298,35 +1108,85 @@ impl<'a, T> std::iter::Iterator for SubsetIterator<'a, T> {
     type Item = std::vec::Vec<&'a T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.combinations_iter.next() {
+        match self.combinations_iter.as_mut().and_then(|iter| iter.next()) {
             Some(x) => Some(x),
             None => match self.sizes.next() {
-                Some(s) => {
-                    self.combinations_iter =
-                        SimpleCombinationsIterator::new(self.source, s).unwrap();
-                    self.combinations_iter.next()
-                }
+                Some(s) => self
+                    .combinations_iter
+                    .insert(SimpleCombinationsIterator::new(self.source, s).unwrap())
+                    .next(),
                 None => None,
             },
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact_size_hint(self.remaining())
+    }
 }
 
+impl<'a, T> ExactSizeIterator for SubsetIterator<'a, T> {}
+
 impl<'a, T> SubsetIterator<'a, T> {
     #[warn(clippy::new_ret_no_self)]
     pub fn new(source: &'a [T]) -> SubsetIterator<'a, T> {
+        SubsetIterator::power_set(source)
+    }
+
+    /// Every subset, from the empty one to the whole slice. The same as
+    /// `new`.
+    pub fn power_set(source: &'a [T]) -> SubsetIterator<'a, T> {
         SubsetIterator {
             source,
-            combinations_iter: SimpleCombinationsIterator::new(source, 0).unwrap(),
-            sizes: 1..=source.len(),
+            combinations_iter: None,
+            sizes: 0..=source.len(),
         }
     }
+
+    /// The subsets with sizes in `sizes`, so unwanted sizes are never
+    /// generated.
+    pub fn with_sizes(
+        source: &'a [T],
+        sizes: std::ops::RangeInclusive<usize>,
+    ) -> Result<SubsetIterator<'a, T>, CombinatorialError> {
+        if *sizes.end() > source.len() {
+            return Err(CombinatorialError::OutputSizeTooLarge);
+        }
+
+        Ok(SubsetIterator {
+            source,
+            combinations_iter: None,
+            sizes,
+        })
+    }
+
+    /// Yields owned clones of the elements instead of references to them.
+    pub fn cloned(self) -> ClonedItems<Self>
+    where
+        T: Clone,
+    {
+        ClonedItems { iter: self }
+    }
+
+    // Items left of the current size and of the sizes to come
+    fn remaining(&self) -> Option<usize> {
+        let n = self.source.len();
+        let current = self
+            .combinations_iter
+            .as_ref()
+            .map_or(Some(0), |iter| iter.remaining());
+        self.sizes.clone().try_fold(current?, |remaining, size| {
+            remaining.checked_add(binomial(n, size)?)
+        })
+    }
 }
 
-/// Generates all Sublists from a given slice.
+/// Generates all Sublists from a given slice, or those with a range of sizes, in
+/// order of size. The sublists of each size come in the order of
+/// `SimplePermutationsIterator::new`.
 pub struct SublistIterator<'a, T> {
     source: &'a [T],
-    combinations_iter: SimplePermutationsIterator<'a, T>,
+    combinations_iter: Option<SimplePermutationsIterator<'a, T>>,
     sizes: std::ops::RangeInclusive<usize>,
 }
 
@@ -334,27 +1194,75 @@ impl<'a, T> std::iter::Iterator for SublistIterator<'a, T> {
     type Item = std::vec::Vec<&'a T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.combinations_iter.next() {
+        match self.combinations_iter.as_mut().and_then(|iter| iter.next()) {
             Some(x) => Some(x),
             None => match self.sizes.next() {
-                Some(s) => {
-                    self.combinations_iter =
-                        SimplePermutationsIterator::new(self.source, s).unwrap();
-                    self.combinations_iter.next()
-                }
+                Some(s) => self
+                    .combinations_iter
+                    .insert(SimplePermutationsIterator::new(self.source, s).unwrap())
+                    .next(),
                 None => None,
             },
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        exact_size_hint(self.remaining())
+    }
 }
 
+impl<'a, T> ExactSizeIterator for SublistIterator<'a, T> {}
+
 impl<'a, T> SublistIterator<'a, T> {
     #[warn(clippy::new_ret_no_self)]
     pub fn new(source: &'a [T]) -> SublistIterator<'a, T> {
+        SublistIterator::power_set(source)
+    }
+
+    /// Every sublist, from the empty one to the whole slice. The same as
+    /// `new`.
+    pub fn power_set(source: &'a [T]) -> SublistIterator<'a, T> {
         SublistIterator {
             source,
-            combinations_iter: SimplePermutationsIterator::new(source, 0).unwrap(),
-            sizes: 1..=source.len(),
+            combinations_iter: None,
+            sizes: 0..=source.len(),
         }
     }
+
+    /// The sublists with sizes in `sizes`, so unwanted sizes are never
+    /// generated.
+    pub fn with_sizes(
+        source: &'a [T],
+        sizes: std::ops::RangeInclusive<usize>,
+    ) -> Result<SublistIterator<'a, T>, CombinatorialError> {
+        if *sizes.end() > source.len() {
+            return Err(CombinatorialError::OutputSizeTooLarge);
+        }
+
+        Ok(SublistIterator {
+            source,
+            combinations_iter: None,
+            sizes,
+        })
+    }
+
+    /// Yields owned clones of the elements instead of references to them.
+    pub fn cloned(self) -> ClonedItems<Self>
+    where
+        T: Clone,
+    {
+        ClonedItems { iter: self }
+    }
+
+    // Items left of the current size and of the sizes to come
+    fn remaining(&self) -> Option<usize> {
+        let n = self.source.len();
+        let current = self
+            .combinations_iter
+            .as_ref()
+            .map_or(Some(0), |iter| iter.remaining());
+        self.sizes.clone().try_fold(current?, |remaining, size| {
+            remaining.checked_add(falling_factorial(n, size)?)
+        })
+    }
 }
@@ -0,0 +1,103 @@
+extern crate combinatorial;
+
+use combinatorial::{
+    GeneralCombinationsIterator, GeneralPermutationsIterator, SimpleCombinationsIterator,
+    SimplePermutationsIterator, SublistIterator, SubsetIterator,
+};
+
+fn words() -> Vec<String> {
+    ["red", "green", "blue", "grey"]
+        .iter()
+        .map(|word| word.to_string())
+        .collect()
+}
+
+fn owned(items: Vec<&String>) -> Vec<String> {
+    items.into_iter().cloned().collect()
+}
+
+#[test]
+fn cloned_matches_references() {
+    let source = words();
+
+    let expected: Vec<_> = GeneralPermutationsIterator::new(&source, 2)
+        .unwrap()
+        .map(owned)
+        .collect();
+    let cloned: Vec<Vec<String>> = GeneralPermutationsIterator::new(&source, 2)
+        .unwrap()
+        .cloned()
+        .collect();
+    assert_eq!(cloned, expected);
+
+    let expected: Vec<_> = SimpleCombinationsIterator::new(&source, 2)
+        .unwrap()
+        .map(owned)
+        .collect();
+    let cloned: Vec<_> = SimpleCombinationsIterator::new(&source, 2)
+        .unwrap()
+        .cloned()
+        .collect();
+    assert_eq!(cloned, expected);
+
+    let expected: Vec<_> = SimplePermutationsIterator::new(&source, 3)
+        .unwrap()
+        .map(owned)
+        .collect();
+    let cloned: Vec<_> = SimplePermutationsIterator::new(&source, 3)
+        .unwrap()
+        .cloned()
+        .collect();
+    assert_eq!(cloned, expected);
+
+    let expected: Vec<_> = GeneralCombinationsIterator::new(&source, 3)
+        .unwrap()
+        .map(owned)
+        .collect();
+    let cloned: Vec<_> = GeneralCombinationsIterator::new(&source, 3)
+        .unwrap()
+        .cloned()
+        .collect();
+    assert_eq!(cloned, expected);
+
+    let expected: Vec<_> = SubsetIterator::new(&source).map(owned).collect();
+    let cloned: Vec<_> = SubsetIterator::new(&source).cloned().collect();
+    assert_eq!(cloned, expected);
+
+    let expected: Vec<_> = SublistIterator::new(&source).map(owned).collect();
+    let cloned: Vec<_> = SublistIterator::new(&source).cloned().collect();
+    assert_eq!(cloned, expected);
+}
+
+#[test]
+fn cloned_items_outlive_the_source() {
+    let combinations: Vec<Vec<String>> = {
+        let source = words();
+        SimpleCombinationsIterator::new(&source, 3)
+            .unwrap()
+            .cloned()
+            .collect()
+    };
+
+    let counted = std::thread::spawn(move || combinations.len())
+        .join()
+        .unwrap();
+    assert_eq!(counted, 4);
+}
+
+#[test]
+fn cloned_keeps_the_size_hint() {
+    let source = words();
+    let mut sublists = SublistIterator::new(&source).cloned();
+    let total = SublistIterator::new(&source).len();
+    assert_eq!(sublists.len(), total);
+    sublists.next();
+    assert_eq!(sublists.len(), total - 1);
+}
+
+#[test]
+fn construction_errors_are_unchanged() {
+    let empty: [String; 0] = [];
+    assert!(GeneralPermutationsIterator::new(&empty, 1).is_err());
+    assert!(SimpleCombinationsIterator::new(&words(), 5).is_err());
+}
@@ -0,0 +1,105 @@
+extern crate combinatorial;
+
+use combinatorial::{DistinctPermutationsIterator, SimplePermutationsIterator};
+
+fn values<'a, T: Copy + 'a>(iter: impl Iterator<Item = Vec<&'a T>>) -> Vec<Vec<T>> {
+    iter.map(|items| items.into_iter().copied().collect())
+        .collect()
+}
+
+// Distinct values of every simple permutation, in sorted order
+fn deduplicated(source: &[i32], output_size: usize) -> Vec<Vec<i32>> {
+    let mut all = values(SimplePermutationsIterator::new(source, output_size).unwrap());
+    all.sort();
+    all.dedup();
+    all
+}
+
+#[test]
+fn equal_elements_are_interchangeable() {
+    let distinct = values(DistinctPermutationsIterator::new(&['a', 'a', 'b'], 3).unwrap());
+    assert_eq!(
+        distinct,
+        vec![
+            vec!['a', 'a', 'b'],
+            vec!['a', 'b', 'a'],
+            vec!['b', 'a', 'a']
+        ]
+    );
+}
+
+#[test]
+fn heavily_duplicated_sources() {
+    let sources: [&[i32]; 4] = [&[1, 1, 1, 2], &[3, 3, 3, 3], &[1, 1, 2, 2, 3], &[]];
+    for source in sources.iter() {
+        for output_size in 0..=source.len() {
+            let distinct = values(DistinctPermutationsIterator::new(source, output_size).unwrap());
+            assert_eq!(distinct, deduplicated(source, output_size));
+        }
+    }
+
+    let counts: Vec<usize> = (0..=4)
+        .map(|k| {
+            DistinctPermutationsIterator::new(&[1, 1, 1, 2], k)
+                .unwrap()
+                .count()
+        })
+        .collect();
+    assert_eq!(counts, vec![1, 2, 3, 4, 4]);
+}
+
+#[test]
+fn order_follows_first_appearance() {
+    let distinct = values(DistinctPermutationsIterator::new(&[2, 1, 2, 1], 2).unwrap());
+    assert_eq!(
+        distinct,
+        vec![vec![2, 2], vec![2, 1], vec![1, 2], vec![1, 1]]
+    );
+}
+
+#[test]
+fn distinct_sources_give_every_permutation() {
+    let source = [4, 1, 3, 2];
+    for output_size in 0..=4 {
+        let mut distinct = values(DistinctPermutationsIterator::new(&source, output_size).unwrap());
+        let all = SimplePermutationsIterator::new(&source, output_size).unwrap();
+        assert_eq!(distinct.len(), all.len());
+
+        assert!(distinct.windows(2).all(|pair| {
+            let rank = |value: &i32| source.iter().position(|x| x == value);
+            pair[0].iter().map(rank).lt(pair[1].iter().map(rank))
+        }));
+        distinct.sort();
+        assert_eq!(distinct, deduplicated(&source, output_size));
+    }
+}
+
+#[test]
+fn size_hint_counts_distinct_permutations() {
+    let source = [1, 1, 1, 2, 2, 3, 1];
+    for output_size in 0..=source.len() {
+        let mut iter = DistinctPermutationsIterator::new(&source, output_size).unwrap();
+        let mut remaining = DistinctPermutationsIterator::new(&source, output_size)
+            .unwrap()
+            .count();
+        loop {
+            assert_eq!(iter.size_hint(), (remaining, Some(remaining)));
+            if iter.next().is_none() {
+                break;
+            }
+            remaining -= 1;
+        }
+        assert_eq!(remaining, 0);
+    }
+}
+
+#[test]
+fn output_size_is_checked() {
+    assert!(DistinctPermutationsIterator::new(&[1, 1], 3).is_err());
+    let source = vec!["x".to_string(), "x".to_string()];
+    let owned: Vec<Vec<String>> = DistinctPermutationsIterator::new(&source, 2)
+        .unwrap()
+        .cloned()
+        .collect();
+    assert_eq!(owned, vec![vec!["x".to_string(), "x".to_string()]]);
+}
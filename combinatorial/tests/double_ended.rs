@@ -0,0 +1,135 @@
+extern crate combinatorial;
+
+use combinatorial::{GeneralPermutationsIterator, SimpleCombinationsIterator};
+
+// Small xorshift generator, so the interleavings are the same on every run
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+// Consumes from both ends in an order picked by `seed` and checks that the
+// items from the front followed by those from the back, reversed, make up
+// the forward sequence
+fn assert_interleaving_matches<'a, I, F>(make: F, seed: u64)
+where
+    I: DoubleEndedIterator<Item = Vec<&'a usize>> + ExactSizeIterator,
+    F: Fn() -> I,
+{
+    let forward: Vec<_> = make().collect();
+    let mut iter = make();
+    let (mut front, mut back) = (Vec::new(), Vec::new());
+    let mut state = seed;
+
+    loop {
+        assert_eq!(iter.len(), forward.len() - front.len() - back.len());
+        let item = if xorshift(&mut state) & 1 == 0 {
+            iter.next().map(|item| front.push(item))
+        } else {
+            iter.next_back().map(|item| back.push(item))
+        };
+        if item.is_none() {
+            break;
+        }
+    }
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    back.reverse();
+    front.extend(back);
+    assert_eq!(front, forward);
+}
+
+#[test]
+fn simple_combinations_reverse() {
+    for n in 0..=7 {
+        let source: Vec<usize> = (0..n).collect();
+        for k in 0..=n {
+            let mut forward: Vec<_> = SimpleCombinationsIterator::new(&source, k)
+                .unwrap()
+                .collect();
+            forward.reverse();
+            let reversed: Vec<_> = SimpleCombinationsIterator::new(&source, k)
+                .unwrap()
+                .rev()
+                .collect();
+            assert_eq!(reversed, forward);
+        }
+    }
+}
+
+#[test]
+fn general_permutations_reverse() {
+    for n in 1..=4 {
+        let source: Vec<usize> = (0..n).collect();
+        for k in 0..=4 {
+            let mut forward: Vec<_> = GeneralPermutationsIterator::new(&source, k)
+                .unwrap()
+                .collect();
+            forward.reverse();
+            let reversed: Vec<_> = GeneralPermutationsIterator::new(&source, k)
+                .unwrap()
+                .rev()
+                .collect();
+            assert_eq!(reversed, forward);
+        }
+    }
+}
+
+#[test]
+fn interleaved_simple_combinations() {
+    for n in 0..=7 {
+        let source: Vec<usize> = (0..n).collect();
+        for k in 0..=n {
+            for seed in 1..=20 {
+                assert_interleaving_matches(
+                    || SimpleCombinationsIterator::new(&source, k).unwrap(),
+                    seed,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn interleaved_general_permutations() {
+    let empty: [usize; 0] = [];
+    assert_interleaving_matches(|| GeneralPermutationsIterator::new(&empty, 0).unwrap(), 1);
+
+    for n in 1..=4 {
+        let source: Vec<usize> = (0..n).collect();
+        for k in 0..=3 {
+            for seed in 1..=20 {
+                assert_interleaving_matches(
+                    || GeneralPermutationsIterator::new(&source, k).unwrap(),
+                    seed,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn seeking_stops_at_the_back_cursor() {
+    let source = [0, 1, 2, 3, 4];
+
+    let mut combinations = SimpleCombinationsIterator::new(&source, 2).unwrap();
+    combinations.next_back();
+    combinations.next_back();
+    combinations.seek(8);
+    assert_eq!(combinations.next(), None);
+    combinations.seek(7);
+    assert_eq!(combinations.len(), 1);
+    assert_eq!(combinations.next_back(), Some(vec![&0, &3]));
+    assert_eq!(combinations.next(), None);
+
+    let mut permutations = GeneralPermutationsIterator::new(&source, 2).unwrap();
+    permutations.next_back();
+    assert_eq!(permutations.nth(24), None);
+    permutations.seek(20);
+    assert_eq!(permutations.len(), 4);
+    assert_eq!(permutations.next_back(), Some(vec![&3, &4]));
+    assert_eq!(permutations.next(), Some(vec![&0, &4]));
+}
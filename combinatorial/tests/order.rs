@@ -0,0 +1,137 @@
+extern crate combinatorial;
+
+use combinatorial::{
+    GeneralCombinationsIterator, GeneralPermutationsIterator, SimpleCombinationsIterator,
+    SimplePermutationsIterator,
+};
+
+// Positions of the elements of each item, over a source of its own positions
+fn positions<'a>(iter: impl Iterator<Item = Vec<&'a usize>>) -> Vec<Vec<usize>> {
+    iter.map(|items| items.into_iter().copied().collect())
+        .collect()
+}
+
+fn source(len: usize) -> Vec<usize> {
+    (0..len).collect()
+}
+
+#[test]
+fn lexicographic_simple_permutations() {
+    let source = source(3);
+    assert_eq!(
+        positions(SimplePermutationsIterator::new_lexicographic(&source, 2).unwrap()),
+        vec![
+            vec![0, 1],
+            vec![0, 2],
+            vec![1, 0],
+            vec![1, 2],
+            vec![2, 0],
+            vec![2, 1]
+        ]
+    );
+    assert_eq!(
+        positions(SimplePermutationsIterator::new_lexicographic(&source, 3).unwrap()),
+        vec![
+            vec![0, 1, 2],
+            vec![0, 2, 1],
+            vec![1, 0, 2],
+            vec![1, 2, 0],
+            vec![2, 0, 1],
+            vec![2, 1, 0]
+        ]
+    );
+}
+
+#[test]
+fn lexicographic_simple_permutations_match_the_sorted_unordered_ones() {
+    for len in 0..6 {
+        let source = source(len);
+        for output_size in 0..=len {
+            let mut unordered =
+                positions(SimplePermutationsIterator::new(&source, output_size).unwrap());
+            unordered.sort();
+
+            let mut iter =
+                SimplePermutationsIterator::new_lexicographic(&source, output_size).unwrap();
+            let mut ordered = Vec::new();
+            while let Some(item) = iter.next() {
+                ordered.push(item.into_iter().copied().collect::<Vec<_>>());
+                assert_eq!(iter.len(), unordered.len() - ordered.len());
+            }
+            assert_eq!(ordered, unordered);
+        }
+    }
+}
+
+#[test]
+fn general_permutations_count_from_either_end() {
+    let source = source(2);
+    assert_eq!(
+        positions(GeneralPermutationsIterator::new(&source, 2).unwrap()),
+        vec![vec![0, 0], vec![1, 0], vec![0, 1], vec![1, 1]]
+    );
+    assert_eq!(
+        positions(GeneralPermutationsIterator::new_lexicographic(&source, 2).unwrap()),
+        vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]]
+    );
+}
+
+#[test]
+fn lexicographic_general_permutations_seek_and_reverse() {
+    let source = source(3);
+    let all = positions(GeneralPermutationsIterator::new_lexicographic(&source, 3).unwrap());
+    let mut sorted = all.clone();
+    sorted.sort();
+    assert_eq!(all, sorted);
+
+    let mut iter = GeneralPermutationsIterator::new_lexicographic(&source, 3).unwrap();
+    iter.seek(5);
+    assert_eq!(positions(iter.by_ref().take(1)), vec![all[5].clone()]);
+    assert_eq!(
+        positions(iter.rev()),
+        all[6..].iter().rev().cloned().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn simple_combinations_come_in_reverse_lexicographic_order() {
+    let source = source(4);
+    assert_eq!(
+        positions(SimpleCombinationsIterator::new(&source, 2).unwrap()),
+        vec![
+            vec![2, 3],
+            vec![1, 3],
+            vec![1, 2],
+            vec![0, 3],
+            vec![0, 2],
+            vec![0, 1]
+        ]
+    );
+
+    for len in 0..7 {
+        let source = self::source(len);
+        for output_size in 0..=len {
+            let all = positions(SimpleCombinationsIterator::new(&source, output_size).unwrap());
+            let mut sorted = all.clone();
+            sorted.sort();
+            sorted.reverse();
+            assert_eq!(all, sorted);
+        }
+    }
+}
+
+#[test]
+fn general_combinations_come_in_order_of_their_reversed_positions() {
+    let source = source(3);
+    assert_eq!(
+        positions(GeneralCombinationsIterator::new(&source, 2).unwrap()),
+        vec![
+            vec![0, 0],
+            vec![1, 0],
+            vec![2, 0],
+            vec![1, 1],
+            vec![2, 1],
+            vec![2, 2]
+        ]
+    );
+}
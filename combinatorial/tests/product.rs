@@ -0,0 +1,67 @@
+extern crate combinatorial;
+
+use combinatorial::{CartesianProductIterator, GeneralPermutationsIterator};
+
+#[test]
+fn product_of_slices_of_different_lengths() {
+    let letters: &[char] = &['a', 'b'];
+    let digits: &[char] = &['1', '2', '3'];
+    let last: &[char] = &['x'];
+
+    let product: Vec<String> = CartesianProductIterator::new(&[letters, digits, last])
+        .map(|items| items.into_iter().collect())
+        .collect();
+    assert_eq!(product, vec!["a1x", "b1x", "a2x", "b2x", "a3x", "b3x"]);
+}
+
+#[test]
+fn counts_and_size_hint() {
+    let lengths = [[1, 4, 2], [3, 3, 3], [5, 1, 1], [2, 2, 2]];
+    let pool: Vec<usize> = (0..5).collect();
+    for shape in lengths.iter() {
+        let sources: Vec<&[usize]> = shape.iter().map(|&len| &pool[..len]).collect();
+        let total: usize = shape.iter().product();
+
+        let mut product = CartesianProductIterator::new(&sources);
+        for remaining in (0..=total).rev() {
+            assert_eq!(product.size_hint(), (remaining, Some(remaining)));
+            assert_eq!(product.next().is_some(), remaining > 0);
+        }
+    }
+}
+
+#[test]
+fn matches_general_permutations_of_one_slice() {
+    let source = [1, 2, 3];
+    let product: Vec<_> = CartesianProductIterator::new(&[&source[..]; 3]).collect();
+    let permutations: Vec<_> = GeneralPermutationsIterator::new(&source, 3)
+        .unwrap()
+        .collect();
+    assert_eq!(product, permutations);
+}
+
+#[test]
+fn empty_slice_gives_empty_product() {
+    let empty: &[u8] = &[];
+    let mut product = CartesianProductIterator::new(&[&[1, 2][..], empty, &[3][..]]);
+    assert_eq!(product.len(), 0);
+    assert_eq!(product.next(), None);
+}
+
+#[test]
+fn product_of_nothing_is_one_empty_tuple() {
+    let no_sources: [&[u8]; 0] = [];
+    let product: Vec<Vec<&u8>> = CartesianProductIterator::new(&no_sources).collect();
+    assert_eq!(product, vec![Vec::<&u8>::new()]);
+}
+
+#[test]
+fn cloned_products_own_their_elements() {
+    let names = ["ann".to_string(), "bob".to_string()];
+    let roles = ["admin".to_string()];
+    let pairs: Vec<Vec<String>> = CartesianProductIterator::new(&[&names[..], &roles[..]])
+        .cloned()
+        .collect();
+    assert_eq!(pairs.len(), 2);
+    assert_eq!(pairs[1], vec!["bob".to_string(), "admin".to_string()]);
+}
@@ -0,0 +1,126 @@
+extern crate combinatorial;
+
+use combinatorial::{GeneralPermutationsIterator, SimpleCombinationsIterator};
+
+// Checks `nth` from every starting point against stepping with `next`
+fn assert_nth_matches_next<'a, I, F>(make: F)
+where
+    I: Iterator<Item = Vec<&'a usize>>,
+    F: Fn() -> I,
+{
+    let total = make().count();
+    for start in 0..=total {
+        for skip in 0..=total - start + 1 {
+            let mut jumped = make();
+            let mut stepped = make();
+            for _ in 0..start {
+                jumped.next();
+                stepped.next();
+            }
+            for _ in 0..skip {
+                stepped.next();
+            }
+
+            assert_eq!(jumped.nth(skip), stepped.next());
+            assert_eq!(jumped.next(), stepped.next());
+            assert_eq!(jumped.size_hint(), stepped.size_hint());
+        }
+    }
+}
+
+#[test]
+fn simple_combinations_nth_matches_next() {
+    for n in 0..=6 {
+        let source: Vec<usize> = (0..n).collect();
+        for k in 0..=n {
+            assert_nth_matches_next(|| SimpleCombinationsIterator::new(&source, k).unwrap());
+        }
+    }
+}
+
+#[test]
+fn general_permutations_nth_matches_next() {
+    for n in 0..=4 {
+        let source: Vec<usize> = (0..n).collect();
+        for k in 0..=3 {
+            if GeneralPermutationsIterator::new(&source, k).is_ok() {
+                assert_nth_matches_next(|| GeneralPermutationsIterator::new(&source, k).unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn seek_is_absolute() {
+    let source: Vec<usize> = (0..7).collect();
+    let all: Vec<_> = SimpleCombinationsIterator::new(&source, 3)
+        .unwrap()
+        .collect();
+
+    let mut combinations = SimpleCombinationsIterator::new(&source, 3).unwrap();
+    for &rank in &[20, 3, 34, 0, 17] {
+        combinations.next();
+        combinations.seek(rank);
+        assert_eq!(combinations.len(), all.len() - rank);
+        assert_eq!(combinations.next().as_ref(), all.get(rank));
+    }
+
+    let all: Vec<_> = GeneralPermutationsIterator::new(&source, 3)
+        .unwrap()
+        .collect();
+    let mut permutations = GeneralPermutationsIterator::new(&source, 3).unwrap();
+    for &rank in &[300, 3, 342, 0, 49] {
+        permutations.next();
+        permutations.seek(rank);
+        assert_eq!(permutations.len(), all.len() - rank);
+        assert_eq!(permutations.next().as_ref(), all.get(rank));
+    }
+}
+
+#[test]
+fn seeking_past_the_end_exhausts() {
+    let source = [1, 2, 3, 4];
+
+    let mut combinations = SimpleCombinationsIterator::new(&source, 2).unwrap();
+    combinations.seek(6);
+    assert_eq!(combinations.next(), None);
+    assert_eq!(combinations.len(), 0);
+    combinations.seek(5);
+    assert_eq!(combinations.next(), Some(vec![&1, &2]));
+    assert_eq!(combinations.nth(usize::MAX), None);
+    assert_eq!(combinations.next(), None);
+
+    let mut permutations = GeneralPermutationsIterator::new(&source, 2).unwrap();
+    permutations.seek(16);
+    assert_eq!(permutations.next(), None);
+    assert_eq!(permutations.len(), 0);
+    permutations.seek(15);
+    assert_eq!(permutations.next(), Some(vec![&4, &4]));
+    assert_eq!(permutations.nth(usize::MAX), None);
+    assert_eq!(permutations.next(), None);
+}
+
+#[test]
+fn seek_jumps_into_huge_spaces() {
+    let source: Vec<usize> = (0..60).collect();
+    let mut combinations = SimpleCombinationsIterator::new(&source, 30).unwrap();
+    combinations.nth(4_999_999);
+    let worker = combinations.next().unwrap();
+
+    let mut seeked = SimpleCombinationsIterator::new(&source, 30).unwrap();
+    seeked.seek(5_000_000);
+    assert_eq!(seeked.next().unwrap(), worker);
+    assert_eq!(seeked.len(), 118_264_581_564_861_424 - 5_000_001);
+
+    let mut permutations = GeneralPermutationsIterator::new(&source, 40).unwrap();
+    permutations.seek(61);
+    let mut expected = vec![0; 40];
+    expected[0] = 1;
+    expected[1] = 1;
+    assert_eq!(
+        permutations.next().unwrap(),
+        expected.iter().collect::<Vec<_>>()
+    );
+    assert!(permutations.nth(usize::MAX - 100).is_some());
+    assert_eq!(permutations.nth(usize::MAX), None);
+}
@@ -0,0 +1,148 @@
+extern crate combinatorial;
+
+use combinatorial::{
+    GeneralCombinationsIterator, GeneralPermutationsIterator, SimpleCombinationsIterator,
+    SimplePermutationsIterator, SublistIterator, SubsetIterator,
+};
+
+// Checks the hint at every step against the number of items actually left
+fn assert_exact<I: ExactSizeIterator>(mut iter: I, expected_total: usize) {
+    let mut remaining = expected_total;
+    loop {
+        assert_eq!(iter.size_hint(), (remaining, Some(remaining)));
+        assert_eq!(iter.len(), remaining);
+        if iter.next().is_none() {
+            break;
+        }
+        remaining -= 1;
+    }
+    assert_eq!(remaining, 0);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    (0..k).fold(1, |result, i| result * (n - i) / (i + 1))
+}
+
+fn falling_factorial(n: usize, k: usize) -> usize {
+    (n - k + 1..=n).product()
+}
+
+#[test]
+fn general_permutations() {
+    for n in 0..=5 {
+        let source: Vec<usize> = (0..n).collect();
+        for k in 0..=4 {
+            if let Ok(iter) = GeneralPermutationsIterator::new(&source, k) {
+                assert_exact(iter, n.pow(k as u32));
+            }
+        }
+    }
+}
+
+#[test]
+fn general_combinations() {
+    for n in 0..=5 {
+        let source: Vec<usize> = (0..n).collect();
+        for k in 0..=4 {
+            if let Ok(iter) = GeneralCombinationsIterator::new(&source, k) {
+                let total = if k == 0 { 1 } else { binomial(n + k - 1, k) };
+                assert_exact(iter, total);
+            }
+        }
+    }
+}
+
+#[test]
+fn simple_combinations() {
+    for n in 0..=7 {
+        let source: Vec<usize> = (0..n).collect();
+        for k in 0..=n {
+            let iter = SimpleCombinationsIterator::new(&source, k).unwrap();
+            assert_exact(iter, binomial(n, k));
+        }
+    }
+}
+
+#[test]
+fn simple_permutations() {
+    for n in 0..=6 {
+        let source: Vec<usize> = (0..n).collect();
+        for k in 0..=n {
+            let iter = SimplePermutationsIterator::new(&source, k).unwrap();
+            assert_exact(iter, falling_factorial(n, k));
+        }
+    }
+}
+
+#[test]
+fn subsets_and_sublists() {
+    for n in 0..=6 {
+        let source: Vec<usize> = (0..n).collect();
+        assert_exact(SubsetIterator::new(&source), 1 << n);
+
+        let sublists = (0..=n).map(|k| falling_factorial(n, k)).sum();
+        assert_exact(SublistIterator::new(&source), sublists);
+    }
+}
+
+#[test]
+fn collect_allocates_once() {
+    let source = [1, 2, 3, 4, 5, 6];
+    let all: Vec<_> = SimplePermutationsIterator::new(&source, 4)
+        .unwrap()
+        .collect();
+    assert_eq!(all.len(), 360);
+    assert_eq!(all.capacity(), 360);
+}
+
+#[test]
+fn overflowing_counts_have_no_upper_bound() {
+    let bits = usize::BITS as usize;
+    let mut iter = GeneralPermutationsIterator::new(&[0, 1], bits).unwrap();
+    assert_eq!(iter.size_hint(), (0, None));
+    iter.next();
+    assert_eq!(iter.len(), usize::MAX);
+    iter.next();
+    assert_eq!(iter.len(), usize::MAX - 1);
+
+    let iter = GeneralPermutationsIterator::new(&[0, 1], bits - 1).unwrap();
+    assert_eq!(iter.len(), 1 << (bits - 1));
+
+    let source: Vec<usize> = (0..200).collect();
+    let combinations = SimpleCombinationsIterator::new(&source, 100).unwrap();
+    assert_eq!(combinations.size_hint().1, None);
+    let permutations = SimplePermutationsIterator::new(&source, 30).unwrap();
+    assert_eq!(permutations.size_hint().1, None);
+    assert_eq!(SubsetIterator::new(&source).size_hint().1, None);
+    assert_eq!(SublistIterator::new(&source).size_hint().1, None);
+    let multisets = GeneralCombinationsIterator::new(&source, 100).unwrap();
+    assert_eq!(multisets.size_hint().1, None);
+}
+
+#[test]
+fn overflowing_spaces_can_still_be_consumed() {
+    let source: Vec<usize> = (0..10).collect();
+    let mut permutations = GeneralPermutationsIterator::new(&source, 40).unwrap();
+    assert_eq!(permutations.size_hint(), (0, None));
+    // As much as `collect` reserves before taking the first item
+    Vec::<Vec<&usize>>::new().reserve(permutations.size_hint().0);
+
+    let first: Vec<_> = permutations.by_ref().take(1000).collect();
+    assert_eq!(first.len(), 1000);
+    assert_eq!(first[0], vec![&0; 40]);
+
+    let mut count = 0;
+    for permutation in permutations {
+        assert_eq!(permutation.len(), 40);
+        count += 1;
+        if count == 1000 {
+            break;
+        }
+    }
+    assert_eq!(count, 1000);
+
+    let wide: Vec<usize> = (0..100).collect();
+    let subsets: Vec<_> = SubsetIterator::new(&wide).take(5).collect();
+    assert_eq!(subsets.len(), 5);
+}
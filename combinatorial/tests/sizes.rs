@@ -0,0 +1,84 @@
+extern crate combinatorial;
+
+use combinatorial::{SublistIterator, SubsetIterator};
+
+fn binomial(n: usize, k: usize) -> usize {
+    (0..k).fold(1, |result, i| result * (n - i) / (i + 1))
+}
+
+fn falling_factorial(n: usize, k: usize) -> usize {
+    (n - k + 1..=n).product()
+}
+
+#[test]
+fn subset_counts_match_binomial_sums() {
+    for n in 0..=6 {
+        let source: Vec<usize> = (0..n).collect();
+        for low in 0..=n {
+            for high in low..=n {
+                let subsets: Vec<_> = SubsetIterator::with_sizes(&source, low..=high)
+                    .unwrap()
+                    .collect();
+                let expected: usize = (low..=high).map(|k| binomial(n, k)).sum();
+                assert_eq!(subsets.len(), expected);
+                assert!(subsets.iter().all(|s| (low..=high).contains(&s.len())));
+                assert!(subsets
+                    .windows(2)
+                    .all(|pair| pair[0].len() <= pair[1].len()));
+            }
+        }
+    }
+}
+
+#[test]
+fn sublist_counts_match_permutation_sums() {
+    for n in 0..=5 {
+        let source: Vec<usize> = (0..n).collect();
+        for low in 0..=n {
+            for high in low..=n {
+                let mut sublists = SublistIterator::with_sizes(&source, low..=high).unwrap();
+                let expected: usize = (low..=high).map(|k| falling_factorial(n, k)).sum();
+                assert_eq!(sublists.len(), expected);
+                for remaining in (0..expected).rev() {
+                    let sublist = sublists.next().unwrap();
+                    assert!((low..=high).contains(&sublist.len()));
+                    assert_eq!(sublists.len(), remaining);
+                }
+                assert_eq!(sublists.next(), None);
+            }
+        }
+    }
+}
+
+#[test]
+fn power_set_includes_the_empty_set() {
+    let source = [1, 2, 3];
+    let power_set: Vec<_> = SubsetIterator::power_set(&source).collect();
+    assert_eq!(power_set.len(), 8);
+    assert_eq!(power_set[0], Vec::<&i32>::new());
+    assert_eq!(power_set[7], vec![&1, &2, &3]);
+    assert_eq!(power_set, SubsetIterator::new(&source).collect::<Vec<_>>());
+
+    let sublists: Vec<_> = SublistIterator::power_set(&source).collect();
+    assert_eq!(sublists.len(), 1 + 3 + 6 + 6);
+    assert_eq!(sublists, SublistIterator::new(&source).collect::<Vec<_>>());
+}
+
+#[test]
+fn ranges_are_checked_against_the_source() {
+    let source = [1, 2, 3, 4];
+    assert!(SubsetIterator::with_sizes(&source, 2..=5).is_err());
+    assert!(SublistIterator::with_sizes(&source, 0..=5).is_err());
+
+    let empty = std::ops::RangeInclusive::new(3, 2);
+    assert_eq!(
+        SubsetIterator::with_sizes(&source, empty.clone())
+            .unwrap()
+            .count(),
+        0
+    );
+    assert_eq!(
+        SublistIterator::with_sizes(&source, empty).unwrap().len(),
+        0
+    );
+}
@@ -0,0 +1,162 @@
+extern crate criterion;
+extern crate gameoflife;
+extern crate rand;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gameoflife::{patterns, LifeBoard, PackedLifeBoard};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+const SIZES: [usize; 3] = [64, 256, 1024];
+// Sizes where wrapping cannot be done with a mask
+const ODD_SIZES: [usize; 2] = [100, 1000];
+const PARALLEL_SIZES: [usize; 2] = [1024, 4096];
+
+fn soup(size: usize) -> LifeBoard {
+    let mut board = LifeBoard::new_square(size);
+    board
+        .randomize(0.5, &mut SmallRng::seed_from_u64(42))
+        .unwrap();
+    board
+}
+
+// Generations per second of a fresh soup with each stepper
+fn step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step");
+    group.throughput(Throughput::Elements(1));
+
+    for &size in SIZES.iter() {
+        let board = soup(size);
+
+        group.bench_with_input(BenchmarkId::new("dense", size), &board, |b, board| {
+            let mut next = LifeBoard::new_square(size);
+            b.iter(|| board.step(&mut next).unwrap());
+        });
+
+        group.bench_function(BenchmarkId::new("advance", size), |b| {
+            let mut board = board.clone();
+            b.iter(|| board.advance());
+        });
+
+        // Keeping the Zobrist hash, as cycle detection does
+        group.bench_function(BenchmarkId::new("advance_hashed", size), |b| {
+            let mut board = board.clone();
+            board.keep_zobrist_hash();
+            b.iter(|| board.advance());
+        });
+
+        #[cfg(feature = "rayon")]
+        group.bench_with_input(BenchmarkId::new("parallel", size), &board, |b, board| {
+            let mut next = LifeBoard::new_square(size);
+            b.iter(|| board.par_step(&mut next).unwrap());
+        });
+
+        #[cfg(feature = "simd")]
+        group.bench_with_input(BenchmarkId::new("simd", size), &board, |b, board| {
+            let mut next = LifeBoard::new_square(size);
+            b.iter(|| board.simd_step(&mut next).unwrap());
+        });
+
+        let packed = PackedLifeBoard::from_board(&board);
+        group.bench_with_input(BenchmarkId::new("packed", size), &packed, |b, packed| {
+            let mut next = PackedLifeBoard::new(size, size);
+            b.iter(|| packed.step(&mut next));
+        });
+    }
+
+    for &size in ODD_SIZES.iter() {
+        let board = soup(size);
+        group.bench_with_input(BenchmarkId::new("dense", size), &board, |b, board| {
+            let mut next = LifeBoard::new_square(size);
+            b.iter(|| board.step(&mut next).unwrap());
+        });
+
+        #[cfg(feature = "simd")]
+        group.bench_with_input(BenchmarkId::new("simd", size), &board, |b, board| {
+            let mut next = LifeBoard::new_square(size);
+            b.iter(|| board.simd_step(&mut next).unwrap());
+        });
+
+        // Also collects the cells that changed
+        group.bench_with_input(BenchmarkId::new("diff", size), &board, |b, board| {
+            let mut next = LifeBoard::new_square(size);
+            b.iter(|| board.step_diff(&mut next).unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+// Generations per second of a soup left to settle, where little is changing
+fn settled(c: &mut Criterion) {
+    let mut group = c.benchmark_group("settled");
+    group.throughput(Throughput::Elements(1));
+
+    for &size in SIZES.iter() {
+        let mut board = soup(size);
+        board.advance_by(2000);
+
+        group.bench_function(BenchmarkId::new("advance", size), |b| {
+            b.iter(|| board.advance());
+        });
+        group.bench_function(BenchmarkId::new("advance_active", size), |b| {
+            b.iter(|| board.advance_active());
+        });
+    }
+
+    // Still lifes across the whole board with a blinker among every 64 of
+    // them, as a soup leaves things once it has burnt out
+    let mut board = LifeBoard::new_square(1024);
+    for row in (0..1024).step_by(8) {
+        for col in (0..1024).step_by(8) {
+            let pattern = if (row / 8 * 128 + col / 8) % 64 == 0 {
+                patterns::blinker()
+            } else {
+                patterns::block()
+            };
+            board.stamp(&pattern, row + 2, col + 2).unwrap();
+        }
+    }
+    group.bench_function(BenchmarkId::new("advance", "field"), |b| {
+        b.iter(|| board.advance());
+    });
+    group.bench_function(BenchmarkId::new("advance_active", "field"), |b| {
+        b.iter(|| board.advance_active());
+    });
+
+    group.finish();
+}
+
+// Generations per second of big soups, serial against parallel. Run with
+// RAYON_NUM_THREADS set to compare thread counts.
+fn parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel");
+    group.throughput(Throughput::Elements(1));
+    group.sample_size(10);
+
+    for &size in PARALLEL_SIZES.iter() {
+        let board = soup(size);
+
+        group.bench_with_input(BenchmarkId::new("step", size), &board, |b, board| {
+            let mut next = LifeBoard::new_square(size);
+            b.iter(|| board.step(&mut next).unwrap());
+        });
+
+        #[cfg(feature = "rayon")]
+        group.bench_with_input(BenchmarkId::new("par_step", size), &board, |b, board| {
+            let mut next = LifeBoard::new_square(size);
+            b.iter(|| board.par_step(&mut next).unwrap());
+        });
+
+        #[cfg(feature = "rayon")]
+        group.bench_function(BenchmarkId::new("par_advance", size), |b| {
+            let mut board = board.clone();
+            b.iter(|| board.par_advance());
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, step, settled, parallel);
+criterion_main!(benches);
@@ -0,0 +1,136 @@
+use crate::zobrist::zobrist_key;
+use crate::LifeBoard;
+
+impl LifeBoard {
+    /// Replaces this board with its next generation, recomputing only the
+    /// cells next to a cell that changed in the previous call.
+    ///
+    /// A cell whose neighbourhood did not change keeps its state, so once a
+    /// board has mostly settled each generation costs time in proportion to
+    /// the activity left rather than to the area. The result is always the
+    /// same as `advance`. The first call, and the first call after the board
+    /// is changed in any other way, steps every cell.
+    ///
+    /// Each cell recomputed this way costs several times as much as one
+    /// stepped by `advance`. A soup left for thousands of generations still
+    /// has enough activity that this is no faster, so it is only worth using
+    /// on boards where nearly everything has stopped.
+    pub fn advance_active(&mut self) {
+        let changed = match self.changed.take() {
+            Some(changed) => self.step_changed(&changed),
+            None => self.step_all(),
+        };
+
+        self.changed = Some(changed);
+    }
+
+    fn step_all(&mut self) -> Vec<usize> {
+        let mut next = std::mem::take(&mut self.scratch);
+        next.resize(self.cells.len(), false);
+        let mut changed = Vec::new();
+        self.step_rows(0, &mut next, |index, _| changed.push(index));
+        let hash = self.hash.map(|hash| {
+            changed
+                .iter()
+                .fold(hash, |hash, &index| hash ^ zobrist_key(index))
+        });
+
+        self.scratch = std::mem::replace(&mut self.cells, next);
+        self.finish_advance(hash);
+
+        // Left clear for marking queued cells in the next call
+        self.scratch.iter_mut().for_each(|queued| *queued = false);
+        changed
+    }
+
+    fn step_changed(&mut self, changed: &[usize]) -> Vec<usize> {
+        // The scratch buffer marks cells already queued for recomputing. It is
+        // all clear between calls, so only the marks made here are undone
+        // rather than the whole board
+        let mut queued = std::mem::take(&mut self.scratch);
+
+        let mut candidates = Vec::new();
+        for &index in changed {
+            let (row, col) = (index / self.width, index % self.width);
+            let width = self.width;
+            let mut queue = |row: usize, col: usize| {
+                let index = row * width + col;
+                if !queued[index] {
+                    queued[index] = true;
+                    candidates.push(index);
+                }
+            };
+
+            if self.boundary.couples_axes() {
+                queue(row, col);
+                for (row, col, _) in self.neighbours(row, col) {
+                    queue(row, col);
+                }
+                continue;
+            }
+            for delta_row in -1..=1 {
+                for delta_col in -1..=1 {
+                    let row = self.neighbours.row(row, delta_row);
+                    let col = self.neighbours.col(col, delta_col);
+                    if let (Some(row), Some(col)) = (row, col) {
+                        queue(row, col);
+                    }
+                }
+            }
+        }
+
+        for &index in &candidates {
+            queued[index] = false;
+        }
+
+        // Work out every new state before writing any of them
+        let mut updates: Vec<(usize, bool)> = candidates
+            .into_iter()
+            .filter_map(|index| {
+                let alive = self.next_cell(index / self.width, index % self.width);
+                if alive != self.cells[index] {
+                    Some((index, alive))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        for &(index, alive) in &updates {
+            self.cells[index] = alive;
+        }
+        // Every live cell that did not change survived
+        if let Some(ages) = &mut self.ages {
+            for (age, &alive) in ages.iter_mut().zip(&self.cells) {
+                *age = if alive { age.saturating_add(1) } else { 0 };
+            }
+            for &(index, _) in &updates {
+                ages[index] = 0;
+            }
+            // Cells past the age limit die whether or not they were queued
+            if let Some(max_age) = self.max_age {
+                for (index, age) in ages.iter_mut().enumerate() {
+                    if *age > max_age {
+                        *age = 0;
+                        self.cells[index] = false;
+                        updates.push((index, false));
+                    }
+                }
+            }
+        }
+        if let Some(heat) = &mut self.heat {
+            for &(index, _) in &updates {
+                heat[index] += 1;
+            }
+        }
+        if let Some(hash) = &mut self.hash {
+            for &(index, _) in &updates {
+                *hash ^= zobrist_key(index);
+            }
+        }
+
+        self.scratch = queued;
+        self.generation += 1;
+        updates.into_iter().map(|(index, _)| index).collect()
+    }
+}
@@ -0,0 +1,95 @@
+use crate::LifeBoard;
+
+impl LifeBoard {
+    /// Starts counting how many generations each live cell has survived.
+    /// Every cell alive now starts at age 0.
+    ///
+    /// Ages are kept up to date by every way of stepping the board and never
+    /// change how it steps. Keeping them costs a pass over the whole board
+    /// per generation, which `advance_active` otherwise avoids. Cells brought
+    /// to life by `set` or `toggle` start at age 0, but cells changed through
+    /// indexing or `iter_mut` keep whatever age their position had.
+    pub fn enable_ages(&mut self) {
+        self.ages = Some(vec![0; self.cells.len()]);
+    }
+
+    /// Stops counting ages, which also lifts any limit set by
+    /// `set_max_age`.
+    pub fn disable_ages(&mut self) {
+        self.ages = None;
+        self.max_age = None;
+    }
+
+    pub fn tracks_ages(&self) -> bool {
+        self.ages.is_some()
+    }
+
+    /// Generations the cell at `row`, `col` has survived since it was born,
+    /// or `None` if ages are not tracked, the cell is dead or it is outside
+    /// the board.
+    pub fn age(&self, row: usize, col: usize) -> Option<u16> {
+        let ages = self.ages.as_ref()?;
+        if self.get(row, col)? {
+            Some(ages[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Oldest age a cell may reach, or `None` if cells may live forever.
+    pub fn max_age(&self) -> Option<u16> {
+        self.max_age
+    }
+
+    /// Kills every cell that has survived more than `max_age` generations,
+    /// whatever its neighbours, after each step under the board's rule. A
+    /// cell alive from generation 0 with a limit of N dies in generation
+    /// N + 1. `None` lets cells live as long as the rule allows.
+    ///
+    /// Setting a limit starts counting ages if they are not counted already,
+    /// with every live cell at age 0.
+    pub fn set_max_age(&mut self, max_age: Option<u16>) {
+        if max_age.is_some() && self.ages.is_none() {
+            self.enable_ages();
+        }
+        self.changed = None;
+        self.max_age = max_age;
+    }
+
+    // Ages of the cells of `next`, the generation after this board, killing
+    // those past the board's age limit
+    pub(crate) fn next_ages(&self, next: &mut [bool]) -> Option<Vec<u16>> {
+        let mut ages = self.ages.clone()?;
+        age_cells(&mut ages, &self.cells, next, self.max_age);
+
+        Some(ages)
+    }
+}
+
+// Ages cells that survived from `before` to `after` by a generation and
+// resets the rest, then kills the cells of `after` older than `max_age`
+pub(crate) fn age_cells(
+    ages: &mut [u16],
+    before: &[bool],
+    after: &mut [bool],
+    max_age: Option<u16>,
+) {
+    for ((age, &was), is) in ages.iter_mut().zip(before).zip(after) {
+        *age = if was && *is { age.saturating_add(1) } else { 0 };
+        if max_age.is_some_and(|max_age| *age > max_age) {
+            *is = false;
+            *age = 0;
+        }
+    }
+}
+
+// Kills the cells of a row of `after` that survived from `before` but would
+// pass `max_age`, as `age_cells` does, so the row can be compared with
+// `before` before the rest of the board is written
+pub(crate) fn kill_aged(ages: &[u16], before: &[bool], after: &mut [bool], max_age: u16) {
+    for ((is, &was), &age) in after.iter_mut().zip(before).zip(ages) {
+        if was && *is && age.saturating_add(1) > max_age {
+            *is = false;
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::library::{LibraryEntry, PatternLibrary};
+use crate::{BoundaryMode, LifeBoard, Rect};
+
+/// Live cells of a board connected to each other through any of their eight
+/// neighbours, with no live cell next to them left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// (row, column) of each cell, in row order.
+    pub cells: Vec<(usize, usize)>,
+    pub bounding_box: Rect,
+}
+
+impl Component {
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Cells moved so the bounding box starts at the origin, in row order.
+    pub fn shape(&self) -> Vec<(usize, usize)> {
+        let Rect { row, col, .. } = self.bounding_box;
+        self.cells
+            .iter()
+            .map(|&(r, c)| (r - row, c - col))
+            .collect()
+    }
+}
+
+/// Objects left on a board, as counted by `LifeBoard::census`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Census {
+    /// Number of components found of each library pattern, by name. Patterns
+    /// that were not found are left out.
+    pub counts: BTreeMap<&'static str, usize>,
+    /// Number of components that matched no pattern.
+    pub unidentified: usize,
+}
+
+impl Census {
+    /// Number of components found of the library pattern `name`.
+    pub fn count(&self, name: &str) -> usize {
+        self.counts.get(name).copied().unwrap_or(0)
+    }
+
+    /// Number of components counted, identified or not.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum::<usize>() + self.unidentified
+    }
+}
+
+impl std::fmt::Display for Census {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (name, count) in &self.counts {
+            writeln!(f, "{:<18} {:>8}", name, count)?;
+        }
+        write!(f, "{:<18} {:>8}", "unidentified", self.unidentified)
+    }
+}
+
+impl LifeBoard {
+    /// Live cells grouped into components connected through any of their
+    /// eight neighbours, in the row order of their first cells. Cells are not
+    /// connected across the board's edges whatever its boundary mode, so an
+    /// object straddling the edge of a torus counts as several.
+    pub fn components(&self) -> Vec<Component> {
+        let (width, height) = (self.width, self.height);
+        let mut seen = vec![false; width * height];
+        let mut components = Vec::new();
+
+        for (row, col) in self.live_cells() {
+            if seen[row * width + col] {
+                continue;
+            }
+            seen[row * width + col] = true;
+
+            let mut cells = vec![(row, col)];
+            let mut next = 0;
+            while next < cells.len() {
+                let (row, col) = cells[next];
+                next += 1;
+
+                for near_row in row.saturating_sub(1)..=(row + 1).min(height - 1) {
+                    for near_col in col.saturating_sub(1)..=(col + 1).min(width - 1) {
+                        let index = near_row * width + near_col;
+                        if !seen[index] && self.cells[index] {
+                            seen[index] = true;
+                            cells.push((near_row, near_col));
+                        }
+                    }
+                }
+            }
+
+            cells.sort_unstable();
+            let (top, bottom) = (cells[0].0, cells[cells.len() - 1].0);
+            let left = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+            let right = cells.iter().map(|&(_, col)| col).max().unwrap_or(0);
+            components.push(Component {
+                cells,
+                bounding_box: Rect {
+                    row: top,
+                    col: left,
+                    width: right - left + 1,
+                    height: bottom - top + 1,
+                },
+            });
+        }
+
+        components
+    }
+
+    /// Census of the components of the board against
+    /// `PatternLibrary::classic`. See `census_with`.
+    pub fn census(&self) -> Census {
+        self.census_with(&PatternLibrary::classic())
+    }
+
+    /// Counts the components of the board that take the shape of each entry
+    /// of `library` in any of its phases, rotated or reflected, and those
+    /// that match none.
+    ///
+    /// Matching is by component, so a phase of a pattern that falls apart
+    /// into several components, like the second phase of a beacon, is never
+    /// matched and its pieces are counted as unidentified. Where entries
+    /// share a shape the first one added wins.
+    pub fn census_with(&self, library: &PatternLibrary) -> Census {
+        let mut names = HashMap::new();
+        for entry in library {
+            for phase in phases(entry) {
+                names.entry(phase).or_insert(entry.name);
+            }
+        }
+
+        let mut census = Census::default();
+        for component in self.components() {
+            match names.get(&canonical(&component.shape())) {
+                Some(name) => *census.counts.entry(name).or_insert(0) += 1,
+                None => census.unidentified += 1,
+            }
+        }
+
+        census
+    }
+}
+
+// Canonical shapes of the phases of `entry` that are a single component,
+// run on a board with dead edges it cannot reach
+fn phases(entry: &LibraryEntry) -> Vec<Vec<(usize, usize)>> {
+    let (rows, cols) = entry.displacement;
+    let margin = rows.unsigned_abs().max(cols.unsigned_abs()) as usize + 4;
+    let (width, height) = entry.bounding_box();
+
+    let mut board = LifeBoard::new(width + 2 * margin, height + 2 * margin);
+    board.set_boundary(BoundaryMode::Dead);
+    board.stamp(&entry.pattern, margin, margin).unwrap();
+
+    let mut phases = Vec::new();
+    for _ in 0..entry.period.max(1) {
+        if let [component] = board.components().as_slice() {
+            phases.push(canonical(&component.shape()));
+        }
+        board.advance();
+    }
+
+    phases
+}
+
+// `shape`, which starts at the origin, under whichever of the eight
+// rotations and reflections sorts first
+fn canonical(shape: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let image = |symmetry: usize| {
+        let cells: Vec<(isize, isize)> = shape
+            .iter()
+            .map(|&(row, col)| {
+                let (mut row, mut col) = (row as isize, col as isize);
+                if symmetry & 1 != 0 {
+                    row = -row;
+                }
+                if symmetry & 2 != 0 {
+                    col = -col;
+                }
+                if symmetry & 4 != 0 {
+                    std::mem::swap(&mut row, &mut col);
+                }
+                (row, col)
+            })
+            .collect();
+        let top = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+        let left = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+
+        let mut image: Vec<(usize, usize)> = cells
+            .into_iter()
+            .map(|(row, col)| ((row - top) as usize, (col - left) as usize))
+            .collect();
+        image.sort_unstable();
+        image
+    };
+
+    (0..8).map(image).min().unwrap_or_default()
+}
@@ -0,0 +1,177 @@
+use crate::render::{compose_styled_frame, RenderStyle};
+use crate::rule::Rule;
+use crate::{BoundaryMode, LifeBoard};
+
+/// Columns between the frames drawn by `Comparison::compose_frame`.
+pub const COMPARISON_GAP: usize = 2;
+
+/// One of the boards of a `Comparison` and the label drawn above it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Side {
+    pub label: String,
+    pub board: LifeBoard,
+}
+
+/// Boards started from the same cells and stepped in lockstep, each under
+/// its own rule and boundary mode, to see how the rules drift apart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    sides: Vec<Side>,
+}
+
+impl Comparison {
+    /// Comparison with `board` as its first side, which the others are
+    /// measured against.
+    pub fn new(label: impl Into<String>, board: LifeBoard) -> Comparison {
+        Comparison {
+            sides: vec![Side {
+                label: label.into(),
+                board,
+            }],
+        }
+    }
+
+    /// Adds a side with the cells of the first side's current generation,
+    /// stepped under `rule` and `boundary` from now on.
+    pub fn add_side(&mut self, label: impl Into<String>, rule: Rule, boundary: BoundaryMode) {
+        let mut board = self.sides[0].board.clone();
+        board.set_rule(rule);
+        board.set_boundary(boundary);
+        self.sides.push(Side {
+            label: label.into(),
+            board,
+        });
+    }
+
+    pub fn sides(&self) -> &[Side] {
+        &self.sides
+    }
+
+    /// Steps every side by one generation.
+    pub fn advance(&mut self) {
+        for side in &mut self.sides {
+            side.board.advance();
+        }
+    }
+
+    /// Cells of each side after the first that differ from the first side.
+    pub fn divergences(&self) -> Vec<usize> {
+        let first = &self.sides[0].board;
+        self.sides[1..]
+            .iter()
+            .map(|side| divergence(first, &side.board))
+            .collect()
+    }
+
+    /// Draws the sides next to each other as `side_by_side` does, each in
+    /// `style` under its label, followed by a line giving the generation
+    /// and each side's divergence from the first, then a newline.
+    pub fn compose_frame(&self, style: RenderStyle, color: bool) -> String {
+        let frames: Vec<String> = self
+            .sides
+            .iter()
+            .map(|side| compose_styled_frame(&side.board, style, color))
+            .collect();
+        let labelled: Vec<(&str, &str)> = self
+            .sides
+            .iter()
+            .zip(&frames)
+            .map(|(side, frame)| (side.label.as_str(), frame.trim_end_matches('\n')))
+            .collect();
+
+        let mut frame = side_by_side(&labelled, COMPARISON_GAP);
+        frame.push_str(&format!("generation {}", self.sides[0].board.generation()));
+        for (side, divergence) in self.sides[1..].iter().zip(self.divergences()) {
+            frame.push_str(&format!(", {}: {} differ", side.label, divergence));
+        }
+        frame.push('\n');
+
+        frame
+    }
+}
+
+/// Number of cells that are alive on one of `a` and `b` but not the other.
+///
+/// Panics if the boards differ in size.
+pub fn divergence(a: &LifeBoard, b: &LifeBoard) -> usize {
+    assert_eq!(
+        (a.width, a.height),
+        (b.width, b.height),
+        "boards of different sizes cannot be compared"
+    );
+
+    a.cells.iter().zip(&b.cells).filter(|(a, b)| a != b).count()
+}
+
+/// Lays out `frames`, each given as (label, frame), next to each other with
+/// `gap` spaces between them and each label on the line above its frame.
+/// Every column is as wide as its widest line, not counting ANSI escape
+/// codes, and shorter frames are padded with blank lines. Each line ends in
+/// a newline and has no trailing spaces.
+pub fn side_by_side(frames: &[(&str, &str)], gap: usize) -> String {
+    let columns: Vec<Vec<&str>> = frames
+        .iter()
+        .map(|&(label, frame)| std::iter::once(label).chain(frame.lines()).collect())
+        .collect();
+    let widths: Vec<usize> = columns
+        .iter()
+        .map(|lines| {
+            lines
+                .iter()
+                .map(|line| visible_width(line))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut out = String::new();
+    for row in 0..rows {
+        let mut line = String::new();
+        for (index, (lines, &width)) in columns.iter().zip(&widths).enumerate() {
+            if index > 0 {
+                line.push_str(&" ".repeat(gap));
+            }
+            let text = lines.get(row).copied().unwrap_or("");
+            line.push_str(text);
+            line.push_str(&" ".repeat(width - visible_width(text)));
+        }
+        out.push_str(line.trim_end_matches(' '));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Largest board, as (width, height), of which `count` copies can be drawn
+/// whole in `style` side by side on a terminal of `columns` by `rows`
+/// characters, `gap` columns apart with a label line above them and the line
+/// below them free. Never less than a single cell.
+pub fn fit_side_by_side(
+    style: RenderStyle,
+    count: usize,
+    gap: usize,
+    columns: u16,
+    rows: u16,
+) -> (usize, usize) {
+    let count = count.max(1);
+    let shared = (columns as usize).saturating_sub(gap * (count - 1)) / count;
+
+    style.fit_to_terminal(shared as u16, rows.saturating_sub(1))
+}
+
+// Characters of `line` a terminal shows, skipping ANSI escape codes
+fn visible_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Codes run up to and including their final letter
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            width += 1;
+        }
+    }
+
+    width
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::LifeBoard;
+
+/// How a run stopped by `LifeBoard::run_until_cycle` ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(tag = "kind", rename_all = "snake_case")
+)]
+pub enum RunOutcome {
+    /// The board stopped changing at generation `start`.
+    Stable { start: u64 },
+    /// The board repeats every `period` generations, from generation `start`.
+    Oscillating { period: u64, start: u64 },
+    /// No repeat was found within the generation limit.
+    MaxedOut,
+}
+
+// Boards are compared and hashed by their dimensions and cells alone, so
+// the same pattern reached under another rule or at another generation is
+// the same state
+impl Hash for LifeBoard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.cells.hash(state);
+    }
+}
+
+impl PartialEq for LifeBoard {
+    fn eq(&self, other: &LifeBoard) -> bool {
+        self.width == other.width && self.height == other.height && self.cells == other.cells
+    }
+}
+
+impl Eq for LifeBoard {}
+
+impl LifeBoard {
+    /// Steps the board until it returns to a state it has been in before, or
+    /// until `max_generations` steps have been taken.
+    ///
+    /// Board states are remembered by hash, so a hash collision can in
+    /// principle report a cycle that is not there.
+    pub fn run_until_cycle(&mut self, max_generations: usize) -> RunOutcome {
+        let mut seen = HashMap::new();
+
+        for steps in 0..=max_generations {
+            if let Some(outcome) = self.check_cycle(&mut seen) {
+                return outcome;
+            }
+            if steps == max_generations {
+                break;
+            }
+            self.advance();
+        }
+
+        RunOutcome::MaxedOut
+    }
+
+    // Looks the board up among the states in `seen`, keyed by Zobrist hash,
+    // and remembers it if it is new. The board keeps its hash from then on.
+    pub(crate) fn check_cycle(&mut self, seen: &mut HashMap<u64, u64>) -> Option<RunOutcome> {
+        let hash = self.keep_zobrist_hash();
+
+        match seen.get(&hash) {
+            Some(&start) => Some(match self.generation - start {
+                1 => RunOutcome::Stable { start },
+                period => RunOutcome::Oscillating { period, start },
+            }),
+            None => {
+                seen.insert(hash, self.generation);
+                None
+            }
+        }
+    }
+}
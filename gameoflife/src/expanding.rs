@@ -0,0 +1,329 @@
+use crate::pattern::Pattern;
+use crate::rule::Rule;
+use crate::sparse::SparseLifeBoard;
+use crate::{Anchor, BoundaryMode, LifeBoard, Rect};
+
+const INITIAL_SIZE: usize = 16;
+const DEFAULT_MARGIN: usize = 2;
+// Stepping that would grow the board to at least this many cells while fewer
+// than one in SPARSE_RATIO of them would be alive switches to storing the
+// live cells only
+const MIN_SPARSE_CELLS: usize = 128 * 128;
+const SPARSE_RATIO: usize = 64;
+
+/// Rule with birth on zero neighbours (B0) given to an `ExpandingLifeBoard`,
+/// which would have to bring the whole infinite plane to life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BirthOnZero(pub Rule);
+
+impl std::fmt::Display for BirthOnZero {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "rule {} gives birth on zero neighbours", self.0)
+    }
+}
+
+impl std::error::Error for BirthOnZero {}
+
+/// Unbounded board kept in a dense `LifeBoard` that grows as the pattern does.
+///
+/// Cells are addressed by signed (row, column) world coordinates. Before every
+/// step, if a live cell lies within the margin of the allocated edge, the
+/// allocation doubles in each direction around its centre, so patterns never
+/// meet a wall. Growth keeps every cell at its world coordinates. Rules with
+/// birth on zero neighbours (B0) would fill the infinite plane and are
+/// rejected.
+///
+/// Patterns that spread out, like a gun's stream of gliders, would leave most
+/// of a doubled board empty. Once stepping would grow the board to 128 by 128
+/// cells or more with fewer than one cell in 64 alive, it keeps its live
+/// cells in a `SparseLifeBoard` instead, so memory follows the population
+/// rather than the area the pattern has reached.
+#[derive(Debug)]
+pub struct ExpandingLifeBoard {
+    board: LifeBoard,
+    // World coordinates of the board's top left cell
+    origin_row: i64,
+    origin_col: i64,
+    margin: usize,
+    // Takes over from the dense board once that would be mostly empty
+    sparse: Option<SparseLifeBoard>,
+}
+
+impl ExpandingLifeBoard {
+    pub fn new() -> ExpandingLifeBoard {
+        ExpandingLifeBoard::with_margin(DEFAULT_MARGIN)
+    }
+
+    /// Creates a board that grows once a live cell comes within `margin`
+    /// cells of its edge. The margin is at least one.
+    pub fn with_margin(margin: usize) -> ExpandingLifeBoard {
+        let mut board = LifeBoard::new_square(INITIAL_SIZE.max(4 * margin));
+        board.set_boundary(BoundaryMode::Dead);
+        let half = (board.width / 2) as i64;
+
+        ExpandingLifeBoard {
+            board,
+            origin_row: -half,
+            origin_col: -half,
+            margin: margin.max(1),
+            sparse: None,
+        }
+    }
+
+    /// Copies the live cells of a dense board, keeping their coordinates,
+    /// rule and generation. Boards following a B0 rule are rejected.
+    pub fn from_board(board: &LifeBoard) -> Result<ExpandingLifeBoard, BirthOnZero> {
+        let mut expanding = ExpandingLifeBoard::new();
+        expanding.set_rule(board.rule)?;
+        expanding.board.generation = board.generation;
+        for (row, col) in board.live_cells() {
+            expanding.set(row as i64, col as i64, true);
+        }
+
+        Ok(expanding)
+    }
+
+    /// Width of the allocated board, or zero once only live cells are kept.
+    pub fn width(&self) -> usize {
+        self.board.width
+    }
+
+    /// Height of the allocated board, or zero once only live cells are kept.
+    pub fn height(&self) -> usize {
+        self.board.height
+    }
+
+    /// Whether the board has switched to keeping only its live cells.
+    pub fn is_sparse(&self) -> bool {
+        self.sparse.is_some()
+    }
+
+    pub fn rule(&self) -> Rule {
+        match &self.sparse {
+            Some(sparse) => sparse.rule(),
+            None => self.board.rule,
+        }
+    }
+
+    /// Sets the rule followed from the next step on. B0 rules are rejected,
+    /// leaving the rule as it was.
+    pub fn set_rule(&mut self, rule: Rule) -> Result<(), BirthOnZero> {
+        if rule.applies_to(0) {
+            return Err(BirthOnZero(rule));
+        }
+
+        match &mut self.sparse {
+            Some(sparse) => sparse.set_rule(rule),
+            None => self.board.set_rule(rule),
+        }
+        Ok(())
+    }
+
+    pub fn generation(&self) -> u64 {
+        match &self.sparse {
+            Some(sparse) => sparse.generation(),
+            None => self.board.generation,
+        }
+    }
+
+    pub fn population(&self) -> usize {
+        match &self.sparse {
+            Some(sparse) => sparse.population(),
+            None => self.board.population(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match &self.sparse {
+            Some(sparse) => sparse.is_empty(),
+            None => self.board.is_empty(),
+        }
+    }
+
+    pub fn get(&self, row: i64, col: i64) -> bool {
+        if let Some(sparse) = &self.sparse {
+            return sparse.get(row, col);
+        }
+
+        match self.local(row, col) {
+            Some((row, col)) => self.board[(row, col)],
+            None => false,
+        }
+    }
+
+    pub fn set(&mut self, row: i64, col: i64, alive: bool) {
+        if alive {
+            self.reserve(row, col, row, col);
+        }
+        if let Some(sparse) = &mut self.sparse {
+            return sparse.set(row, col, alive);
+        }
+        if let Some((row, col)) = self.local(row, col) {
+            self.board.set(row, col, alive).unwrap();
+        }
+    }
+
+    /// (row, column) world coordinates of the live cells, in row order while
+    /// the board is dense and in no particular order once it is sparse.
+    pub fn live_cells(&self) -> Box<dyn Iterator<Item = (i64, i64)> + '_> {
+        if let Some(sparse) = &self.sparse {
+            return Box::new(sparse.live_cells());
+        }
+
+        Box::new(
+            self.board.live_cells().map(move |(row, col)| {
+                (self.origin_row + row as i64, self.origin_col + col as i64)
+            }),
+        )
+    }
+
+    /// Sets the cells of `pattern` alive with its top left corner at `row`, `col`.
+    pub fn stamp(&mut self, pattern: &Pattern, row: i64, col: i64) {
+        if pattern.cells().is_empty() {
+            return;
+        }
+
+        self.reserve(
+            row,
+            col,
+            row + pattern.height() as i64 - 1,
+            col + pattern.width() as i64 - 1,
+        );
+        for &(r, c) in pattern.cells() {
+            self.set(row + r as i64, col + c as i64, true);
+        }
+    }
+
+    /// Replaces the board with its next generation, growing it first if the
+    /// pattern is near the edge.
+    pub fn advance(&mut self) {
+        if let Some((top, left, bottom, right)) = self.edge_bounds() {
+            let cells = self.board.width * self.board.height * 4;
+            if cells >= MIN_SPARSE_CELLS && self.board.population() * SPARSE_RATIO < cells {
+                let sparse =
+                    SparseLifeBoard::from_board_at(&self.board, self.origin_row, self.origin_col);
+                self.sparse = Some(sparse);
+                self.board = LifeBoard::new(0, 0);
+            } else {
+                self.reserve(top, left, bottom, right);
+            }
+        }
+        match &mut self.sparse {
+            Some(sparse) => sparse.advance(),
+            None => self.board.advance(),
+        }
+    }
+
+    pub fn advance_by(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.advance();
+        }
+    }
+
+    /// Copies the `width` by `height` region with its top left corner at
+    /// `row`, `col` into a dense board, which can then be rendered.
+    pub fn viewport(&self, row: i64, col: i64, width: usize, height: usize) -> LifeBoard {
+        let mut board = LifeBoard::new(width, height);
+        board.rule = self.rule();
+        board.generation = self.generation();
+
+        for (r, c) in self.live_cells() {
+            let (r, c) = (r - row, c - col);
+            if r >= 0 && c >= 0 && (r as usize) < height && (c as usize) < width {
+                board[(r as usize, c as usize)] = true;
+            }
+        }
+
+        board
+    }
+
+    // Board coordinates of a world cell, if it is allocated
+    fn local(&self, row: i64, col: i64) -> Option<(usize, usize)> {
+        let (row, col) = (row - self.origin_row, col - self.origin_col);
+        if row >= 0
+            && col >= 0
+            && (row as usize) < self.board.height
+            && (col as usize) < self.board.width
+        {
+            Some((row as usize, col as usize))
+        } else {
+            None
+        }
+    }
+
+    // World coordinates of the top left and bottom right corners around the
+    // live cells within the margin of the edge
+    fn edge_bounds(&self) -> Option<(i64, i64, i64, i64)> {
+        if self.sparse.is_some() {
+            return None;
+        }
+
+        let (width, height, margin) = (self.board.width, self.board.height, self.margin);
+
+        // Only the cells near the edge matter, so look at those strips alone
+        let strips = [
+            Rect {
+                row: 0,
+                col: 0,
+                width,
+                height: margin,
+            },
+            Rect {
+                row: height.saturating_sub(margin),
+                col: 0,
+                width,
+                height: margin,
+            },
+            Rect {
+                row: 0,
+                col: 0,
+                width: margin,
+                height,
+            },
+            Rect {
+                row: 0,
+                col: width.saturating_sub(margin),
+                width: margin,
+                height,
+            },
+        ];
+        let mut bounds: Option<(i64, i64, i64, i64)> = None;
+        for (row, col) in strips
+            .iter()
+            .flat_map(|&rect| self.board.live_cells_in(rect))
+        {
+            let (row, col) = (self.origin_row + row as i64, self.origin_col + col as i64);
+            bounds = Some(match bounds {
+                Some((top, left, bottom, right)) => {
+                    (top.min(row), left.min(col), bottom.max(row), right.max(col))
+                }
+                None => (row, col, row, col),
+            });
+        }
+
+        bounds
+    }
+
+    // Doubles the board until the given world rectangle lies at least the
+    // margin inside its edges
+    fn reserve(&mut self, top: i64, left: i64, bottom: i64, right: i64) {
+        let margin = self.margin as i64;
+        while self.sparse.is_none()
+            && (top - margin < self.origin_row
+                || left - margin < self.origin_col
+                || bottom + margin >= self.origin_row + self.board.height as i64
+                || right + margin >= self.origin_col + self.board.width as i64)
+        {
+            let (width, height) = (self.board.width, self.board.height);
+            self.board.resize(width * 2, height * 2, Anchor::Center);
+            self.origin_row -= Anchor::Center.offset(height, height * 2) as i64;
+            self.origin_col -= Anchor::Center.offset(width, width * 2) as i64;
+        }
+    }
+}
+
+impl Default for ExpandingLifeBoard {
+    fn default() -> ExpandingLifeBoard {
+        ExpandingLifeBoard::new()
+    }
+}
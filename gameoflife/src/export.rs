@@ -0,0 +1,182 @@
+use crate::LifeBoard;
+
+impl LifeBoard {
+    /// Encodes the board as a binary PBM (P4) image with one pixel per cell,
+    /// black for live cells. Each row is padded to a whole number of bytes.
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let mut pbm = format!("P4\n{} {}\n", self.width, self.height).into_bytes();
+
+        for r in 0..self.height {
+            for byte in self[r].chunks(8) {
+                let bits = byte
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |bits, (i, &alive)| bits | ((alive as u8) << (7 - i)));
+                pbm.push(bits);
+            }
+        }
+
+        pbm
+    }
+
+    /// Fills `buf` with one RGBA pixel per cell in row order, `live` for live
+    /// cells and `dead` for dead ones. This is the layout of the data behind a
+    /// canvas `ImageData` of the board's size.
+    ///
+    /// Panics if `buf` is not exactly `width * height * 4` bytes long.
+    pub fn write_rgba(&self, buf: &mut [u8], live: [u8; 4], dead: [u8; 4]) {
+        assert_eq!(
+            buf.len(),
+            self.cells.len() * 4,
+            "RGBA buffer does not fit a {}x{} board",
+            self.width,
+            self.height
+        );
+
+        for (pixel, &alive) in buf.chunks_exact_mut(4).zip(&self.cells) {
+            pixel.copy_from_slice(if alive { &live } else { &dead });
+        }
+    }
+
+    /// Encodes the board as a PNG image where every cell is a `scale` by
+    /// `scale` block, black for live cells and white for dead ones.
+    #[cfg(feature = "image")]
+    pub fn to_png(&self, scale: u32) -> image::ImageResult<Vec<u8>> {
+        let image = image::GrayImage::from_fn(
+            self.width as u32 * scale,
+            self.height as u32 * scale,
+            |x, y| {
+                let alive = self[(y / scale) as usize][(x / scale) as usize];
+                image::Luma([if alive { 0 } else { 255 }])
+            },
+        );
+
+        let mut png = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut png, image::ImageFormat::Png)?;
+
+        Ok(png.into_inner())
+    }
+
+    /// Encodes the heat map as a binary PGM (P5) image with one pixel per
+    /// cell, scaled so cells that never changed are black and the cells that
+    /// changed most are white. A board that does not track heat is all black.
+    pub fn heat_to_pgm(&self) -> Vec<u8> {
+        let mut pgm = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        pgm.extend(self.heat_levels());
+
+        pgm
+    }
+
+    /// Encodes the heat map as a PNG image where every cell is a `scale` by
+    /// `scale` block, shaded as for `heat_to_pgm`.
+    #[cfg(feature = "image")]
+    pub fn heat_to_png(&self, scale: u32) -> image::ImageResult<Vec<u8>> {
+        let levels = self.heat_levels();
+        let image = image::GrayImage::from_fn(
+            self.width as u32 * scale,
+            self.height as u32 * scale,
+            |x, y| {
+                let index = (y / scale) as usize * self.width + (x / scale) as usize;
+                image::Luma([levels[index]])
+            },
+        );
+
+        let mut png = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut png, image::ImageFormat::Png)?;
+
+        Ok(png.into_inner())
+    }
+
+    /// The heat map as CSV, one line of comma separated counts per row. A
+    /// board that does not track heat gives all zeros.
+    pub fn heat_to_csv(&self) -> String {
+        let mut csv = String::new();
+        for row in 0..self.height {
+            let counts: Vec<String> = (0..self.width)
+                .map(|col| self.heat_at(row * self.width + col).to_string())
+                .collect();
+            csv.push_str(&counts.join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    // Grey level of every cell's heat, 255 for the hottest
+    fn heat_levels(&self) -> Vec<u8> {
+        let hottest = self.heat().iter().copied().max().unwrap_or(0).max(1) as u64;
+        (0..self.cells.len())
+            .map(|index| (self.heat_at(index) as u64 * 255 / hottest) as u8)
+            .collect()
+    }
+
+    fn heat_at(&self, index: usize) -> u32 {
+        self.heat().get(index).copied().unwrap_or(0)
+    }
+}
+
+/// Writes `generations` frames of a run to an animated GIF at `path`,
+/// advancing `board` after each frame. Every cell is drawn as a `scale` by
+/// `scale` block and each frame is shown for `delay_cs` hundredths of a second.
+///
+/// Frames are encoded as they are produced rather than collected first. With
+/// `stop_on_cycle` set the export ends as soon as the board returns to a state
+/// it has already been in, which would otherwise only repeat earlier frames.
+#[cfg(feature = "gif")]
+pub fn export_gif(
+    board: &mut LifeBoard,
+    generations: usize,
+    scale: u32,
+    delay_cs: u16,
+    stop_on_cycle: bool,
+    path: &std::path::Path,
+) -> std::io::Result<()> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::convert::TryFrom;
+    use std::hash::{Hash, Hasher};
+    use std::io::{Error, ErrorKind};
+
+    let too_large = || Error::new(ErrorKind::InvalidInput, "board is too large for a GIF");
+    let scale = scale as usize;
+    let width = u16::try_from(board.width * scale).map_err(|_| too_large())?;
+    let height = u16::try_from(board.height * scale).map_err(|_| too_large())?;
+
+    // Dead cells are white and live cells black
+    let palette = [0xff, 0xff, 0xff, 0x00, 0x00, 0x00];
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    let mut encoder = gif::Encoder::new(file, width, height, &palette).map_err(Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(Error::other)?;
+
+    let mut seen = HashSet::new();
+    let mut pixels = vec![0; width as usize * height as usize];
+    for _ in 0..generations {
+        if stop_on_cycle {
+            let mut hasher = DefaultHasher::new();
+            board.hash(&mut hasher);
+            if !seen.insert(hasher.finish()) {
+                break;
+            }
+        }
+
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            let (y, x) = (i / width as usize, i % width as usize);
+            *pixel = board[y / scale][x / scale] as u8;
+        }
+
+        let frame = gif::Frame {
+            width,
+            height,
+            delay: delay_cs,
+            buffer: std::borrow::Cow::Borrowed(&pixels),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(Error::other)?;
+
+        board.advance();
+    }
+
+    Ok(())
+}
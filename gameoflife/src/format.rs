@@ -0,0 +1,437 @@
+use std::convert::TryFrom;
+
+use crate::rule::{Rule, RuleParseError};
+use crate::sparse::SparseLifeBoard;
+use crate::LifeBoard;
+
+const RLE_LINE_WIDTH: usize = 70;
+
+const LIFE_106_HEADER: &str = "#Life 1.06";
+
+/// Longest side, in cells, of a board loaded from an RLE, plaintext or Life
+/// 1.06 pattern, offset included. Files asking for more are rejected before
+/// anything is allocated.
+pub const MAX_PATTERN_SIDE: usize = 4096;
+
+/// Reasons a pattern file can be rejected.
+#[derive(Debug, PartialEq)]
+pub enum FormatError {
+    /// The RLE `x = .., y = ..` header line, the `#Life 1.06` header line or
+    /// the `[M2]` header line is missing or malformed.
+    InvalidHeader,
+    /// The header names a rule that could not be parsed.
+    InvalidRule(RuleParseError),
+    /// The pattern contains a character that is not part of the format.
+    UnexpectedChar(char),
+    /// The pattern has more cells than its header declares.
+    OutOfBounds,
+    /// An RLE run count, or the position it moves to, does not fit in a
+    /// `usize`.
+    InvalidRunCount,
+    /// The pattern, with its offset, would need a board wider or taller than
+    /// `MAX_PATTERN_SIDE` cells.
+    TooLarge { width: usize, height: usize },
+    /// A line of a Life 1.06 file, counted from 1, is not an `x y` pair of
+    /// whole numbers.
+    InvalidCoordinates(usize),
+    /// A line of a macrocell file, counted from 1, is neither an 8 by 8 leaf
+    /// nor a `level nw ne sw se` node.
+    InvalidNode(usize),
+    /// A macrocell node refers to a node not defined on an earlier line.
+    UndefinedNode { line: usize, node: usize },
+    /// A quadrant of a macrocell node is not one level below the node.
+    LevelMismatch { line: usize, node: usize },
+    /// The pattern's rule cannot be run by the board it is loaded into.
+    UnsupportedRule(Rule),
+}
+
+impl std::fmt::Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FormatError::InvalidHeader => f.write_str("missing or malformed header"),
+            FormatError::InvalidRule(e) => write!(f, "invalid rule: {}", e),
+            FormatError::UnexpectedChar(c) => write!(f, "unexpected character '{}'", c),
+            FormatError::OutOfBounds => f.write_str("pattern is larger than its header"),
+            FormatError::InvalidRunCount => f.write_str("run count is too large"),
+            FormatError::TooLarge { width, height } => write!(
+                f,
+                "{}x{} pattern is larger than {} cells on a side",
+                width, height, MAX_PATTERN_SIDE
+            ),
+            FormatError::InvalidCoordinates(line) => {
+                write!(f, "line {}: expected an x y coordinate pair", line)
+            }
+            FormatError::InvalidNode(line) => write!(f, "line {}: malformed macrocell node", line),
+            FormatError::UndefinedNode { line, node } => {
+                write!(
+                    f,
+                    "line {}: node {} is not defined on an earlier line",
+                    line, node
+                )
+            }
+            FormatError::LevelMismatch { line, node } => {
+                write!(
+                    f,
+                    "line {}: node {} is not one level below this node",
+                    line, node
+                )
+            }
+            FormatError::UnsupportedRule(rule) => write!(f, "rule {} is not supported", rule),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl From<RuleParseError> for FormatError {
+    fn from(e: RuleParseError) -> FormatError {
+        FormatError::InvalidRule(e)
+    }
+}
+
+impl LifeBoard {
+    /// Loads a pattern in run length encoded format.
+    ///
+    /// The board is sized to fit the pattern. If the file has an `#R x y`
+    /// line, as written by `to_rle`, the pattern is placed at that offset and
+    /// the board grows to fit it.
+    pub fn from_rle(rle: &str) -> Result<LifeBoard, FormatError> {
+        let mut origin = (0, 0);
+        let mut lines = rle.lines().map(str::trim).filter(|l| !l.is_empty());
+
+        let header = loop {
+            match lines.next() {
+                Some(line) if line.starts_with('#') => {
+                    if let Some(offset) = parse_origin(line, "#R") {
+                        origin = offset;
+                    }
+                }
+                Some(line) => break line,
+                None => return Err(FormatError::InvalidHeader),
+            }
+        };
+
+        let (width, height, rule) = parse_header(header)?;
+        let mut board = new_pattern_board(width, height, origin)?;
+        board.rule = rule;
+
+        let (mut row, mut col): (usize, usize) = (0, 0);
+        let mut run: usize = 0;
+        'body: for line in lines {
+            for c in line.chars() {
+                let count = run.max(1);
+                match c {
+                    '0'..='9' => {
+                        run = run
+                            .checked_mul(10)
+                            .and_then(|run| run.checked_add(c.to_digit(10).unwrap() as usize))
+                            .ok_or(FormatError::InvalidRunCount)?;
+                        continue;
+                    }
+                    'b' | '.' => {
+                        col = col.checked_add(count).ok_or(FormatError::InvalidRunCount)?
+                    }
+                    'o' | 'A'..='Z' => {
+                        if row >= height || count > width || col > width - count {
+                            return Err(FormatError::OutOfBounds);
+                        }
+                        for c in col..col + count {
+                            board[row + origin.1][c + origin.0] = true;
+                        }
+                        col += count;
+                    }
+                    '$' => {
+                        row = row.checked_add(count).ok_or(FormatError::InvalidRunCount)?;
+                        col = 0;
+                    }
+                    '!' => break 'body,
+                    c if c.is_whitespace() => {}
+                    c => return Err(FormatError::UnexpectedChar(c)),
+                }
+                run = 0;
+            }
+        }
+
+        Ok(board)
+    }
+
+    /// Writes the live cells in run length encoded format.
+    ///
+    /// Only the bounding box of the live cells is written, with its top left
+    /// corner recorded in an `#R x y` comment.
+    pub fn to_rle(&self) -> String {
+        let (min, max) = match self.live_bounds() {
+            Some(bounds) => bounds,
+            None => return format!("x = 0, y = 0, rule = {}\n!\n", self.rule),
+        };
+
+        let mut tokens = Vec::new();
+        let mut blank_rows = 0;
+        for row in min.1..=max.1 {
+            let cells = &self[row][min.0..=max.0];
+            if !cells.contains(&true) {
+                blank_rows += 1;
+                continue;
+            }
+            if row > min.1 {
+                tokens.push(run_token(blank_rows + 1, '$'));
+            }
+            blank_rows = 0;
+
+            let mut col = 0;
+            while col < cells.len() {
+                let state = cells[col];
+                let run = cells[col..].iter().take_while(|&&c| c == state).count();
+                // Dead cells at the end of a row are implied
+                if state || col + run < cells.len() {
+                    tokens.push(run_token(run, if state { 'o' } else { 'b' }));
+                }
+                col += run;
+            }
+        }
+        tokens.push("!".to_string());
+
+        let mut rle = format!(
+            "#R {} {}\nx = {}, y = {}, rule = {}\n",
+            min.0,
+            min.1,
+            max.0 - min.0 + 1,
+            max.1 - min.1 + 1,
+            self.rule
+        );
+        let mut line_len = 0;
+        for token in tokens {
+            if line_len + token.len() > RLE_LINE_WIDTH {
+                rle.push('\n');
+                line_len = 0;
+            }
+            line_len += token.len();
+            rle.push_str(&token);
+        }
+        rle.push('\n');
+
+        rle
+    }
+
+    /// Loads a pattern in plaintext format, where `O` is a live cell and `.` a
+    /// dead one. An `!Origin: x y` comment, as written by `to_plaintext`,
+    /// places the pattern at that offset.
+    pub fn from_plaintext(text: &str) -> Result<LifeBoard, FormatError> {
+        let mut origin = (0, 0);
+        let mut rows = Vec::new();
+
+        for line in text.lines() {
+            if line.starts_with('!') {
+                if let Some(offset) = parse_origin(line, "!Origin:") {
+                    origin = offset;
+                }
+                continue;
+            }
+
+            let row = line
+                .trim_end()
+                .chars()
+                .map(|c| match c {
+                    'O' | '*' => Ok(true),
+                    '.' => Ok(false),
+                    c => Err(FormatError::UnexpectedChar(c)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            rows.push(row);
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut board = new_pattern_board(width, rows.len(), origin)?;
+        for (r, row) in rows.iter().enumerate() {
+            for (c, &alive) in row.iter().enumerate() {
+                board[r + origin.1][c + origin.0] = alive;
+            }
+        }
+
+        Ok(board)
+    }
+
+    /// Writes the live cells in plaintext format.
+    ///
+    /// Only the bounding box of the live cells is written, with its top left
+    /// corner recorded in an `!Origin: x y` comment.
+    pub fn to_plaintext(&self) -> String {
+        let (min, max) = match self.live_bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        let mut text = format!("!Origin: {} {}\n", min.0, min.1);
+        for row in min.1..=max.1 {
+            for &alive in &self[row][min.0..=max.0] {
+                text.push(if alive { 'O' } else { '.' });
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// Loads a pattern in Life 1.06 format: a `#Life 1.06` header followed by
+    /// the `x y` coordinates of each live cell, one pair to a line.
+    ///
+    /// Cell `x y` is placed at column `x` and row `y`, so files written by
+    /// `to_life106` load back in place. If any coordinate is negative, the
+    /// whole pattern is shifted right or down just enough to fit. The board
+    /// is sized to fit the pattern and uses Conway's rule, as the format does
+    /// not record one.
+    pub fn from_life106(text: &str) -> Result<LifeBoard, FormatError> {
+        let cells = parse_life106(text)?;
+        let min_x = cells.iter().map(|&(x, _, _)| x).min().unwrap_or(0).min(0);
+        let min_y = cells.iter().map(|&(_, y, _)| y).min().unwrap_or(0).min(0);
+
+        let mut positions = Vec::with_capacity(cells.len());
+        for (x, y, line) in cells {
+            let col = x.checked_sub(min_x).and_then(|c| usize::try_from(c).ok());
+            let row = y.checked_sub(min_y).and_then(|r| usize::try_from(r).ok());
+            match (col, row) {
+                (Some(col), Some(row)) => positions.push((col, row)),
+                _ => return Err(FormatError::InvalidCoordinates(line)),
+            }
+        }
+
+        let width = positions.iter().map(|&(col, _)| col + 1).max().unwrap_or(0);
+        let height = positions.iter().map(|&(_, row)| row + 1).max().unwrap_or(0);
+        let mut board = new_pattern_board(width, height, (0, 0))?;
+        for (col, row) in positions {
+            board[row][col] = true;
+        }
+
+        Ok(board)
+    }
+
+    /// Writes the live cells in Life 1.06 format, in row order, with each
+    /// cell's column as its x and its row as its y.
+    pub fn to_life106(&self) -> String {
+        write_life106(self.live_cells().map(|(row, col)| (col as i64, row as i64)))
+    }
+
+    // Smallest and largest (column, row) holding a live cell
+    fn live_bounds(&self) -> Option<((usize, usize), (usize, usize))> {
+        self.iter()
+            .filter(|cell| *cell.state)
+            .fold(None, |bounds, cell| {
+                let ((min_col, min_row), (max_col, max_row)) =
+                    bounds.unwrap_or(((cell.col, cell.row), (cell.col, cell.row)));
+                Some((
+                    (min_col.min(cell.col), min_row.min(cell.row)),
+                    (max_col.max(cell.col), max_row.max(cell.row)),
+                ))
+            })
+    }
+}
+
+impl SparseLifeBoard {
+    /// Loads a pattern in Life 1.06 format, setting cell `x y` alive at row
+    /// `y`, column `x` without moving it. The board uses Conway's rule.
+    pub fn from_life106(text: &str) -> Result<SparseLifeBoard, FormatError> {
+        let mut board = SparseLifeBoard::new();
+        for (x, y, _) in parse_life106(text)? {
+            board.set(y, x, true);
+        }
+
+        Ok(board)
+    }
+
+    /// Writes the live cells in Life 1.06 format, in row order.
+    pub fn to_life106(&self) -> String {
+        let mut cells: Vec<_> = self.live_cells().collect();
+        cells.sort_unstable();
+
+        write_life106(cells.into_iter().map(|(row, col)| (col, row)))
+    }
+}
+
+// Parses a Life 1.06 file into the x, y and line number of every live cell.
+// Blank lines and comments after the header are skipped.
+fn parse_life106(text: &str) -> Result<Vec<(i64, i64, usize)>, FormatError> {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty());
+
+    match lines.next() {
+        Some((_, header)) if header == LIFE_106_HEADER => {}
+        _ => return Err(FormatError::InvalidHeader),
+    }
+
+    let mut cells = Vec::new();
+    for (index, line) in lines.filter(|(_, line)| !line.starts_with('#')) {
+        let mut parts = line.split_whitespace().map(str::parse::<i64>);
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(x)), Some(Ok(y)), None) => cells.push((x, y, index + 1)),
+            _ => return Err(FormatError::InvalidCoordinates(index + 1)),
+        }
+    }
+
+    Ok(cells)
+}
+
+fn write_life106(cells: impl Iterator<Item = (i64, i64)>) -> String {
+    let mut text = format!("{}\n", LIFE_106_HEADER);
+    for (x, y) in cells {
+        text.push_str(&format!("{} {}\n", x, y));
+    }
+
+    text
+}
+
+// Dead board holding a `width` by `height` pattern placed at `origin`, a
+// (column, row) pair, unless that is more than `MAX_PATTERN_SIDE` cells on a
+// side
+fn new_pattern_board(
+    width: usize,
+    height: usize,
+    origin: (usize, usize),
+) -> Result<LifeBoard, FormatError> {
+    let width = width.saturating_add(origin.0);
+    let height = height.saturating_add(origin.1);
+    if width > MAX_PATTERN_SIDE || height > MAX_PATTERN_SIDE {
+        return Err(FormatError::TooLarge { width, height });
+    }
+
+    Ok(LifeBoard::new(width, height))
+}
+
+// Parses "x = 3, y = 2, rule = B3/S23", defaulting the rule to Conway's
+fn parse_header(header: &str) -> Result<(usize, usize, Rule), FormatError> {
+    let (mut width, mut height, mut rule) = (None, None, Rule::conway());
+
+    for field in header.split(',') {
+        let mut parts = field.splitn(2, '=').map(str::trim);
+        match (parts.next(), parts.next()) {
+            (Some("x"), Some(x)) => width = x.parse().ok(),
+            (Some("y"), Some(y)) => height = y.parse().ok(),
+            (Some("rule"), Some(r)) => rule = Rule::parse(r)?,
+            _ => return Err(FormatError::InvalidHeader),
+        }
+    }
+
+    match (width, height) {
+        (Some(width), Some(height)) => Ok((width, height, rule)),
+        _ => Err(FormatError::InvalidHeader),
+    }
+}
+
+// Parses the column and row following `prefix`, such as "#R 4 10"
+fn parse_origin(line: &str, prefix: &str) -> Option<(usize, usize)> {
+    let mut parts = line.strip_prefix(prefix)?.split_whitespace();
+    let col = parts.next()?.parse().ok()?;
+    let row = parts.next()?.parse().ok()?;
+
+    Some((col, row))
+}
+
+fn run_token(run: usize, tag: char) -> String {
+    if run == 1 {
+        tag.to_string()
+    } else {
+        format!("{}{}", run, tag)
+    }
+}
@@ -0,0 +1,204 @@
+use crate::render::render_grid;
+use crate::rule::GenerationsRule;
+use crate::{BoundaryMode, CharSet, LifeBoard, OutOfBounds};
+
+/// Fixed size board for Generations rules, holding a `u8` state per cell.
+///
+/// State 0 is dead, 1 is alive and higher states are decaying; see
+/// `GenerationsRule`. Boards are addressed like `LifeBoard`, which remains the
+/// faster choice for two state rules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationsBoard {
+    width: usize,
+    height: usize,
+    boundary: BoundaryMode,
+    rule: GenerationsRule,
+    generation: u64,
+    cells: Vec<u8>,
+}
+
+impl GenerationsBoard {
+    /// Creates a board of dead cells that wraps at its edges.
+    pub fn new(width: usize, height: usize, rule: GenerationsRule) -> GenerationsBoard {
+        GenerationsBoard {
+            width,
+            height,
+            boundary: BoundaryMode::Wrap,
+            rule,
+            generation: 0,
+            cells: vec![0; width * height],
+        }
+    }
+
+    /// Copies a two state board, keeping its boundary mode and generation.
+    /// Live cells start in state 1.
+    pub fn from_board(board: &LifeBoard, rule: GenerationsRule) -> GenerationsBoard {
+        GenerationsBoard {
+            width: board.width,
+            height: board.height,
+            boundary: board.boundary,
+            rule,
+            generation: board.generation,
+            cells: board.cells.iter().map(|&alive| alive as u8).collect(),
+        }
+    }
+
+    /// Two state board of the cells in state 1. Decaying cells become dead.
+    pub fn to_board(&self) -> LifeBoard {
+        let mut board = LifeBoard::new(self.width, self.height);
+        board.set_boundary(self.boundary);
+        board.generation = self.generation;
+
+        for (alive, &state) in board.cells.iter_mut().zip(self.cells.iter()) {
+            *alive = state == 1;
+        }
+
+        board
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn boundary(&self) -> BoundaryMode {
+        self.boundary
+    }
+
+    pub fn set_boundary(&mut self, boundary: BoundaryMode) {
+        self.boundary = boundary;
+    }
+
+    pub fn rule(&self) -> GenerationsRule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: GenerationsRule) {
+        self.rule = rule;
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Number of cells in state 1.
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|&&state| state == 1).count()
+    }
+
+    /// Whether every cell is dead, counting decaying cells as not dead.
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().all(|&state| state == 0)
+    }
+
+    /// State of the cell at `row`, `col`, or `None` if it is outside the board.
+    pub fn get(&self, row: usize, col: usize) -> Option<u8> {
+        if row < self.height && col < self.width {
+            Some(self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Sets the state of the cell at `row`, `col`. Panics if the state is not
+    /// one of the rule's states.
+    pub fn set(&mut self, row: usize, col: usize, state: u8) -> Result<(), OutOfBounds> {
+        assert!(
+            state < self.rule.states(),
+            "state {} is out of range for a rule with {} states",
+            state,
+            self.rule.states()
+        );
+
+        if row < self.height && col < self.width {
+            self.cells[row * self.width + col] = state;
+            Ok(())
+        } else {
+            Err(OutOfBounds { row, col })
+        }
+    }
+
+    /// Writes the next generation of this board into `next`, which must have
+    /// the same dimensions.
+    pub fn step(&self, next: &mut GenerationsBoard) {
+        assert!(
+            self.width == next.width && self.height == next.height,
+            "boards differ in size"
+        );
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let state = self.cells[row * self.width + col];
+                let count = self.live_neighbours(row, col);
+                next.cells[row * self.width + col] = self.rule.next_state(state, count);
+            }
+        }
+
+        next.boundary = self.boundary;
+        next.rule = self.rule;
+        next.generation = self.generation + 1;
+    }
+
+    /// Replaces this board with its next generation.
+    pub fn advance(&mut self) {
+        let mut next = GenerationsBoard::new(self.width, self.height, self.rule);
+        self.step(&mut next);
+        *self = next;
+    }
+
+    pub fn advance_by(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.advance();
+        }
+    }
+
+    /// Draws the board like `LifeBoard::render_to`, with decaying cells drawn
+    /// from `charset.dying`.
+    pub fn render_to(&self, out: &mut impl std::fmt::Write, charset: &CharSet) -> std::fmt::Result {
+        render_grid(out, charset, self.width, self.height, |index| {
+            match self.cells[index] {
+                0 => charset.dead,
+                1 => charset.live,
+                state => charset
+                    .dying
+                    .get(state as usize - 2)
+                    .or_else(|| charset.dying.last())
+                    .copied()
+                    .unwrap_or(charset.dead),
+            }
+        })
+    }
+
+    // Neighbours in state 1; decaying neighbours count as dead
+    fn live_neighbours(&self, row: usize, col: usize) -> u8 {
+        let mut count = 0;
+        for delta_row in -1..=1 {
+            for delta_col in -1..=1 {
+                if (delta_row, delta_col) == (0, 0) {
+                    continue;
+                }
+
+                let neighbour = self.boundary.resolve(
+                    row as isize + delta_row,
+                    col as isize + delta_col,
+                    self.width,
+                    self.height,
+                );
+                if let Some((r, c)) = neighbour {
+                    count += (self.cells[r * self.width + c] == 1) as u8;
+                }
+            }
+        }
+
+        count
+    }
+}
+
+impl std::fmt::Display for GenerationsBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.render_to(f, &CharSet::ASCII)
+    }
+}
@@ -0,0 +1,153 @@
+use std::time::Instant;
+
+use minifb::{KeyRepeat, MouseButton, MouseMode, Window, WindowOptions};
+
+use crate::interactive::{Command, Key, Session};
+use crate::options::Options;
+use crate::rewind::HistoryBuffer;
+use crate::window::WindowLayout;
+use crate::{LifeBoard, Rect};
+
+// Size of the window in pixels, which the board is zoomed to fit
+const WINDOW_SIZE: (usize, usize) = (800, 600);
+
+const LIVE: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+const DEAD: [u8; 4] = [0x00, 0x00, 0x00, 0xff];
+const CURSOR: [u8; 4] = [0x40, 0x80, 0xff, 0xff];
+const BACKGROUND: [u8; 4] = [0x30, 0x30, 0x30, 0xff];
+
+/// Runs `board` in a window until it is closed or `q` is pressed, starting
+/// from `session`.
+///
+/// Takes the keys of the terminal's interactive mode, except for panning,
+/// as the whole board is shown. Clicking a cell toggles it. The speed,
+/// soups, noise and rewinding come from `options`.
+pub fn run_window(
+    board: &mut LifeBoard,
+    mut session: Session,
+    options: &Options,
+) -> Result<(), minifb::Error> {
+    let (width, height) = WINDOW_SIZE;
+    let layout = WindowLayout::fit(board.width(), board.height(), width, height);
+    let mut window = Window::new("Game of Life", width, height, WindowOptions::default())?;
+
+    let mut rng = options.rng();
+    let mut noise_rng = options.noise_rng();
+    let mut pacer = options.pacer();
+    let mut history = (options.rewind > 0).then(|| HistoryBuffer::new(options.rewind));
+    let mut next_step = Instant::now() + pacer.interval();
+
+    let mut frame = vec![0; board.width() * board.height() * 4];
+    let mut rgba = vec![0; width * height * 4];
+    let mut pixels = vec![0; width * height];
+    let mut was_down = false;
+
+    while window.is_open() {
+        let mut commands = Vec::new();
+        for key in window.get_keys_pressed(KeyRepeat::Yes) {
+            let key = match key_of(key) {
+                Some(key) => key,
+                None => continue,
+            };
+            let (next, command) = session.handle_key(key, board.width(), board.height());
+            if session.paused && !next.paused {
+                pacer.reset();
+                next_step = Instant::now() + pacer.interval();
+            }
+            session = next;
+            commands.extend(command);
+        }
+
+        // Only the press counts, not holding the button down
+        let down = window.get_mouse_down(MouseButton::Left);
+        if down && !was_down {
+            if let Some((x, y)) = window.get_mouse_pos(MouseMode::Discard) {
+                let (next, command) = layout.click(session, x as usize, y as usize);
+                session = next;
+                commands.extend(command);
+            }
+        }
+        was_down = down;
+
+        for command in commands {
+            match command {
+                Command::Step => {
+                    if let Some(history) = &mut history {
+                        history.push(board);
+                    }
+                    board.advance_noisy(options.noise, &mut noise_rng);
+                }
+                Command::StepBack => {
+                    if let Some(history) = &mut history {
+                        history.step_back(board);
+                    }
+                }
+                Command::Toggle { row, col } => {
+                    board.toggle(row, col).unwrap();
+                }
+                Command::Randomize => board.randomize(options.density(), &mut rng).unwrap(),
+                Command::Clear => board.clear_region(Rect {
+                    row: 0,
+                    col: 0,
+                    width: board.width(),
+                    height: board.height(),
+                }),
+                Command::Faster => {
+                    pacer.speed_up();
+                    next_step = Instant::now() + pacer.interval();
+                }
+                Command::Slower => {
+                    pacer.slow_down();
+                    next_step = Instant::now() + pacer.interval();
+                }
+                Command::Quit => return Ok(()),
+                Command::Pan { .. } => {}
+            }
+        }
+
+        if !session.paused && Instant::now() >= next_step {
+            if let Some(history) = &mut history {
+                history.push(board);
+            }
+            board.advance_noisy(options.noise, &mut noise_rng);
+            let now = Instant::now();
+            next_step = now + pacer.tick(now);
+        }
+
+        board.write_rgba(&mut frame, LIVE, DEAD);
+        let cursor = (session.cursor_row * board.width() + session.cursor_col) * 4;
+        if let Some(pixel) = frame.get_mut(cursor..cursor + 4) {
+            pixel.copy_from_slice(&CURSOR);
+        }
+        layout.draw(&frame, &mut rgba, BACKGROUND);
+        // minifb takes one 0RGB word per pixel
+        for (pixel, rgba) in pixels.iter_mut().zip(rgba.chunks_exact(4)) {
+            *pixel = u32::from_be_bytes([0, rgba[0], rgba[1], rgba[2]]);
+        }
+        window.update_with_buffer(&pixels, width, height)?;
+    }
+
+    Ok(())
+}
+
+fn key_of(key: minifb::Key) -> Option<Key> {
+    use minifb::Key as K;
+
+    let key = match key {
+        K::Space => Key::Char(' '),
+        K::N => Key::Char('n'),
+        K::B => Key::Char('b'),
+        K::R => Key::Char('r'),
+        K::C => Key::Char('c'),
+        K::Equal | K::NumPadPlus => Key::Char('+'),
+        K::Minus | K::NumPadMinus => Key::Char('-'),
+        K::Q | K::Escape => Key::Char('q'),
+        K::Up => Key::Up,
+        K::Down => Key::Down,
+        K::Left => Key::Left,
+        K::Right => Key::Right,
+        K::Enter => Key::Enter,
+        _ => return None,
+    };
+    Some(key)
+}
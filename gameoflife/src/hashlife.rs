@@ -0,0 +1,516 @@
+use std::collections::HashMap;
+
+use crate::format::FormatError;
+use crate::macrocell::{parse_macrocell, write_macrocell, MacroNode, LEAF_LEVEL};
+use crate::rule::Rule;
+use crate::LifeBoard;
+
+type NodeId = usize;
+
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+
+// Largest root, whose cells are addressed from -2^62 to 2^62 so that offsets
+// into it fit in an i64
+const MAX_LEVEL: u8 = 63;
+
+/// Reason a `HashLifeUniverse` could not be built or advanced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashLifeError {
+    /// The board to copy follows a rule other than Conway's.
+    UnsupportedRule(Rule),
+    /// Advancing by 2^`log2_generations` generations would take the
+    /// generation count past `u64::MAX`, or the pattern further from the
+    /// origin than 2^62 cells.
+    TooFar { log2_generations: u8 },
+    /// The cell to set lies 2^62 cells or more from the origin, outside the
+    /// largest square the universe can hold.
+    OutOfRange { row: i64, col: i64 },
+}
+
+impl std::fmt::Display for HashLifeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HashLifeError::UnsupportedRule(rule) => {
+                write!(f, "rule {} is not supported, only B3/S23", rule)
+            }
+            HashLifeError::TooFar { log2_generations } => write!(
+                f,
+                "cannot advance 2^{} generations from here",
+                log2_generations
+            ),
+            HashLifeError::OutOfRange { row, col } => {
+                write!(f, "cell ({}, {}) is out of range", row, col)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HashLifeError {}
+
+// Square of 2^level cells split into four quadrants. Leaves (level 0) are the
+// two single cells and have no quadrants.
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    level: u8,
+    nw: NodeId,
+    ne: NodeId,
+    sw: NodeId,
+    se: NodeId,
+    population: u64,
+}
+
+/// Unbounded Conway's Life universe using Gosper's HashLife algorithm.
+///
+/// The universe is a quadtree in which identical squares are stored once, and
+/// the result of advancing a square is remembered for every square and step
+/// size it is asked for. Repetitive patterns therefore advance through huge
+/// numbers of generations at almost no cost, at the price of memory for the
+/// nodes and results, which are kept for the life of the universe.
+///
+/// Cells are addressed by signed (row, column) coordinates. Only Conway's rule
+/// is supported.
+#[derive(Debug, Clone)]
+pub struct HashLifeUniverse {
+    nodes: Vec<Node>,
+    canonical: HashMap<[NodeId; 4], NodeId>,
+    results: HashMap<(NodeId, u8), NodeId>,
+    // Empty node of each level, built on demand
+    empty: Vec<NodeId>,
+    // Covers rows and columns from -2^(level - 1) to 2^(level - 1)
+    root: NodeId,
+    generation: u64,
+}
+
+impl HashLifeUniverse {
+    pub fn new() -> HashLifeUniverse {
+        let leaf = |population| Node {
+            level: 0,
+            nw: DEAD,
+            ne: DEAD,
+            sw: DEAD,
+            se: DEAD,
+            population,
+        };
+
+        let mut universe = HashLifeUniverse {
+            nodes: vec![leaf(0), leaf(1)],
+            canonical: HashMap::new(),
+            results: HashMap::new(),
+            empty: vec![DEAD],
+            root: DEAD,
+            generation: 0,
+        };
+        universe.root = universe.empty(3);
+
+        universe
+    }
+
+    /// Copies the live cells of a dense board, keeping their coordinates.
+    /// Boards following a rule other than Conway's are rejected.
+    pub fn from_board(board: &LifeBoard) -> Result<HashLifeUniverse, HashLifeError> {
+        if board.rule != Rule::conway() {
+            return Err(HashLifeError::UnsupportedRule(board.rule));
+        }
+
+        let mut universe = HashLifeUniverse::new();
+        for (row, col) in board.live_cells() {
+            universe.set_cell(row as i64, col as i64, true)?;
+        }
+        universe.generation = board.generation;
+
+        Ok(universe)
+    }
+
+    /// Copies the `width` by `height` region with its top left corner at
+    /// `row`, `col` into a dense board. Cells of the region past `i64::MAX`
+    /// are dead.
+    pub fn to_board(&self, row: i64, col: i64, width: usize, height: usize) -> LifeBoard {
+        let mut board = LifeBoard::new(width, height);
+        board.generation = self.generation;
+
+        for cell in board.iter_mut() {
+            let at = row
+                .checked_add(cell.row as i64)
+                .zip(col.checked_add(cell.col as i64));
+            *cell.state = at.is_some_and(|(row, col)| self.get_cell(row, col));
+        }
+
+        board
+    }
+
+    /// Loads a pattern in Golly's macrocell format, `[M2]`, node by node, so
+    /// the quadtree is shared as in the file and loading takes time in
+    /// proportion to the file's length however many cells it holds. The root
+    /// square is centred on the origin, as in Golly. Files naming a rule other
+    /// than Conway's are rejected.
+    pub fn from_macrocell(text: &str) -> Result<HashLifeUniverse, FormatError> {
+        let macrocell = parse_macrocell(text)?;
+        if macrocell.rule != Rule::conway() {
+            return Err(FormatError::UnsupportedRule(macrocell.rule));
+        }
+
+        let mut universe = HashLifeUniverse::new();
+        let mut ids = Vec::with_capacity(macrocell.nodes.len());
+        for node in &macrocell.nodes {
+            let id = match *node {
+                MacroNode::Leaf(rows) => universe.leaf(&rows, 0, 0, LEAF_LEVEL),
+                MacroNode::Branch { level, quadrants } => {
+                    let [nw, ne, sw, se] = quadrants.map(|number| match number {
+                        0 => universe.empty(level - 1),
+                        number => ids[number - 1],
+                    });
+                    universe.join(nw, ne, sw, se)
+                }
+            };
+            ids.push(id);
+        }
+        if let Some(&root) = ids.last() {
+            universe.root = root;
+        }
+
+        Ok(universe)
+    }
+
+    /// Writes the universe in macrocell format, with one line for each
+    /// distinct square of 8 by 8 cells or more that has live cells, so
+    /// repetitive patterns stay small. `from_macrocell` loads it back in
+    /// place. The generation is not recorded.
+    pub fn to_macrocell(&self) -> String {
+        let mut nodes = Vec::new();
+        self.macrocell_node(self.root, &mut HashMap::new(), &mut nodes);
+
+        write_macrocell(Rule::conway(), &nodes)
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root].population
+    }
+
+    pub fn get_cell(&self, row: i64, col: i64) -> bool {
+        let half = self.half_size();
+        if row < -half || row >= half || col < -half || col >= half {
+            return false;
+        }
+
+        self.cell(self.root, row + half, col + half)
+    }
+
+    /// Sets the cell at `row`, `col`, growing the universe to take it in.
+    ///
+    /// Fails, leaving the universe as it was, for cells from 2^62 cells
+    /// away from the origin, which cannot be addressed.
+    pub fn set_cell(&mut self, row: i64, col: i64, alive: bool) -> Result<(), HashLifeError> {
+        let limit = 1 << (MAX_LEVEL - 1);
+        if row < -limit || row >= limit || col < -limit || col >= limit {
+            return Err(HashLifeError::OutOfRange { row, col });
+        }
+
+        while row < -self.half_size()
+            || row >= self.half_size()
+            || col < -self.half_size()
+            || col >= self.half_size()
+        {
+            self.root = self.expand(self.root);
+        }
+
+        let half = self.half_size();
+        self.root = self.set(self.root, row + half, col + half, alive);
+        Ok(())
+    }
+
+    /// Advances the universe by 2^`log2_generations` generations.
+    ///
+    /// Fails, leaving the universe as it was, when the generation count would
+    /// overflow or the pattern could reach more than 2^62 cells from the
+    /// origin. At most 2^60 generations can be taken in one call.
+    pub fn advance(&mut self, log2_generations: u8) -> Result<(), HashLifeError> {
+        let too_far = HashLifeError::TooFar { log2_generations };
+        let generation = 1u64
+            .checked_shl(log2_generations.into())
+            .and_then(|generations| self.generation.checked_add(generations))
+            .ok_or(too_far)?;
+
+        // Pad the pattern with enough empty space that nothing can travel out
+        // of the square the result covers
+        let mut root = self.root;
+        while self.nodes[root].level < log2_generations + 3 || !self.is_padded(root) {
+            if self.nodes[root].level == MAX_LEVEL {
+                return Err(too_far);
+            }
+            root = self.expand(root);
+        }
+
+        self.root = self.successor(root, log2_generations);
+        self.generation = generation;
+        Ok(())
+    }
+
+    fn half_size(&self) -> i64 {
+        1 << (self.nodes[self.root].level - 1)
+    }
+
+    // State of the cell at `row`, `col` from the top left corner of `node`
+    fn cell(&self, mut node: NodeId, mut row: i64, mut col: i64) -> bool {
+        while self.nodes[node].level > 0 {
+            let n = self.nodes[node];
+            let half = 1 << (n.level - 1);
+            node = match (row < half, col < half) {
+                (true, true) => n.nw,
+                (true, false) => n.ne,
+                (false, true) => n.sw,
+                (false, false) => n.se,
+            };
+            row %= half;
+            col %= half;
+        }
+
+        node == ALIVE
+    }
+
+    fn join(&mut self, nw: NodeId, ne: NodeId, sw: NodeId, se: NodeId) -> NodeId {
+        if let Some(&node) = self.canonical.get(&[nw, ne, sw, se]) {
+            return node;
+        }
+
+        let node = Node {
+            level: self.nodes[nw].level + 1,
+            nw,
+            ne,
+            sw,
+            se,
+            population: [nw, ne, sw, se]
+                .iter()
+                .map(|&q| self.nodes[q].population)
+                .sum(),
+        };
+        self.nodes.push(node);
+        self.canonical
+            .insert([nw, ne, sw, se], self.nodes.len() - 1);
+
+        self.nodes.len() - 1
+    }
+
+    fn empty(&mut self, level: u8) -> NodeId {
+        while self.empty.len() <= level as usize {
+            let e = *self.empty.last().unwrap();
+            let next = self.join(e, e, e, e);
+            self.empty.push(next);
+        }
+
+        self.empty[level as usize]
+    }
+
+    // Surrounds a node with empty space, doubling its size around the same centre
+    fn expand(&mut self, node: NodeId) -> NodeId {
+        let n = self.nodes[node];
+        let e = self.empty(n.level - 1);
+
+        let nw = self.join(e, e, e, n.nw);
+        let ne = self.join(e, e, n.ne, e);
+        let sw = self.join(e, n.sw, e, e);
+        let se = self.join(n.se, e, e, e);
+
+        self.join(nw, ne, sw, se)
+    }
+
+    // Square of half the size sharing the node's centre
+    fn centre(&mut self, node: NodeId) -> NodeId {
+        let n = self.nodes[node];
+        let (nw, ne, sw, se) = (
+            self.nodes[n.nw].se,
+            self.nodes[n.ne].sw,
+            self.nodes[n.sw].ne,
+            self.nodes[n.se].nw,
+        );
+
+        self.join(nw, ne, sw, se)
+    }
+
+    // Whether every live cell lies in the middle quarter of the node
+    fn is_padded(&mut self, node: NodeId) -> bool {
+        let inner = self.centre(node);
+        let inner = self.centre(inner);
+
+        self.nodes[inner].population == self.nodes[node].population
+    }
+
+    fn set(&mut self, node: NodeId, row: i64, col: i64, alive: bool) -> NodeId {
+        let n = self.nodes[node];
+        if n.level == 0 {
+            return if alive { ALIVE } else { DEAD };
+        }
+
+        let half = 1 << (n.level - 1);
+        let (mut nw, mut ne, mut sw, mut se) = (n.nw, n.ne, n.sw, n.se);
+        match (row < half, col < half) {
+            (true, true) => nw = self.set(nw, row, col, alive),
+            (true, false) => ne = self.set(ne, row, col - half, alive),
+            (false, true) => sw = self.set(sw, row - half, col, alive),
+            (false, false) => se = self.set(se, row - half, col - half, alive),
+        }
+
+        self.join(nw, ne, sw, se)
+    }
+
+    // Centre half of a node of level 2 or more, advanced by 2^`log2_steps`
+    // generations, where `log2_steps` is at most the level less two
+    fn successor(&mut self, node: NodeId, log2_steps: u8) -> NodeId {
+        if let Some(&result) = self.results.get(&(node, log2_steps)) {
+            return result;
+        }
+
+        let n = self.nodes[node];
+        let result = if n.population == 0 {
+            self.empty(n.level - 1)
+        } else if n.level == 2 {
+            self.step_4x4(node)
+        } else {
+            let [nw, ne, sw, se] = [n.nw, n.ne, n.sw, n.se].map(|q| self.nodes[q]);
+
+            // Nine overlapping squares of half the size, in row order
+            let squares = [
+                n.nw,
+                self.join(nw.ne, ne.nw, nw.se, ne.sw),
+                n.ne,
+                self.join(nw.sw, nw.se, sw.nw, sw.ne),
+                self.join(nw.se, ne.sw, sw.ne, se.nw),
+                self.join(ne.sw, ne.se, se.nw, se.ne),
+                n.sw,
+                self.join(sw.ne, se.nw, sw.se, se.sw),
+                n.se,
+            ];
+
+            // At full speed each half of the time is spent in one of the two
+            // rounds, otherwise the first round only crops to the centre
+            let full_speed = log2_steps == n.level - 2;
+            let mut r = [DEAD; 9];
+            for (r, &square) in r.iter_mut().zip(squares.iter()) {
+                *r = if full_speed {
+                    self.successor(square, n.level - 3)
+                } else {
+                    self.centre(square)
+                };
+            }
+
+            let next_steps = if full_speed { n.level - 3 } else { log2_steps };
+            let quadrants = [
+                self.join(r[0], r[1], r[3], r[4]),
+                self.join(r[1], r[2], r[4], r[5]),
+                self.join(r[3], r[4], r[6], r[7]),
+                self.join(r[4], r[5], r[7], r[8]),
+            ];
+            let [nw, ne, sw, se] = quadrants.map(|q| self.successor(q, next_steps));
+
+            self.join(nw, ne, sw, se)
+        };
+
+        self.results.insert((node, log2_steps), result);
+        result
+    }
+
+    // Node of 2^`level` cells of the 8 by 8 leaf `rows`, with its top left
+    // corner at `row`, `col` of the leaf
+    fn leaf(&mut self, rows: &[u8; 8], row: usize, col: usize, level: u8) -> NodeId {
+        if level == 0 {
+            return if rows[row] >> col & 1 == 1 {
+                ALIVE
+            } else {
+                DEAD
+            };
+        }
+
+        let half = 1 << (level - 1);
+        let nw = self.leaf(rows, row, col, level - 1);
+        let ne = self.leaf(rows, row, col + half, level - 1);
+        let sw = self.leaf(rows, row + half, col, level - 1);
+        let se = self.leaf(rows, row + half, col + half, level - 1);
+
+        self.join(nw, ne, sw, se)
+    }
+
+    // Number of `node` in a macrocell file, adding it after its quadrants to
+    // `nodes` if it is not numbered yet. Empty nodes are 0.
+    fn macrocell_node(
+        &self,
+        node: NodeId,
+        numbers: &mut HashMap<NodeId, usize>,
+        nodes: &mut Vec<MacroNode>,
+    ) -> usize {
+        let n = self.nodes[node];
+        if n.population == 0 {
+            return 0;
+        }
+        if let Some(&number) = numbers.get(&node) {
+            return number;
+        }
+
+        let written = if n.level == LEAF_LEVEL {
+            let mut rows = [0u8; 8];
+            for (row, bits) in rows.iter_mut().enumerate() {
+                for col in 0..8 {
+                    if self.cell(node, row as i64, col) {
+                        *bits |= 1 << col;
+                    }
+                }
+            }
+            MacroNode::Leaf(rows)
+        } else {
+            let quadrants = [n.nw, n.ne, n.sw, n.se]
+                .map(|quadrant| self.macrocell_node(quadrant, numbers, nodes));
+            MacroNode::Branch {
+                level: n.level,
+                quadrants,
+            }
+        };
+        nodes.push(written);
+        numbers.insert(node, nodes.len());
+
+        nodes.len()
+    }
+
+    // Advances the centre 2x2 cells of a 4x4 node by one generation
+    fn step_4x4(&mut self, node: NodeId) -> NodeId {
+        let n = self.nodes[node];
+        let mut cells = [[false; 4]; 4];
+        for (quadrant, (row, col)) in [
+            (n.nw, (0, 0)),
+            (n.ne, (0, 2)),
+            (n.sw, (2, 0)),
+            (n.se, (2, 2)),
+        ] {
+            let q = self.nodes[quadrant];
+            cells[row][col] = q.nw == ALIVE;
+            cells[row][col + 1] = q.ne == ALIVE;
+            cells[row + 1][col] = q.sw == ALIVE;
+            cells[row + 1][col + 1] = q.se == ALIVE;
+        }
+
+        let next = |row: usize, col: usize| {
+            let count = cells[row - 1..=row + 1]
+                .iter()
+                .flat_map(|r| &r[col - 1..=col + 1])
+                .filter(|&&alive| alive)
+                .count();
+            let alive = cells[row][col];
+            if crate::next_state(alive, count as u8 - alive as u8) {
+                ALIVE
+            } else {
+                DEAD
+            }
+        };
+
+        let (nw, ne, sw, se) = (next(1, 1), next(1, 2), next(2, 1), next(2, 2));
+        self.join(nw, ne, sw, se)
+    }
+}
+
+impl Default for HashLifeUniverse {
+    fn default() -> HashLifeUniverse {
+        HashLifeUniverse::new()
+    }
+}
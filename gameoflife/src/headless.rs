@@ -0,0 +1,133 @@
+#[cfg(feature = "native")]
+use std::collections::HashMap;
+#[cfg(feature = "native")]
+use std::time::Instant;
+
+#[cfg(feature = "native")]
+use crate::cycle::RunOutcome;
+use crate::{LifeBoard, Rect};
+
+/// Statistics of one generation of a headless run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GenerationRecord {
+    pub generation: u64,
+    pub population: usize,
+    /// Cells that came alive in the step to this generation.
+    pub births: usize,
+    /// Cells that died in the step to this generation.
+    pub deaths: usize,
+    /// Smallest rectangle holding the live cells, if there are any.
+    pub bounding_box: Option<Rect>,
+}
+
+impl GenerationRecord {
+    /// Record of `current`, the generation after `previous`. Without a
+    /// previous generation there are no births or deaths.
+    pub fn new(previous: Option<&LifeBoard>, current: &LifeBoard) -> GenerationRecord {
+        let stats = match previous {
+            Some(previous) => current.stats_since(previous),
+            None => current.stats(),
+        };
+
+        GenerationRecord {
+            generation: current.generation(),
+            population: stats.population,
+            births: stats.births.unwrap_or(0),
+            deaths: stats.deaths.unwrap_or(0),
+            bounding_box: stats.bounding_box,
+        }
+    }
+}
+
+impl std::fmt::Display for GenerationRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "generation {} population {} births {} deaths {}",
+            self.generation, self.population, self.births, self.deaths
+        )?;
+        match self.bounding_box {
+            Some(rect) => write!(
+                f,
+                " box {}x{} at {},{}",
+                rect.width, rect.height, rect.row, rect.col
+            ),
+            None => f.write_str(" box none"),
+        }
+    }
+}
+
+/// How a headless run ended.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RunSummary {
+    pub outcome: RunOutcome,
+    /// Generations stepped through.
+    pub generations: u64,
+    pub wall_time_secs: f64,
+}
+
+#[cfg(feature = "native")]
+impl std::fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let outcome = match self.outcome {
+            RunOutcome::Stable { start } => format!("stable from generation {}", start),
+            RunOutcome::Oscillating { period, start } => {
+                format!("period {} from generation {}", period, start)
+            }
+            RunOutcome::MaxedOut => "did not settle".to_string(),
+        };
+        write!(
+            f,
+            "{} after {} generations in {:.3}s",
+            outcome, self.generations, self.wall_time_secs
+        )
+    }
+}
+
+/// Steps `board` up to `generations` times without drawing it, passing a
+/// record of the first generation and of every generation that is a multiple
+/// of `every` to `on_record`. The run stops early once the board returns to
+/// a state it has been in, like `LifeBoard::run_until_cycle`.
+///
+/// Panics if `every` is zero.
+#[cfg(feature = "native")]
+pub fn run_headless(
+    board: &mut LifeBoard,
+    generations: usize,
+    every: u64,
+    mut on_record: impl FnMut(&GenerationRecord),
+) -> RunSummary {
+    assert!(every > 0, "sampling interval must be positive");
+    let started = Instant::now();
+    let first = board.generation();
+    let mut seen = HashMap::new();
+    let mut next = board.clone();
+    let mut outcome = RunOutcome::MaxedOut;
+
+    on_record(&GenerationRecord::new(None, board));
+    for steps in 0..=generations {
+        if let Some(found) = board.check_cycle(&mut seen) {
+            outcome = found;
+            break;
+        }
+        if steps == generations {
+            break;
+        }
+
+        // Both boards have the size `board` started with
+        board.step(&mut next).unwrap();
+        std::mem::swap(board, &mut next);
+        if board.generation().is_multiple_of(every) {
+            on_record(&GenerationRecord::new(Some(&next), board));
+        }
+    }
+
+    RunSummary {
+        outcome,
+        generations: board.generation() - first,
+        wall_time_secs: started.elapsed().as_secs_f64(),
+    }
+}
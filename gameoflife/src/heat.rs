@@ -0,0 +1,51 @@
+use crate::LifeBoard;
+
+impl LifeBoard {
+    /// Starts counting how many times each cell changes state, with every
+    /// count at zero.
+    ///
+    /// Counts are kept up to date by every way of stepping the board, noise
+    /// added by `advance_noisy` included, and never change how it steps.
+    /// Cells changed in any other way, through `set` or indexing for example,
+    /// are not counted. Like ages, keeping them costs a pass over the whole
+    /// board per generation except with `advance_active`.
+    pub fn enable_heat(&mut self) {
+        self.heat = Some(vec![0; self.cells.len()]);
+    }
+
+    pub fn disable_heat(&mut self) {
+        self.heat = None;
+    }
+
+    pub fn tracks_heat(&self) -> bool {
+        self.heat.is_some()
+    }
+
+    /// Sets every count back to zero, if counts are kept.
+    pub fn reset_heat(&mut self) {
+        if let Some(heat) = &mut self.heat {
+            heat.fill(0);
+        }
+    }
+
+    /// Times each cell has changed state since counting started or was last
+    /// reset, one count per cell in row order. Empty if counts are not kept.
+    pub fn heat(&self) -> &[u32] {
+        self.heat.as_deref().unwrap_or(&[])
+    }
+
+    // Counts of the cells of `next`, the generation after this board
+    pub(crate) fn next_heat(&self, next: &[bool]) -> Option<Vec<u32>> {
+        let mut heat = self.heat.clone()?;
+        heat_cells(&mut heat, &self.cells, next);
+
+        Some(heat)
+    }
+}
+
+// Counts a change for every cell that differs between `before` and `after`
+pub(crate) fn heat_cells(heat: &mut [u32], before: &[bool], after: &[bool]) {
+    for ((count, &was), &is) in heat.iter_mut().zip(before).zip(after) {
+        *count += (was != is) as u32;
+    }
+}
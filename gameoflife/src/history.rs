@@ -0,0 +1,206 @@
+use std::io::Write;
+
+use crate::cycle::RunOutcome;
+use crate::observer::SimulationObserver;
+use crate::LifeBoard;
+
+/// Bars of a sparkline, from lowest to highest.
+pub const SPARKLINE_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Population of one recorded generation, with the births and deaths since
+/// the recorded generation before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PopulationSample {
+    pub generation: u64,
+    pub population: usize,
+    pub births: usize,
+    pub deaths: usize,
+}
+
+/// Observer that records the population, births and deaths of a run, for
+/// plotting or export as CSV.
+///
+/// A history may be given a limit on the samples it keeps. Once a run goes
+/// past the limit, every other sample is dropped and from then on only every
+/// other generation is recorded, halving again as often as needed. The
+/// births and deaths of dropped generations are added to the next sample
+/// kept, so they still add up to the run's totals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PopulationHistory {
+    samples: Vec<PopulationSample>,
+    limit: Option<usize>,
+    // Generations from one recorded sample to the next
+    stride: u64,
+    // Generations seen, recorded or not
+    seen: u64,
+    // Births and deaths since the last recorded sample
+    births: usize,
+    deaths: usize,
+}
+
+impl PopulationHistory {
+    /// History keeping every generation.
+    pub fn new() -> PopulationHistory {
+        PopulationHistory {
+            samples: Vec::new(),
+            limit: None,
+            stride: 1,
+            seen: 0,
+            births: 0,
+            deaths: 0,
+        }
+    }
+
+    /// History keeping at most `limit` samples.
+    ///
+    /// Panics if `limit` is less than two.
+    pub fn with_limit(limit: usize) -> PopulationHistory {
+        assert!(limit >= 2, "a history must keep at least two samples");
+
+        PopulationHistory {
+            limit: Some(limit),
+            ..PopulationHistory::new()
+        }
+    }
+
+    pub fn samples(&self) -> &[PopulationSample] {
+        &self.samples
+    }
+
+    /// Generations from one sample to the next, one until the history has
+    /// had to drop samples to stay within its limit.
+    pub fn stride(&self) -> u64 {
+        self.stride
+    }
+
+    /// Records a generation with `population` live cells, `births` and
+    /// `deaths` being the cells that changed since the generation before.
+    pub fn record(&mut self, generation: u64, population: usize, births: usize, deaths: usize) {
+        self.births += births;
+        self.deaths += deaths;
+        if self.seen.is_multiple_of(self.stride) {
+            self.samples.push(PopulationSample {
+                generation,
+                population,
+                births: self.births,
+                deaths: self.deaths,
+            });
+            self.births = 0;
+            self.deaths = 0;
+        }
+        self.seen += 1;
+
+        if self.limit.is_some_and(|limit| self.samples.len() > limit) {
+            self.halve();
+        }
+    }
+
+    // Drops every other sample, folding the births and deaths of each dropped
+    // one into the one after it
+    fn halve(&mut self) {
+        let mut kept = Vec::with_capacity(self.samples.len() / 2 + 1);
+        let (mut births, mut deaths) = (0, 0);
+        for (index, sample) in self.samples.drain(..).enumerate() {
+            births += sample.births;
+            deaths += sample.deaths;
+            if index % 2 == 0 {
+                kept.push(PopulationSample {
+                    births,
+                    deaths,
+                    ..sample
+                });
+                births = 0;
+                deaths = 0;
+            }
+        }
+
+        self.samples = kept;
+        self.births += births;
+        self.deaths += deaths;
+        self.stride *= 2;
+    }
+
+    /// Writes the samples as CSV with a header line, one
+    /// `generation,population,births,deaths` line per sample.
+    pub fn to_csv(&self, mut w: impl Write) -> std::io::Result<()> {
+        writeln!(w, "generation,population,births,deaths")?;
+        for sample in &self.samples {
+            writeln!(
+                w,
+                "{},{},{},{}",
+                sample.generation, sample.population, sample.births, sample.deaths
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Sparkline of the population at most `width` characters long, each
+    /// character averaging an equal share of the samples.
+    pub fn sparkline(&self, width: usize) -> String {
+        let populations: Vec<f64> = self
+            .samples
+            .iter()
+            .map(|sample| sample.population as f64)
+            .collect();
+        if populations.len() <= width {
+            return sparkline(&populations);
+        }
+
+        let buckets: Vec<f64> = (0..width)
+            .map(|bucket| {
+                let start = bucket * populations.len() / width;
+                let end = (bucket + 1) * populations.len() / width;
+                populations[start..end].iter().sum::<f64>() / (end - start) as f64
+            })
+            .collect();
+        sparkline(&buckets)
+    }
+}
+
+impl Default for PopulationHistory {
+    fn default() -> PopulationHistory {
+        PopulationHistory::new()
+    }
+}
+
+impl SimulationObserver for PopulationHistory {
+    fn on_generation(&mut self, board: &LifeBoard, generation: u64) {
+        // The changes leading to this generation are already counted
+        self.record(generation, board.population(), 0, 0);
+    }
+
+    fn on_cell_changed(&mut self, _row: usize, _col: usize, alive: bool) {
+        if alive {
+            self.births += 1;
+        } else {
+            self.deaths += 1;
+        }
+    }
+
+    fn on_finished(&mut self, _outcome: RunOutcome) {}
+}
+
+/// One bar of `SPARKLINE_BARS` per value, scaled so the largest value gets
+/// the full bar and zero the lowest. Values that are all zero, or not
+/// positive and finite, get the lowest bar.
+pub fn sparkline(values: &[f64]) -> String {
+    let max = values
+        .iter()
+        .copied()
+        .filter(|value| value.is_finite())
+        .fold(0.0, f64::max);
+    let top = (SPARKLINE_BARS.len() - 1) as f64;
+
+    values
+        .iter()
+        .map(|&value| {
+            let level = if max > 0.0 && value > 0.0 {
+                (value.min(max) / max * top).round() as usize
+            } else {
+                0
+            };
+            SPARKLINE_BARS[level]
+        })
+        .collect()
+}
@@ -0,0 +1,293 @@
+use rand::Rng;
+
+use crate::render::render_grid;
+use crate::rule::Rule;
+use crate::{BoardMismatch, BoundaryMode, CharSet, DensityError, LifeBoard, OutOfBounds, Rect};
+
+/// State of a cell of an `ImmigrationBoard`: dead, or alive as one of two
+/// species.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ImmigrationCell {
+    #[default]
+    Dead,
+    A,
+    B,
+}
+
+impl ImmigrationCell {
+    pub fn is_alive(self) -> bool {
+        self != ImmigrationCell::Dead
+    }
+}
+
+/// Fixed size board for the Immigration game, where live cells belong to one
+/// of two species.
+///
+/// The rule decides which cells live exactly as on a `LifeBoard`, looking at
+/// the live cells of both species together. Survivors keep their species and
+/// a newborn cell takes the species most of its live neighbours have. Under
+/// Conway's rule a birth always has three parents, so there is always a
+/// majority; rules with even birth counts can tie, and ties go to A.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImmigrationBoard {
+    width: usize,
+    height: usize,
+    boundary: BoundaryMode,
+    rule: Rule,
+    generation: u64,
+    cells: Vec<ImmigrationCell>,
+}
+
+impl ImmigrationBoard {
+    /// Creates a board of dead cells that wraps at its edges and steps by
+    /// Conway's rule.
+    pub fn new(width: usize, height: usize) -> ImmigrationBoard {
+        ImmigrationBoard {
+            width,
+            height,
+            boundary: BoundaryMode::Wrap,
+            rule: Rule::conway(),
+            generation: 0,
+            cells: vec![ImmigrationCell::Dead; width * height],
+        }
+    }
+
+    /// Copies a two state board, keeping its boundary mode, rule and
+    /// generation. Live cells become `species`.
+    ///
+    /// Panics if `species` is `ImmigrationCell::Dead`.
+    pub fn from_board(board: &LifeBoard, species: ImmigrationCell) -> ImmigrationBoard {
+        assert!(species.is_alive(), "live cells need a species");
+
+        ImmigrationBoard {
+            width: board.width,
+            height: board.height,
+            boundary: board.boundary,
+            rule: board.rule,
+            generation: board.generation,
+            cells: board
+                .cells
+                .iter()
+                .map(|&alive| {
+                    if alive {
+                        species
+                    } else {
+                        ImmigrationCell::Dead
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Two state board of the live cells of both species.
+    pub fn to_board(&self) -> LifeBoard {
+        let mut board = LifeBoard::new(self.width, self.height);
+        board.set_boundary(self.boundary);
+        board.rule = self.rule;
+        board.generation = self.generation;
+
+        for (alive, cell) in board.cells.iter_mut().zip(self.cells.iter()) {
+            *alive = cell.is_alive();
+        }
+
+        board
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn boundary(&self) -> BoundaryMode {
+        self.boundary
+    }
+
+    pub fn set_boundary(&mut self, boundary: BoundaryMode) {
+        self.boundary = boundary;
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Number of live cells of either species.
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_alive()).count()
+    }
+
+    /// Number of live cells of each species, as (A, B).
+    pub fn populations(&self) -> (usize, usize) {
+        self.cells.iter().fold((0, 0), |(a, b), &cell| match cell {
+            ImmigrationCell::Dead => (a, b),
+            ImmigrationCell::A => (a + 1, b),
+            ImmigrationCell::B => (a, b + 1),
+        })
+    }
+
+    /// Whether every cell is dead.
+    pub fn is_empty(&self) -> bool {
+        !self.cells.iter().any(|cell| cell.is_alive())
+    }
+
+    /// State of the cell at `row`, `col`, or `None` if it is outside the board.
+    pub fn get(&self, row: usize, col: usize) -> Option<ImmigrationCell> {
+        if row < self.height && col < self.width {
+            Some(self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn set(
+        &mut self,
+        row: usize,
+        col: usize,
+        cell: ImmigrationCell,
+    ) -> Result<(), OutOfBounds> {
+        if row < self.height && col < self.width {
+            self.cells[row * self.width + col] = cell;
+            Ok(())
+        } else {
+            Err(OutOfBounds { row, col })
+        }
+    }
+
+    /// Fills `rect`, which is clipped to the board, with a random soup of
+    /// `species`: each cell inside is set to it with probability `density`
+    /// and killed otherwise. Seeding two regions with different species sets
+    /// up a contest between them.
+    pub fn randomize_region(
+        &mut self,
+        rect: Rect,
+        density: f64,
+        species: ImmigrationCell,
+        rng: &mut impl Rng,
+    ) -> Result<(), DensityError> {
+        if !(0.0..=1.0).contains(&density) {
+            return Err(DensityError(density));
+        }
+
+        let rows = rect.row.min(self.height)..rect.row.saturating_add(rect.height).min(self.height);
+        let cols = rect.col.min(self.width)..rect.col.saturating_add(rect.width).min(self.width);
+        for row in rows {
+            for cell in &mut self.cells[row * self.width + cols.start..row * self.width + cols.end]
+            {
+                *cell = if rng.gen_bool(density) {
+                    species
+                } else {
+                    ImmigrationCell::Dead
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the next generation of this board into `next`, which must have
+    /// the same dimensions. On a mismatch `next` is left untouched.
+    pub fn step(&self, next: &mut ImmigrationBoard) -> Result<(), BoardMismatch> {
+        if (next.width, next.height) != (self.width, self.height) {
+            return Err(BoardMismatch {
+                expected: (self.width, self.height),
+                found: (next.width, next.height),
+            });
+        }
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let index = row * self.width + col;
+                let cell = self.cells[index];
+                let (a, b, neighbourhood) = self.neighbours(row, col);
+
+                next.cells[index] = if !self.rule.applies_to(neighbourhood) {
+                    ImmigrationCell::Dead
+                } else if cell.is_alive() {
+                    cell
+                } else if a >= b {
+                    ImmigrationCell::A
+                } else {
+                    ImmigrationCell::B
+                };
+            }
+        }
+
+        next.boundary = self.boundary;
+        next.rule = self.rule;
+        next.generation = self.generation + 1;
+        Ok(())
+    }
+
+    /// Replaces this board with its next generation.
+    pub fn advance(&mut self) {
+        let mut next = ImmigrationBoard::new(self.width, self.height);
+        self.step(&mut next).unwrap();
+        *self = next;
+    }
+
+    pub fn advance_by(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.advance();
+        }
+    }
+
+    /// Draws the board like `LifeBoard::render_to`, with species A drawn as
+    /// `charset.live` and species B as `charset.rival`.
+    pub fn render_to(&self, out: &mut impl std::fmt::Write, charset: &CharSet) -> std::fmt::Result {
+        render_grid(out, charset, self.width, self.height, |index| {
+            match self.cells[index] {
+                ImmigrationCell::Dead => charset.dead,
+                ImmigrationCell::A => charset.live,
+                ImmigrationCell::B => charset.rival,
+            }
+        })
+    }
+
+    // Live neighbours of each species, as (A, B), and the neighbourhood of
+    // the cell laid out as for `Rule::applies_to` with either species alive
+    fn neighbours(&self, row: usize, col: usize) -> (u8, u8, u16) {
+        let (mut a, mut b) = (0, 0);
+        let mut neighbourhood = (self.cells[row * self.width + col].is_alive() as u16) << 4;
+        for delta_row in -1..=1 {
+            for delta_col in -1..=1 {
+                if (delta_row, delta_col) == (0, 0) {
+                    continue;
+                }
+
+                let neighbour = self.boundary.resolve(
+                    row as isize + delta_row,
+                    col as isize + delta_col,
+                    self.width,
+                    self.height,
+                );
+                if let Some((r, c)) = neighbour {
+                    let bit = 3 * (delta_row + 1) + delta_col + 1;
+                    match self.cells[r * self.width + c] {
+                        ImmigrationCell::Dead => continue,
+                        ImmigrationCell::A => a += 1,
+                        ImmigrationCell::B => b += 1,
+                    }
+                    neighbourhood |= 1 << bit;
+                }
+            }
+        }
+
+        (a, b, neighbourhood)
+    }
+}
+
+impl std::fmt::Display for ImmigrationBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.render_to(f, &CharSet::ASCII)
+    }
+}
@@ -0,0 +1,131 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use image::imageops::FilterType;
+use image::{GrayImage, ImageReader, Luma};
+
+use crate::LifeBoard;
+
+/// Longest side, in pixels or cells, of an image `LifeBoard::from_image`
+/// accepts and of a board it makes.
+pub const MAX_IMAGE_SIDE: u32 = 4096;
+
+/// Reasons an image cannot be turned into a board.
+#[derive(Debug)]
+pub enum ImageError {
+    Io(std::io::Error),
+    /// The data is not an image in a format the `image` crate can read.
+    Decode(image::ImageError),
+    /// The image is wider or taller than `MAX_IMAGE_SIDE` pixels.
+    TooLarge {
+        width: u32,
+        height: u32,
+    },
+    /// The board asked for is empty or wider or taller than `MAX_IMAGE_SIDE`
+    /// cells.
+    InvalidSize {
+        width: usize,
+        height: usize,
+    },
+    /// No pixel is darker than a threshold of zero.
+    InvalidThreshold,
+}
+
+impl std::fmt::Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ImageError::Io(e) => write!(f, "{}", e),
+            ImageError::Decode(e) => write!(f, "could not decode image: {}", e),
+            ImageError::TooLarge { width, height } => write!(
+                f,
+                "{}x{} image is larger than {} pixels on a side",
+                width, height, MAX_IMAGE_SIDE
+            ),
+            ImageError::InvalidSize { width, height } => write!(
+                f,
+                "a board made from an image must be 1 to {} cells on a side, not {}x{}",
+                MAX_IMAGE_SIDE, width, height
+            ),
+            ImageError::InvalidThreshold => f.write_str("threshold must be above zero"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<std::io::Error> for ImageError {
+    fn from(e: std::io::Error) -> ImageError {
+        ImageError::Io(e)
+    }
+}
+
+impl From<image::ImageError> for ImageError {
+    fn from(e: image::ImageError) -> ImageError {
+        ImageError::Decode(e)
+    }
+}
+
+impl LifeBoard {
+    /// Board with one cell per pixel of the PNG or JPEG image at `path`,
+    /// alive where the pixel's gray level is darker than `threshold`.
+    /// Transparent pixels count as white.
+    pub fn from_image(path: impl AsRef<Path>, threshold: u8) -> Result<LifeBoard, ImageError> {
+        LifeBoard::from_image_bytes(&std::fs::read(path)?, threshold, None)
+    }
+
+    /// Board of `width` by `height` cells made from the image at `path` as
+    /// `from_image` does, after scaling the image to that size. Each cell's
+    /// gray level averages the pixels it covers.
+    pub fn from_image_scaled(
+        path: impl AsRef<Path>,
+        threshold: u8,
+        width: usize,
+        height: usize,
+    ) -> Result<LifeBoard, ImageError> {
+        LifeBoard::from_image_bytes(&std::fs::read(path)?, threshold, Some((width, height)))
+    }
+
+    /// Board made from an encoded image as `from_image` does, scaled to
+    /// `size` as (width, height) if given.
+    pub fn from_image_bytes(
+        bytes: &[u8],
+        threshold: u8,
+        size: Option<(usize, usize)>,
+    ) -> Result<LifeBoard, ImageError> {
+        if threshold == 0 {
+            return Err(ImageError::InvalidThreshold);
+        }
+        if let Some((width, height)) = size {
+            let fits = |side: usize| (1..=MAX_IMAGE_SIDE as usize).contains(&side);
+            if !fits(width) || !fits(height) {
+                return Err(ImageError::InvalidSize { width, height });
+            }
+        }
+
+        // Reading the header first keeps a huge image from being decoded
+        let reader = || ImageReader::new(Cursor::new(bytes)).with_guessed_format();
+        let (width, height) = reader()?.into_dimensions()?;
+        if width > MAX_IMAGE_SIDE || height > MAX_IMAGE_SIDE {
+            return Err(ImageError::TooLarge { width, height });
+        }
+
+        let image = reader()?.decode()?.to_luma_alpha8();
+        let mut gray = GrayImage::from_fn(width, height, |x, y| {
+            let [level, alpha] = image.get_pixel(x, y).0;
+            // Blend the pixel over white
+            let white = 255 - alpha as u32;
+            Luma([((level as u32 * alpha as u32 + 255 * white) / 255) as u8])
+        });
+        if let Some((width, height)) = size {
+            gray =
+                image::imageops::resize(&gray, width as u32, height as u32, FilterType::Triangle);
+        }
+
+        let mut board = LifeBoard::new(gray.width() as usize, gray.height() as usize);
+        for (cell, pixel) in board.cells.iter_mut().zip(gray.pixels()) {
+            *cell = pixel.0[0] < threshold;
+        }
+
+        Ok(board)
+    }
+}
@@ -0,0 +1,108 @@
+//! Key handling for the interactive mode of the binary.
+//!
+//! The mapping from keys to simulator commands is kept free of terminal I/O:
+//! the binary translates terminal events into `Key`s, feeds them through
+//! `Session::handle_key` and carries out the returned `Command`.
+
+/// Key presses the interactive mode responds to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+}
+
+/// Changes to the board or the run requested by a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Advance a single generation.
+    Step,
+    /// Go back to the generation before the current one.
+    StepBack,
+    /// Move the view of the board by the given rows and columns.
+    Pan {
+        rows: isize,
+        cols: isize,
+    },
+    /// Flip the cell at the given row and column.
+    Toggle {
+        row: usize,
+        col: usize,
+    },
+    /// Fill the board with a new random soup.
+    Randomize,
+    /// Kill every cell.
+    Clear,
+    /// Run generations twice as fast.
+    Faster,
+    /// Run generations half as fast.
+    Slower,
+    Quit,
+}
+
+/// State of an interactive run that lives outside the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Session {
+    pub paused: bool,
+    pub cursor_row: usize,
+    pub cursor_col: usize,
+}
+
+impl Session {
+    /// Applies `key` to a session on a `width` by `height` board, returning
+    /// the new session and the command to carry out, if any.
+    ///
+    /// Space pauses and resumes, `n` steps and `b` steps back while paused,
+    /// the arrow keys move the cursor within the board, `w`, `a`, `s` and
+    /// `d` pan the view up, left, down and right, enter toggles the cell
+    /// under the cursor, `+` and `-` change the speed, `r` randomizes, `c`
+    /// clears and `q` quits. Other keys are ignored.
+    pub fn handle_key(self, key: Key, width: usize, height: usize) -> (Session, Option<Command>) {
+        let mut next = self;
+        let command = match key {
+            Key::Char(' ') => {
+                next.paused = !self.paused;
+                None
+            }
+            Key::Char('n') if self.paused => Some(Command::Step),
+            Key::Char('b') if self.paused => Some(Command::StepBack),
+            Key::Char('w') => Some(Command::Pan { rows: -1, cols: 0 }),
+            Key::Char('a') => Some(Command::Pan { rows: 0, cols: -1 }),
+            Key::Char('s') => Some(Command::Pan { rows: 1, cols: 0 }),
+            Key::Char('d') => Some(Command::Pan { rows: 0, cols: 1 }),
+            Key::Char('r') => Some(Command::Randomize),
+            Key::Char('c') => Some(Command::Clear),
+            Key::Char('+') | Key::Char('=') => Some(Command::Faster),
+            Key::Char('-') => Some(Command::Slower),
+            Key::Char('q') => Some(Command::Quit),
+            Key::Up => {
+                next.cursor_row = self.cursor_row.saturating_sub(1);
+                None
+            }
+            Key::Down => {
+                next.cursor_row = (self.cursor_row + 1).min(height.saturating_sub(1));
+                None
+            }
+            Key::Left => {
+                next.cursor_col = self.cursor_col.saturating_sub(1);
+                None
+            }
+            Key::Right => {
+                next.cursor_col = (self.cursor_col + 1).min(width.saturating_sub(1));
+                None
+            }
+            Key::Enter if self.cursor_row < height && self.cursor_col < width => {
+                Some(Command::Toggle {
+                    row: self.cursor_row,
+                    col: self.cursor_col,
+                })
+            }
+            _ => None,
+        };
+
+        (next, command)
+    }
+}
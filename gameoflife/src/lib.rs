@@ -0,0 +1,1267 @@
+//! Conway's Game of Life and its relatives.
+//!
+//! `LifeBoard` is the dense, fixed size board everything else builds on. It
+//! steps under any B/S `Rule` with a choice of `BoundaryMode` at its edges.
+//! `SparseLifeBoard` and `PackedLifeBoard` are alternative representations
+//! for huge, mostly dead universes and for fast stepping of dense boards.
+
+extern crate rand;
+
+mod active;
+mod age;
+mod census;
+mod compare;
+mod cycle;
+mod expanding;
+mod export;
+mod format;
+mod generations;
+#[cfg(feature = "gui")]
+mod gui;
+mod hashlife;
+mod headless;
+mod heat;
+mod history;
+mod immigration;
+#[cfg(feature = "image")]
+mod import;
+pub mod interactive;
+mod library;
+mod ltl;
+mod macrocell;
+mod noise;
+mod observer;
+#[cfg(feature = "native")]
+pub mod options;
+#[cfg(feature = "native")]
+mod pacer;
+mod packed;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod pattern;
+pub mod patterns;
+#[cfg(feature = "native")]
+mod pipeline;
+mod predecessor;
+mod probe;
+mod recording;
+mod render;
+mod rewind;
+mod rule;
+mod search;
+#[cfg(feature = "serde")]
+mod serialize;
+mod ships;
+#[cfg(feature = "simd")]
+mod simd;
+mod soup;
+mod sparse;
+#[cfg(feature = "serde")]
+mod state;
+mod stats;
+mod transform;
+mod viewport;
+mod window;
+mod zobrist;
+
+pub use crate::census::{Census, Component};
+pub use crate::compare::{
+    divergence, fit_side_by_side, side_by_side, Comparison, Side, COMPARISON_GAP,
+};
+pub use crate::cycle::RunOutcome;
+pub use crate::expanding::{BirthOnZero, ExpandingLifeBoard};
+#[cfg(feature = "gif")]
+pub use crate::export::export_gif;
+pub use crate::format::{FormatError, MAX_PATTERN_SIDE};
+pub use crate::generations::GenerationsBoard;
+#[cfg(feature = "gui")]
+pub use crate::gui::run_window;
+pub use crate::hashlife::{HashLifeError, HashLifeUniverse};
+pub use crate::headless::GenerationRecord;
+#[cfg(feature = "native")]
+pub use crate::headless::{run_headless, RunSummary};
+pub use crate::history::{sparkline, PopulationHistory, PopulationSample, SPARKLINE_BARS};
+pub use crate::immigration::{ImmigrationBoard, ImmigrationCell};
+#[cfg(feature = "image")]
+pub use crate::import::{ImageError, MAX_IMAGE_SIDE};
+pub use crate::library::{LibraryEntry, PatternLibrary, VerifyError};
+pub use crate::ltl::LtlRule;
+pub use crate::noise::{Noise, ProbabilityError};
+pub use crate::observer::{FrameDumper, SimulationObserver, StatsLogger};
+#[cfg(feature = "native")]
+pub use crate::pacer::FramePacer;
+pub use crate::packed::PackedLifeBoard;
+pub use crate::pattern::{Pattern, StampError};
+#[cfg(feature = "native")]
+pub use crate::pipeline::{
+    run_pipelined, run_pipelined_with, run_rendered, run_rendered_with, FramePolicy, QUEUED_FRAMES,
+};
+pub use crate::predecessor::{find_predecessors, is_garden_of_eden};
+pub use crate::probe::{CellProbes, ProbeTransition};
+pub use crate::recording::{
+    Playback, Recorder, Recording, RecordingError, RecordingHeader, RECORDING_VERSION,
+};
+pub use crate::render::{
+    braille_glyph, compose_frame, compose_styled_frame, compose_zoomed_frame, density_glyph,
+    fit_to_terminal, fit_zoomed_out, half_block_glyph, zoom_to_fit, CharSet, NullRenderer,
+    RenderStyle, Renderer, DENSITY_RAMP,
+};
+#[cfg(feature = "native")]
+pub use crate::render::{terminal_size, AnsiRenderer, PlainStdoutRenderer};
+pub use crate::rewind::HistoryBuffer;
+pub use crate::rule::{next_state, GenerationsRule, Rule, RuleParseError};
+pub use crate::search::{exhaustive_search, SearchReport, SearchResult};
+pub use crate::ships::{detect_ships, ShipDetector, ShipSighting, DEFAULT_MAX_PERIOD};
+pub use crate::soup::{run_soups, SoupConfig, SoupReport, SoupResult};
+pub use crate::sparse::SparseLifeBoard;
+#[cfg(feature = "serde")]
+pub use crate::state::{SimulationState, StateError, STATE_VERSION};
+pub use crate::stats::BoardStats;
+pub use crate::transform::Symmetry;
+pub use crate::viewport::Viewport;
+pub use crate::window::WindowLayout;
+pub use crate::zobrist::ZOBRIST_SEED;
+/// Generator whose state `SimulationState` saves, the algorithm behind
+/// `rand::rngs::SmallRng` on 64 bit targets.
+#[cfg(feature = "serde")]
+pub use rand_xoshiro::Xoshiro256PlusPlus;
+
+use rand::Rng;
+use std::ops::{ControlFlow, Index, IndexMut};
+
+// Offsets of the eight neighbours of a cell, as (row, column)
+const NEIGHBOURS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// How neighbours that fall outside the board are resolved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoundaryMode {
+    /// Opposite edges are joined, making the board a torus.
+    Wrap,
+    /// Everything outside the board is dead.
+    Dead,
+    /// The board is reflected at its edges, so the cell just outside an edge
+    /// has the state of the edge cell itself.
+    Mirror,
+    /// A torus whose left and right edges are joined `offset` rows apart, so
+    /// leaving the right edge at row r comes back on the left edge at row
+    /// r + offset. The top and bottom edges are joined as for `Wrap`.
+    ShiftedTorus { offset: isize },
+    /// The top and bottom edges are joined as for `Wrap`, and the left and
+    /// right edges with the board turned upside down, so leaving the right
+    /// edge at row r comes back on the left edge at row height - 1 - r.
+    KleinBottle,
+}
+
+impl BoundaryMode {
+    // Maps a possibly out of range cell back onto a `width` by `height`
+    // board, as (row, column). Every stepper finds neighbours through this,
+    // directly or through tables built from it.
+    pub(crate) fn resolve(
+        self,
+        row: isize,
+        col: isize,
+        width: usize,
+        height: usize,
+    ) -> Option<(usize, usize)> {
+        let (width, height) = (width as isize, height as isize);
+        let (row, col) = match self {
+            BoundaryMode::Wrap => (row.rem_euclid(height), col.rem_euclid(width)),
+            BoundaryMode::Dead if row < 0 || row >= height || col < 0 || col >= width => {
+                return None
+            }
+            BoundaryMode::Dead => (row, col),
+            BoundaryMode::Mirror => (mirror(row, height), mirror(col, width)),
+            BoundaryMode::ShiftedTorus { offset } => {
+                // Times the left or right edge is crossed, negative for left
+                let crossings = col.div_euclid(width);
+                (
+                    (row + crossings * offset.rem_euclid(height)).rem_euclid(height),
+                    col.rem_euclid(width),
+                )
+            }
+            BoundaryMode::KleinBottle => {
+                let row = match col.div_euclid(width).rem_euclid(2) {
+                    0 => row,
+                    _ => height - 1 - row,
+                };
+                (row.rem_euclid(height), col.rem_euclid(width))
+            }
+        };
+
+        Some((row as usize, col as usize))
+    }
+
+    // Whether crossing the left or right edge also moves a cell along the
+    // rows, so its neighbours cannot be found one axis at a time
+    pub(crate) fn couples_axes(self) -> bool {
+        matches!(
+            self,
+            BoundaryMode::ShiftedTorus { .. } | BoundaryMode::KleinBottle
+        )
+    }
+}
+
+// Reflects an index on an axis of length `len` at either end
+fn mirror(index: isize, len: isize) -> isize {
+    if index < 0 {
+        (-index - 1).min(len - 1)
+    } else if index >= len {
+        (2 * len - index - 1).max(0)
+    } else {
+        index
+    }
+}
+
+// Neighbouring row and column of every row and column under a boundary mode,
+// worked out once so stepping needs no wrapping arithmetic. `None` marks a
+// neighbour off the edge of a board with dead edges.
+#[derive(Debug, Clone, Default)]
+struct NeighbourTables {
+    up: Vec<Option<usize>>,
+    down: Vec<Option<usize>>,
+    left: Vec<Option<usize>>,
+    right: Vec<Option<usize>>,
+}
+
+impl NeighbourTables {
+    fn new(width: usize, height: usize, boundary: BoundaryMode) -> NeighbourTables {
+        if width == 0 || height == 0 {
+            return NeighbourTables::default();
+        }
+
+        // Moving straight up or down never crosses the left or right edge,
+        // and the column reached crossing them is the same on every row
+        let rows = |delta: isize| -> Vec<Option<usize>> {
+            (0..height as isize)
+                .map(|row| boundary.resolve(row + delta, 0, width, height))
+                .map(|cell| cell.map(|(row, _)| row))
+                .collect()
+        };
+        let cols = |delta: isize| -> Vec<Option<usize>> {
+            (0..width as isize)
+                .map(|col| boundary.resolve(0, col + delta, width, height))
+                .map(|cell| cell.map(|(_, col)| col))
+                .collect()
+        };
+
+        NeighbourTables {
+            up: rows(-1),
+            down: rows(1),
+            left: cols(-1),
+            right: cols(1),
+        }
+    }
+
+    // Row `delta_row` rows away from `row`, for a delta from -1 to 1
+    fn row(&self, row: usize, delta_row: isize) -> Option<usize> {
+        match delta_row {
+            -1 => self.up[row],
+            0 => Some(row),
+            _ => self.down[row],
+        }
+    }
+
+    // Column `delta_col` columns away from `col`, for a delta from -1 to 1
+    fn col(&self, col: usize, delta_col: isize) -> Option<usize> {
+        match delta_col {
+            -1 => self.left[col],
+            0 => Some(col),
+            _ => self.right[col],
+        }
+    }
+}
+
+/// Density outside of the range [0, 1] passed to `LifeBoard::randomize`.
+#[derive(Debug, PartialEq)]
+pub struct DensityError(pub f64);
+
+impl std::fmt::Display for DensityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "density {} is not between 0 and 1", self.0)
+    }
+}
+
+impl std::error::Error for DensityError {}
+
+/// Coordinate passed to a checked accessor that lies outside the board.
+#[derive(Debug, PartialEq)]
+pub struct OutOfBounds {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl std::fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "cell ({}, {}) is outside the board", self.row, self.col)
+    }
+}
+
+impl std::error::Error for OutOfBounds {}
+
+/// Board passed to `LifeBoard::step`, one of its variants or
+/// `ImmigrationBoard::step` to hold the next generation that differs in size
+/// from the board being stepped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardMismatch {
+    /// Width and height of the board being stepped.
+    pub expected: (usize, usize),
+    /// Width and height of the board given for the next generation.
+    pub found: (usize, usize),
+}
+
+impl std::fmt::Display for BoardMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "next generation board is {}x{}, expected {}x{}",
+            self.found.0, self.found.1, self.expected.0, self.expected.1
+        )
+    }
+}
+
+impl std::error::Error for BoardMismatch {}
+
+/// Where the existing cells end up when a board is resized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    Center,
+    BottomRight,
+}
+
+impl Anchor {
+    // Distance the existing cells move along an axis growing from `old` to `new`
+    fn offset(self, old: usize, new: usize) -> isize {
+        let grow = new as isize - old as isize;
+        match self {
+            Anchor::TopLeft => 0,
+            Anchor::Center => grow / 2,
+            Anchor::BottomRight => grow,
+        }
+    }
+}
+
+/// Rectangle of cells with its top left corner at `row`, `col`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Rect {
+    pub row: usize,
+    pub col: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Fixed size board of cells that are either alive or dead.
+///
+/// Cells are stored row by row and addressed by (row, column) from the top
+/// left corner. A board carries the `Rule` and `BoundaryMode` it steps with
+/// and counts the generations it has been stepped through. Boards compare
+/// equal when they have the same dimensions and cells.
+#[derive(Debug, Clone)]
+pub struct LifeBoard {
+    width: usize,
+    height: usize,
+    boundary: BoundaryMode,
+    rule: Rule,
+    generation: u64,
+    cells: std::vec::Vec<bool>,
+    // Next generation buffer for `advance`, kept to avoid reallocating every step
+    scratch: std::vec::Vec<bool>,
+    // Cells changed by the last `advance_active`, or None if the board may
+    // have changed in any other way since
+    changed: Option<Vec<usize>>,
+    // Rebuilt whenever the size or boundary mode changes
+    neighbours: NeighbourTables,
+    // Generations each live cell has survived, when enabled
+    ages: Option<Vec<u16>>,
+    // Age past which cells die whatever the rule says, only set with ages
+    max_age: Option<u16>,
+    // Zobrist hash of the cells, once asked to keep it. Kept by stepping and
+    // dropped when the cells are changed any other way.
+    hash: Option<u64>,
+    // Times each cell has changed state, when enabled
+    heat: Option<Vec<u32>>,
+}
+
+/// Cell yielded by `LifeBoard::iter_mut`.
+pub struct CellMut<'a> {
+    pub row: usize,
+    pub col: usize,
+    pub state: &'a mut bool,
+}
+
+/// Cell yielded by `LifeBoard::iter`.
+pub struct Cell<'a> {
+    pub row: usize,
+    pub col: usize,
+    pub state: &'a bool,
+}
+
+/// Cell whose state differs between two generations. See
+/// `LifeBoard::step_diff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellChange {
+    pub row: usize,
+    pub col: usize,
+    /// State of the cell in the new generation.
+    pub alive: bool,
+}
+
+pub struct CellMutIterator<'a> {
+    width: usize,
+    cells: std::iter::Enumerate<std::slice::IterMut<'a, bool>>,
+}
+
+/// Iterator over the (row, column) coordinates of live cells, in row order.
+/// See `LifeBoard::live_cells`.
+#[derive(Clone)]
+pub struct LiveCells<'a> {
+    board: &'a LifeBoard,
+    rect: Rect,
+    row: usize,
+    col: usize,
+}
+
+pub struct CellIterator<'a> {
+    board: &'a LifeBoard,
+    index: usize,
+}
+
+impl LifeBoard {
+    /// Creates a board of dead cells that wraps at its edges and follows
+    /// Conway's rule.
+    pub fn new(width: usize, height: usize) -> LifeBoard {
+        let mut cells = Vec::new();
+        cells.resize(width * height, false);
+
+        LifeBoard {
+            width,
+            height,
+            boundary: BoundaryMode::Wrap,
+            rule: Rule::conway(),
+            generation: 0,
+            cells,
+            scratch: Vec::new(),
+            changed: None,
+            neighbours: NeighbourTables::new(width, height, BoundaryMode::Wrap),
+            ages: None,
+            max_age: None,
+            heat: None,
+            hash: None,
+        }
+    }
+
+    pub fn new_square(board_size: usize) -> LifeBoard {
+        LifeBoard::new(board_size, board_size)
+    }
+
+    pub fn boundary(&self) -> BoundaryMode {
+        self.boundary
+    }
+
+    pub fn set_boundary(&mut self, boundary: BoundaryMode) {
+        self.changed = None;
+        if boundary != self.boundary {
+            self.boundary = boundary;
+            self.neighbours = NeighbourTables::new(self.width, self.height, boundary);
+        }
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.changed = None;
+        self.rule = rule;
+    }
+
+    /// Number of steps taken to reach this board.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Number of live cells.
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|&&alive| alive).count()
+    }
+
+    /// Whether every cell is dead.
+    pub fn is_empty(&self) -> bool {
+        !self.cells.contains(&true)
+    }
+
+    /// Sets each cell alive with probability `density`, independently of the
+    /// others.
+    pub fn randomize(&mut self, density: f64, rng: &mut impl Rng) -> Result<(), DensityError> {
+        self.randomize_region(self.whole(), density, rng)
+    }
+
+    /// Sets each cell inside `rect`, which is clipped to the board, alive with
+    /// probability `density`. Cells outside are left untouched.
+    pub fn randomize_region(
+        &mut self,
+        rect: Rect,
+        density: f64,
+        rng: &mut impl Rng,
+    ) -> Result<(), DensityError> {
+        if !(0.0..=1.0).contains(&density) {
+            return Err(DensityError(density));
+        }
+        self.cells_edited();
+
+        let rect = self.clip(rect);
+        for row in rect.row..rect.row + rect.height {
+            let start = row * self.width + rect.col;
+            for cell in self.cells[start..start + rect.width].iter_mut() {
+                *cell = rng.gen_bool(density);
+            }
+            if let Some(ages) = &mut self.ages {
+                ages[start..start + rect.width].fill(0);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Kills every cell inside `rect`, which is clipped to the board.
+    pub fn clear_region(&mut self, rect: Rect) {
+        self.cells_edited();
+
+        let rect = self.clip(rect);
+        for row in rect.row..rect.row + rect.height {
+            let start = row * self.width + rect.col;
+            self.cells[start..start + rect.width].fill(false);
+            if let Some(ages) = &mut self.ages {
+                ages[start..start + rect.width].fill(0);
+            }
+        }
+    }
+
+    /// Copies the cells inside `rect`, which is clipped to the board, into a
+    /// new board with the same boundary mode, rule and generation. Ages and
+    /// heat are copied too when the board tracks them.
+    pub fn crop(&self, rect: Rect) -> LifeBoard {
+        let rect = self.clip(rect);
+        let mut board = LifeBoard::new(rect.width, rect.height);
+        board.set_boundary(self.boundary);
+        board.rule = self.rule;
+        board.generation = self.generation;
+
+        for (row, col) in self.live_cells_in(rect) {
+            board[(row - rect.row, col - rect.col)] = true;
+        }
+        if let Some(ages) = &self.ages {
+            let mut cropped = Vec::with_capacity(rect.width * rect.height);
+            for row in rect.row..rect.row + rect.height {
+                let start = row * self.width + rect.col;
+                cropped.extend_from_slice(&ages[start..start + rect.width]);
+            }
+            board.ages = Some(cropped);
+            board.max_age = self.max_age;
+        }
+        if let Some(heat) = &self.heat {
+            let mut cropped = Vec::with_capacity(rect.width * rect.height);
+            for row in rect.row..rect.row + rect.height {
+                let start = row * self.width + rect.col;
+                cropped.extend_from_slice(&heat[start..start + rect.width]);
+            }
+            board.heat = Some(cropped);
+        }
+
+        board
+    }
+
+    /// Copies the cells inside `src_rect` of `src`, dead ones included, onto
+    /// this board with the rectangle's top left corner at `dest`, a (row,
+    /// column) pair. The rectangle is clipped to `src`, and whatever then
+    /// falls off this board is left out.
+    pub fn copy_from(&mut self, src: &LifeBoard, src_rect: Rect, dest: (usize, usize)) {
+        self.cells_edited();
+
+        let src_rect = src.clip(src_rect);
+        let dest = self.clip(Rect {
+            row: dest.0,
+            col: dest.1,
+            width: src_rect.width,
+            height: src_rect.height,
+        });
+        for row in 0..dest.height {
+            let from = (src_rect.row + row) * src.width + src_rect.col;
+            let to = (dest.row + row) * self.width + dest.col;
+            self.cells[to..to + dest.width].copy_from_slice(&src.cells[from..from + dest.width]);
+            if let Some(ages) = &mut self.ages {
+                ages[to..to + dest.width].fill(0);
+            }
+        }
+    }
+
+    // Rectangle covering the whole board
+    fn whole(&self) -> Rect {
+        Rect {
+            row: 0,
+            col: 0,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    // Part of `rect` that lies on the board
+    fn clip(&self, rect: Rect) -> Rect {
+        let row = rect.row.min(self.height);
+        let col = rect.col.min(self.width);
+
+        Rect {
+            row,
+            col,
+            width: rect.width.min(self.width - col),
+            height: rect.height.min(self.height - row),
+        }
+    }
+
+    /// State of the cell at `row`, `col`, or `None` if it is outside the board.
+    pub fn get(&self, row: usize, col: usize) -> Option<bool> {
+        if row < self.height && col < self.width {
+            Some(self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, alive: bool) -> Result<(), OutOfBounds> {
+        if row < self.height && col < self.width {
+            self.cells_edited();
+            self.cells[row * self.width + col] = alive;
+            if let Some(ages) = &mut self.ages {
+                ages[row * self.width + col] = 0;
+            }
+            Ok(())
+        } else {
+            Err(OutOfBounds { row, col })
+        }
+    }
+
+    /// Flips the cell at `row`, `col`, returning its new state.
+    pub fn toggle(&mut self, row: usize, col: usize) -> Result<bool, OutOfBounds> {
+        let alive = !self.get(row, col).ok_or(OutOfBounds { row, col })?;
+        self.set(row, col, alive)?;
+
+        Ok(alive)
+    }
+
+    fn check_row(&self, row: usize) {
+        assert!(
+            row < self.height,
+            "row {} is out of range for a board of height {}",
+            row,
+            self.height
+        );
+    }
+
+    fn cell_index(&self, row: usize, col: usize) -> usize {
+        self.check_row(row);
+        assert!(
+            col < self.width,
+            "column {} is out of range for a board of width {}",
+            col,
+            self.width
+        );
+
+        row * self.width + col
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Writes the board to stdout.
+    pub fn print(&self) {
+        println!("{}", self);
+    }
+
+    /// Iterates over every cell in row order.
+    pub fn iter_mut(&mut self) -> CellMutIterator<'_> {
+        self.cells_edited();
+        CellMutIterator {
+            width: self.width,
+            cells: self.cells.iter_mut().enumerate(),
+        }
+    }
+
+    /// Iterates over every cell in row order.
+    pub fn iter(&self) -> CellIterator<'_> {
+        CellIterator {
+            board: self,
+            index: 0,
+        }
+    }
+
+    /// Changes the size of the board, keeping the existing cells in place
+    /// relative to `anchor`. Cells that no longer fit are lost and new cells
+    /// are dead, with no heat.
+    pub fn resize(&mut self, new_width: usize, new_height: usize, anchor: Anchor) {
+        let row_offset = anchor.offset(self.height, new_height);
+        let col_offset = anchor.offset(self.width, new_width);
+        let mut cells = vec![false; new_width * new_height];
+        let mut ages = self.ages.as_ref().map(|_| vec![0; new_width * new_height]);
+        let moved = |index: usize| {
+            let new_row = (index / self.width) as isize + row_offset;
+            let new_col = (index % self.width) as isize + col_offset;
+            if (0..new_height as isize).contains(&new_row)
+                && (0..new_width as isize).contains(&new_col)
+            {
+                Some(new_row as usize * new_width + new_col as usize)
+            } else {
+                None
+            }
+        };
+
+        for (row, col) in self.live_cells() {
+            if let Some(index) = moved(row * self.width + col) {
+                cells[index] = true;
+                if let (Some(ages), Some(old)) = (&mut ages, &self.ages) {
+                    ages[index] = old[row * self.width + col];
+                }
+            }
+        }
+        let heat = self.heat.as_ref().map(|old| {
+            let mut heat = vec![0; new_width * new_height];
+            for (index, &count) in old.iter().enumerate() {
+                if let Some(index) = moved(index) {
+                    heat[index] = count;
+                }
+            }
+            heat
+        });
+
+        self.width = new_width;
+        self.height = new_height;
+        self.cells = cells;
+        self.ages = ages;
+        self.heat = heat;
+        self.scratch.clear();
+        self.cells_edited();
+        self.neighbours = NeighbourTables::new(new_width, new_height, self.boundary);
+    }
+
+    /// Coordinates of the live cells, as (row, column) pairs in row order.
+    pub fn live_cells(&self) -> LiveCells<'_> {
+        self.live_cells_in(self.whole())
+    }
+
+    /// Coordinates of the live cells inside `rect`, which is clipped to the
+    /// board.
+    pub fn live_cells_in(&self, rect: Rect) -> LiveCells<'_> {
+        let rect = self.clip(rect);
+
+        LiveCells {
+            board: self,
+            rect,
+            row: rect.row,
+            col: rect.col,
+        }
+    }
+
+    /// Smallest rectangle holding every live cell, or `None` when the board
+    /// is empty.
+    pub fn bounding_box(&self) -> Option<Rect> {
+        let mut cells = self.live_cells();
+        let (top, first_col) = cells.next()?;
+        let (mut bottom, mut left, mut right) = (top, first_col, first_col);
+        for (row, col) in cells {
+            bottom = row;
+            left = left.min(col);
+            right = right.max(col);
+        }
+
+        Some(Rect {
+            row: top,
+            col: left,
+            width: right - left + 1,
+            height: bottom - top + 1,
+        })
+    }
+
+    /// The neighbours of the cell at `row`, `col` under the board's boundary
+    /// mode, as (row, column, alive). Neighbours off a board with dead edges
+    /// are left out, and at a mirrored edge a cell is its own neighbour.
+    ///
+    /// Panics if the cell is outside the board.
+    pub fn neighbours(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+        self.cell_index(row, col);
+        let coupled = self.boundary.couples_axes();
+
+        NEIGHBOURS
+            .iter()
+            .filter_map(move |&(delta_row, delta_col)| {
+                let (row, col) = self.neighbour(coupled, row, col, delta_row, delta_col)?;
+                Some((row, col, self.cells[row * self.width + col]))
+            })
+    }
+
+    // Neighbour `delta_row` rows down and `delta_col` columns right of a
+    // cell, with `coupled` set when the boundary mode couples the axes
+    fn neighbour(
+        &self,
+        coupled: bool,
+        row: usize,
+        col: usize,
+        delta_row: isize,
+        delta_col: isize,
+    ) -> Option<(usize, usize)> {
+        if coupled {
+            self.resolve_neighbour(row, col, delta_row, delta_col)
+        } else {
+            Some((
+                self.neighbours.row(row, delta_row)?,
+                self.neighbours.col(col, delta_col)?,
+            ))
+        }
+    }
+
+    // Neighbour of a cell found through the boundary mode rather than the
+    // tables, kept out of line so the common path stays small
+    #[inline(never)]
+    fn resolve_neighbour(
+        &self,
+        row: usize,
+        col: usize,
+        delta_row: isize,
+        delta_col: isize,
+    ) -> Option<(usize, usize)> {
+        self.boundary.resolve(
+            row as isize + delta_row,
+            col as isize + delta_col,
+            self.width,
+            self.height,
+        )
+    }
+
+    // States of the cell at `row`, `col` and its neighbours laid out as for
+    // `Rule::applies_to`
+    pub(crate) fn neighbourhood(&self, row: usize, col: usize) -> u16 {
+        let coupled = self.boundary.couples_axes();
+        let mut neighbourhood = (self.cells[row * self.width + col] as u16) << 4;
+        for &(delta_row, delta_col) in NEIGHBOURS.iter() {
+            if let Some((r, c)) = self.neighbour(coupled, row, col, delta_row, delta_col) {
+                let bit = 3 * (delta_row + 1) + delta_col + 1;
+                neighbourhood |= (self.cells[r * self.width + c] as u16) << bit;
+            }
+        }
+
+        neighbourhood
+    }
+
+    // State of the cell at `row`, `col` in the next generation
+    pub(crate) fn next_cell(&self, row: usize, col: usize) -> bool {
+        if self.rule.is_totalistic() {
+            let alive = self.cells[row * self.width + col];
+            self.rule
+                .applies(alive, self.live_neighbour_count(row, col))
+        } else {
+            self.rule.applies_to(self.neighbourhood(row, col))
+        }
+    }
+
+    /// Number of live neighbours of the cell at `row`, `col`, as counted when
+    /// stepping.
+    ///
+    /// Panics if the cell is outside the board.
+    pub fn live_neighbour_count(&self, row: usize, col: usize) -> u8 {
+        self.neighbours(row, col)
+            .filter(|&(_, _, alive)| alive)
+            .count() as u8
+    }
+
+    /// Writes the next generation of this board into `next`, which must have
+    /// the same dimensions. On a mismatch `next` is left untouched.
+    pub fn step(&self, next: &mut LifeBoard) -> Result<(), BoardMismatch> {
+        self.check_next(next)?;
+
+        let hash = self.step_cells(&mut next.cells);
+        self.finish_step(next, hash);
+        Ok(())
+    }
+
+    /// Writes the next generation of this board into `next`, like `step`, and
+    /// returns the cells that changed in row order.
+    pub fn step_diff(&self, next: &mut LifeBoard) -> Result<Vec<CellChange>, BoardMismatch> {
+        self.check_next(next)?;
+
+        let width = self.width;
+        let mut changes = Vec::new();
+        self.step_rows(0, &mut next.cells, |index, alive| {
+            changes.push(CellChange {
+                row: index / width,
+                col: index % width,
+                alive,
+            })
+        });
+        let hash = self.hash.map(|hash| {
+            changes.iter().fold(hash, |hash, change| {
+                hash ^ zobrist::zobrist_key(change.row * width + change.col)
+            })
+        });
+        self.finish_step(next, hash);
+
+        Ok(changes)
+    }
+
+    // Fails unless `next` can hold the next generation of this board
+    pub(crate) fn check_next(&self, next: &LifeBoard) -> Result<(), BoardMismatch> {
+        if (next.width, next.height) == (self.width, self.height) {
+            Ok(())
+        } else {
+            Err(BoardMismatch {
+                expected: (self.width, self.height),
+                found: (next.width, next.height),
+            })
+        }
+    }
+
+    // Brings everything but the cells of `next`, which have been written,
+    // up to the next generation. `hash` is the hash of the new cells if this
+    // board keeps one.
+    pub(crate) fn finish_step(&self, next: &mut LifeBoard, hash: Option<u64>) {
+        next.ages = self.next_ages(&mut next.cells);
+        next.heat = self.next_heat(&next.cells);
+        next.hash = hash;
+        next.set_boundary(self.boundary);
+        next.rule = self.rule;
+        next.max_age = self.max_age;
+        next.generation = self.generation + 1;
+        next.changed = None;
+    }
+
+    // Forgets what stepping keeps track of about the cells, after they have
+    // been changed some other way
+    pub(crate) fn cells_edited(&mut self) {
+        self.changed = None;
+        self.hash = None;
+    }
+
+    /// Replaces this board with its next generation.
+    pub fn advance(&mut self) {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.resize(self.cells.len(), false);
+
+        let hash = self.step_cells(&mut scratch);
+        self.scratch = std::mem::replace(&mut self.cells, scratch);
+        self.finish_advance(hash);
+    }
+
+    // Brings everything but the cells up to the next generation, once the
+    // cells have been stepped and the previous generation moved to scratch.
+    // `hash` is the hash of the new cells if the board keeps one.
+    pub(crate) fn finish_advance(&mut self, hash: Option<u64>) {
+        if let Some(ages) = &mut self.ages {
+            age::age_cells(ages, &self.scratch, &mut self.cells, self.max_age);
+        }
+        if let Some(heat) = &mut self.heat {
+            heat::heat_cells(heat, &self.scratch, &self.cells);
+        }
+        self.hash = hash;
+        self.changed = None;
+        self.generation += 1;
+    }
+
+    /// Advances the board by `generations` steps.
+    pub fn advance_by(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.advance();
+        }
+    }
+
+    /// Advances the board up to `generations` times, calling `on_generation`
+    /// with the new board and its generation after every step. Stops early
+    /// when the callback breaks.
+    pub fn run(
+        &mut self,
+        generations: usize,
+        mut on_generation: impl FnMut(&LifeBoard, u64) -> ControlFlow<()>,
+    ) {
+        for _ in 0..generations {
+            self.advance();
+            if on_generation(self, self.generation).is_break() {
+                break;
+            }
+        }
+    }
+
+    // Writes the next generation of every cell into `next`, returning its
+    // hash if this board keeps one
+    fn step_cells(&self, next: &mut [bool]) -> Option<u64> {
+        match self.hash {
+            Some(mut hash) => {
+                self.step_rows(0, next, |index, _| hash ^= zobrist::zobrist_key(index));
+                Some(hash)
+            }
+            None => {
+                self.step_rows(0, next, |_, _| {});
+                None
+            }
+        }
+    }
+
+    // Writes the next generation of the whole rows in `next`, the first of
+    // which is `first_row`, calling `on_change` with the index and new state
+    // of each cell that changed as every row is finished.
+    //
+    // Only the rows above, at and below the row being written are read. Their
+    // live cells are summed column by column first, so the count for a cell
+    // is the sums of its own and the two neighbouring columns less the cell
+    // itself, and every column away from the edges is found by plain index
+    // arithmetic.
+    fn step_rows(
+        &self,
+        first_row: usize,
+        next: &mut [bool],
+        mut on_change: impl FnMut(usize, bool),
+    ) {
+        if !self.rule.is_totalistic() {
+            return self.step_rows_by_arrangement(first_row, next, on_change);
+        }
+        if self.width == 0 {
+            return;
+        }
+
+        let width = self.width;
+        let row_cells = |row: usize| &self.cells[row * width..(row + 1) * width];
+        let mut sums = vec![0u8; width];
+
+        for (offset, target) in next.chunks_mut(width).enumerate() {
+            let row = first_row + offset;
+            let centre = row_cells(row);
+
+            for (sum, &alive) in sums.iter_mut().zip(centre) {
+                *sum = alive as u8;
+            }
+            for cells in [self.neighbours.up[row], self.neighbours.down[row]]
+                .iter()
+                .flatten()
+                .map(|&row| row_cells(row))
+            {
+                for (sum, &alive) in sums.iter_mut().zip(cells) {
+                    *sum += alive as u8;
+                }
+            }
+
+            // Every column but the first and last, with a window of three
+            // column sums centred on each
+            for ((out, &alive), window) in
+                target.iter_mut().zip(centre).skip(1).zip(sums.windows(3))
+            {
+                let count = window[0] + window[1] + window[2] - alive as u8;
+                *out = self.rule.applies(alive, count);
+            }
+
+            // The first and last columns, whose neighbours depend on the
+            // boundary mode. Where crossing the edge moves to another row
+            // the column sums do not apply.
+            for &col in &[0, width - 1] {
+                if self.boundary.couples_axes() {
+                    let count = self.live_neighbour_count(row, col);
+                    target[col] = self.rule.applies(centre[col], count);
+                    continue;
+                }
+                let side = |col: Option<usize>| col.map_or(0, |col| sums[col]);
+                let count =
+                    side(self.neighbours.left[col]) + sums[col] + side(self.neighbours.right[col])
+                        - centre[col] as u8;
+                target[col] = self.rule.applies(centre[col], count);
+            }
+
+            self.kill_aged_row(row, target);
+            report_changes(row * width, centre, target, &mut on_change);
+        }
+    }
+
+    // Applies the age limit to `row` of the next generation as soon as it is
+    // written, so the cells it kills are among the row's changes
+    fn kill_aged_row(&self, row: usize, target: &mut [bool]) {
+        if let (Some(ages), Some(max_age)) = (&self.ages, self.max_age) {
+            let cells = row * self.width..(row + 1) * self.width;
+            age::kill_aged(&ages[cells.clone()], &self.cells[cells], target, max_age);
+        }
+    }
+
+    // Like `step_rows`, for rules that look at how the live neighbours are
+    // arranged. The states of each column in the rows above, at and below
+    // the row being written are packed into a three bit code first, so the
+    // neighbourhood of a cell is the codes of its own and the two
+    // neighbouring columns side by side.
+    fn step_rows_by_arrangement(
+        &self,
+        first_row: usize,
+        next: &mut [bool],
+        mut on_change: impl FnMut(usize, bool),
+    ) {
+        if self.width == 0 {
+            return;
+        }
+
+        let width = self.width;
+        let row_cells = |row: usize| &self.cells[row * width..(row + 1) * width];
+        let mut codes = vec![0u16; width];
+
+        for (offset, target) in next.chunks_mut(width).enumerate() {
+            let row = first_row + offset;
+            let rows = [
+                self.neighbours.up[row],
+                Some(row),
+                self.neighbours.down[row],
+            ];
+
+            codes.fill(0);
+            for (shift, cells) in rows.iter().enumerate() {
+                for (code, &alive) in codes.iter_mut().zip(cells.map_or(&[][..], row_cells)) {
+                    *code |= (alive as u16) << (3 * shift);
+                }
+            }
+
+            for (out, window) in target.iter_mut().skip(1).zip(codes.windows(3)) {
+                *out = self
+                    .rule
+                    .applies_to(window[0] | window[1] << 1 | window[2] << 2);
+            }
+
+            // The first and last columns, whose neighbours depend on the
+            // boundary mode
+            for &col in &[0, width - 1] {
+                target[col] = self.rule.applies_to(self.neighbourhood(row, col));
+            }
+
+            self.kill_aged_row(row, target);
+            report_changes(row * width, row_cells(row), target, &mut on_change);
+        }
+    }
+}
+
+// Calls `on_change` with the index and new state of each cell of a row that
+// differs between `old` and `new`, where `first` is the index of the row's
+// first cell. Stepping without a use for the changes passes a closure that
+// does nothing, and the comparisons are optimised away with it.
+fn report_changes(
+    first: usize,
+    old: &[bool],
+    new: &[bool],
+    on_change: &mut impl FnMut(usize, bool),
+) {
+    for (col, (&was, &is)) in old.iter().zip(new).enumerate() {
+        if was != is {
+            on_change(first + col, is);
+        }
+    }
+}
+
+impl Index<usize> for LifeBoard {
+    type Output = [bool];
+    fn index(&self, row: usize) -> &[bool] {
+        self.check_row(row);
+        let row_index = row * self.width;
+        &self.cells[row_index..row_index + self.width]
+    }
+}
+
+impl IndexMut<usize> for LifeBoard {
+    fn index_mut(&mut self, row: usize) -> &mut [bool] {
+        self.cells_edited();
+        self.check_row(row);
+        let row_index = row * self.width;
+        &mut self.cells[row_index..row_index + self.width]
+    }
+}
+
+impl Index<(usize, usize)> for LifeBoard {
+    type Output = bool;
+    fn index(&self, (row, col): (usize, usize)) -> &bool {
+        &self.cells[self.cell_index(row, col)]
+    }
+}
+
+impl IndexMut<(usize, usize)> for LifeBoard {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut bool {
+        self.cells_edited();
+        let index = self.cell_index(row, col);
+        &mut self.cells[index]
+    }
+}
+
+impl<'a> std::iter::Iterator for CellMutIterator<'a> {
+    type Item = CellMut<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, state) = self.cells.next()?;
+
+        Some(CellMut {
+            row: index / self.width,
+            col: index % self.width,
+            state,
+        })
+    }
+}
+
+impl<'a> std::iter::Iterator for LiveCells<'a> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end_col = self.rect.col + self.rect.width;
+
+        while self.row < self.rect.row + self.rect.height {
+            let row = &self.board[self.row][self.col..end_col];
+            if let Some(offset) = row.iter().position(|&alive| alive) {
+                let found = (self.row, self.col + offset);
+                self.col += offset + 1;
+                return Some(found);
+            }
+
+            self.row += 1;
+            self.col = self.rect.col;
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rows_left = (self.rect.row + self.rect.height).saturating_sub(self.row);
+        let cells_left = (rows_left * self.rect.width).saturating_sub(self.col - self.rect.col);
+
+        (0, Some(cells_left))
+    }
+}
+
+impl<'a> std::iter::Iterator for CellIterator<'a> {
+    type Item = Cell<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.board.cells.len() {
+            let width = self.board.width;
+            let row = self.index / width;
+            let col = self.index % width;
+            let state = &self.board.cells[self.index];
+            self.index += 1;
+
+            Some(Cell { row, col, state })
+        } else {
+            None
+        }
+    }
+}
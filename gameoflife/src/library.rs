@@ -0,0 +1,207 @@
+use crate::pattern::Pattern;
+use crate::{patterns, BoundaryMode, LifeBoard};
+
+/// Ways a library entry can disagree with the engine. See
+/// `LibraryEntry::verify`.
+#[derive(Debug, PartialEq)]
+pub enum VerifyError {
+    /// The pattern took its shape again after fewer generations than its
+    /// period.
+    RepeatsEarly(u64),
+    /// The pattern did not take its shape again after its period.
+    NoRepeat,
+    /// The pattern repeated after its period but moved by another (row,
+    /// column) displacement.
+    WrongDisplacement((i64, i64)),
+}
+
+impl std::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            VerifyError::RepeatsEarly(generation) => {
+                write!(f, "pattern repeats after {} generations", generation)
+            }
+            VerifyError::NoRepeat => f.write_str("pattern does not repeat after its period"),
+            VerifyError::WrongDisplacement((rows, cols)) => {
+                write!(f, "pattern moves by ({}, {}) each period", rows, cols)
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Pattern together with how it behaves under Conway's rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibraryEntry {
+    pub name: &'static str,
+    pub pattern: Pattern,
+    /// Generations until the pattern takes the same shape again; 1 for a
+    /// still life.
+    pub period: u64,
+    /// (row, column) distance the pattern moves each period, with down and
+    /// right positive. Zero for everything but spaceships.
+    pub displacement: (i64, i64),
+}
+
+impl LibraryEntry {
+    pub fn new(
+        name: &'static str,
+        pattern: Pattern,
+        period: u64,
+        displacement: (i64, i64),
+    ) -> LibraryEntry {
+        LibraryEntry {
+            name,
+            pattern,
+            period,
+            displacement,
+        }
+    }
+
+    /// Width and height of the pattern's bounding box.
+    pub fn bounding_box(&self) -> (usize, usize) {
+        (self.pattern.width(), self.pattern.height())
+    }
+
+    pub fn is_spaceship(&self) -> bool {
+        self.displacement != (0, 0)
+    }
+
+    /// Runs the pattern for one period on a board with dead edges large
+    /// enough that it cannot reach them, and checks that it first takes its
+    /// shape again after exactly `period` generations, moved by
+    /// `displacement`.
+    pub fn verify(&self) -> Result<(), VerifyError> {
+        let (rows, cols) = self.displacement;
+        let margin = rows.unsigned_abs().max(cols.unsigned_abs()) as usize + 4;
+        let (width, height) = self.bounding_box();
+
+        let mut board = LifeBoard::new(width + 2 * margin, height + 2 * margin);
+        board.set_boundary(BoundaryMode::Dead);
+        board.stamp(&self.pattern, margin, margin).unwrap();
+        let (start, start_corner) = shape(&board);
+
+        for generation in 1..=self.period {
+            board.advance();
+            let (cells, corner) = shape(&board);
+            if cells != start {
+                continue;
+            }
+            if generation < self.period {
+                return Err(VerifyError::RepeatsEarly(generation));
+            }
+
+            let moved = (corner.0 - start_corner.0, corner.1 - start_corner.1);
+            return if moved == self.displacement {
+                Ok(())
+            } else {
+                Err(VerifyError::WrongDisplacement(moved))
+            };
+        }
+
+        Err(VerifyError::NoRepeat)
+    }
+}
+
+// Live cells translated so the bounding box starts at the origin, and the
+// position of that corner on the board
+fn shape(board: &LifeBoard) -> (Vec<(i64, i64)>, (i64, i64)) {
+    let cells: Vec<(i64, i64)> = board
+        .live_cells()
+        .map(|(row, col)| (row as i64, col as i64))
+        .collect();
+    let top = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+    let left = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+
+    (
+        cells
+            .into_iter()
+            .map(|(row, col)| (row - top, col - left))
+            .collect(),
+        (top, left),
+    )
+}
+
+/// Collection of named patterns with their periods and displacements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternLibrary {
+    entries: Vec<LibraryEntry>,
+}
+
+impl PatternLibrary {
+    pub fn new() -> PatternLibrary {
+        PatternLibrary {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Classic still lifes, oscillators and spaceships, in the orientations
+    /// given by the `patterns` module.
+    pub fn classic() -> PatternLibrary {
+        let mut library = PatternLibrary::new();
+        let entries = [
+            ("block", patterns::block(), 1, (0, 0)),
+            ("beehive", patterns::beehive(), 1, (0, 0)),
+            ("loaf", patterns::loaf(), 1, (0, 0)),
+            ("boat", patterns::boat(), 1, (0, 0)),
+            ("blinker", patterns::blinker(), 2, (0, 0)),
+            ("toad", patterns::toad(), 2, (0, 0)),
+            ("beacon", patterns::beacon(), 2, (0, 0)),
+            ("pulsar", patterns::pulsar(), 3, (0, 0)),
+            ("pentadecathlon", patterns::pentadecathlon(), 15, (0, 0)),
+            ("glider", patterns::glider(), 4, (1, 1)),
+            ("lwss", patterns::lwss(), 4, (0, -2)),
+            ("mwss", patterns::mwss(), 4, (0, -2)),
+            ("hwss", patterns::hwss(), 4, (0, -2)),
+        ];
+        for (name, pattern, period, displacement) in entries {
+            library.insert(LibraryEntry::new(name, pattern, period, displacement));
+        }
+
+        library
+    }
+
+    /// Adds an entry, replacing any entry of the same name.
+    pub fn insert(&mut self, entry: LibraryEntry) {
+        match self.entries.iter_mut().find(|e| e.name == entry.name) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// Entry with the given name, ignoring case.
+    pub fn lookup(&self, name: &str) -> Option<&LibraryEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Entries in the order they were added.
+    pub fn iter(&self) -> std::slice::Iter<'_, LibraryEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for PatternLibrary {
+    fn default() -> PatternLibrary {
+        PatternLibrary::classic()
+    }
+}
+
+impl<'a> IntoIterator for &'a PatternLibrary {
+    type Item = &'a LibraryEntry;
+    type IntoIter = std::slice::Iter<'a, LibraryEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,167 @@
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+
+use crate::zobrist::zobrist_key;
+use crate::{report_changes, BoardMismatch, LifeBoard};
+
+/// A Larger than Life rule, under which each cell counts the live cells in
+/// the square reaching `radius` cells out from it on every side, not
+/// counting itself. A dead cell is born when the count is in `birth` and a
+/// live cell survives when it is in `survive`.
+///
+/// Rules are often written counting the cell itself in the survival range,
+/// as Golly's "M1" does. Such a range is one lower here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LtlRule {
+    pub radius: usize,
+    pub birth: RangeInclusive<u32>,
+    pub survive: RangeInclusive<u32>,
+}
+
+impl LtlRule {
+    /// Conway's Game of Life, radius 1 with birth on 3 and survival on 2
+    /// or 3.
+    pub fn life() -> LtlRule {
+        LtlRule {
+            radius: 1,
+            birth: 3..=3,
+            survive: 2..=3,
+        }
+    }
+
+    /// Bugs, radius 5 with birth on 34 to 45 and survival on 34 to 58
+    /// counting the cell itself.
+    pub fn bugs() -> LtlRule {
+        LtlRule {
+            radius: 5,
+            birth: 34..=45,
+            survive: 33..=57,
+        }
+    }
+
+    /// Whether a cell with `count` live cells around it is alive in the next
+    /// generation.
+    pub fn applies(&self, alive: bool, count: u32) -> bool {
+        if alive {
+            self.survive.contains(&count)
+        } else {
+            self.birth.contains(&count)
+        }
+    }
+}
+
+impl LifeBoard {
+    /// Live cells in the square reaching `radius` cells out from each cell,
+    /// not counting the cell itself, in row order. Cells past the edges are
+    /// found through the boundary mode, so on a board smaller than the
+    /// square a wrapping board counts some cells more than once.
+    ///
+    /// The board is padded by `radius` cells on every side and summed into
+    /// a summed-area table, so every count takes four lookups whatever the
+    /// radius.
+    pub fn neighbour_counts_within(&self, radius: usize) -> Vec<u32> {
+        let (width, height) = (self.width, self.height);
+        if width == 0 || height == 0 {
+            return Vec::new();
+        }
+
+        // sums[y][x] holds the live cells of the padded board above row y and
+        // left of column x
+        let (padded_width, padded_height) = (width + 2 * radius, height + 2 * radius);
+        let stride = padded_width + 1;
+        let mut sums = vec![0u32; stride * (padded_height + 1)];
+        for y in 0..padded_height {
+            let row = y as isize - radius as isize;
+            let mut row_sum = 0;
+            for x in 0..padded_width {
+                let col = x as isize - radius as isize;
+                let alive = match (usize::try_from(row), usize::try_from(col)) {
+                    (Ok(row), Ok(col)) if row < height && col < width => {
+                        self.cells[row * width + col]
+                    }
+                    _ => self
+                        .boundary
+                        .resolve(row, col, width, height)
+                        .is_some_and(|(row, col)| self.cells[row * width + col]),
+                };
+                row_sum += alive as u32;
+                sums[(y + 1) * stride + x + 1] = sums[y * stride + x + 1] + row_sum;
+            }
+        }
+
+        let side = 2 * radius + 1;
+        let mut counts = Vec::with_capacity(self.cells.len());
+        for row in 0..height {
+            for col in 0..width {
+                let (top, bottom) = (row * stride, (row + side) * stride);
+                let total = sums[bottom + col + side] + sums[top + col]
+                    - sums[top + col + side]
+                    - sums[bottom + col];
+                counts.push(total - self.cells[row * width + col] as u32);
+            }
+        }
+
+        counts
+    }
+
+    /// Writes the next generation of this board under `rule` into `next`,
+    /// like `step` does under the board's own rule, which is kept.
+    pub fn step_ltl(&self, rule: &LtlRule, next: &mut LifeBoard) -> Result<(), BoardMismatch> {
+        self.check_next(next)?;
+
+        let hash = self.step_ltl_cells(rule, &mut next.cells);
+        self.finish_step(next, hash);
+        Ok(())
+    }
+
+    /// Replaces this board with its next generation under `rule`.
+    pub fn advance_ltl(&mut self, rule: &LtlRule) {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.resize(self.cells.len(), false);
+
+        let hash = self.step_ltl_cells(rule, &mut scratch);
+        self.scratch = std::mem::replace(&mut self.cells, scratch);
+        self.finish_advance(hash);
+    }
+
+    // Like `step_cells`, under `rule`
+    fn step_ltl_cells(&self, rule: &LtlRule, next: &mut [bool]) -> Option<u64> {
+        match self.hash {
+            Some(mut hash) => {
+                self.step_ltl_rows(rule, next, |index, _| hash ^= zobrist_key(index));
+                Some(hash)
+            }
+            None => {
+                self.step_ltl_rows(rule, next, |_, _| {});
+                None
+            }
+        }
+    }
+
+    // Like `step_rows` over the whole board, under `rule`
+    fn step_ltl_rows(
+        &self,
+        rule: &LtlRule,
+        next: &mut [bool],
+        mut on_change: impl FnMut(usize, bool),
+    ) {
+        if self.width == 0 {
+            return;
+        }
+
+        let counts = self.neighbour_counts_within(rule.radius);
+        for (row, target) in next.chunks_mut(self.width).enumerate() {
+            let cells = row * self.width..(row + 1) * self.width;
+            for ((out, &alive), &count) in target
+                .iter_mut()
+                .zip(&self.cells[cells.clone()])
+                .zip(&counts[cells.clone()])
+            {
+                *out = rule.applies(alive, count);
+            }
+
+            self.kill_aged_row(row, target);
+            report_changes(cells.start, &self.cells[cells], target, &mut on_change);
+        }
+    }
+}
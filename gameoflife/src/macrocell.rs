@@ -0,0 +1,216 @@
+use crate::format::FormatError;
+use crate::rule::Rule;
+use crate::sparse::SparseLifeBoard;
+
+const MACROCELL_HEADER: &str = "[M2]";
+
+/// Level of the 8 by 8 leaves of a macrocell file, a square of 2^level cells.
+pub(crate) const LEAF_LEVEL: u8 = 3;
+
+// Deepest node whose cells all have coordinates that fit in an i64
+const MAX_LEVEL: u8 = 63;
+
+// Node of a macrocell file. Nodes are numbered from 1 in the order they are
+// defined; 0 is the empty square of any level and is never defined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum MacroNode {
+    // Rows of an 8 by 8 square, top first, with the leftmost cell in the
+    // lowest bit
+    Leaf([u8; 8]),
+    // Square of 2^level cells made of the nodes numbered in `quadrants`, in
+    // nw, ne, sw, se order
+    Branch { level: u8, quadrants: [usize; 4] },
+}
+
+impl MacroNode {
+    fn level(&self) -> u8 {
+        match self {
+            MacroNode::Leaf(_) => LEAF_LEVEL,
+            MacroNode::Branch { level, .. } => *level,
+        }
+    }
+}
+
+// Parsed macrocell file. The last node is the root.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Macrocell {
+    pub(crate) rule: Rule,
+    pub(crate) nodes: Vec<MacroNode>,
+}
+
+impl SparseLifeBoard {
+    /// Loads a pattern in Golly's macrocell format, `[M2]`, which stores a
+    /// quadtree with every distinct square written once.
+    ///
+    /// The root square is centred on the origin, as in Golly, and the board
+    /// takes the rule of an `#R` line, or Conway's rule without one. Every
+    /// live cell is listed on the board, so for patterns too big for that,
+    /// load them into a `HashLifeUniverse` instead.
+    pub fn from_macrocell(text: &str) -> Result<SparseLifeBoard, FormatError> {
+        let macrocell = parse_macrocell(text)?;
+        let mut board = SparseLifeBoard::new();
+        board.set_rule(macrocell.rule);
+
+        if let Some(root) = macrocell.nodes.len().checked_sub(1) {
+            let half = 1i64 << (macrocell.nodes[root].level() - 1);
+            add_cells(&mut board, &macrocell.nodes, root + 1, -half, -half);
+        }
+
+        Ok(board)
+    }
+}
+
+// Sets the cells of node `number` alive with its top left corner at `row`,
+// `col`
+fn add_cells(board: &mut SparseLifeBoard, nodes: &[MacroNode], number: usize, row: i64, col: i64) {
+    if number == 0 {
+        return;
+    }
+
+    match nodes[number - 1] {
+        MacroNode::Leaf(rows) => {
+            for (r, bits) in rows.iter().enumerate() {
+                for c in (0..8).filter(|c| bits >> c & 1 == 1) {
+                    board.set(row + r as i64, col + c as i64, true);
+                }
+            }
+        }
+        MacroNode::Branch { level, quadrants } => {
+            let half = 1i64 << (level - 1);
+            let [nw, ne, sw, se] = quadrants;
+            add_cells(board, nodes, nw, row, col);
+            add_cells(board, nodes, ne, row, col + half);
+            add_cells(board, nodes, sw, row + half, col);
+            add_cells(board, nodes, se, row + half, col + half);
+        }
+    }
+}
+
+// Parses a macrocell file. Blank lines and comments other than the rule are
+// skipped, and every node may only use nodes defined before it.
+pub(crate) fn parse_macrocell(text: &str) -> Result<Macrocell, FormatError> {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty());
+
+    match lines.next() {
+        Some((_, header)) if header.starts_with(MACROCELL_HEADER) => {}
+        _ => return Err(FormatError::InvalidHeader),
+    }
+
+    let mut rule = Rule::conway();
+    let mut nodes = Vec::new();
+    for (index, line) in lines {
+        if let Some(name) = line.strip_prefix("#R") {
+            rule = Rule::parse(name.trim())?;
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let node = if line.starts_with(|c: char| c.is_ascii_digit()) {
+            parse_branch(line, index + 1, &nodes)?
+        } else {
+            parse_leaf(line).ok_or(FormatError::InvalidNode(index + 1))?
+        };
+        nodes.push(node);
+    }
+
+    Ok(Macrocell { rule, nodes })
+}
+
+// Parses a leaf such as "..*$...*$.***$", in which `$` ends a row and rows
+// left out at the end are dead
+fn parse_leaf(line: &str) -> Option<MacroNode> {
+    let mut rows = [0u8; 8];
+    let (mut row, mut col) = (0, 0);
+
+    for c in line.chars() {
+        match c {
+            '.' => col += 1,
+            '*' if row < 8 && col < 8 => {
+                rows[row] |= 1 << col;
+                col += 1;
+            }
+            '$' => {
+                row += 1;
+                col = 0;
+            }
+            _ => return None,
+        }
+    }
+
+    Some(MacroNode::Leaf(rows))
+}
+
+// Parses a node such as "4 1 0 2 1", checking its quadrants against the
+// nodes defined so far
+fn parse_branch(
+    line: &str,
+    line_number: usize,
+    nodes: &[MacroNode],
+) -> Result<MacroNode, FormatError> {
+    let numbers: Option<Vec<usize>> = line
+        .split_whitespace()
+        .map(|part| part.parse().ok())
+        .collect();
+    let (level, quadrants) = match numbers.as_deref() {
+        Some(&[level, nw, ne, sw, se])
+            if level > LEAF_LEVEL as usize && level <= MAX_LEVEL as usize =>
+        {
+            (level as u8, [nw, ne, sw, se])
+        }
+        _ => return Err(FormatError::InvalidNode(line_number)),
+    };
+
+    for &number in quadrants.iter().filter(|&&number| number != 0) {
+        match nodes.get(number - 1) {
+            None => {
+                return Err(FormatError::UndefinedNode {
+                    line: line_number,
+                    node: number,
+                })
+            }
+            Some(quadrant) if quadrant.level() + 1 != level => {
+                return Err(FormatError::LevelMismatch {
+                    line: line_number,
+                    node: number,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(MacroNode::Branch { level, quadrants })
+}
+
+// Writes `nodes`, the last of which is the root, as a macrocell file
+pub(crate) fn write_macrocell(rule: Rule, nodes: &[MacroNode]) -> String {
+    let mut text = format!("{} (gameoflife)\n#R {}\n", MACROCELL_HEADER, rule);
+
+    for node in nodes {
+        match node {
+            MacroNode::Leaf(rows) => {
+                let used = rows
+                    .iter()
+                    .rposition(|&bits| bits != 0)
+                    .map_or(0, |last| last + 1);
+                for &bits in &rows[..used] {
+                    let width = 8 - bits.leading_zeros() as usize;
+                    text.extend((0..width).map(|c| if bits >> c & 1 == 1 { '*' } else { '.' }));
+                    text.push('$');
+                }
+            }
+            MacroNode::Branch { level, quadrants } => {
+                let [nw, ne, sw, se] = quadrants;
+                text.push_str(&format!("{} {} {} {} {}", level, nw, ne, sw, se));
+            }
+        }
+        text.push('\n');
+    }
+
+    text
+}
@@ -1,191 +1,565 @@
-extern crate rand;
-use rand::distributions::{IndependentSample, Range};
-use std::ops::{Index, IndexMut};
-use std::{thread, time};
+extern crate crossterm;
+extern crate gameoflife;
 
-#[derive(Debug)]
-struct LifeBoard {
-    board_size: usize,
-    cells: std::vec::Vec<bool>,
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, style, terminal};
+use gameoflife::interactive::{Command, Key, Session};
+use gameoflife::options::{self, Options, OptionsError, OutputFormat, RendererKind};
+use gameoflife::{
+    compose_styled_frame, fit_zoomed_out, run_headless, run_rendered_with, run_soups,
+    terminal_size, AnsiRenderer, CellProbes, FramePacer, HistoryBuffer, LifeBoard, NullRenderer,
+    PlainStdoutRenderer, PopulationHistory, Recorder, Recording, Rect, RenderStyle, Renderer,
+    Viewport,
+};
+use std::fs::File;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::time;
+
+// Frames between the keyframes of a recording made with --record
+const KEYFRAME_INTERVAL: u64 = 64;
+
+// Samples kept by the population history of --history and --sparkline
+const HISTORY_LIMIT: usize = 4096;
+
+fn run(board: &mut LifeBoard, options: &Options) -> std::io::Result<()> {
+    let mut output = output(options, board.width(), board.height())?;
+    if let Some(path) = &options.record {
+        let file = io::BufWriter::new(File::create(path)?);
+        output.recorder = Some(Recorder::new(file, board, options.seed, KEYFRAME_INTERVAL)?);
+    }
+    if options.history.is_some() || options.sparkline {
+        output.history = Some(PopulationHistory::with_limit(HISTORY_LIMIT));
+    }
+
+    let mut probes = CellProbes::streaming(io::stderr());
+    for (row, col, label) in &options.probes {
+        probes.add_probe(*row, *col, label.as_str());
+    }
+    probes.observe(board);
+
+    let noise = options.noise;
+    let mut rng = options.noise_rng();
+    run_rendered_with(
+        board,
+        options.generations,
+        options.frames,
+        options.pacer().interval(),
+        |board| {
+            board.advance_noisy(noise, &mut rng);
+            probes.observe(board);
+        },
+        &mut output,
+    )?;
+    if let Some(e) = probes.take_error() {
+        return Err(e);
+    }
+    if let Some(recorder) = output.recorder.take() {
+        recorder.finish()?;
+    }
+    if let Some(history) = &output.history {
+        if let Some(path) = &options.history {
+            let mut file = io::BufWriter::new(File::create(path)?);
+            history.to_csv(&mut file)?;
+            file.flush()?;
+        }
+        if options.sparkline {
+            let width = terminal_size().map_or(80, |(columns, _)| columns as usize);
+            println!("{}", history.sparkline(width));
+        }
+    }
+
+    Ok(())
 }
-struct CellMut<'a> {
-    row: usize,
-    col: usize,
-    state: &'a mut bool,
+
+// Runs the board alongside the rules it is compared with, drawing them next to
+// each other by hand as the renderers only draw a single board
+fn run_comparison(board: LifeBoard, options: &Options) -> io::Result<()> {
+    let mut comparison = options.comparison(board);
+    let tty = io::stdout().is_terminal();
+    let color = options.color && tty;
+    let in_place = match options.renderer {
+        RendererKind::Ansi => true,
+        RendererKind::Auto => tty,
+        RendererKind::Plain | RendererKind::Null => false,
+    };
+    let _cursor = if in_place {
+        Some(HiddenCursor::hide()?)
+    } else {
+        None
+    };
+    let mut pacer = options.pacer();
+
+    for generation in 0..=options.generations {
+        if generation > 0 {
+            comparison.advance();
+        }
+        if options.renderer == RendererKind::Null {
+            continue;
+        }
+
+        let frame = comparison.compose_frame(options.style, color);
+        let mut out = io::stdout().lock();
+        if in_place {
+            queue!(out, cursor::MoveTo(0, 0))?;
+        }
+        out.write_all(frame.as_bytes())?;
+        out.flush()?;
+        drop(out);
+        pacer.wait();
+    }
+
+    Ok(())
 }
 
-struct Cell<'a> {
-    row: usize,
-    col: usize,
-    state: &'a bool,
+// Hidden cursor on a cleared screen, shown again when dropped
+struct HiddenCursor;
+
+impl HiddenCursor {
+    fn hide() -> io::Result<HiddenCursor> {
+        execute!(
+            io::stdout(),
+            cursor::Hide,
+            terminal::Clear(terminal::ClearType::All)
+        )?;
+
+        Ok(HiddenCursor)
+    }
 }
 
-struct CellMutIterator<'a> {
-    board: &'a mut LifeBoard,
-    index: usize,
+impl Drop for HiddenCursor {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show);
+    }
 }
 
-struct CellIterator<'a> {
-    board: &'a LifeBoard,
-    index: usize,
+fn replay(path: &Path, options: &Options) -> io::Result<()> {
+    let recording = Recording::load(path).map_err(io::Error::other)?;
+    let header = recording.header();
+    let mut output = output(options, header.width, header.height)?;
+
+    recording.play(options.seek, options.pacer().interval(), &mut output)
 }
 
-impl LifeBoard {
-    fn new(board_size: usize) -> LifeBoard {
-        let mut cells = Vec::new();
-        cells.resize(board_size * board_size, false);
+// Renderer chosen by the options, drawing boards of `width` by `height` cells
+// to the terminal
+fn output(options: &Options, width: usize, height: usize) -> io::Result<Output> {
+    let tty = io::stdout().is_terminal();
+    let color = options.color && tty;
+    let terminal = terminal_size();
+    let zoom_out = options.zoom_out_for(width, height, terminal);
+    let renderer: Box<dyn Renderer> = match options.renderer {
+        RendererKind::Ansi => {
+            Box::new(AnsiRenderer::new(options.style, color)?.zoomed_out(zoom_out))
+        }
+        RendererKind::Auto if tty => {
+            Box::new(AnsiRenderer::new(options.style, color)?.zoomed_out(zoom_out))
+        }
+        RendererKind::Auto | RendererKind::Plain => {
+            Box::new(PlainStdoutRenderer::new(options.style, color).zoomed_out(zoom_out))
+        }
+        RendererKind::Null => Box::new(NullRenderer),
+    };
 
-        LifeBoard { board_size, cells }
-    }
+    Ok(Output {
+        inner: renderer,
+        visible: terminal.map(|(columns, rows)| match zoom_out {
+            1 => options.style.fit_to_terminal(columns, rows),
+            k => fit_zoomed_out(k, columns, rows),
+        }),
+        viewport: Viewport::new(width, height),
+        follow: options.follow,
+        recorder: None,
+        history: None,
+        previous: None,
+    })
+}
 
-    fn print(&self) {
-        let header_footer = || {
-            print!("+");
-                for _ in 0..self.board_size {
-                print!("--");
-            }
-            println!("--+");
-        };
+// Renderer that records whole boards and their populations when asked to, then
+// draws them through a viewport if they are too big for the terminal, keeping
+// the live cells in the middle of it with --follow
+struct Output {
+    inner: Box<dyn Renderer>,
+    visible: Option<(usize, usize)>,
+    viewport: Viewport,
+    follow: bool,
+    recorder: Option<Recorder<io::BufWriter<File>>>,
+    history: Option<PopulationHistory>,
+    // Board drawn last, to count births and deaths from
+    previous: Option<LifeBoard>,
+}
 
-        header_footer( );
-        for r in 0..self.board_size {
-            print!("| ");
-            for c in 0..self.board_size {
-                let board = &["  ", "# "];
-                let cell = self[r][c];
-                print!("{}", board[cell as usize]);
-            }
-            println!(" |");
+impl Renderer for Output {
+    fn render(&mut self, board: &LifeBoard, generation: u64) -> io::Result<()> {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(board)?;
+        }
+        if let Some(history) = &mut self.history {
+            let stats = match &self.previous {
+                Some(previous) => board.stats_since(previous),
+                None => board.stats(),
+            };
+            history.record(
+                generation,
+                stats.population,
+                stats.births.unwrap_or(0),
+                stats.deaths.unwrap_or(0),
+            );
+            self.previous = Some(board.clone());
         }
-        header_footer( );
-    }
 
-    fn iter_mut(&mut self) -> CellMutIterator {
-        // THIS IS A HACK TO ENABLE MUTABLE ITERATORS
-        // https://stackoverflow.com/questions/25730586/how-can-i-create-my-own-data-structure-with-an-iterator-that-returns-mutable-ref
-        unsafe {
-            CellMutIterator {
-                board: &mut *(self as *mut _),
-                index: 0,
+        match self.visible {
+            Some((width, height)) if board.width() > width || board.height() > height => {
+                let mut viewport = Viewport {
+                    width,
+                    height,
+                    ..self.viewport
+                };
+                if self.follow {
+                    viewport = viewport.follow(board);
+                }
+                self.viewport = viewport;
+                self.inner.render(&board.window(viewport), generation)
             }
+            _ => self.inner.render(board, generation),
         }
     }
 
-    fn iter(&self) -> CellIterator {
-        CellIterator {
-            board: &self,
-            index: 0,
-        }
+    fn finish(&mut self) {
+        self.inner.finish();
     }
+}
 
-    fn is_neighbour_alive(&self, cell: &Cell, delta_row: isize, delta_col: isize) -> u8 {
-        self[(cell.row.wrapping_add(delta_row as usize)) % self.board_size]
-            [(cell.col.wrapping_add(delta_col as usize)) % self.board_size] as u8
-    }
+fn run_without_drawing(board: &mut LifeBoard, options: &Options) -> io::Result<()> {
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let mut result = Ok(());
 
-    fn step(&self, next: &mut LifeBoard) {
-        for (source, target) in self.iter().zip(next.iter_mut()) {
-            let neighours = &[
-                (-1, -1),
-                (-1, 0),
-                (-1, 1),
-                (0, -1),
-                (0, 1),
-                (1, -1),
-                (1, 0),
-                (1, 1),
-            ];
-            let mut count = 0_u8;
+    let summary = run_headless(board, options.generations, options.every, |record| {
+        if result.is_ok() {
+            result = print_record(&mut out, options.format, record);
+        }
+    });
+    result?;
+    print_record(&mut out, options.format, &summary)?;
 
-            for cood in neighours {
-                count += self.is_neighbour_alive(&source, cood.0, cood.1);
-            }
-            count |= *source.state as u8;
-            
-            *target.state = count == 3;
+    out.flush()
+}
+
+#[cfg(feature = "serde")]
+fn print_record<T>(out: &mut impl Write, format: OutputFormat, record: &T) -> io::Result<()>
+where
+    T: std::fmt::Display + serde::Serialize,
+{
+    match format {
+        OutputFormat::Text => writeln!(out, "{}", record),
+        OutputFormat::Json => {
+            serde_json::to_writer(&mut *out, record)?;
+            writeln!(out)
         }
     }
 }
 
-impl Index<usize> for LifeBoard {
-    type Output = [bool];
-    fn index(&self, row: usize) -> &[bool] {
-        let row_index = row * self.board_size;
-        &self.cells[row_index..row_index + self.board_size]
-    }
+#[cfg(not(feature = "serde"))]
+fn print_record<T: std::fmt::Display>(
+    out: &mut impl Write,
+    _format: OutputFormat,
+    record: &T,
+) -> io::Result<()> {
+    // Options only accept JSON with the serde feature
+    writeln!(out, "{}", record)
 }
 
-impl IndexMut<usize> for LifeBoard {
-    fn index_mut(&mut self, row: usize) -> &mut [bool] {
-        let row_index = row * self.board_size;
-        &mut self.cells[row_index..row_index + self.board_size]
+// Raw mode on the alternate screen, restored when dropped
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> io::Result<RawTerminal> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen)?;
+
+        Ok(RawTerminal)
     }
 }
 
-impl<'a> std::iter::Iterator for CellMutIterator<'a> {
-    type Item = CellMut<'a>;
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), terminal::LeaveAlternateScreen, cursor::Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.board.cells.len() {
-            let board_size = self.board.board_size;
-            let row = self.index / board_size;
-            let col = self.index % board_size;
-            let state = &mut self.board.cells[self.index];
-            self.index += 1;
+fn run_interactive(board: &mut LifeBoard, options: &Options) -> io::Result<()> {
+    let _terminal = RawTerminal::enter()?;
+    let mut rng = options.rng();
+    let mut noise_rng = options.noise_rng();
+    let mut pacer = options.pacer();
+    let mut session = Session::default();
+    let mut history = (options.rewind > 0).then(|| HistoryBuffer::new(options.rewind));
+    let mut next_step = time::Instant::now() + pacer.interval();
+    let mut viewport = Viewport::new(board.width(), board.height());
 
-            // THIS IS A HACK TO ENABLE MUTABLE ITERATORS
-            // https://stackoverflow.com/questions/25730586/how-can-i-create-my-own-data-structure-with-an-iterator-that-returns-mutable-ref
-            unsafe {
-                Some(CellMut {
-                    row,
-                    col,
-                    state: &mut *(state as *mut _),
-                })
+    loop {
+        // Scroll boards bigger than the terminal to keep the cursor in sight,
+        // or with --follow keep the live cells in sight and the cursor with
+        // them
+        let (width, height) = terminal_size()
+            .map(|(columns, rows)| options.style.fit_to_terminal(columns, rows))
+            .unwrap_or((board.width(), board.height()));
+        let (board_width, board_height) = (board.width(), board.height());
+        viewport = Viewport {
+            width,
+            height,
+            ..viewport
+        };
+        if options.follow {
+            viewport = viewport.follow(board);
+            if viewport
+                .position_of(
+                    session.cursor_row,
+                    session.cursor_col,
+                    board_width,
+                    board_height,
+                )
+                .is_none()
+            {
+                session.cursor_row = (viewport.row + viewport.height / 2) % board_height.max(1);
+                session.cursor_col = (viewport.col + viewport.width / 2) % board_width.max(1);
             }
         } else {
-            None
+            viewport = viewport.scroll_to(
+                session.cursor_row,
+                session.cursor_col,
+                board_width,
+                board_height,
+                board.boundary(),
+            );
+        }
+        draw(board, viewport, &session, &pacer, options)?;
+
+        // Sleep in the poll until a key arrives or the next generation is due
+        let event = if session.paused {
+            Some(event::read()?)
+        } else {
+            let timeout = next_step.saturating_duration_since(time::Instant::now());
+            if event::poll(timeout)? {
+                Some(event::read()?)
+            } else {
+                None
+            }
+        };
+
+        let key = match event {
+            Some(event) => match key_of(event) {
+                Some(key) => key,
+                None => continue,
+            },
+            None => {
+                if let Some(history) = &mut history {
+                    history.push(board);
+                }
+                board.advance_noisy(options.noise, &mut noise_rng);
+                let now = time::Instant::now();
+                next_step = now + pacer.tick(now);
+                continue;
+            }
+        };
+
+        let (next, command) = session.handle_key(key, board.width(), board.height());
+        if session.paused && !next.paused {
+            pacer.reset();
+            next_step = time::Instant::now() + pacer.interval();
+        }
+        session = next;
+
+        match command {
+            Some(Command::Step) => {
+                if let Some(history) = &mut history {
+                    history.push(board);
+                }
+                board.advance_noisy(options.noise, &mut noise_rng);
+            }
+            Some(Command::StepBack) => {
+                if let Some(history) = &mut history {
+                    history.step_back(board);
+                }
+            }
+            Some(Command::Pan { rows, cols }) => {
+                // The cursor moves with the view, keeping its place on screen
+                let (width, height) = (board.width(), board.height());
+                let panned = viewport.pan(rows, cols, width, height, board.boundary());
+                let moved = |cursor: usize, from: usize, to: usize, len: usize| {
+                    (cursor + len + to - from) % len.max(1)
+                };
+                session.cursor_row = moved(session.cursor_row, viewport.row, panned.row, height);
+                session.cursor_col = moved(session.cursor_col, viewport.col, panned.col, width);
+                viewport = panned;
+            }
+            Some(Command::Toggle { row, col }) => {
+                board.toggle(row, col).unwrap();
+            }
+            Some(Command::Randomize) => board.randomize(options.density(), &mut rng).unwrap(),
+            Some(Command::Clear) => board.clear_region(Rect {
+                row: 0,
+                col: 0,
+                width: board.width(),
+                height: board.height(),
+            }),
+            Some(Command::Faster) => {
+                pacer.speed_up();
+                next_step = time::Instant::now() + pacer.interval();
+            }
+            Some(Command::Slower) => {
+                pacer.slow_down();
+                next_step = time::Instant::now() + pacer.interval();
+            }
+            Some(Command::Quit) => return Ok(()),
+            None => {}
         }
     }
 }
 
-impl<'a> std::iter::Iterator for CellIterator<'a> {
-    type Item = Cell<'a>;
+#[cfg(feature = "gui")]
+fn run_in_window(board: &mut LifeBoard, options: &Options) -> io::Result<()> {
+    gameoflife::run_window(board, Session::default(), options).map_err(io::Error::other)
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.board.cells.len() {
-            let board_size = self.board.board_size;
-            let row = self.index / board_size;
-            let col = self.index % board_size;
-            let state = &self.board.cells[self.index];
-            self.index += 1;
+#[cfg(not(feature = "gui"))]
+fn run_in_window(_board: &mut LifeBoard, _options: &Options) -> io::Result<()> {
+    Err(io::Error::other("--window needs the gui feature"))
+}
 
-            Some(Cell { row, col, state })
-        } else {
-            None
-        }
+fn key_of(event: Event) -> Option<Key> {
+    let (code, modifiers) = match event {
+        Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) => (code, modifiers),
+        _ => return None,
+    };
+
+    match code {
+        // Raw mode swallows the interrupt, so treat it as quitting
+        KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Some(Key::Char('q')),
+        KeyCode::Char(c) => Some(Key::Char(c)),
+        KeyCode::Up => Some(Key::Up),
+        KeyCode::Down => Some(Key::Down),
+        KeyCode::Left => Some(Key::Left),
+        KeyCode::Right => Some(Key::Right),
+        KeyCode::Enter => Some(Key::Enter),
+        _ => None,
     }
 }
 
+// Draws the part of the board in `viewport` and a status line, then parks the
+// terminal cursor on the selected cell
+fn draw(
+    board: &LifeBoard,
+    viewport: Viewport,
+    session: &Session,
+    pacer: &FramePacer,
+    options: &Options,
+) -> io::Result<()> {
+    let frame = compose_styled_frame(&board.window(viewport), options.style, options.color)
+        .replace('\n', "\r\n");
+    let status = match (session.paused, pacer.rate()) {
+        (true, _) => "paused".to_string(),
+        (false, None) => "running".to_string(),
+        (false, Some(rate)) if pacer.is_behind() => format!("running, {:.1}/s, behind", rate),
+        (false, Some(rate)) => format!("running, {:.1}/s", rate),
+    };
+    // Cells start after a border and a space. An ASCII cell is two columns
+    // wide, while the other styles park the cursor on the character holding
+    // the cell.
+    let (row, col) = viewport
+        .position_of(
+            session.cursor_row,
+            session.cursor_col,
+            board.width(),
+            board.height(),
+        )
+        .unwrap_or((0, 0));
+    let (column, row) = match options.style {
+        RenderStyle::Ascii => (2 + 2 * col, 1 + row),
+        RenderStyle::HalfBlock => (2 + col, 1 + row / 2),
+        RenderStyle::Braille => (2 + col / 2, 1 + row / 4),
+    };
+    let (column, row) = (column as u16, row as u16);
+    let mut out = io::stdout().lock();
+
+    queue!(
+        out,
+        cursor::MoveTo(0, 0),
+        style::Print(frame),
+        terminal::Clear(terminal::ClearType::UntilNewLine),
+        style::Print(format!("generation {} ({})", board.generation(), status)),
+        terminal::Clear(terminal::ClearType::UntilNewLine),
+        cursor::MoveTo(column, row),
+        cursor::Show
+    )?;
+    out.flush()
+}
+
 fn main() {
-    let mut x = LifeBoard::new(32);
-    let mut y = LifeBoard::new(32);
+    let mut options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(OptionsError::Help) => {
+            println!("{}", options::USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, options::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(count) = options.soup_search {
+        match run_soups(&options.soup_config(count)) {
+            Ok(report) => println!("{}", report),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
 
-    let step = Range::new(0, 2);
-    let mut rng = rand::thread_rng();
+    if let Some(path) = &options.replay {
+        if let Err(e) = replay(path, &options) {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
 
-    for cell in x.iter_mut() {
-        let state = cell.state;
-        *state = step.ind_sample(&mut rng) == 0;
+    if !options.headless && !options.window {
+        if let Some((columns, rows)) = terminal_size() {
+            options.fit_to_terminal(columns, rows);
+        }
     }
 
-    let ten_millis = time::Duration::from_millis(100);
-    for _ in 0..1000 {
-        x.step(&mut y);
-        x.print();
-        thread::sleep(ten_millis);
+    let mut board = match options.build_board() {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
 
-        y.step(&mut x);
-        y.print();
-        thread::sleep(ten_millis);
+    let result = if options.headless {
+        run_without_drawing(&mut board, &options)
+    } else if !options.compare.is_empty() {
+        run_comparison(board, &options)
+    } else if options.window {
+        run_in_window(&mut board, &options)
+    } else if options.interactive {
+        run_interactive(&mut board, &options)
+    } else {
+        run(&mut board, &options)
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
     }
 }
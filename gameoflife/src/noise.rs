@@ -0,0 +1,95 @@
+use rand::Rng;
+
+use crate::zobrist::zobrist_key;
+use crate::LifeBoard;
+
+/// Probability outside of the range [0, 1] passed to `Noise::new`.
+#[derive(Debug, PartialEq)]
+pub struct ProbabilityError(pub f64);
+
+impl std::fmt::Display for ProbabilityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "probability {} is not between 0 and 1", self.0)
+    }
+}
+
+impl std::error::Error for ProbabilityError {}
+
+/// Error rates applied to the outcome of the rule by
+/// `LifeBoard::advance_noisy`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Noise {
+    birth: f64,
+    death: f64,
+}
+
+impl Noise {
+    /// Noise bringing each cell the rule leaves dead to life with
+    /// probability `birth`, and killing each cell it leaves alive with
+    /// probability `death`.
+    pub fn new(birth: f64, death: f64) -> Result<Noise, ProbabilityError> {
+        for p in [birth, death] {
+            if !(0.0..=1.0).contains(&p) {
+                return Err(ProbabilityError(p));
+            }
+        }
+
+        Ok(Noise { birth, death })
+    }
+
+    /// Noise bringing each cell the rule leaves dead to life with
+    /// probability `p`, like rain falling on the board, and never killing
+    /// any.
+    pub fn rain(p: f64) -> Result<Noise, ProbabilityError> {
+        Noise::new(p, 0.0)
+    }
+
+    /// Noise flipping the outcome of the rule for each cell with probability
+    /// `p`.
+    pub fn flip(p: f64) -> Result<Noise, ProbabilityError> {
+        Noise::new(p, p)
+    }
+
+    pub fn birth(&self) -> f64 {
+        self.birth
+    }
+
+    pub fn death(&self) -> f64 {
+        self.death
+    }
+
+    /// Whether no outcome can ever be changed.
+    pub fn is_none(&self) -> bool {
+        self.birth == 0.0 && self.death == 0.0
+    }
+}
+
+impl LifeBoard {
+    /// Replaces this board with its next generation, like `advance`, then
+    /// perturbs the outcome of each cell as `noise` describes, drawing from
+    /// `rng`. The same seed gives the same run.
+    ///
+    /// Without noise this is exactly `advance` and `rng` is left untouched.
+    pub fn advance_noisy(&mut self, noise: Noise, rng: &mut impl Rng) {
+        self.advance();
+        if noise.is_none() {
+            return;
+        }
+
+        for (index, cell) in self.cells.iter_mut().enumerate() {
+            let p = if *cell { noise.death } else { noise.birth };
+            if p > 0.0 && rng.gen_bool(p) {
+                *cell = !*cell;
+                if let Some(ages) = &mut self.ages {
+                    ages[index] = 0;
+                }
+                if let Some(heat) = &mut self.heat {
+                    heat[index] += 1;
+                }
+                if let Some(hash) = &mut self.hash {
+                    *hash ^= zobrist_key(index);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::cycle::RunOutcome;
+use crate::LifeBoard;
+
+/// Hooks called by `LifeBoard::run_observed` as a run progresses.
+pub trait SimulationObserver {
+    /// Called with the starting board and again after every step.
+    fn on_generation(&mut self, board: &LifeBoard, generation: u64);
+
+    /// Called for every cell a step changed, in row order, before
+    /// `on_generation` is called with the new board.
+    fn on_cell_changed(&mut self, _row: usize, _col: usize, _alive: bool) {}
+
+    /// Called once when the run ends.
+    fn on_finished(&mut self, outcome: RunOutcome);
+}
+
+impl LifeBoard {
+    /// Steps the board until it returns to a state it has been in before, or
+    /// until `max_generations` steps have been taken, like `run_until_cycle`,
+    /// reporting each generation to `observer`.
+    pub fn run_observed(
+        &mut self,
+        max_generations: usize,
+        observer: &mut dyn SimulationObserver,
+    ) -> RunOutcome {
+        let mut seen = HashMap::new();
+        let mut outcome = RunOutcome::MaxedOut;
+
+        observer.on_generation(self, self.generation);
+        for steps in 0..=max_generations {
+            if let Some(found) = self.check_cycle(&mut seen) {
+                outcome = found;
+                break;
+            }
+            if steps == max_generations {
+                break;
+            }
+
+            self.advance();
+            // The scratch buffer holds the previous generation after `advance`
+            let changes = self.scratch.iter().zip(&self.cells).enumerate();
+            for (index, (&was, &is)) in changes {
+                if was != is {
+                    observer.on_cell_changed(index / self.width, index % self.width, is);
+                }
+            }
+            observer.on_generation(self, self.generation);
+        }
+
+        observer.on_finished(outcome);
+        outcome
+    }
+}
+
+/// Observer that records the population of every generation and counts
+/// births and deaths.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsLogger {
+    /// Generation and population, in the order they were seen.
+    pub populations: Vec<(u64, usize)>,
+    pub births: u64,
+    pub deaths: u64,
+    /// How the run ended, once it has.
+    pub outcome: Option<RunOutcome>,
+}
+
+impl StatsLogger {
+    pub fn new() -> StatsLogger {
+        StatsLogger::default()
+    }
+
+    /// Generation with the most live cells, the first one on ties.
+    pub fn peak(&self) -> Option<(u64, usize)> {
+        self.populations
+            .iter()
+            .rev()
+            .max_by_key(|&&(_, population)| population)
+            .copied()
+    }
+}
+
+impl SimulationObserver for StatsLogger {
+    fn on_generation(&mut self, board: &LifeBoard, generation: u64) {
+        self.populations.push((generation, board.population()));
+    }
+
+    fn on_cell_changed(&mut self, _row: usize, _col: usize, alive: bool) {
+        if alive {
+            self.births += 1;
+        } else {
+            self.deaths += 1;
+        }
+    }
+
+    fn on_finished(&mut self, outcome: RunOutcome) {
+        self.outcome = Some(outcome);
+    }
+}
+
+/// Observer that writes every `every`th generation to a PBM file named after
+/// its generation, such as `frame_000120.pbm`, in a directory.
+///
+/// Observers cannot fail a run, so the first error writing a frame is kept
+/// for `take_error` and no more frames are written until it is taken.
+#[derive(Debug)]
+pub struct FrameDumper {
+    directory: PathBuf,
+    every: u64,
+    written: usize,
+    error: Option<std::io::Error>,
+}
+
+impl FrameDumper {
+    /// Dumper writing into `directory`, which must exist.
+    ///
+    /// Panics if `every` is zero.
+    pub fn new(directory: impl Into<PathBuf>, every: u64) -> FrameDumper {
+        assert!(every > 0, "frame interval must be positive");
+
+        FrameDumper {
+            directory: directory.into(),
+            every,
+            written: 0,
+            error: None,
+        }
+    }
+
+    /// Number of frames written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// First error hit writing a frame, if any.
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+}
+
+impl SimulationObserver for FrameDumper {
+    fn on_generation(&mut self, board: &LifeBoard, generation: u64) {
+        if self.error.is_some() || !generation.is_multiple_of(self.every) {
+            return;
+        }
+
+        let path = self.directory.join(format!("frame_{:06}.pbm", generation));
+        match std::fs::write(path, board.to_pbm()) {
+            Ok(()) => self.written += 1,
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    fn on_finished(&mut self, _outcome: RunOutcome) {}
+}
@@ -0,0 +1,657 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::compare::{fit_side_by_side, Comparison, COMPARISON_GAP};
+use crate::format::FormatError;
+use crate::noise::Noise;
+use crate::pacer::FramePacer;
+use crate::pipeline::FramePolicy;
+use crate::render::{fit_zoomed_out, zoom_to_fit, RenderStyle};
+use crate::rule::{Rule, RuleParseError};
+use crate::soup::SoupConfig;
+use crate::{BoundaryMode, LifeBoard};
+
+pub const USAGE: &str = "\
+Usage: gameoflife [OPTIONS]
+
+Options:
+    --width <CELLS>       Board width [default: fit the terminal, or 32]
+    --height <CELLS>      Board height [default: fit the terminal, or 32]
+    --density <0..1>      Fraction of cells alive in the random soup [default: 0.5]
+    --pattern <FILE>      Start from an RLE, plaintext or Life 1.06 pattern instead
+                          of a soup
+    --generations <N>     Number of generations to run [default: 2000]
+    --delay-ms <MS>       Time from one generation to the next [default: 100]
+    --rate <N>            Generations per second, instead of --delay-ms
+    --frames <POLICY>     Draw every generation (every), or only the newest one
+                          when drawing falls behind (latest) [default: every]
+    --seed <N>            Seed for the random soup
+    --rule <RULE>         Rule in B/S or Hensel notation [default: the pattern's rule or B3/S23]
+    --color               Color live cells when drawing to a terminal
+    --style <STYLE>       Draw two characters per cell (ascii), two cells per
+                          character (half) or eight (braille) [default: ascii]
+    --renderer <KIND>     Draw frames in place (ansi), one after another (plain),
+                          or not at all (null) [default: auto, which is ansi on a
+                          terminal and plain otherwise]
+    --zoom-out <K>        Draw each K by K block of cells as one shaded cell, or
+                          zoom out only as far as needed to fit the terminal
+                          (auto), in place of --style
+    --compare <RULE>      Also run the board under RULE, drawn alongside and counting
+                          the cells that differ. RULE may end in :BOUNDARY, one of
+                          wrap, dead, mirror or klein, or be just :BOUNDARY to keep
+                          the rule. May be given more than once
+    --rain <P>            After every step, bring each dead cell to life with
+                          probability P, drawn from the seeded generator
+    --decay <Q>           After every step, kill each live cell with probability Q
+    --probe <ROW,COL>     Write a line to standard error whenever the cell at ROW,
+                          COL changes state. May end in :LABEL to name the cell
+                          in the log, and be given more than once
+    --follow              Keep the live cells in the middle of the view when the
+                          board is bigger than the terminal
+    --ages                With --color, fade live cells the longer they survive
+    --soup-search <N>     Run N random soups, seeded from --seed upwards, until they
+                          settle or reach --generations and print statistics
+    --interactive         Run until quit, taking keys to pause (space), step (n),
+                          move the cursor (arrows), pan (w, a, s and d),
+                          toggle cells (enter), change speed (+ and -),
+                          randomize (r), clear (c) and quit (q)
+    --rewind <N>          Keep the last N generations of --interactive, to step
+                          back through with b while paused [default: 0]
+    --window              Like --interactive, drawn in a window where clicking a
+                          cell toggles it. Needs the gui feature
+    --headless            Print statistics for each generation instead of drawing,
+                          stopping early once the board repeats
+    --format <FORMAT>     Output of --headless, text or json (one object per line)
+                          [default: text]
+    --every <N>           Print every Nth generation in --headless mode [default: 1]
+    --history <FILE>      Write the population, births and deaths of every generation
+                          drawn to FILE as CSV
+    --sparkline           Print a sparkline of the population after the run
+    --record <FILE>       Also write every generation drawn to FILE, for --replay
+    --replay <FILE>       Play back a recording instead of running a simulation, at
+                          the speed set by --rate or --delay-ms
+    --seek <N>            Start --replay at generation N [default: 0]
+    --help                Print this message";
+
+/// Reasons the command line can be rejected.
+#[derive(Debug)]
+pub enum OptionsError {
+    /// `--help` was given.
+    Help,
+    UnknownOption(String),
+    MissingValue(String),
+    InvalidValue {
+        option: String,
+        value: String,
+    },
+    InvalidRule(RuleParseError),
+    /// `--pattern` and `--density` were both given.
+    PatternWithDensity,
+    /// `--pattern` and `--soup-search` were both given.
+    PatternWithSoupSearch,
+    /// `--compare` was given with `--headless`, `--interactive` or
+    /// `--window`.
+    CompareWithoutDrawing,
+    Pattern(std::io::Error),
+    PatternFormat(FormatError),
+}
+
+impl std::fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            OptionsError::Help => f.write_str(USAGE),
+            OptionsError::UnknownOption(o) => write!(f, "unknown option '{}'", o),
+            OptionsError::MissingValue(o) => write!(f, "missing value for '{}'", o),
+            OptionsError::InvalidValue { option, value } => {
+                write!(f, "invalid value '{}' for '{}'", value, option)
+            }
+            OptionsError::InvalidRule(e) => write!(f, "invalid rule: {}", e),
+            OptionsError::PatternWithDensity => {
+                f.write_str("'--pattern' and '--density' cannot be used together")
+            }
+            OptionsError::PatternWithSoupSearch => {
+                f.write_str("'--pattern' and '--soup-search' cannot be used together")
+            }
+            OptionsError::CompareWithoutDrawing => f.write_str(
+                "'--compare' cannot be used with '--headless', '--interactive' or '--window'",
+            ),
+            OptionsError::Pattern(e) => write!(f, "could not read pattern: {}", e),
+            OptionsError::PatternFormat(e) => write!(f, "could not load pattern: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OptionsError {}
+
+/// How the first generation is filled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Start {
+    /// Random soup where each cell is alive with the given probability.
+    Soup { density: f64 },
+    /// Pattern loaded from an RLE, plaintext or Life 1.06 file.
+    Pattern(PathBuf),
+}
+
+/// Where `--renderer` sends the frames of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RendererKind {
+    /// In place on a terminal, otherwise one frame after another.
+    Auto,
+    /// One frame after another.
+    Plain,
+    /// In place at the top of the terminal.
+    Ansi,
+    /// Nowhere, leaving only the cost of the simulation.
+    Null,
+}
+
+/// How far `--zoom-out` zooms out the drawn board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoomOut {
+    /// Blocks of this many cells across and down.
+    By(usize),
+    /// Just far enough for the board to fit the terminal.
+    Fit,
+}
+
+/// How `--headless` prints its records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    /// One JSON object per line. Needs the `serde` feature.
+    Json,
+}
+
+/// Settings for a run of the simulator, as given on the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub width: usize,
+    pub height: usize,
+    /// Whether the board may be sized to fit the terminal, which is the case
+    /// unless `--width` or `--height` was given.
+    pub fit_terminal: bool,
+    pub start: Start,
+    pub generations: usize,
+    pub delay_ms: u64,
+    /// Generations per second, overriding `delay_ms`.
+    pub rate: Option<f64>,
+    /// Which generations to draw when drawing is slower than the simulation.
+    pub frames: FramePolicy,
+    pub seed: Option<u64>,
+    /// Rule overriding the one given by the pattern file.
+    pub rule: Option<Rule>,
+    pub color: bool,
+    pub style: RenderStyle,
+    pub zoom_out: Option<ZoomOut>,
+    /// Rules and boundary modes to run alongside the board, each replacing
+    /// the board's own if given.
+    pub compare: Vec<(Option<Rule>, Option<BoundaryMode>)>,
+    pub renderer: RendererKind,
+    /// Random births and deaths after each step of a drawn run.
+    pub noise: Noise,
+    /// Cells of a drawn run to log the changes of, as row, column and label.
+    pub probes: Vec<(usize, usize, String)>,
+    /// Track how long cells have lived, so they can be drawn by age.
+    pub ages: bool,
+    /// Centre the view of a board bigger than the terminal on its live
+    /// cells.
+    pub follow: bool,
+    /// Take keyboard commands instead of running a fixed number of
+    /// generations.
+    pub interactive: bool,
+    /// Generations an interactive run keeps for stepping back.
+    pub rewind: usize,
+    /// Run interactively in a window instead of the terminal.
+    pub window: bool,
+    /// Number of soups to run in a soup search instead of drawing a board.
+    pub soup_search: Option<usize>,
+    /// Print per-generation statistics instead of drawing the board.
+    pub headless: bool,
+    pub format: OutputFormat,
+    /// Generations between the records printed by a headless run.
+    pub every: u64,
+    /// File to write the population history of the drawn generations to.
+    pub history: Option<PathBuf>,
+    /// Print a sparkline of the population after the run.
+    pub sparkline: bool,
+    /// File to record the drawn generations to.
+    pub record: Option<PathBuf>,
+    /// Recording to play back instead of running a simulation.
+    pub replay: Option<PathBuf>,
+    /// Generation to start playing a recording from.
+    pub seek: u64,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            width: 32,
+            height: 32,
+            fit_terminal: true,
+            start: Start::Soup { density: 0.5 },
+            generations: 2000,
+            delay_ms: 100,
+            rate: None,
+            frames: FramePolicy::EveryGeneration,
+            seed: None,
+            rule: None,
+            color: false,
+            style: RenderStyle::Ascii,
+            zoom_out: None,
+            compare: Vec::new(),
+            renderer: RendererKind::Auto,
+            noise: Noise::default(),
+            probes: Vec::new(),
+            ages: false,
+            follow: false,
+            interactive: false,
+            rewind: 0,
+            window: false,
+            soup_search: None,
+            headless: false,
+            format: OutputFormat::Text,
+            every: 1,
+            history: None,
+            sparkline: false,
+            record: None,
+            replay: None,
+            seek: 0,
+        }
+    }
+}
+
+impl Options {
+    /// Parses the arguments following the program name.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, OptionsError> {
+        let mut options = Options::default();
+        let mut density = None;
+        let mut pattern = None;
+
+        let mut args = args.into_iter();
+        while let Some(option) = args.next() {
+            match option.as_str() {
+                "--help" | "-h" => return Err(OptionsError::Help),
+                "--color" => {
+                    options.color = true;
+                    continue;
+                }
+                "--interactive" => {
+                    options.interactive = true;
+                    continue;
+                }
+                "--ages" => {
+                    options.ages = true;
+                    continue;
+                }
+                "--follow" => {
+                    options.follow = true;
+                    continue;
+                }
+                "--window" => {
+                    options.window = true;
+                    continue;
+                }
+                "--headless" => {
+                    options.headless = true;
+                    continue;
+                }
+                "--sparkline" => {
+                    options.sparkline = true;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let value = match option.as_str() {
+                "--width" | "--height" | "--density" | "--pattern" | "--generations"
+                | "--delay-ms" | "--seed" | "--rule" | "--soup-search" | "--format" | "--every"
+                | "--frames" | "--rate" | "--style" | "--zoom-out" | "--renderer" | "--record"
+                | "--replay" | "--seek" | "--compare" | "--history" | "--rain" | "--decay"
+                | "--rewind" | "--probe" => args
+                    .next()
+                    .ok_or_else(|| OptionsError::MissingValue(option.clone()))?,
+                _ => return Err(OptionsError::UnknownOption(option)),
+            };
+
+            match option.as_str() {
+                "--width" => {
+                    options.width = parse_value(&option, &value)?;
+                    options.fit_terminal = false;
+                }
+                "--height" => {
+                    options.height = parse_value(&option, &value)?;
+                    options.fit_terminal = false;
+                }
+                "--density" => {
+                    let d: f64 = parse_value(&option, &value)?;
+                    if !(0.0..=1.0).contains(&d) {
+                        return Err(OptionsError::InvalidValue { option, value });
+                    }
+                    density = Some(d);
+                }
+                "--pattern" => pattern = Some(PathBuf::from(value)),
+                "--record" => options.record = Some(PathBuf::from(value)),
+                "--history" => options.history = Some(PathBuf::from(value)),
+                "--replay" => options.replay = Some(PathBuf::from(value)),
+                "--seek" => options.seek = parse_value(&option, &value)?,
+                "--generations" => options.generations = parse_value(&option, &value)?,
+                "--delay-ms" => options.delay_ms = parse_value(&option, &value)?,
+                "--rate" => {
+                    let rate: f64 = parse_value(&option, &value)?;
+                    if !(rate.is_finite() && rate > 0.0) {
+                        return Err(OptionsError::InvalidValue { option, value });
+                    }
+                    options.rate = Some(rate);
+                }
+                "--rain" | "--decay" => {
+                    let p: f64 = parse_value(&option, &value)?;
+                    let (birth, death) = match option.as_str() {
+                        "--rain" => (p, options.noise.death()),
+                        _ => (options.noise.birth(), p),
+                    };
+                    options.noise = Noise::new(birth, death)
+                        .map_err(|_| OptionsError::InvalidValue { option, value })?;
+                }
+                "--seed" => options.seed = Some(parse_value(&option, &value)?),
+                "--rule" => {
+                    options.rule = Some(Rule::parse(&value).map_err(OptionsError::InvalidRule)?)
+                }
+                "--soup-search" => options.soup_search = Some(parse_value(&option, &value)?),
+                "--format" => {
+                    options.format = match value.as_str() {
+                        "text" => OutputFormat::Text,
+                        "json" if cfg!(feature = "serde") => OutputFormat::Json,
+                        _ => return Err(OptionsError::InvalidValue { option, value }),
+                    }
+                }
+                "--frames" => {
+                    options.frames = match value.as_str() {
+                        "every" => FramePolicy::EveryGeneration,
+                        "latest" => FramePolicy::LatestOnly,
+                        _ => return Err(OptionsError::InvalidValue { option, value }),
+                    }
+                }
+                "--style" => {
+                    options.style = match value.as_str() {
+                        "ascii" => RenderStyle::Ascii,
+                        "half" => RenderStyle::HalfBlock,
+                        "braille" => RenderStyle::Braille,
+                        _ => return Err(OptionsError::InvalidValue { option, value }),
+                    }
+                }
+                "--compare" => {
+                    let (rule, boundary) = match value.split_once(':') {
+                        Some((rule, boundary)) => (rule, Some(boundary)),
+                        None => (value.as_str(), None),
+                    };
+                    let rule = match rule {
+                        "" => None,
+                        rule => Some(Rule::parse(rule).map_err(OptionsError::InvalidRule)?),
+                    };
+                    let boundary = match boundary.map(parse_boundary) {
+                        Some(None) => return Err(OptionsError::InvalidValue { option, value }),
+                        Some(boundary) => boundary,
+                        None => None,
+                    };
+                    if rule.is_none() && boundary.is_none() {
+                        return Err(OptionsError::InvalidValue { option, value });
+                    }
+                    options.compare.push((rule, boundary));
+                }
+                "--probe" => {
+                    let (cell, label) = match value.split_once(':') {
+                        Some((cell, label)) => (cell, label.to_string()),
+                        None => (value.as_str(), value.clone()),
+                    };
+                    let (row, col) =
+                        cell.split_once(',')
+                            .ok_or_else(|| OptionsError::InvalidValue {
+                                option: option.clone(),
+                                value: value.clone(),
+                            })?;
+                    let row = parse_value(&option, row)?;
+                    let col = parse_value(&option, col)?;
+                    options.probes.push((row, col, label));
+                }
+                "--zoom-out" => {
+                    options.zoom_out = match value.as_str() {
+                        "auto" => Some(ZoomOut::Fit),
+                        _ => match parse_value(&option, &value)? {
+                            0 => return Err(OptionsError::InvalidValue { option, value }),
+                            k => Some(ZoomOut::By(k)),
+                        },
+                    }
+                }
+                "--renderer" => {
+                    options.renderer = match value.as_str() {
+                        "auto" => RendererKind::Auto,
+                        "plain" => RendererKind::Plain,
+                        "ansi" => RendererKind::Ansi,
+                        "null" => RendererKind::Null,
+                        _ => return Err(OptionsError::InvalidValue { option, value }),
+                    }
+                }
+                "--rewind" => options.rewind = parse_value(&option, &value)?,
+                "--every" => {
+                    options.every = parse_value(&option, &value)?;
+                    if options.every == 0 {
+                        return Err(OptionsError::InvalidValue { option, value });
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if !options.compare.is_empty()
+            && (options.headless || options.interactive || options.window)
+        {
+            return Err(OptionsError::CompareWithoutDrawing);
+        }
+        if pattern.is_some() && options.soup_search.is_some() {
+            return Err(OptionsError::PatternWithSoupSearch);
+        }
+        options.start = match (pattern, density) {
+            (Some(_), Some(_)) => return Err(OptionsError::PatternWithDensity),
+            (Some(path), None) => Start::Pattern(path),
+            (None, Some(density)) => Start::Soup { density },
+            (None, None) => options.start,
+        };
+
+        Ok(options)
+    }
+
+    /// Builds the first generation described by these options. A loaded
+    /// pattern is placed in the top left corner of the board, which grows to
+    /// fit it if needed.
+    pub fn build_board(&self) -> Result<LifeBoard, OptionsError> {
+        let mut board = match &self.start {
+            Start::Soup { density } => {
+                let mut rng = self.rng();
+                let mut board = LifeBoard::new(self.width, self.height);
+                board
+                    .randomize(*density, &mut rng)
+                    .map_err(|e| OptionsError::InvalidValue {
+                        option: "--density".to_string(),
+                        value: e.0.to_string(),
+                    })?;
+                board
+            }
+            Start::Pattern(path) => {
+                let text = std::fs::read_to_string(path).map_err(OptionsError::Pattern)?;
+                let pattern = load_pattern(&text).map_err(OptionsError::PatternFormat)?;
+
+                let mut board = LifeBoard::new(
+                    self.width.max(pattern.width),
+                    self.height.max(pattern.height),
+                );
+                for cell in pattern.iter().filter(|cell| *cell.state) {
+                    board[cell.row][cell.col] = true;
+                }
+                board.set_rule(pattern.rule());
+                board
+            }
+        };
+
+        if let Some(rule) = self.rule {
+            board.set_rule(rule);
+        }
+        if self.ages {
+            board.enable_ages();
+        }
+
+        Ok(board)
+    }
+
+    /// Sizes the board to fill a terminal of `columns` by `rows` characters
+    /// when drawn in the chosen style, unless the size was given on the
+    /// command line.
+    pub fn fit_to_terminal(&mut self, columns: u16, rows: u16) {
+        if self.fit_terminal {
+            let (width, height) = match self.zoom_out {
+                Some(ZoomOut::By(k)) => fit_zoomed_out(k, columns, rows),
+                _ if !self.compare.is_empty() => fit_side_by_side(
+                    self.style,
+                    self.compare.len() + 1,
+                    COMPARISON_GAP,
+                    columns,
+                    rows,
+                ),
+                _ => self.style.fit_to_terminal(columns, rows),
+            };
+            self.width = width;
+            self.height = height;
+        }
+    }
+
+    /// Cells across each block when drawing a `width` by `height` board on a
+    /// terminal of `columns` by `rows` characters, if known. One means every
+    /// cell is drawn in the chosen style. With `--zoom-out auto` a board that
+    /// fits the terminal in that style is not zoomed out.
+    pub fn zoom_out_for(&self, width: usize, height: usize, terminal: Option<(u16, u16)>) -> usize {
+        match (self.zoom_out, terminal) {
+            (Some(ZoomOut::By(k)), _) => k,
+            (Some(ZoomOut::Fit), Some((columns, rows))) => {
+                let (fit_width, fit_height) = self.style.fit_to_terminal(columns, rows);
+                if width <= fit_width && height <= fit_height {
+                    1
+                } else {
+                    zoom_to_fit(width, height, columns, rows)
+                }
+            }
+            (Some(ZoomOut::Fit), None) | (None, _) => 1,
+        }
+    }
+
+    /// Comparison of `board` under its own rule and boundary mode with the
+    /// ones given by `--compare`, each side labelled with its rule and
+    /// boundary mode.
+    pub fn comparison(&self, board: LifeBoard) -> Comparison {
+        let (rule, boundary) = (board.rule(), board.boundary());
+        let label = |rule: Rule, boundary| format!("{} {}", rule, boundary_name(boundary));
+
+        let mut comparison = Comparison::new(label(rule, boundary), board);
+        for &(other_rule, other_boundary) in &self.compare {
+            let rule = other_rule.unwrap_or(rule);
+            let boundary = other_boundary.unwrap_or(boundary);
+            comparison.add_side(label(rule, boundary), rule, boundary);
+        }
+
+        comparison
+    }
+
+    /// Soup search of `count` soups described by these options. Without a
+    /// seed the first soup's seed is picked at random.
+    pub fn soup_config(&self, count: usize) -> SoupConfig {
+        SoupConfig {
+            count,
+            width: self.width,
+            height: self.height,
+            density: self.density(),
+            rule: self.rule.unwrap_or_default(),
+            seed: self.seed.unwrap_or_else(rand::random),
+            max_generations: self.generations,
+        }
+    }
+
+    /// Pacer for the generation rate given by `--rate` or `--delay-ms`.
+    pub fn pacer(&self) -> FramePacer {
+        match self.rate {
+            Some(rate) => FramePacer::new(rate),
+            None => FramePacer::with_interval(Duration::from_millis(self.delay_ms)),
+        }
+    }
+
+    /// Random number generator seeded by `--seed`, or from entropy without it.
+    pub fn rng(&self) -> SmallRng {
+        match self.seed {
+            Some(seed) => SmallRng::seed_from_u64(seed),
+            None => SmallRng::from_entropy(),
+        }
+    }
+
+    /// Random number generator for `--rain` and `--decay`, seeded from
+    /// `--seed` apart from the soup's so the two do not repeat each other.
+    pub fn noise_rng(&self) -> SmallRng {
+        match self.seed {
+            Some(seed) => SmallRng::seed_from_u64(!seed),
+            None => SmallRng::from_entropy(),
+        }
+    }
+
+    /// Density of the random soup, or the default density when starting from
+    /// a pattern.
+    pub fn density(&self) -> f64 {
+        match self.start {
+            Start::Soup { density } => density,
+            Start::Pattern(_) => 0.5,
+        }
+    }
+}
+
+// Picks the format by the first line that is not a comment: RLE files start with
+// their "x = .." header. Life 1.06 files start with a header of their own.
+fn load_pattern(text: &str) -> Result<LifeBoard, FormatError> {
+    if text.trim_start().starts_with("#Life 1.06") {
+        return LifeBoard::from_life106(text);
+    }
+
+    let first = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'));
+
+    match first {
+        Some(line) if line.starts_with('x') => LifeBoard::from_rle(text),
+        _ => LifeBoard::from_plaintext(text),
+    }
+}
+
+fn parse_boundary(name: &str) -> Option<BoundaryMode> {
+    match name {
+        "wrap" => Some(BoundaryMode::Wrap),
+        "dead" => Some(BoundaryMode::Dead),
+        "mirror" => Some(BoundaryMode::Mirror),
+        "klein" => Some(BoundaryMode::KleinBottle),
+        _ => None,
+    }
+}
+
+fn boundary_name(boundary: BoundaryMode) -> String {
+    match boundary {
+        BoundaryMode::Wrap => "wrap".to_string(),
+        BoundaryMode::Dead => "dead".to_string(),
+        BoundaryMode::Mirror => "mirror".to_string(),
+        BoundaryMode::KleinBottle => "klein".to_string(),
+        BoundaryMode::ShiftedTorus { offset } => format!("shifted by {}", offset),
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, OptionsError> {
+    value.parse().map_err(|_| OptionsError::InvalidValue {
+        option: option.to_string(),
+        value: value.to_string(),
+    })
+}
@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// Frames the measured rate is averaged over
+const RATE_WINDOW: usize = 16;
+
+/// Keeps a loop running at a steady number of frames per second, whatever
+/// each frame costs.
+///
+/// After each frame, `tick` returns how long to wait before the next one:
+/// the part of the frame's time budget that computing and drawing did not
+/// use. Deadlines follow a fixed schedule so short frames do not drift. A
+/// frame that overruns its budget gets no wait, marks the pacer as behind
+/// and restarts the schedule from that frame rather than rushing to catch
+/// up.
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    interval: Duration,
+    next_frame: Option<Instant>,
+    behind: bool,
+    // Start times of the most recent frames, oldest first
+    frames: VecDeque<Instant>,
+}
+
+impl FramePacer {
+    /// Pacer for `generations_per_second` frames a second. Rates that are
+    /// not positive and finite run frames back to back.
+    pub fn new(generations_per_second: f64) -> FramePacer {
+        let interval = if generations_per_second.is_finite() && generations_per_second > 0.0 {
+            Duration::from_secs_f64(1.0 / generations_per_second)
+        } else {
+            Duration::ZERO
+        };
+
+        FramePacer::with_interval(interval)
+    }
+
+    /// Pacer aiming for one frame every `interval`.
+    pub fn with_interval(interval: Duration) -> FramePacer {
+        FramePacer {
+            interval,
+            next_frame: None,
+            behind: false,
+            frames: VecDeque::with_capacity(RATE_WINDOW),
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Halves the time between frames.
+    pub fn speed_up(&mut self) {
+        self.set_interval(self.interval / 2);
+    }
+
+    /// Doubles the time between frames. A pacer running frames back to back
+    /// slows to one frame a millisecond.
+    pub fn slow_down(&mut self) {
+        self.set_interval((self.interval * 2).max(Duration::from_millis(1)));
+    }
+
+    // Starts a new schedule, since the old deadlines and rate no longer apply
+    fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+        self.reset();
+    }
+
+    /// Records that a frame finished at `now` and returns how long to wait
+    /// before starting the next one.
+    pub fn tick(&mut self, now: Instant) -> Duration {
+        let deadline = self.next_frame.unwrap_or(now) + self.interval;
+        self.behind = self.next_frame.is_some() && now > deadline;
+
+        let start = if self.behind { now } else { deadline };
+        self.next_frame = Some(start);
+        if self.frames.len() == RATE_WINDOW {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(start);
+
+        start - now
+    }
+
+    /// Records that a frame just finished and sleeps until the next one is
+    /// due.
+    pub fn wait(&mut self) {
+        let delay = self.tick(Instant::now());
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+    }
+
+    /// Forgets the schedule and the measured rate, for example after the
+    /// loop was paused.
+    pub fn reset(&mut self) {
+        self.next_frame = None;
+        self.behind = false;
+        self.frames.clear();
+    }
+
+    /// Whether the last frame took longer than its budget.
+    pub fn is_behind(&self) -> bool {
+        self.behind
+    }
+
+    /// Frames per second over the last few frames, once there have been at
+    /// least two.
+    pub fn rate(&self) -> Option<f64> {
+        let first = self.frames.front()?;
+        let last = self.frames.back()?;
+        let elapsed = last.duration_since(*first).as_secs_f64();
+
+        if elapsed > 0.0 {
+            Some((self.frames.len() - 1) as f64 / elapsed)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use crate::rule::Rule;
+use crate::{BoundaryMode, LifeBoard};
+
+const WORD_BITS: usize = 64;
+
+/// Board storing one bit per cell, 64 cells to a word.
+///
+/// Each row starts on a new word and the unused bits at the end of a row are
+/// kept clear. Stepping works on whole words at once: the eight neighbour
+/// rows are formed by shifting, summed with bitwise adders, and the rule is
+/// applied to every bit of the resulting counts together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackedLifeBoard {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    boundary: BoundaryMode,
+    rule: Rule,
+    generation: u64,
+    words: Vec<u64>,
+}
+
+impl PackedLifeBoard {
+    pub fn new(width: usize, height: usize) -> PackedLifeBoard {
+        let words_per_row = width.div_ceil(WORD_BITS);
+
+        PackedLifeBoard {
+            width,
+            height,
+            words_per_row,
+            boundary: BoundaryMode::Wrap,
+            rule: Rule::conway(),
+            generation: 0,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    /// Packs a dense board, keeping its boundary mode, rule and generation.
+    pub fn from_board(board: &LifeBoard) -> PackedLifeBoard {
+        let mut packed = PackedLifeBoard::new(board.width, board.height);
+        packed.boundary = board.boundary;
+        packed.rule = board.rule;
+        packed.generation = board.generation;
+
+        for cell in board.iter().filter(|cell| *cell.state) {
+            packed.set(cell.row, cell.col, true);
+        }
+
+        packed
+    }
+
+    /// Unpacks into a dense board.
+    pub fn to_board(&self) -> LifeBoard {
+        let mut board = LifeBoard::new(self.width, self.height);
+        board.set_boundary(self.boundary);
+        board.rule = self.rule;
+        board.generation = self.generation;
+
+        for cell in board.iter_mut() {
+            *cell.state = self.get(cell.row, cell.col);
+        }
+
+        board
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn boundary(&self) -> BoundaryMode {
+        self.boundary
+    }
+
+    pub fn set_boundary(&mut self, boundary: BoundaryMode) {
+        self.boundary = boundary;
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn population(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// State of the cell at `row`, `col`. Panics if it is outside the board.
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        let (index, bit) = self.locate(row, col);
+        self.words[index] & bit != 0
+    }
+
+    /// Sets the cell at `row`, `col`. Panics if it is outside the board.
+    pub fn set(&mut self, row: usize, col: usize, alive: bool) {
+        let (index, bit) = self.locate(row, col);
+        if alive {
+            self.words[index] |= bit;
+        } else {
+            self.words[index] &= !bit;
+        }
+    }
+
+    fn locate(&self, row: usize, col: usize) -> (usize, u64) {
+        assert!(
+            row < self.height && col < self.width,
+            "cell ({}, {}) is outside a {}x{} board",
+            row,
+            col,
+            self.width,
+            self.height
+        );
+
+        (
+            row * self.words_per_row + col / WORD_BITS,
+            1 << (col % WORD_BITS),
+        )
+    }
+
+    fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    /// Writes the next generation of this board into `next`, which must have
+    /// the same dimensions.
+    pub fn step(&self, next: &mut PackedLifeBoard) {
+        assert!(
+            self.width == next.width && self.height == next.height,
+            "boards differ in size"
+        );
+
+        // The bit sliced counts cannot tell arrangements of neighbours apart
+        if !self.rule.is_totalistic() {
+            let mut board = self.to_board();
+            board.advance();
+            *next = PackedLifeBoard::from_board(&board);
+            return;
+        }
+
+        let zero = vec![0; self.words_per_row];
+        let mut neighbours: [Vec<u64>; 8] = Default::default();
+
+        for r in 0..self.height {
+            // Moving straight up or down never crosses the left or right edge
+            let vertical = |delta: isize| {
+                self.boundary
+                    .resolve(r as isize + delta, 0, self.width.max(1), self.height)
+                    .map(|(row, _)| row)
+            };
+            let (above_row, below_row) = (vertical(-1), vertical(1));
+            let above = above_row.map_or(&zero[..], |row| self.row(row));
+            let below = below_row.map_or(&zero[..], |row| self.row(row));
+            let centre = self.row(r);
+
+            self.shift_west(above, above_row, &mut neighbours[0]);
+            neighbours[1].clear();
+            neighbours[1].extend_from_slice(above);
+            self.shift_east(above, above_row, &mut neighbours[2]);
+            self.shift_west(centre, Some(r), &mut neighbours[3]);
+            self.shift_east(centre, Some(r), &mut neighbours[4]);
+            self.shift_west(below, below_row, &mut neighbours[5]);
+            neighbours[6].clear();
+            neighbours[6].extend_from_slice(below);
+            self.shift_east(below, below_row, &mut neighbours[7]);
+
+            let target = &mut next.words[r * self.words_per_row..(r + 1) * self.words_per_row];
+            for (w, out) in target.iter_mut().enumerate() {
+                let counts = sum_bits(neighbours.iter().map(|n| n[w]));
+                *out = self.apply_rule(centre[w], counts);
+            }
+            if let Some(last) = target.last_mut() {
+                *last &= self.last_word_mask();
+            }
+        }
+
+        next.boundary = self.boundary;
+        next.rule = self.rule;
+        next.generation = self.generation + 1;
+    }
+
+    /// Replaces this board with its next generation.
+    pub fn advance(&mut self) {
+        let mut next = PackedLifeBoard::new(self.width, self.height);
+        self.step(&mut next);
+        *self = next;
+    }
+
+    fn last_word_mask(&self) -> u64 {
+        match self.width % WORD_BITS {
+            0 => !0,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    // State of the cell beyond either end of row `row`, or false if it is
+    // dead or the row is off the board
+    fn edge(&self, row: Option<usize>, col: isize) -> bool {
+        row.and_then(|row| {
+            self.boundary
+                .resolve(row as isize, col, self.width, self.height)
+        })
+        .is_some_and(|(r, c)| self.row(r)[c / WORD_BITS] & (1 << (c % WORD_BITS)) != 0)
+    }
+
+    // Moves every cell of `row`, the words of row `index`, one column east, so
+    // each bit holds its west neighbour
+    fn shift_west(&self, row: &[u64], index: Option<usize>, out: &mut Vec<u64>) {
+        out.clear();
+        let mut carry = (self.width > 0 && self.edge(index, -1)) as u64;
+        for &word in row {
+            out.push((word << 1) | carry);
+            carry = word >> (WORD_BITS - 1);
+        }
+        if let Some(last) = out.last_mut() {
+            *last &= self.last_word_mask();
+        }
+    }
+
+    // Moves every cell of `row`, the words of row `index`, one column west, so
+    // each bit holds its east neighbour
+    fn shift_east(&self, row: &[u64], index: Option<usize>, out: &mut Vec<u64>) {
+        out.clear();
+        for (w, &word) in row.iter().enumerate() {
+            let carry = row.get(w + 1).map_or(0, |next| next << (WORD_BITS - 1));
+            out.push((word >> 1) | carry);
+        }
+        if self.width > 0 && self.edge(index, self.width as isize) {
+            let last = self.width - 1;
+            out[last / WORD_BITS] |= 1 << (last % WORD_BITS);
+        }
+    }
+
+    // Picks the next state of 64 cells at once from their neighbour counts
+    fn apply_rule(&self, alive: u64, counts: [u64; 4]) -> u64 {
+        let mut next = 0;
+        for n in 0..=8 {
+            let born = self.rule.applies(false, n as u8);
+            let survives = self.rule.applies(true, n as u8);
+            if !born && !survives {
+                continue;
+            }
+
+            let mut equal = !0;
+            for (bit, &count) in counts.iter().enumerate() {
+                equal &= if n & (1 << bit) != 0 { count } else { !count };
+            }
+
+            match (born, survives) {
+                (true, true) => next |= equal,
+                (true, false) => next |= equal & !alive,
+                (false, true) => next |= equal & alive,
+                (false, false) => {}
+            }
+        }
+
+        next
+    }
+}
+
+// Adds up to fifteen one bit inputs in every bit position at once, giving the
+// four bits of each sum
+fn sum_bits(inputs: impl Iterator<Item = u64>) -> [u64; 4] {
+    let mut sum = [0; 4];
+    for mut carry in inputs {
+        for bit in sum.iter_mut() {
+            let next_carry = *bit & carry;
+            *bit ^= carry;
+            carry = next_carry;
+        }
+    }
+
+    sum
+}
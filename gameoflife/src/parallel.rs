@@ -0,0 +1,62 @@
+use rayon::prelude::*;
+
+use crate::zobrist::zobrist_key;
+use crate::{BoardMismatch, LifeBoard};
+
+impl LifeBoard {
+    /// Writes the next generation of this board into `next`, computing a band
+    /// of rows on each thread of the rayon pool. `next` must have the same dimensions, as for
+    /// `step`.
+    pub fn par_step(&self, next: &mut LifeBoard) -> Result<(), BoardMismatch> {
+        self.check_next(next)?;
+
+        let hash = self.par_step_cells(&mut next.cells);
+        self.finish_step(next, hash);
+        Ok(())
+    }
+
+    /// Replaces this board with its next generation, computing a band of rows
+    /// on each thread of the rayon pool.
+    pub fn par_advance(&mut self) {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.resize(self.cells.len(), false);
+
+        let hash = self.par_step_cells(&mut scratch);
+        self.scratch = std::mem::replace(&mut self.cells, scratch);
+        self.finish_advance(hash);
+    }
+
+    // Like `step_cells`, with each band gathering the keys of its own
+    // changes to be flipped into the hash at the end
+    fn par_step_cells(&self, next: &mut [bool]) -> Option<u64> {
+        if self.width == 0 || self.height == 0 {
+            return self.hash;
+        }
+
+        // One band per thread. Every row costs the same, so smaller bands
+        // only add tasks to schedule, and on a single thread the board is
+        // stepped as one band just as `step_cells` does.
+        let band_rows = self.height.div_ceil(rayon::current_num_threads());
+        let bands = next.par_chunks_mut(self.width * band_rows).enumerate();
+        match self.hash {
+            Some(hash) => {
+                let flipped = bands
+                    .map(|(band, targets)| {
+                        let mut flipped = 0;
+                        self.step_rows(band * band_rows, targets, |index, _| {
+                            flipped ^= zobrist_key(index)
+                        });
+                        flipped
+                    })
+                    .reduce(|| 0, |a, b| a ^ b);
+                Some(hash ^ flipped)
+            }
+            None => {
+                bands.for_each(|(band, targets)| {
+                    self.step_rows(band * band_rows, targets, |_, _| {})
+                });
+                None
+            }
+        }
+    }
+}
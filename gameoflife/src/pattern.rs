@@ -0,0 +1,96 @@
+use crate::{BoundaryMode, LifeBoard};
+
+/// Reasons a pattern cannot be stamped onto a board.
+#[derive(Debug, PartialEq)]
+pub enum StampError {
+    /// Part of the pattern falls outside a board that does not wrap.
+    OutOfBounds,
+}
+
+impl std::fmt::Display for StampError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StampError::OutOfBounds => f.write_str("pattern does not fit on the board"),
+        }
+    }
+}
+
+impl std::error::Error for StampError {}
+
+/// Set of live cells, relative to the top left corner of the pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    width: usize,
+    height: usize,
+    cells: Vec<(usize, usize)>,
+}
+
+impl Pattern {
+    /// Builds a pattern from (row, column) coordinates of its live cells.
+    pub fn from_cells(cells: Vec<(usize, usize)>) -> Pattern {
+        let width = cells.iter().map(|&(_, col)| col + 1).max().unwrap_or(0);
+        let height = cells.iter().map(|&(row, _)| row + 1).max().unwrap_or(0);
+
+        Pattern {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // Pattern with a bounding box that may be larger than its cells need
+    pub(crate) fn with_size(width: usize, height: usize, cells: Vec<(usize, usize)>) -> Pattern {
+        Pattern {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a pattern from rows drawn with `O` for live and `.` for dead cells.
+    pub fn from_rows(rows: &[&str]) -> Pattern {
+        let mut cells = Vec::new();
+        for (row, line) in rows.iter().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                if c == 'O' {
+                    cells.push((row, col));
+                }
+            }
+        }
+
+        Pattern::from_cells(cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// (row, column) coordinates of the live cells.
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+}
+
+impl LifeBoard {
+    /// Sets the cells of `pattern` alive with its top left corner at `row`,
+    /// `col`. Cells past the edge wrap around on a wrapping board; on any
+    /// other board the pattern must fit and nothing is written if it does not.
+    pub fn stamp(&mut self, pattern: &Pattern, row: usize, col: usize) -> Result<(), StampError> {
+        let (width, height) = (self.width, self.height);
+        let fits = row + pattern.height <= height && col + pattern.width <= width;
+        let wraps = self.boundary == BoundaryMode::Wrap && width > 0 && height > 0;
+        if !fits && !wraps {
+            return Err(StampError::OutOfBounds);
+        }
+
+        for &(r, c) in &pattern.cells {
+            self[(row + r) % height][(col + c) % width] = true;
+        }
+
+        Ok(())
+    }
+}
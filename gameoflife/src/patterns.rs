@@ -0,0 +1,97 @@
+//! Well known patterns, in the phase and orientation given on the LifeWiki.
+
+use crate::pattern::Pattern;
+
+/// Period 4 spaceship travelling down and to the right.
+pub fn glider() -> Pattern {
+    Pattern::from_rows(&[".O.", "..O", "OOO"])
+}
+
+/// Period 2 oscillator, in its horizontal phase.
+pub fn blinker() -> Pattern {
+    Pattern::from_rows(&["OOO"])
+}
+
+/// Period 2 oscillator.
+pub fn toad() -> Pattern {
+    Pattern::from_rows(&[".OOO", "OOO."])
+}
+
+/// Period 2 oscillator of two blocks touching at a corner.
+pub fn beacon() -> Pattern {
+    Pattern::from_rows(&["OO..", "OO..", "..OO", "..OO"])
+}
+
+/// 2 by 2 still life.
+pub fn block() -> Pattern {
+    Pattern::from_rows(&["OO", "OO"])
+}
+
+/// Six cell still life.
+pub fn beehive() -> Pattern {
+    Pattern::from_rows(&[".OO.", "O..O", ".OO."])
+}
+
+/// Seven cell still life.
+pub fn loaf() -> Pattern {
+    Pattern::from_rows(&[".OO.", "O..O", ".O.O", "..O."])
+}
+
+/// Five cell still life.
+pub fn boat() -> Pattern {
+    Pattern::from_rows(&["OO.", "O.O", ".O."])
+}
+
+/// Lightweight spaceship, travelling left.
+pub fn lwss() -> Pattern {
+    Pattern::from_rows(&[".O..O", "O....", "O...O", "OOOO."])
+}
+
+/// Middleweight spaceship, travelling left.
+pub fn mwss() -> Pattern {
+    Pattern::from_rows(&["...O..", ".O...O", "O.....", "O....O", "OOOOO."])
+}
+
+/// Heavyweight spaceship, travelling left.
+pub fn hwss() -> Pattern {
+    Pattern::from_rows(&["...OO..", ".O....O", "O......", "O.....O", "OOOOOO."])
+}
+
+/// Period 3 oscillator.
+pub fn pulsar() -> Pattern {
+    Pattern::from_rows(&[
+        "..OOO...OOO..",
+        ".............",
+        "O....O.O....O",
+        "O....O.O....O",
+        "O....O.O....O",
+        "..OOO...OOO..",
+        ".............",
+        "..OOO...OOO..",
+        "O....O.O....O",
+        "O....O.O....O",
+        "O....O.O....O",
+        ".............",
+        "..OOO...OOO..",
+    ])
+}
+
+/// Period 15 oscillator.
+pub fn pentadecathlon() -> Pattern {
+    Pattern::from_rows(&["..O....O..", "OO.OOOO.OO", "..O....O.."])
+}
+
+/// Gosper glider gun, emitting a glider every 30 generations.
+pub fn gosper_gun() -> Pattern {
+    Pattern::from_rows(&[
+        "........................O...........",
+        "......................O.O...........",
+        "............OO......OO............OO",
+        "...........O...O....OO............OO",
+        "OO........O.....O...OO..............",
+        "OO........O...O.OO....O.O...........",
+        "..........O.....O.......O...........",
+        "...........O...O....................",
+        "............OO......................",
+    ])
+}
@@ -0,0 +1,135 @@
+use std::ops::ControlFlow;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::pacer::FramePacer;
+use crate::render::Renderer;
+use crate::LifeBoard;
+
+/// Generations the simulation may run ahead of the frame being drawn.
+pub const QUEUED_FRAMES: usize = 4;
+
+/// Which generations `run_pipelined` draws when drawing falls behind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramePolicy {
+    /// Draw every generation, holding the simulation back to the frame rate.
+    EveryGeneration,
+    /// Draw the newest generation available at each frame and skip the
+    /// rest, letting the simulation run as fast as it can.
+    LatestOnly,
+}
+
+/// Steps `board` through `generations` generations on a simulation thread
+/// while the calling thread draws them with `render`, at most one frame per
+/// `frame_interval`. The first generation is drawn too.
+///
+/// Generations are passed between the threads through a channel holding
+/// `QUEUED_FRAMES` boards, so the simulation blocks rather than running
+/// unboundedly ahead. Once `render` breaks, the simulation is stopped and
+/// `board` is left at the last generation it computed.
+pub fn run_pipelined(
+    board: &mut LifeBoard,
+    generations: usize,
+    policy: FramePolicy,
+    frame_interval: Duration,
+    render: impl FnMut(&LifeBoard) -> ControlFlow<()>,
+) {
+    run_pipelined_with(
+        board,
+        generations,
+        policy,
+        frame_interval,
+        LifeBoard::advance,
+        render,
+    );
+}
+
+/// Runs `board` like `run_pipelined`, taking each step with `step` on the
+/// simulation thread instead of `LifeBoard::advance`, for example to add
+/// noise after the rule.
+pub fn run_pipelined_with(
+    board: &mut LifeBoard,
+    generations: usize,
+    policy: FramePolicy,
+    frame_interval: Duration,
+    mut step: impl FnMut(&mut LifeBoard) + Send,
+    mut render: impl FnMut(&LifeBoard) -> ControlFlow<()>,
+) {
+    let (sender, receiver) = mpsc::sync_channel(QUEUED_FRAMES);
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            if sender.send(board.clone()).is_err() {
+                return;
+            }
+            for _ in 0..generations {
+                step(board);
+                // Fails once the drawing side has hung up
+                if sender.send(board.clone()).is_err() {
+                    return;
+                }
+            }
+        });
+
+        let mut pacer = FramePacer::with_interval(frame_interval);
+        while let Ok(mut frame) = receiver.recv() {
+            if policy == FramePolicy::LatestOnly {
+                frame = receiver.try_iter().last().unwrap_or(frame);
+            }
+
+            if render(&frame).is_break() {
+                break;
+            }
+            pacer.wait();
+        }
+
+        // Unblocks the simulation if it is waiting to send
+        drop(receiver);
+    });
+}
+
+/// Runs `board` through `generations` generations as `run_pipelined` does,
+/// drawing each frame with `renderer`, and finishes the renderer at the end.
+/// The run stops at the first frame that fails to draw, returning its error.
+pub fn run_rendered(
+    board: &mut LifeBoard,
+    generations: usize,
+    policy: FramePolicy,
+    frame_interval: Duration,
+    renderer: &mut dyn Renderer,
+) -> std::io::Result<()> {
+    run_rendered_with(
+        board,
+        generations,
+        policy,
+        frame_interval,
+        LifeBoard::advance,
+        renderer,
+    )
+}
+
+/// Runs `board` like `run_rendered`, taking each step with `step` as
+/// `run_pipelined_with` does.
+pub fn run_rendered_with(
+    board: &mut LifeBoard,
+    generations: usize,
+    policy: FramePolicy,
+    frame_interval: Duration,
+    step: impl FnMut(&mut LifeBoard) + Send,
+    renderer: &mut dyn Renderer,
+) -> std::io::Result<()> {
+    let mut result = Ok(());
+
+    run_pipelined_with(board, generations, policy, frame_interval, step, |frame| {
+        result = renderer.render(frame, frame.generation());
+        if result.is_ok() {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    });
+    renderer.finish();
+
+    result
+}
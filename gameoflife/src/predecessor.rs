@@ -0,0 +1,98 @@
+use combinatorial::SubsetIterator;
+
+use crate::LifeBoard;
+
+/// Boards that step to `target` under its boundary mode and rule, at most
+/// `limit` of them, each the size of `target` and at generation 0.
+///
+/// The search fills in one row at a time, trying every arrangement of live
+/// cells in the row, fewest live cells first. As soon as every row a row of
+/// `target` depends on is filled in, that row is checked, so most partial
+/// boards are abandoned long before they are complete. Even so each row has
+/// 2^width arrangements, so this is meant for boards of a few cells across.
+pub fn find_predecessors(target: &LifeBoard, limit: usize) -> Vec<LifeBoard> {
+    let (width, height) = (target.width(), target.height());
+    let cols: Vec<usize> = (0..width).collect();
+    let arrangements: Vec<Vec<bool>> = SubsetIterator::new(&cols)
+        .map(|live| {
+            let mut row = vec![false; width];
+            for &col in live {
+                row[col] = true;
+            }
+            row
+        })
+        .collect();
+
+    // The rows of `target` that can be checked once each row is filled in,
+    // those whose cells have no neighbour further down
+    let mut checks = vec![Vec::new(); height];
+    for row in 0..height {
+        let last = (0..width)
+            .flat_map(|col| target.neighbours(row, col))
+            .map(|(row, _, _)| row)
+            .fold(row, usize::max);
+        checks[last].push(row);
+    }
+
+    let mut candidate = LifeBoard::new(width, height);
+    candidate.set_boundary(target.boundary());
+    candidate.set_rule(target.rule());
+    let mut search = Search {
+        target,
+        arrangements: &arrangements,
+        checks: &checks,
+        limit,
+        found: Vec::new(),
+    };
+    if limit > 0 {
+        search.fill(&mut candidate, 0);
+    }
+
+    search.found
+}
+
+/// Whether no board of the size of `target` steps to it under its boundary
+/// mode and rule. Cells beyond the board's edges are not searched, so on a
+/// board with dead edges a pattern with no predecessor here may still have
+/// one on a larger board.
+pub fn is_garden_of_eden(target: &LifeBoard) -> bool {
+    find_predecessors(target, 1).is_empty()
+}
+
+struct Search<'a> {
+    target: &'a LifeBoard,
+    arrangements: &'a [Vec<bool>],
+    checks: &'a [Vec<usize>],
+    limit: usize,
+    found: Vec<LifeBoard>,
+}
+
+impl Search<'_> {
+    // Tries every arrangement of row `row` and the rows below it, with the
+    // rows above already filled in
+    fn fill(&mut self, candidate: &mut LifeBoard, row: usize) {
+        if row == candidate.height() {
+            self.found.push(candidate.clone());
+            return;
+        }
+
+        for arrangement in self.arrangements {
+            candidate[row].copy_from_slice(arrangement);
+            if self.checks[row]
+                .iter()
+                .all(|&row| self.matches(candidate, row))
+            {
+                self.fill(candidate, row + 1);
+                if self.found.len() == self.limit {
+                    return;
+                }
+            }
+        }
+        candidate[row].fill(false);
+    }
+
+    // Whether row `row` of `candidate` steps to the same row of the target
+    fn matches(&self, candidate: &LifeBoard, row: usize) -> bool {
+        (0..candidate.width()).all(|col| candidate.next_cell(row, col) == self.target[row][col])
+    }
+}
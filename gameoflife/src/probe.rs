@@ -0,0 +1,163 @@
+use std::io::Write;
+
+use crate::cycle::RunOutcome;
+use crate::observer::SimulationObserver;
+use crate::LifeBoard;
+
+/// Change in the state of a cell watched by `CellProbes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeTransition {
+    /// Generation in which the cell has its new state.
+    pub generation: u64,
+    pub label: String,
+    pub row: usize,
+    pub col: usize,
+    pub was: bool,
+    pub is: bool,
+}
+
+impl std::fmt::Display for ProbeTransition {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let state = |alive: bool| if alive { "alive" } else { "dead" };
+        write!(
+            f,
+            "generation {}: {} ({}, {}) {} -> {}",
+            self.generation,
+            self.label,
+            self.row,
+            self.col,
+            state(self.was),
+            state(self.is)
+        )
+    }
+}
+
+// A watched cell and its state when last looked at, unknown before the first
+// look
+#[derive(Debug, Clone)]
+struct Probe {
+    row: usize,
+    col: usize,
+    label: String,
+    state: Option<bool>,
+}
+
+/// Labelled cells to watch through a run, logging every change in their
+/// state.
+///
+/// The probes are looked at after each step, by `observe` or as an observer
+/// of `LifeBoard::run_observed`, which costs time in proportion to the number
+/// of probes and nothing when there are none. A cell off the board counts as
+/// dead. Transitions are kept in order for `transitions`, or written to a
+/// writer one line each by probes made with `streaming`.
+///
+/// Observers cannot fail a run, so the first error writing a transition is
+/// kept for `take_error` and no more are written until it is taken.
+#[derive(Debug)]
+pub struct CellProbes<W: Write = std::io::Sink> {
+    probes: Vec<Probe>,
+    transitions: Vec<ProbeTransition>,
+    out: Option<W>,
+    error: Option<std::io::Error>,
+}
+
+impl CellProbes {
+    /// Probes keeping their transitions for `transitions`.
+    pub fn new() -> CellProbes {
+        CellProbes {
+            probes: Vec::new(),
+            transitions: Vec::new(),
+            out: None,
+            error: None,
+        }
+    }
+}
+
+impl Default for CellProbes {
+    fn default() -> CellProbes {
+        CellProbes::new()
+    }
+}
+
+impl<W: Write> CellProbes<W> {
+    /// Probes writing each transition to `out` as a line instead of keeping
+    /// it.
+    pub fn streaming(out: W) -> CellProbes<W> {
+        CellProbes {
+            probes: Vec::new(),
+            transitions: Vec::new(),
+            out: Some(out),
+            error: None,
+        }
+    }
+
+    /// Watches the cell at `row`, `col`, naming it `label` in the log. Its
+    /// state is taken as it is the next time the probes look.
+    pub fn add_probe(&mut self, row: usize, col: usize, label: impl Into<String>) {
+        self.probes.push(Probe {
+            row,
+            col,
+            label: label.into(),
+            state: None,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.probes.is_empty()
+    }
+
+    /// Looks at the probed cells of `board`, logging those that changed
+    /// since the last look, in the order they were added, as transitions
+    /// into the board's generation.
+    pub fn observe(&mut self, board: &LifeBoard) {
+        for probe in &mut self.probes {
+            let is = board.get(probe.row, probe.col).unwrap_or(false);
+            let was = probe.state.replace(is);
+            if was.is_none_or(|was| was == is) {
+                continue;
+            }
+
+            let transition = ProbeTransition {
+                generation: board.generation(),
+                label: probe.label.clone(),
+                row: probe.row,
+                col: probe.col,
+                was: !is,
+                is,
+            };
+            match &mut self.out {
+                Some(out) => {
+                    if self.error.is_none() {
+                        if let Err(e) = writeln!(out, "{}", transition) {
+                            self.error = Some(e);
+                        }
+                    }
+                }
+                None => self.transitions.push(transition),
+            }
+        }
+    }
+
+    /// Transitions logged so far, unless they are being streamed.
+    pub fn transitions(&self) -> &[ProbeTransition] {
+        &self.transitions
+    }
+
+    /// Transitions logged so far, leaving the log empty.
+    pub fn take_transitions(&mut self) -> Vec<ProbeTransition> {
+        std::mem::take(&mut self.transitions)
+    }
+
+    /// First error hit writing a transition, if any.
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+}
+
+impl<W: Write> SimulationObserver for CellProbes<W> {
+    fn on_generation(&mut self, board: &LifeBoard, _generation: u64) {
+        self.observe(board);
+    }
+
+    fn on_finished(&mut self, _outcome: RunOutcome) {}
+}
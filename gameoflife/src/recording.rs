@@ -0,0 +1,535 @@
+use std::convert::TryFrom;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use crate::rule::Rule;
+use crate::{BoundaryMode, LifeBoard};
+
+/// Version of the recording format written by `Recorder`.
+pub const RECORDING_VERSION: u32 = 1;
+
+// Start of every recording, followed by the version as a little endian u32,
+// the header and then one frame per recorded generation
+const MAGIC: &[u8; 8] = b"LIFEREC\0";
+
+// First byte of a frame holding every cell
+const KEYFRAME: u8 = 0;
+// First byte of a frame holding the cells that changed since the last frame,
+// as their number followed by the gap before each changed cell from the one
+// before it, all as LEB128 varints
+const DIFF: u8 = 1;
+
+/// Reasons a recording cannot be read.
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(std::io::Error),
+    /// The data does not start like a recording.
+    NotARecording,
+    /// The recording was written by another version of the format.
+    UnsupportedVersion(u32),
+    /// The recording is damaged or was cut off part way through a frame.
+    Corrupt,
+}
+
+impl std::fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RecordingError::Io(e) => write!(f, "{}", e),
+            RecordingError::NotARecording => f.write_str("not a recording"),
+            RecordingError::UnsupportedVersion(version) => write!(
+                f,
+                "recording version {} is not supported, expected {}",
+                version, RECORDING_VERSION
+            ),
+            RecordingError::Corrupt => f.write_str("damaged recording"),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+impl From<std::io::Error> for RecordingError {
+    fn from(e: std::io::Error) -> RecordingError {
+        RecordingError::Io(e)
+    }
+}
+
+/// What a recording says about the run it was made from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordingHeader {
+    pub width: usize,
+    pub height: usize,
+    pub boundary: BoundaryMode,
+    pub rule: Rule,
+    /// Seed the run's first generation was made from, if there was one.
+    pub seed: Option<u64>,
+    /// Keyframes are at most this many frames apart.
+    pub keyframe_every: u64,
+}
+
+/// Streams the generations of a run to a writer as they happen.
+///
+/// A frame holds either every cell, a keyframe, or just the cells that
+/// changed since the frame before. Keyframes are written at least every
+/// `keyframe_every` frames, and whenever the changes would take more room
+/// than the whole board, so a reader can seek without replaying the run
+/// from the start.
+pub struct Recorder<W: Write> {
+    out: W,
+    header: RecordingHeader,
+    // Cells of the last frame written, empty before the first
+    previous: Vec<bool>,
+    since_keyframe: u64,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Writes the header of a recording of runs on boards like `board` to
+    /// `out`. Nothing of `board` itself is recorded until it is passed to
+    /// `record`.
+    ///
+    /// Panics if `keyframe_every` is zero.
+    pub fn new(
+        mut out: W,
+        board: &LifeBoard,
+        seed: Option<u64>,
+        keyframe_every: u64,
+    ) -> std::io::Result<Recorder<W>> {
+        assert!(keyframe_every > 0, "keyframe interval must be positive");
+        let header = RecordingHeader {
+            width: board.width,
+            height: board.height,
+            boundary: board.boundary,
+            rule: board.rule,
+            seed,
+            keyframe_every,
+        };
+
+        let too_large = || {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "board is too large to record",
+            )
+        };
+        let width = u32::try_from(header.width).map_err(|_| too_large())?;
+        let height = u32::try_from(header.height).map_err(|_| too_large())?;
+        let rule = header.rule.to_string();
+
+        out.write_all(MAGIC)?;
+        out.write_all(&RECORDING_VERSION.to_le_bytes())?;
+        out.write_all(&width.to_le_bytes())?;
+        out.write_all(&height.to_le_bytes())?;
+        out.write_all(&[boundary_code(header.boundary)])?;
+        if let BoundaryMode::ShiftedTorus { offset } = header.boundary {
+            out.write_all(&(offset as i64).to_le_bytes())?;
+        }
+        out.write_all(&[rule.len() as u8])?;
+        out.write_all(rule.as_bytes())?;
+        match seed {
+            Some(seed) => {
+                out.write_all(&[1])?;
+                out.write_all(&seed.to_le_bytes())?;
+            }
+            None => out.write_all(&[0])?,
+        }
+        out.write_all(&keyframe_every.to_le_bytes())?;
+
+        Ok(Recorder {
+            out,
+            header,
+            previous: Vec::new(),
+            since_keyframe: 0,
+        })
+    }
+
+    pub fn header(&self) -> &RecordingHeader {
+        &self.header
+    }
+
+    /// Appends `board` as the next frame. Frames may skip generations, but
+    /// should not go back.
+    ///
+    /// Fails without writing anything if `board` is not the size given to
+    /// `new`.
+    pub fn record(&mut self, board: &LifeBoard) -> std::io::Result<()> {
+        if (board.width, board.height) != (self.header.width, self.header.height) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "board is not the size of the recording",
+            ));
+        }
+
+        let mut diff = Vec::new();
+        let mut count = 0;
+        let mut next = 0;
+        for (index, _) in self
+            .previous
+            .iter()
+            .zip(&board.cells)
+            .enumerate()
+            .filter(|(_, (was, is))| was != is)
+        {
+            write_varint(&mut diff, (index - next) as u64);
+            next = index + 1;
+            count += 1;
+        }
+        let keyframe_len = board.cells.len().div_ceil(8);
+        let keyframe = self.previous.is_empty()
+            || self.since_keyframe + 1 >= self.header.keyframe_every
+            || diff.len() >= keyframe_len;
+
+        if keyframe {
+            self.out.write_all(&[KEYFRAME])?;
+            self.out.write_all(&board.generation.to_le_bytes())?;
+            let mut packed = vec![0u8; keyframe_len];
+            for (index, _) in board.cells.iter().enumerate().filter(|(_, &alive)| alive) {
+                packed[index / 8] |= 1 << (index % 8);
+            }
+            self.out.write_all(&packed)?;
+            self.since_keyframe = 0;
+        } else {
+            let mut count_bytes = Vec::new();
+            write_varint(&mut count_bytes, count);
+            self.out.write_all(&[DIFF])?;
+            self.out.write_all(&board.generation.to_le_bytes())?;
+            self.out.write_all(&count_bytes)?;
+            self.out.write_all(&diff)?;
+            self.since_keyframe += 1;
+        }
+
+        self.previous.clear();
+        self.previous.extend_from_slice(&board.cells);
+        Ok(())
+    }
+
+    /// Flushes the recording and hands back the writer.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+// One decoded frame
+#[derive(Debug, Clone, PartialEq)]
+enum Frame {
+    Key {
+        generation: u64,
+        cells: Vec<bool>,
+    },
+    Diff {
+        generation: u64,
+        changed: Vec<usize>,
+    },
+}
+
+impl Frame {
+    fn generation(&self) -> u64 {
+        match self {
+            Frame::Key { generation, .. } | Frame::Diff { generation, .. } => *generation,
+        }
+    }
+}
+
+/// A recording read back in full, ready to be played from any generation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recording {
+    header: RecordingHeader,
+    frames: Vec<Frame>,
+}
+
+impl Recording {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Recording, RecordingError> {
+        let rest = bytes
+            .strip_prefix(MAGIC)
+            .ok_or(RecordingError::NotARecording)?;
+        let mut reader = Reader { bytes: rest };
+        let version = reader.u32().ok_or(RecordingError::NotARecording)?;
+        if version != RECORDING_VERSION {
+            return Err(RecordingError::UnsupportedVersion(version));
+        }
+
+        let header = reader.header().ok_or(RecordingError::Corrupt)?;
+        let cell_count = header.width * header.height;
+        let mut frames: Vec<Frame> = Vec::new();
+        while !reader.bytes.is_empty() {
+            let frame = reader.frame(cell_count).ok_or(RecordingError::Corrupt)?;
+            // The first frame must be a keyframe and generations only go up
+            let in_order = match frames.last() {
+                Some(last) => frame.generation() > last.generation(),
+                None => matches!(frame, Frame::Key { .. }),
+            };
+            if !in_order {
+                return Err(RecordingError::Corrupt);
+            }
+            frames.push(frame);
+        }
+
+        Ok(Recording { header, frames })
+    }
+
+    pub fn read_from(mut reader: impl Read) -> Result<Recording, RecordingError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Recording::from_bytes(&bytes)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Recording, RecordingError> {
+        Recording::from_bytes(&std::fs::read(path)?)
+    }
+
+    pub fn header(&self) -> &RecordingHeader {
+        &self.header
+    }
+
+    /// Number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Generations that were recorded, in order.
+    pub fn generations(&self) -> impl Iterator<Item = u64> + '_ {
+        self.frames.iter().map(Frame::generation)
+    }
+
+    /// Board as it was at `generation`, or `None` if that generation was not
+    /// recorded. Starts from the nearest keyframe at or before it.
+    pub fn board_at(&self, generation: u64) -> Option<LifeBoard> {
+        let index = self
+            .frames
+            .binary_search_by_key(&generation, Frame::generation)
+            .ok()?;
+        self.playback(index).next()
+    }
+
+    /// Boards of every recorded generation from `generation` on, starting
+    /// from the first one recorded at or after it.
+    pub fn boards_from(&self, generation: u64) -> Playback<'_> {
+        let index = self
+            .frames
+            .partition_point(|frame| frame.generation() < generation);
+        self.playback(index)
+    }
+
+    /// Plays the frames from `generation` on through `renderer`, one every
+    /// `frame_interval`, then finishes the renderer. Stops at the first
+    /// frame that fails to draw.
+    #[cfg(feature = "native")]
+    pub fn play(
+        &self,
+        generation: u64,
+        frame_interval: std::time::Duration,
+        renderer: &mut dyn crate::Renderer,
+    ) -> std::io::Result<()> {
+        let mut pacer = crate::FramePacer::with_interval(frame_interval);
+        let mut result = Ok(());
+        for board in self.boards_from(generation) {
+            result = renderer.render(&board, board.generation);
+            if result.is_err() {
+                break;
+            }
+            pacer.wait();
+        }
+        renderer.finish();
+
+        result
+    }
+
+    // Playback positioned to yield frame `index` next, with the board built
+    // up from the last keyframe before it
+    fn playback(&self, index: usize) -> Playback<'_> {
+        let mut board = LifeBoard::new(self.header.width, self.header.height);
+        board.set_boundary(self.header.boundary);
+        board.set_rule(self.header.rule);
+
+        let keyframe = self.frames[..(index + 1).min(self.frames.len())]
+            .iter()
+            .rposition(|frame| matches!(frame, Frame::Key { .. }))
+            .unwrap_or(index);
+        let mut playback = Playback {
+            recording: self,
+            board,
+            next: keyframe,
+        };
+        for _ in keyframe..index {
+            playback.apply_next();
+        }
+
+        playback
+    }
+}
+
+/// Iterator over the boards of a recording, from `Recording::boards_from`.
+pub struct Playback<'a> {
+    recording: &'a Recording,
+    board: LifeBoard,
+    next: usize,
+}
+
+impl Playback<'_> {
+    // Brings the board up to the next frame
+    fn apply_next(&mut self) -> Option<()> {
+        let frame = self.recording.frames.get(self.next)?;
+        match frame {
+            Frame::Key { generation, cells } => {
+                self.board.cells.copy_from_slice(cells);
+                self.board.generation = *generation;
+            }
+            Frame::Diff {
+                generation,
+                changed,
+            } => {
+                for &index in changed {
+                    self.board.cells[index] = !self.board.cells[index];
+                }
+                self.board.generation = *generation;
+            }
+        }
+        self.board.cells_edited();
+        self.next += 1;
+
+        Some(())
+    }
+}
+
+impl Iterator for Playback<'_> {
+    type Item = LifeBoard;
+
+    fn next(&mut self) -> Option<LifeBoard> {
+        self.apply_next()?;
+        Some(self.board.clone())
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn boundary_code(boundary: BoundaryMode) -> u8 {
+    match boundary {
+        BoundaryMode::Wrap => 0,
+        BoundaryMode::Dead => 1,
+        BoundaryMode::Mirror => 2,
+        // Followed by the offset
+        BoundaryMode::ShiftedTorus { .. } => 3,
+        BoundaryMode::KleinBottle => 4,
+    }
+}
+
+// Reads little endian values off the front of a byte slice, returning `None`
+// once it runs out
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.bytes.len() < len {
+            return None;
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Some(taken)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Some(u32::from_le_bytes(bytes))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Some(u64::from_le_bytes(bytes))
+    }
+
+    fn varint(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+
+        None
+    }
+
+    fn header(&mut self) -> Option<RecordingHeader> {
+        let width = self.u32()? as usize;
+        let height = self.u32()? as usize;
+        let boundary = match self.u8()? {
+            0 => BoundaryMode::Wrap,
+            1 => BoundaryMode::Dead,
+            2 => BoundaryMode::Mirror,
+            3 => BoundaryMode::ShiftedTorus {
+                offset: isize::try_from(self.u64()? as i64).ok()?,
+            },
+            4 => BoundaryMode::KleinBottle,
+            _ => return None,
+        };
+        let rule_len = self.u8()? as usize;
+        let rule = std::str::from_utf8(self.take(rule_len)?).ok()?;
+        let rule = Rule::parse(rule).ok()?;
+        let seed = match self.u8()? {
+            0 => None,
+            1 => Some(self.u64()?),
+            _ => return None,
+        };
+        let keyframe_every = self.u64()?;
+
+        Some(RecordingHeader {
+            width,
+            height,
+            boundary,
+            rule,
+            seed,
+            keyframe_every,
+        })
+    }
+
+    fn frame(&mut self, cell_count: usize) -> Option<Frame> {
+        let kind = self.u8()?;
+        let generation = self.u64()?;
+        match kind {
+            KEYFRAME => {
+                let packed = self.take(cell_count.div_ceil(8))?;
+                let cells = (0..cell_count)
+                    .map(|index| packed[index / 8] & (1 << (index % 8)) != 0)
+                    .collect();
+                Some(Frame::Key { generation, cells })
+            }
+            DIFF => {
+                let count = usize::try_from(self.varint()?).ok()?;
+                let mut changed = Vec::with_capacity(count.min(cell_count));
+                let mut next = 0usize;
+                for _ in 0..count {
+                    let index = next.checked_add(usize::try_from(self.varint()?).ok()?)?;
+                    if index >= cell_count {
+                        return None;
+                    }
+                    changed.push(index);
+                    next = index + 1;
+                }
+                Some(Frame::Diff {
+                    generation,
+                    changed,
+                })
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,597 @@
+#[cfg(feature = "native")]
+use std::io::{IsTerminal, Stdout, Write};
+
+use crate::LifeBoard;
+
+#[cfg(feature = "native")]
+const HIDE_CURSOR: &str = "\x1b[?25l";
+#[cfg(feature = "native")]
+const SHOW_CURSOR: &str = "\x1b[?25h";
+#[cfg(feature = "native")]
+const CLEAR_SCREEN: &str = "\x1b[2J";
+#[cfg(feature = "native")]
+const CURSOR_HOME: &str = "\x1b[H";
+const RESET: &str = "\x1b[0m";
+const GREEN: &str = "\x1b[32m";
+
+// Characters around the cells of each drawn row: a border and a space on
+// either side
+const FRAME_COLUMNS: usize = 4;
+// Lines around the rows of a drawn board: the top and bottom borders and the
+// line after the frame, which holds the cursor or a status line
+const FRAME_ROWS: usize = 3;
+
+// Lowest age of each bucket after the first, which holds the cells born in
+// the last generation
+const AGE_BUCKETS: [u16; 3] = [1, 8, 64];
+
+/// Glyphs used to draw a board. Every cell glyph should be as wide as `dead`,
+/// which sets the width of the border.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharSet {
+    pub live: &'static str,
+    pub dead: &'static str,
+    /// Decaying states of a `GenerationsBoard`, starting from state 2. States
+    /// past the end of the list use its last glyph.
+    pub dying: &'static [&'static str],
+    /// Live cells of a board that tracks ages, from newborn to 64 or more
+    /// generations old in four buckets. Ages past the end of the list use its
+    /// last glyph, and an empty list draws every live cell with `live`.
+    pub aged: &'static [&'static str],
+    /// Live cells of the second species of an `ImmigrationBoard`, whose
+    /// first species is drawn with `live`.
+    pub rival: &'static str,
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+impl CharSet {
+    /// Plain ASCII, as written by `LifeBoard::print`.
+    pub const ASCII: CharSet = CharSet {
+        live: "# ",
+        dead: "  ",
+        dying: &["+ ", ". "],
+        aged: &[],
+        rival: "o ",
+        top_left: '+',
+        top_right: '+',
+        bottom_left: '+',
+        bottom_right: '+',
+        horizontal: '-',
+        vertical: '|',
+    };
+
+    /// ASCII with live cells colored green by ANSI escape codes.
+    pub const ASCII_COLOR: CharSet = CharSet {
+        live: "\x1b[32m# \x1b[0m",
+        dying: &["\x1b[33m+ \x1b[0m", "\x1b[31m. \x1b[0m"],
+        aged: &[
+            "\x1b[1;92m# \x1b[0m",
+            "\x1b[32m# \x1b[0m",
+            "\x1b[2;32m# \x1b[0m",
+            "\x1b[90m# \x1b[0m",
+        ],
+        rival: "\x1b[34m# \x1b[0m",
+        ..CharSet::ASCII
+    };
+
+    /// Solid blocks inside a box drawing border.
+    pub const UNICODE: CharSet = CharSet {
+        live: "\u{2588}\u{2588}",
+        dead: "  ",
+        dying: &["\u{2593}\u{2593}", "\u{2592}\u{2592}", "\u{2591}\u{2591}"],
+        aged: &[
+            "\u{2588}\u{2588}",
+            "\u{2593}\u{2593}",
+            "\u{2592}\u{2592}",
+            "\u{2591}\u{2591}",
+        ],
+        rival: "\u{2592}\u{2592}",
+        top_left: '\u{250c}',
+        top_right: '\u{2510}',
+        bottom_left: '\u{2514}',
+        bottom_right: '\u{2518}',
+        horizontal: '\u{2500}',
+        vertical: '\u{2502}',
+    };
+}
+
+impl Default for CharSet {
+    fn default() -> CharSet {
+        CharSet::ASCII
+    }
+}
+
+/// How many cells each character of a drawn board shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderStyle {
+    /// Two characters to a cell, as drawn by `LifeBoard::print`.
+    #[default]
+    Ascii,
+    /// One character to two cells stacked vertically, using the half blocks
+    /// ▀, ▄ and █.
+    HalfBlock,
+    /// One Braille pattern character to a block of two cells across and four
+    /// down.
+    Braille,
+}
+
+impl RenderStyle {
+    // Columns and rows of cells drawn by each character. ASCII cells take two
+    // characters, which is handled by the glyphs being two characters wide.
+    fn cells_per_char(self) -> (usize, usize) {
+        match self {
+            RenderStyle::Ascii => (1, 1),
+            RenderStyle::HalfBlock => (1, 2),
+            RenderStyle::Braille => (2, 4),
+        }
+    }
+
+    /// Largest board, as (width, height), that can be drawn whole in this
+    /// style on a terminal of `columns` by `rows` characters, leaving the line
+    /// below it free. Never less than a single cell.
+    pub fn fit_to_terminal(self, columns: u16, rows: u16) -> (usize, usize) {
+        let glyph_width = match self {
+            RenderStyle::Ascii => 2,
+            RenderStyle::HalfBlock | RenderStyle::Braille => 1,
+        };
+        let (cell_cols, cell_rows) = self.cells_per_char();
+        let width = (columns as usize).saturating_sub(FRAME_COLUMNS) / glyph_width * cell_cols;
+        let height = (rows as usize).saturating_sub(FRAME_ROWS) * cell_rows;
+
+        (width.max(1), height.max(1))
+    }
+}
+
+/// Half block drawing two vertically stacked cells.
+pub fn half_block_glyph(top: bool, bottom: bool) -> char {
+    match (top, bottom) {
+        (false, false) => ' ',
+        (true, false) => '\u{2580}',
+        (false, true) => '\u{2584}',
+        (true, true) => '\u{2588}',
+    }
+}
+
+/// Braille pattern drawing a block of cells four rows down and two columns
+/// across, given as `cells[row][col]`. An empty block is the blank pattern
+/// U+2800.
+pub fn braille_glyph(cells: [[bool; 2]; 4]) -> char {
+    // Dots 1 to 3 run down the left column and 4 to 6 down the right one,
+    // with dots 7 and 8 added beneath them later
+    const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+    let mut pattern = 0;
+    for (row, dots) in cells.iter().zip(DOTS.iter()) {
+        for (&alive, &dot) in row.iter().zip(dots) {
+            if alive {
+                pattern |= dot;
+            }
+        }
+    }
+
+    // Every value from U+2800 to U+28FF is a Braille pattern
+    char::from_u32(0x2800 + pattern).unwrap()
+}
+
+/// Characters shading a block of a zoomed out board, from empty to full.
+pub const DENSITY_RAMP: &str = " .:-=+*#%@";
+
+/// Character of `DENSITY_RAMP` shading a block whose fraction of live cells
+/// is `density`. Only an empty block is blank, however few of its cells are
+/// alive.
+pub fn density_glyph(density: f32) -> char {
+    let last = DENSITY_RAMP.len() - 1;
+    let level = (density.clamp(0.0, 1.0) * last as f32).ceil() as usize;
+
+    DENSITY_RAMP.as_bytes()[level.min(last)] as char
+}
+
+impl LifeBoard {
+    /// Draws the board with a border, one line per row. No newline is written
+    /// after the bottom border. When the board tracks ages, live cells are
+    /// drawn from `charset.aged` by how long they have survived.
+    pub fn render_to(&self, out: &mut impl std::fmt::Write, charset: &CharSet) -> std::fmt::Result {
+        render_grid(out, charset, self.width, self.height, |index| {
+            match (self.cells[index], &self.ages) {
+                (false, _) => charset.dead,
+                (true, Some(ages)) if !charset.aged.is_empty() => {
+                    let bucket = AGE_BUCKETS
+                        .iter()
+                        .filter(|&&age| ages[index] >= age)
+                        .count();
+                    charset.aged[bucket.min(charset.aged.len() - 1)]
+                }
+                (true, _) => charset.live,
+            }
+        })
+    }
+
+    /// Draws the board in `style` like `render_to`. Half block and Braille
+    /// boards get a box drawing border, treat cells past the edges of the
+    /// board as dead and do not show ages. With `color` set live cells are
+    /// colored by ANSI escape codes.
+    pub fn render_styled_to(
+        &self,
+        out: &mut impl std::fmt::Write,
+        style: RenderStyle,
+        color: bool,
+    ) -> std::fmt::Result {
+        if style == RenderStyle::Ascii {
+            let charset = if color {
+                &CharSet::ASCII_COLOR
+            } else {
+                &CharSet::ASCII
+            };
+            return self.render_to(out, charset);
+        }
+
+        let (cell_cols, cell_rows) = style.cells_per_char();
+        let columns = self.width.div_ceil(cell_cols);
+        let rows = self.height.div_ceil(cell_rows);
+        let alive = |row: usize, col: usize| self.get(row, col).unwrap_or(false);
+
+        let charset = &CharSet::UNICODE;
+        let border = charset.horizontal.to_string().repeat(columns + 2);
+        writeln!(out, "{}{}{}", charset.top_left, border, charset.top_right)?;
+        for r in 0..rows {
+            write!(out, "{} ", charset.vertical)?;
+            for c in 0..columns {
+                let (row, col) = (r * cell_rows, c * cell_cols);
+                let (glyph, empty) = match style {
+                    RenderStyle::HalfBlock => {
+                        let glyph = half_block_glyph(alive(row, col), alive(row + 1, col));
+                        (glyph, glyph == ' ')
+                    }
+                    _ => {
+                        let mut block = [[false; 2]; 4];
+                        for (dr, cells) in block.iter_mut().enumerate() {
+                            for (dc, cell) in cells.iter_mut().enumerate() {
+                                *cell = alive(row + dr, col + dc);
+                            }
+                        }
+                        (braille_glyph(block), block == [[false; 2]; 4])
+                    }
+                };
+
+                if color && !empty {
+                    write!(out, "{}{}{}", GREEN, glyph, RESET)?;
+                } else {
+                    out.write_char(glyph)?;
+                }
+            }
+            writeln!(out, " {}", charset.vertical)?;
+        }
+        write!(
+            out,
+            "{}{}{}",
+            charset.bottom_left, border, charset.bottom_right
+        )
+    }
+}
+
+impl LifeBoard {
+    /// Fraction of live cells in each `k` by `k` block of the board, one
+    /// block per entry in row order. The blocks at the right and bottom edges
+    /// are cut short when the board's size is not a multiple of `k`, and only
+    /// count the cells they cover.
+    ///
+    /// Panics if `k` is zero.
+    pub fn downsample(&self, k: usize) -> Vec<f32> {
+        assert!(k > 0, "blocks must be at least one cell across");
+
+        let columns = self.width.div_ceil(k);
+        let rows = self.height.div_ceil(k);
+        let mut live = vec![0u32; columns * rows];
+        for (index, _) in self.cells.iter().enumerate().filter(|&(_, &alive)| alive) {
+            let (row, col) = (index / self.width, index % self.width);
+            live[row / k * columns + col / k] += 1;
+        }
+
+        live.iter()
+            .enumerate()
+            .map(|(block, &count)| {
+                let (row, col) = (block / columns * k, block % columns * k);
+                let cells = k.min(self.height - row) * k.min(self.width - col);
+                count as f32 / cells as f32
+            })
+            .collect()
+    }
+
+    /// Draws the board zoomed out, each `k` by `k` block of cells shaded by
+    /// `density_glyph` and drawn two characters wide inside an ASCII border.
+    /// With `color` set blocks with live cells are colored by ANSI escape
+    /// codes.
+    ///
+    /// Panics if `k` is zero.
+    pub fn render_zoomed_to(
+        &self,
+        out: &mut impl std::fmt::Write,
+        k: usize,
+        color: bool,
+    ) -> std::fmt::Result {
+        let densities = self.downsample(k);
+        let columns = self.width.div_ceil(k);
+
+        let charset = &CharSet::ASCII;
+        let border = charset.horizontal.to_string().repeat(2 * columns + 2);
+        writeln!(out, "{}{}{}", charset.top_left, border, charset.top_right)?;
+        for row in densities.chunks(columns.max(1)) {
+            write!(out, "{} ", charset.vertical)?;
+            for &density in row {
+                let glyph = density_glyph(density);
+                if color && density > 0.0 {
+                    write!(out, "{}{}{}{}", GREEN, glyph, glyph, RESET)?;
+                } else {
+                    write!(out, "{}{}", glyph, glyph)?;
+                }
+            }
+            writeln!(out, " {}", charset.vertical)?;
+        }
+        write!(
+            out,
+            "{}{}{}",
+            charset.bottom_left, border, charset.bottom_right
+        )
+    }
+}
+
+// Draws `height` rows of `width` cells inside a border, picking the glyph of
+// each cell from its index with `glyph`
+pub(crate) fn render_grid(
+    out: &mut impl std::fmt::Write,
+    charset: &CharSet,
+    width: usize,
+    height: usize,
+    glyph: impl Fn(usize) -> &'static str,
+) -> std::fmt::Result {
+    let border = charset
+        .horizontal
+        .to_string()
+        .repeat(charset.dead.chars().count() * width + 2);
+
+    writeln!(out, "{}{}{}", charset.top_left, border, charset.top_right)?;
+    for r in 0..height {
+        write!(out, "{} ", charset.vertical)?;
+        for index in r * width..(r + 1) * width {
+            out.write_str(glyph(index))?;
+        }
+        writeln!(out, " {}", charset.vertical)?;
+    }
+    write!(
+        out,
+        "{}{}{}",
+        charset.bottom_left, border, charset.bottom_right
+    )
+}
+
+impl std::fmt::Display for LifeBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.render_to(f, &CharSet::ASCII)
+    }
+}
+
+/// Draws the board as `LifeBoard::print` does, followed by a newline. With
+/// `color` set live cells are wrapped in ANSI color codes.
+pub fn compose_frame(board: &LifeBoard, color: bool) -> String {
+    compose_styled_frame(board, RenderStyle::Ascii, color)
+}
+
+/// Draws the board in `style` as `LifeBoard::render_styled_to` does,
+/// followed by a newline.
+pub fn compose_styled_frame(board: &LifeBoard, style: RenderStyle, color: bool) -> String {
+    let mut frame = String::new();
+    board.render_styled_to(&mut frame, style, color).unwrap();
+    frame.push('\n');
+
+    frame
+}
+
+/// Draws the board zoomed out by `k` as `LifeBoard::render_zoomed_to` does,
+/// followed by a newline.
+pub fn compose_zoomed_frame(board: &LifeBoard, k: usize, color: bool) -> String {
+    let mut frame = String::new();
+    board.render_zoomed_to(&mut frame, k, color).unwrap();
+    frame.push('\n');
+
+    frame
+}
+
+// Frame of `board` drawn by a terminal renderer, zoomed out if `zoom_out` is
+// more than one cell to a block
+#[cfg(feature = "native")]
+fn compose_view(board: &LifeBoard, style: RenderStyle, color: bool, zoom_out: usize) -> String {
+    if zoom_out > 1 {
+        compose_zoomed_frame(board, zoom_out, color)
+    } else {
+        compose_styled_frame(board, style, color)
+    }
+}
+
+/// Largest board, as (width, height), that can be drawn whole zoomed out by
+/// `k` on a terminal of `columns` by `rows` characters, leaving the line
+/// below it free.
+pub fn fit_zoomed_out(k: usize, columns: u16, rows: u16) -> (usize, usize) {
+    let (width, height) = RenderStyle::Ascii.fit_to_terminal(columns, rows);
+
+    (width * k, height * k)
+}
+
+/// Smallest zoom at which a `width` by `height` board can be drawn whole on a
+/// terminal of `columns` by `rows` characters, as the number of cells across
+/// each block. One if it fits without zooming out.
+pub fn zoom_to_fit(width: usize, height: usize, columns: u16, rows: u16) -> usize {
+    let (fit_width, fit_height) = RenderStyle::Ascii.fit_to_terminal(columns, rows);
+
+    width
+        .div_ceil(fit_width)
+        .max(height.div_ceil(fit_height))
+        .max(1)
+}
+
+/// Largest board, as (width, height), that can be drawn whole on a terminal
+/// of `columns` by `rows` characters with two characters to a cell, leaving
+/// the line below it free. Never less than a single cell.
+pub fn fit_to_terminal(columns: u16, rows: u16) -> (usize, usize) {
+    RenderStyle::Ascii.fit_to_terminal(columns, rows)
+}
+
+/// Size of the terminal as (columns, rows), or `None` when stdout is not a
+/// terminal or its size cannot be read.
+#[cfg(feature = "native")]
+pub fn terminal_size() -> Option<(u16, u16)> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+
+    crossterm::terminal::size().ok()
+}
+
+/// Somewhere the generations of a run are drawn, such as a terminal.
+/// `run_rendered` drives any renderer, so the run loop does not need to know
+/// where its frames go.
+pub trait Renderer {
+    /// Draws `board`, which is at generation `generation`.
+    fn render(&mut self, board: &LifeBoard, generation: u64) -> std::io::Result<()>;
+
+    /// Called once after the last frame, whether or not the run failed.
+    fn finish(&mut self);
+}
+
+/// Prints frames one after another to stdout.
+#[cfg(feature = "native")]
+pub struct PlainStdoutRenderer {
+    out: Stdout,
+    style: RenderStyle,
+    color: bool,
+    zoom_out: usize,
+}
+
+#[cfg(feature = "native")]
+impl PlainStdoutRenderer {
+    pub fn new(style: RenderStyle, color: bool) -> PlainStdoutRenderer {
+        PlainStdoutRenderer {
+            out: std::io::stdout(),
+            style,
+            color,
+            zoom_out: 1,
+        }
+    }
+
+    /// Draws boards zoomed out, each character shading a `k` by `k` block of
+    /// cells, in place of the style. A `k` of one draws every cell.
+    ///
+    /// Panics if `k` is zero.
+    pub fn zoomed_out(mut self, k: usize) -> PlainStdoutRenderer {
+        assert!(k > 0, "blocks must be at least one cell across");
+        self.zoom_out = k;
+        self
+    }
+}
+
+#[cfg(feature = "native")]
+impl Renderer for PlainStdoutRenderer {
+    fn render(&mut self, board: &LifeBoard, _generation: u64) -> std::io::Result<()> {
+        let frame = compose_view(board, self.style, self.color, self.zoom_out);
+        let mut out = self.out.lock();
+        out.write_all(frame.as_bytes())?;
+        out.flush()
+    }
+
+    fn finish(&mut self) {}
+}
+
+/// Redraws frames in place at the top of the terminal instead of scrolling.
+///
+/// The cursor is hidden while the renderer is in use and shown again by
+/// `finish` or when it is dropped, which also happens while unwinding from a
+/// panic. Frames go to stdout unless another writer is given with
+/// `with_writer`.
+#[cfg(feature = "native")]
+pub struct AnsiRenderer<W: Write = Stdout> {
+    out: W,
+    style: RenderStyle,
+    color: bool,
+    zoom_out: usize,
+    finished: bool,
+}
+
+#[cfg(feature = "native")]
+impl AnsiRenderer {
+    pub fn new(style: RenderStyle, color: bool) -> std::io::Result<AnsiRenderer> {
+        AnsiRenderer::with_writer(std::io::stdout(), style, color)
+    }
+}
+
+#[cfg(feature = "native")]
+impl<W: Write> AnsiRenderer<W> {
+    /// Draws to `out`, which is taken to be a terminal.
+    pub fn with_writer(
+        mut out: W,
+        style: RenderStyle,
+        color: bool,
+    ) -> std::io::Result<AnsiRenderer<W>> {
+        write!(out, "{}{}", HIDE_CURSOR, CLEAR_SCREEN)?;
+        out.flush()?;
+
+        Ok(AnsiRenderer {
+            out,
+            style,
+            color,
+            zoom_out: 1,
+            finished: false,
+        })
+    }
+
+    /// Draws boards zoomed out, as `PlainStdoutRenderer::zoomed_out` does.
+    ///
+    /// Panics if `k` is zero.
+    pub fn zoomed_out(mut self, k: usize) -> AnsiRenderer<W> {
+        assert!(k > 0, "blocks must be at least one cell across");
+        self.zoom_out = k;
+        self
+    }
+}
+
+#[cfg(feature = "native")]
+impl<W: Write> Renderer for AnsiRenderer<W> {
+    fn render(&mut self, board: &LifeBoard, _generation: u64) -> std::io::Result<()> {
+        let frame = compose_view(board, self.style, self.color, self.zoom_out);
+        // One write, so the frame is not split between locks of stdout
+        self.out
+            .write_all(format!("{}{}", CURSOR_HOME, frame).as_bytes())?;
+        self.out.flush()
+    }
+
+    fn finish(&mut self) {
+        if !self.finished {
+            self.finished = true;
+            // Nothing useful can be done if the terminal has gone away
+            let _ = write!(self.out, "{}{}", RESET, SHOW_CURSOR);
+            let _ = self.out.flush();
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl<W: Write> Drop for AnsiRenderer<W> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Draws nothing, for timing the simulation on its own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NullRenderer;
+
+impl Renderer for NullRenderer {
+    fn render(&mut self, _board: &LifeBoard, _generation: u64) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn finish(&mut self) {}
+}
@@ -0,0 +1,74 @@
+use std::collections::VecDeque;
+
+use crate::LifeBoard;
+
+/// The last few generations of a run, kept so the run can be stepped
+/// backwards.
+///
+/// The run loop pushes the board before each step. Stepping back puts the
+/// board as it was before the last step in its place, rule, ages and heat
+/// included, and stepping forward from there simply runs on from the
+/// rewound generation. Once the buffer is full, pushing a board drops the
+/// oldest one. Each generation kept costs a copy of the board.
+#[derive(Debug, Clone)]
+pub struct HistoryBuffer {
+    boards: VecDeque<LifeBoard>,
+    capacity: usize,
+}
+
+impl HistoryBuffer {
+    /// Buffer keeping at most `capacity` generations.
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> HistoryBuffer {
+        assert!(
+            capacity > 0,
+            "a history buffer must keep at least one board"
+        );
+
+        HistoryBuffer {
+            boards: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of generations that can be stepped back.
+    pub fn history_len(&self) -> usize {
+        self.boards.len()
+    }
+
+    /// Remembers `board`, the generation about to be stepped from.
+    pub fn push(&mut self, board: &LifeBoard) {
+        if self.boards.len() == self.capacity {
+            self.boards.pop_front();
+        }
+
+        // The scratch buffer and the active stepper's changes are not needed
+        // to carry on from a board, so are not worth keeping
+        let mut kept = board.clone();
+        kept.scratch = Vec::new();
+        kept.changed = None;
+        self.boards.push_back(kept);
+    }
+
+    /// Replaces `board` with the last generation pushed, or returns false and
+    /// leaves it alone if there is none.
+    pub fn step_back(&mut self, board: &mut LifeBoard) -> bool {
+        match self.boards.pop_back() {
+            Some(previous) => {
+                *board = previous;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forgets every generation kept.
+    pub fn clear(&mut self) {
+        self.boards.clear();
+    }
+}
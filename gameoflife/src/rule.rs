@@ -0,0 +1,492 @@
+/// Reasons a rulestring can be rejected by `Rule::parse`.
+#[derive(Debug, PartialEq)]
+pub enum RuleParseError {
+    /// The string is not of the form `B<digits>/S<digits>`.
+    MissingPrefix,
+    /// A neighbour count was not a digit from 0 to 8.
+    InvalidCount(char),
+    /// A letter does not name an arrangement of the neighbour count before
+    /// it in Hensel notation. Counts of 0 and 8 take no letters.
+    InvalidLetter(u8, char),
+    /// A Generations rulestring is not of the form `<digits>/<digits>/<states>`
+    /// with 2 to 255 states.
+    InvalidGenerations,
+}
+
+impl std::fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RuleParseError::MissingPrefix => f.write_str("expected a rule of the form B3/S23"),
+            RuleParseError::InvalidCount(c) => write!(f, "invalid neighbour count '{}'", c),
+            RuleParseError::InvalidLetter(count, c) => {
+                write!(f, "no arrangement '{}' of {} neighbours", c, count)
+            }
+            RuleParseError::InvalidGenerations => {
+                f.write_str("expected a rule of the form 23/3/8 with 2 to 255 states")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleParseError {}
+
+/// Conway's rule spelled out: a dead cell with three live neighbours is born,
+/// a live cell with two or three live neighbours survives, and every other
+/// cell is dead in the next generation. `Rule::conway` is built from it, so
+/// boards following Conway's rule step through it; other rules are swapped
+/// in through `Rule::parse` or `Rule::from_fn`.
+pub fn next_state(alive: bool, live_neighbours: u8) -> bool {
+    match live_neighbours {
+        // Birth, or survival with three neighbours
+        3 => true,
+        // Survival only
+        2 => alive,
+        // Under or over population
+        _ => false,
+    }
+}
+
+/// Birth/survival rule, such as Conway's B3/S23.
+///
+/// The next state of a cell depends on its own state and those of its eight
+/// neighbours. Outer totalistic rules only count the live neighbours, while
+/// isotropic non-totalistic rules, written in Hensel notation, also look at
+/// how they are arranged up to rotation and reflection. Both are kept as the
+/// outcome for each of the 512 neighbourhoods, worked out when the rule is
+/// parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    // Next state for every neighbourhood, one bit each, indexed as for
+    // `applies_to`
+    table: [u64; 8],
+    // Neighbour counts at which every arrangement is born or survives, which
+    // for an outer totalistic rule is the whole rule
+    birth: [bool; 9],
+    survival: [bool; 9],
+    totalistic: bool,
+}
+
+impl Rule {
+    /// Conway's Game of Life, B3/S23, built from `next_state`.
+    pub fn conway() -> Rule {
+        Rule::from_fn(next_state)
+    }
+
+    /// Outer totalistic rule whose next state for a cell is
+    /// `next_state(alive, live_neighbours)`, evaluated once for each of the
+    /// 2 × 9 inputs.
+    pub fn from_fn(next_state: impl Fn(bool, u8) -> bool) -> Rule {
+        let conditions = |alive: bool| {
+            let mut conditions = [0; 9];
+            for (count, condition) in conditions.iter_mut().enumerate() {
+                if next_state(alive, count as u8) {
+                    *condition = all_arrangements(count);
+                }
+            }
+            conditions
+        };
+
+        Rule::from_conditions(conditions(false), conditions(true))
+    }
+
+    /// Parses a rulestring in B/S notation, for example "B36/S23". The
+    /// prefixes are case insensitive and either count list may be empty.
+    ///
+    /// A count may be followed by Hensel notation letters to pick out some
+    /// arrangements of that many neighbours, or by a minus sign and letters
+    /// to pick all but those, as in "B2-a/S12" or "B3/S2-i34q".
+    pub fn parse(rule: &str) -> Result<Rule, RuleParseError> {
+        let mut parts = rule.trim().split('/');
+        let (birth, survival) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(birth), Some(survival), None) => (birth, survival),
+            _ => return Err(RuleParseError::MissingPrefix),
+        };
+
+        Ok(Rule::from_conditions(
+            parse_conditions(birth, 'b')?,
+            parse_conditions(survival, 's')?,
+        ))
+    }
+
+    // Rule giving birth or survival with each neighbour count to the
+    // arrangements whose bits are set in the entry for that count
+    fn from_conditions(birth: [u16; 9], survival: [u16; 9]) -> Rule {
+        let classes = arrangement_classes();
+        let mut table = [0; 8];
+        for neighbourhood in 0..512 {
+            let neighbours = clockwise(neighbourhood);
+            let conditions = if neighbourhood & CENTRE != 0 {
+                &survival
+            } else {
+                &birth
+            };
+            if conditions[neighbours.count_ones() as usize] & 1 << classes[neighbours as usize] != 0
+            {
+                table[neighbourhood as usize / 64] |= 1 << (neighbourhood % 64);
+            }
+        }
+
+        let every = |conditions: &[u16; 9]| {
+            let mut counts = [false; 9];
+            for (count, &condition) in conditions.iter().enumerate() {
+                counts[count] = condition == all_arrangements(count);
+            }
+            counts
+        };
+        let totalistic = birth
+            .iter()
+            .chain(&survival)
+            .enumerate()
+            .all(|(index, &condition)| condition == 0 || condition == all_arrangements(index % 9));
+
+        Rule {
+            table,
+            birth: every(&birth),
+            survival: every(&survival),
+            totalistic,
+        }
+    }
+
+    /// Whether the rule only depends on the number of live neighbours, as
+    /// every rule without Hensel notation letters does.
+    pub fn is_totalistic(&self) -> bool {
+        self.totalistic
+    }
+
+    /// Whether a cell is alive in the next generation, given its current
+    /// state and its number of live neighbours.
+    ///
+    /// For a rule that is not outer totalistic this is whether every
+    /// arrangement of that many neighbours gives a live cell, which is not
+    /// the whole rule; use `applies_to` for those.
+    pub fn applies(&self, alive: bool, neighbours: u8) -> bool {
+        let counts = if alive { &self.survival } else { &self.birth };
+        counts.get(neighbours as usize).copied().unwrap_or(false)
+    }
+
+    /// Whether a cell is alive in the next generation, given the states of
+    /// it and its neighbours as the low nine bits of `neighbourhood`. Bit
+    /// `3 * (delta_row + 1) + delta_col + 1` holds the cell `delta_row` rows
+    /// down and `delta_col` columns right of the cell, so bit 0 is the top
+    /// left neighbour, bit 4 the cell itself and bit 8 the bottom right
+    /// neighbour.
+    pub fn applies_to(&self, neighbourhood: u16) -> bool {
+        let neighbourhood = (neighbourhood & 0x1ff) as usize;
+        self.table[neighbourhood / 64] & 1 << (neighbourhood % 64) != 0
+    }
+
+    // Arrangements born into and surviving for each neighbour count, as
+    // passed to `from_conditions`
+    fn conditions(&self) -> ([u16; 9], [u16; 9]) {
+        let classes = arrangement_classes();
+        let (mut birth, mut survival) = ([0; 9], [0; 9]);
+        for neighbourhood in 0..512 {
+            if self.applies_to(neighbourhood) {
+                let conditions = if neighbourhood & CENTRE != 0 {
+                    &mut survival
+                } else {
+                    &mut birth
+                };
+                let neighbours = clockwise(neighbourhood);
+                conditions[neighbours.count_ones() as usize] |= 1 << classes[neighbours as usize];
+            }
+        }
+
+        (birth, survival)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Rule {
+        Rule::conway()
+    }
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (birth, survival) = self.conditions();
+        write!(
+            f,
+            "B{}/S{}",
+            format_conditions(&birth),
+            format_conditions(&survival)
+        )
+    }
+}
+
+/// Bit of the centre cell in a neighbourhood passed to `Rule::applies_to`.
+const CENTRE: u16 = 1 << 4;
+
+// Offsets of the neighbours clockwise from north, as (row, column)
+const CLOCKWISE: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+// Hensel notation letter of every arrangement of one to four live
+// neighbours, with one arrangement of each as bits clockwise from north,
+// bit 0 north and bit 7 north west. Five to seven neighbours are named by
+// the arrangements of the dead neighbours among three to one, and zero and
+// eight neighbours have a single arrangement with no letter.
+const ARRANGEMENTS: [&[(char, u8)]; 5] = [
+    &[],
+    &[('c', 0b0000_0010), ('e', 0b0000_0001)],
+    &[
+        ('c', 0b0000_1010),
+        ('e', 0b0000_0101),
+        ('k', 0b0000_1001),
+        ('a', 0b0000_0011),
+        ('i', 0b0001_0001),
+        ('n', 0b0010_0010),
+    ],
+    &[
+        ('c', 0b0010_1010),
+        ('e', 0b0001_0101),
+        ('k', 0b0010_0101),
+        ('a', 0b0000_0111),
+        ('i', 0b1000_0011),
+        ('n', 0b0000_1011),
+        ('y', 0b0010_1001),
+        ('q', 0b0010_0011),
+        ('j', 0b0100_0011),
+        ('r', 0b0001_0011),
+    ],
+    &[
+        ('c', 0b1010_1010),
+        ('e', 0b0101_0101),
+        ('k', 0b0100_1011),
+        ('a', 0b0000_1111),
+        ('i', 0b0001_1011),
+        ('n', 0b1000_1011),
+        ('y', 0b0010_1011),
+        ('q', 0b0010_0111),
+        ('j', 0b0101_0011),
+        ('r', 0b0001_0111),
+        ('t', 0b1001_0011),
+        ('w', 0b0110_0011),
+        ('z', 0b0011_0011),
+    ],
+];
+
+// Letters and arrangements of `count` live neighbours
+fn arrangements(count: usize) -> &'static [(char, u8)] {
+    ARRANGEMENTS[count.min(8 - count)]
+}
+
+// Conditions with every arrangement of `count` neighbours set
+fn all_arrangements(count: usize) -> u16 {
+    (1 << arrangements(count).len().max(1)) - 1
+}
+
+// Neighbours of a neighbourhood laid out as for `Rule::applies_to` as bits
+// clockwise from north
+fn clockwise(neighbourhood: u16) -> u8 {
+    CLOCKWISE
+        .iter()
+        .enumerate()
+        .filter(|&(_, &(row, col))| neighbourhood & 1 << (3 * (row + 1) + col + 1) != 0)
+        .fold(0, |bits, (bit, _)| bits | 1 << bit)
+}
+
+// Index into the arrangements for its count of every set of neighbours, as
+// bits clockwise from north
+fn arrangement_classes() -> [u8; 256] {
+    let mut classes = [0; 256];
+    for count in 1..=4 {
+        for (class, &(_, arrangement)) in arrangements(count).iter().enumerate() {
+            // Every rotation of the arrangement and of its mirror image
+            for mut bits in [arrangement, arrangement.reverse_bits().rotate_left(1)] {
+                for _ in 0..4 {
+                    classes[bits as usize] = class as u8;
+                    if count < 4 {
+                        classes[!bits as usize] = class as u8;
+                    }
+                    bits = bits.rotate_left(2);
+                }
+            }
+        }
+    }
+
+    classes
+}
+
+fn format_conditions(conditions: &[u16; 9]) -> String {
+    let mut formatted = String::new();
+    for (count, &condition) in conditions.iter().enumerate() {
+        if condition == 0 {
+            continue;
+        }
+        formatted.push(std::char::from_digit(count as u32, 10).unwrap());
+        if condition == all_arrangements(count) {
+            continue;
+        }
+
+        // Whichever of the arrangements included and excluded is shorter
+        let letters = arrangements(count);
+        let included = condition.count_ones() as usize;
+        let wanted = included <= letters.len() - included;
+        if !wanted {
+            formatted.push('-');
+        }
+        for (class, &(letter, _)) in letters.iter().enumerate() {
+            if (condition & 1 << class != 0) == wanted {
+                formatted.push(letter);
+            }
+        }
+    }
+
+    formatted
+}
+
+/// Rule with decaying states, such as Brian's Brain.
+///
+/// Cell state 0 is dead and 1 is alive. A live cell that does not survive
+/// passes through states 2, 3 and so on up to `states - 1` before it is dead,
+/// one per generation. Decaying cells count as dead neighbours and cannot be
+/// born into. With two states this is the plain `Rule` of the same counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GenerationsRule {
+    birth: [bool; 9],
+    survival: [bool; 9],
+    states: u8,
+}
+
+impl GenerationsRule {
+    /// Brian's Brain, /2/3: cells are born with two neighbours and always
+    /// decay for a generation afterwards.
+    pub fn brians_brain() -> GenerationsRule {
+        GenerationsRule::parse("/2/3").unwrap()
+    }
+
+    /// Generations rule with the counts of a two state `Rule` and the given
+    /// number of states.
+    ///
+    /// Panics if `rule` is not outer totalistic.
+    pub fn from_rule(rule: Rule, states: u8) -> GenerationsRule {
+        assert!(states >= 2, "a Generations rule needs at least two states");
+        assert!(
+            rule.is_totalistic(),
+            "a Generations rule only counts neighbours"
+        );
+
+        GenerationsRule {
+            birth: rule.birth,
+            survival: rule.survival,
+            states,
+        }
+    }
+
+    /// Parses a rulestring in survival/birth/states notation, for example
+    /// "345/2/4". Either count list may be empty.
+    pub fn parse(rule: &str) -> Result<GenerationsRule, RuleParseError> {
+        let parts: Vec<&str> = rule.trim().split('/').collect();
+        let (survival, birth, states) = match parts[..] {
+            [survival, birth, states] => (survival, birth, states),
+            _ => return Err(RuleParseError::InvalidGenerations),
+        };
+
+        let states = match states.parse::<u8>() {
+            Ok(states) if states >= 2 => states,
+            _ => return Err(RuleParseError::InvalidGenerations),
+        };
+
+        Ok(GenerationsRule {
+            birth: digit_counts(birth.chars())?,
+            survival: digit_counts(survival.chars())?,
+            states,
+        })
+    }
+
+    /// Number of cell states, including dead and alive.
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    /// State of a cell in the next generation, given its current state and
+    /// its number of live neighbours.
+    pub fn next_state(&self, state: u8, live_neighbours: u8) -> u8 {
+        let n = live_neighbours as usize;
+        match state {
+            0 if self.birth.get(n) == Some(&true) => 1,
+            0 => 0,
+            1 if self.survival.get(n) == Some(&true) => 1,
+            // Decay, or die straight away with only two states
+            state if state + 1 < self.states => state + 1,
+            _ => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for GenerationsRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{}/{}",
+            format_counts(&self.survival),
+            format_counts(&self.birth),
+            self.states
+        )
+    }
+}
+
+fn format_counts(counts: &[bool; 9]) -> String {
+    (0..9)
+        .filter(|&n| counts[n])
+        .map(|n| std::char::from_digit(n as u32, 10).unwrap())
+        .collect()
+}
+
+// Parses one half of a rulestring, such as "B36" or "S2-i34q", into the
+// arrangements included for each neighbour count
+fn parse_conditions(part: &str, prefix: char) -> Result<[u16; 9], RuleParseError> {
+    let mut chars = part.chars().peekable();
+    match chars.next() {
+        Some(c) if c.to_ascii_lowercase() == prefix => {}
+        _ => return Err(RuleParseError::MissingPrefix),
+    }
+
+    let mut conditions = [0; 9];
+    while let Some(c) = chars.next() {
+        let count = match c.to_digit(10) {
+            Some(n) if n <= 8 => n as usize,
+            _ => return Err(RuleParseError::InvalidCount(c)),
+        };
+        let letters = arrangements(count);
+        let excluded = chars.next_if_eq(&'-').is_some();
+
+        let mut picked = 0;
+        while let Some(letter) = chars.next_if(|c| !c.is_ascii_digit()) {
+            match letters.iter().position(|&(l, _)| l == letter) {
+                Some(class) => picked |= 1 << class,
+                None => return Err(RuleParseError::InvalidLetter(count as u8, letter)),
+            }
+        }
+
+        let all = all_arrangements(count);
+        conditions[count] |= match (excluded, picked) {
+            (true, _) => all & !picked,
+            (false, 0) => all,
+            (false, _) => picked,
+        };
+    }
+
+    Ok(conditions)
+}
+
+fn digit_counts(chars: impl Iterator<Item = char>) -> Result<[bool; 9], RuleParseError> {
+    let mut counts = [false; 9];
+    for c in chars {
+        match c.to_digit(10) {
+            Some(n) if n <= 8 => counts[n as usize] = true,
+            _ => return Err(RuleParseError::InvalidCount(c)),
+        }
+    }
+
+    Ok(counts)
+}
@@ -0,0 +1,135 @@
+use combinatorial::{SimpleCombinationsIterator, SubsetIterator};
+
+use crate::cycle::RunOutcome;
+use crate::rule::Rule;
+use crate::{BoundaryMode, LifeBoard, Rect};
+
+/// How one starting configuration of an exhaustive search ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    /// Live cells the run started from, relative to the top left of the
+    /// region, in row order.
+    pub cells: Vec<(usize, usize)>,
+    pub outcome: RunOutcome,
+    /// Generation at which the configuration settled into a still life or
+    /// oscillator, or the generation limit if it never did.
+    pub lifetime: u64,
+    /// Number of live cells once the run stopped.
+    pub population: usize,
+}
+
+/// Best configurations found by `exhaustive_search`. Every configuration
+/// that ties for the best is kept, in the order they were evaluated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchReport {
+    /// Number of starting configurations run.
+    pub evaluated: usize,
+    pub longest_lived: Vec<SearchResult>,
+    pub largest: Vec<SearchResult>,
+}
+
+impl std::fmt::Display for SearchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "configurations     {:>8}", self.evaluated)?;
+        match self.longest_lived.first() {
+            Some(best) => writeln!(
+                f,
+                "longest lived      {:>8} generations, {} found",
+                best.lifetime,
+                self.longest_lived.len()
+            )?,
+            None => writeln!(f, "longest lived      none")?,
+        }
+        match self.largest.first() {
+            Some(best) => write!(
+                f,
+                "largest population {:>8} cells, {} found",
+                best.population,
+                self.largest.len()
+            ),
+            None => write!(f, "largest population none"),
+        }
+    }
+}
+
+/// Runs every starting configuration of the cells in `region` until each
+/// settles into a still life or oscillator or reaches `cap` generations, and
+/// reports those that lived longest and ended with the most live cells.
+///
+/// With `live` set only the configurations with exactly that many live cells
+/// are run, otherwise all 2^(width * height) of them are, the empty one
+/// included. The board is dead outside the region and at its edges, with as
+/// many dead rows and columns below and to the right of the region as
+/// `region` leaves above and to the left, so the margin sets how far a
+/// configuration can spread.
+pub fn exhaustive_search(
+    region: Rect,
+    live: Option<usize>,
+    rule: Rule,
+    cap: usize,
+) -> SearchReport {
+    let cells: Vec<(usize, usize)> = (0..region.height)
+        .flat_map(|row| (0..region.width).map(move |col| (row, col)))
+        .collect();
+    let configurations: Box<dyn Iterator<Item = Vec<&(usize, usize)>>> = match live {
+        Some(live) => match SimpleCombinationsIterator::new(&cells, live) {
+            Ok(combinations) => Box::new(combinations),
+            // More live cells than the region holds
+            Err(_) => Box::new(std::iter::empty()),
+        },
+        None => Box::new(SubsetIterator::new(&cells)),
+    };
+
+    let mut board = LifeBoard::new(
+        region.width + 2 * region.col,
+        region.height + 2 * region.row,
+    );
+    board.set_boundary(BoundaryMode::Dead);
+    board.set_rule(rule);
+    let mut report = SearchReport {
+        evaluated: 0,
+        longest_lived: Vec::new(),
+        largest: Vec::new(),
+    };
+
+    for configuration in configurations {
+        let mut run = board.clone();
+        for &&(row, col) in &configuration {
+            run.set(region.row + row, region.col + col, true).unwrap();
+        }
+        let outcome = run.run_until_cycle(cap);
+
+        let lifetime = match outcome {
+            RunOutcome::Stable { start } | RunOutcome::Oscillating { start, .. } => start,
+            RunOutcome::MaxedOut => run.generation(),
+        };
+        let mut cells: Vec<(usize, usize)> = configuration.into_iter().copied().collect();
+        cells.sort_unstable();
+        let result = SearchResult {
+            cells,
+            outcome,
+            lifetime,
+            population: run.population(),
+        };
+
+        report.evaluated += 1;
+        keep_best(&mut report.longest_lived, &result, |r| r.lifetime as usize);
+        keep_best(&mut report.largest, &result, |r| r.population);
+    }
+
+    report
+}
+
+// Adds `result` to `best` if it is at least as good by `key` as the results
+// already there, dropping them if it is better
+fn keep_best(
+    best: &mut Vec<SearchResult>,
+    result: &SearchResult,
+    key: impl Fn(&SearchResult) -> usize,
+) {
+    match best.first().map(&key) {
+        Some(current) if key(result) < current => {}
+        Some(current) if key(result) == current => best.push(result.clone()),
+        _ => *best = vec![result.clone()],
+    }
+}
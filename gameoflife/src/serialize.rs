@@ -0,0 +1,70 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::rule::Rule;
+use crate::LifeBoard;
+
+// Form a board is serialized in: its dimensions and its cells packed eight to
+// a byte in row order, first cell in the highest bit. The rule, boundary mode
+// and generation are left out, as they are by equality.
+#[derive(Serialize, Deserialize)]
+struct PackedBoard {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+impl Serialize for LifeBoard {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut cells = vec![0u8; self.cells.len().div_ceil(8)];
+        for (index, _) in self.cells.iter().enumerate().filter(|(_, &alive)| alive) {
+            cells[index / 8] |= 0x80 >> (index % 8);
+        }
+
+        PackedBoard {
+            width: self.width,
+            height: self.height,
+            cells,
+        }
+        .serialize(serializer)
+    }
+}
+
+// Rules are written as their B/S rulestring
+impl Serialize for Rule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Rule, D::Error> {
+        let rule = String::deserialize(deserializer)?;
+        Rule::parse(&rule).map_err(D::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for LifeBoard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<LifeBoard, D::Error> {
+        let packed = PackedBoard::deserialize(deserializer)?;
+        let len = packed
+            .width
+            .checked_mul(packed.height)
+            .ok_or_else(|| D::Error::custom("board dimensions overflow"))?;
+        if packed.cells.len() != len.div_ceil(8) {
+            return Err(D::Error::custom(format!(
+                "{} bytes of cells do not fit a {} by {} board",
+                packed.cells.len(),
+                packed.width,
+                packed.height
+            )));
+        }
+
+        let mut board = LifeBoard::new(packed.width, packed.height);
+        for (index, cell) in board.cells.iter_mut().enumerate() {
+            *cell = packed.cells[index / 8] & (0x80 >> (index % 8)) != 0;
+        }
+
+        Ok(board)
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+
+use crate::LifeBoard;
+
+/// Longest period looked for by `detect_ships`.
+pub const DEFAULT_MAX_PERIOD: usize = 16;
+
+/// Cluster of live cells that moved since it last had the same shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShipSighting {
+    /// Generation of the board the ship was seen on.
+    pub generation: u64,
+    /// Row and column of each of the ship's cells on that board, in row major
+    /// order.
+    pub cells: Vec<(usize, usize)>,
+    /// Generations since the ship last had the same shape.
+    pub period: u64,
+    /// (row, column) distance moved over one period, with down and right
+    /// positive.
+    pub displacement: (i64, i64),
+}
+
+impl ShipSighting {
+    /// Rows and columns moved per generation. A glider moves (0.25, 0.25) or
+    /// another diagonal, a lightweight spaceship 0.5 along a row or column.
+    pub fn velocity(&self) -> (f64, f64) {
+        (
+            self.displacement.0 as f64 / self.period as f64,
+            self.displacement.1 as f64 / self.period as f64,
+        )
+    }
+}
+
+// Connected live cells, translated so their bounding box starts at the origin
+#[derive(Debug, Clone)]
+struct Cluster {
+    shape: Vec<(usize, usize)>,
+    top: usize,
+    left: usize,
+}
+
+/// Finds spaceships in a run one generation at a time, keeping the clusters
+/// of the last few boards it was shown.
+///
+/// Clusters are sets of live cells connected through any of their eight
+/// neighbours. A cluster is a ship when the same shape appeared `period`
+/// generations earlier within `period` cells of where it is now, and no
+/// earlier generation had it in the same place. Cells are not connected
+/// across a wrapping edge, so a ship is lost while it crosses one, and ships
+/// that touch other debris are not seen until they separate.
+#[derive(Debug, Clone)]
+pub struct ShipDetector {
+    max_period: usize,
+    history: VecDeque<Vec<Cluster>>,
+    generation: Option<u64>,
+}
+
+impl ShipDetector {
+    /// Detector for ships with periods up to `max_period`.
+    pub fn new(max_period: usize) -> ShipDetector {
+        ShipDetector {
+            max_period,
+            history: VecDeque::with_capacity(max_period + 1),
+            generation: None,
+        }
+    }
+
+    /// Adds the next generation of the run and returns the ships on it. A
+    /// board that does not follow the last one shown starts a new run.
+    pub fn observe(&mut self, board: &LifeBoard) -> Vec<ShipSighting> {
+        if self.generation.map(|g| g + 1) != Some(board.generation()) {
+            self.history.clear();
+        }
+        self.generation = Some(board.generation());
+
+        let clusters = clusters(board);
+        let mut sightings = Vec::new();
+        for cluster in &clusters {
+            if let Some((period, displacement)) = self.match_earlier(cluster) {
+                sightings.push(ShipSighting {
+                    generation: board.generation(),
+                    cells: cluster
+                        .shape
+                        .iter()
+                        .map(|&(row, col)| (row + cluster.top, col + cluster.left))
+                        .collect(),
+                    period,
+                    displacement,
+                });
+            }
+        }
+
+        if self.history.len() == self.max_period {
+            self.history.pop_front();
+        }
+        if self.max_period > 0 {
+            self.history.push_back(clusters);
+        }
+
+        sightings
+    }
+
+    // Period and displacement of `cluster` if it is a ship
+    fn match_earlier(&self, cluster: &Cluster) -> Option<(u64, (i64, i64))> {
+        for (period, earlier) in self.history.iter().rev().enumerate() {
+            let period = period as i64 + 1;
+            let nearest = earlier
+                .iter()
+                .filter(|other| other.shape == cluster.shape)
+                .map(|other| {
+                    (
+                        cluster.top as i64 - other.top as i64,
+                        cluster.left as i64 - other.left as i64,
+                    )
+                })
+                .min_by_key(|&(rows, cols)| rows.abs().max(cols.abs()));
+
+            match nearest {
+                Some((0, 0)) => return None,
+                Some((rows, cols)) if rows.abs().max(cols.abs()) <= period => {
+                    return Some((period as u64, (rows, cols)));
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for ShipDetector {
+    fn default() -> ShipDetector {
+        ShipDetector::new(DEFAULT_MAX_PERIOD)
+    }
+}
+
+/// Ships on the last board of `history`, which holds consecutive generations
+/// of one run, oldest first. Only ships with periods up to
+/// `DEFAULT_MAX_PERIOD` that repeat within `history` are found.
+pub fn detect_ships(history: &[LifeBoard]) -> Vec<ShipSighting> {
+    let start = history.len().saturating_sub(DEFAULT_MAX_PERIOD + 1);
+    let mut detector = ShipDetector::default();
+    let mut sightings = Vec::new();
+
+    for board in &history[start..] {
+        sightings = detector.observe(board);
+    }
+
+    sightings
+}
+
+// Components of `board` as clusters
+fn clusters(board: &LifeBoard) -> Vec<Cluster> {
+    board
+        .components()
+        .into_iter()
+        .map(|component| Cluster {
+            shape: component.shape(),
+            top: component.bounding_box.row,
+            left: component.bounding_box.col,
+        })
+        .collect()
+}
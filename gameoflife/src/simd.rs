@@ -0,0 +1,149 @@
+use crate::zobrist::zobrist_key;
+use crate::{report_changes, BoardMismatch, LifeBoard};
+
+impl LifeBoard {
+    /// Writes the next generation of this board into `next`, summing
+    /// neighbours a whole row at a time so the compiler can use vector
+    /// instructions. `next` must have the same dimensions, as for `step`.
+    pub fn simd_step(&self, next: &mut LifeBoard) -> Result<(), BoardMismatch> {
+        self.check_next(next)?;
+
+        let hash = self.simd_step_cells(&mut next.cells);
+        self.finish_step(next, hash);
+        Ok(())
+    }
+
+    /// Replaces this board with its next generation, summing neighbours a
+    /// whole row at a time so the compiler can use vector instructions.
+    pub fn simd_advance(&mut self) {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.resize(self.cells.len(), false);
+
+        let hash = self.simd_step_cells(&mut scratch);
+        self.scratch = std::mem::replace(&mut self.cells, scratch);
+        self.finish_advance(hash);
+    }
+
+    // Like `step_cells`, a whole row at a time
+    fn simd_step_cells(&self, next: &mut [bool]) -> Option<u64> {
+        match self.hash {
+            Some(mut hash) => {
+                self.simd_step_rows(next, |index, _| hash ^= zobrist_key(index));
+                Some(hash)
+            }
+            None => {
+                self.simd_step_rows(next, |_, _| {});
+                None
+            }
+        }
+    }
+
+    // Like `step_rows` over the whole board
+    fn simd_step_rows(&self, next: &mut [bool], mut on_change: impl FnMut(usize, bool)) {
+        // The lanes only hold neighbour counts
+        if !self.rule.is_totalistic() {
+            return self.step_rows(0, next, on_change);
+        }
+        if self.width == 0 {
+            return;
+        }
+
+        // Every row as one byte per cell, with the cells beyond its left and
+        // right edges added at either end so shifted rows line up
+        let padded_width = self.width + 2;
+        let mut padded = vec![0u8; padded_width * self.height];
+        for (row, target) in padded.chunks_mut(padded_width).enumerate() {
+            let cells = &self.cells[row * self.width..(row + 1) * self.width];
+            let edge = |col: isize| {
+                self.boundary
+                    .resolve(row as isize, col, self.width, self.height)
+                    .map_or(0, |(row, col)| self.cells[row * self.width + col] as u8)
+            };
+            target[0] = edge(-1);
+            target[padded_width - 1] = edge(self.width as isize);
+            for (out, &alive) in target[1..=self.width].iter_mut().zip(cells) {
+                *out = alive as u8;
+            }
+        }
+
+        let lanes = LaneRule::new(self);
+        let dead_row = vec![0u8; padded_width];
+        let padded_row = |row: Option<usize>| {
+            row.map_or(&dead_row[..], |row| {
+                &padded[row * padded_width..(row + 1) * padded_width]
+            })
+        };
+        let mut sums = vec![0u8; self.width];
+        let mut states = vec![0u8; self.width];
+
+        for (row, target) in next.chunks_mut(self.width).enumerate() {
+            let above = padded_row(self.neighbours.up[row]);
+            let centre = padded_row(Some(row));
+            let below = padded_row(self.neighbours.down[row]);
+
+            sums.fill(0);
+            for (cells, shift) in [
+                (above, 0),
+                (above, 1),
+                (above, 2),
+                (centre, 0),
+                (centre, 2),
+                (below, 0),
+                (below, 1),
+                (below, 2),
+            ] {
+                add_lanes(&mut sums, &cells[shift..shift + self.width]);
+            }
+
+            lanes.apply(&sums, &centre[1..=self.width], &mut states);
+            for (out, &state) in target.iter_mut().zip(&states) {
+                *out = state != 0;
+            }
+
+            self.kill_aged_row(row, target);
+            let cells = &self.cells[row * self.width..(row + 1) * self.width];
+            report_changes(row * self.width, cells, target, &mut on_change);
+        }
+    }
+}
+
+// Adds `cells` to `sums` lane by lane. Kept to a plain loop over equal length
+// slices so it compiles to vector adds.
+fn add_lanes(sums: &mut [u8], cells: &[u8]) {
+    for (sum, &cell) in sums.iter_mut().zip(cells) {
+        *sum += cell;
+    }
+}
+
+// The neighbour counts at which a rule births and keeps cells
+struct LaneRule {
+    birth: Vec<u8>,
+    survival: Vec<u8>,
+}
+
+impl LaneRule {
+    fn new(board: &LifeBoard) -> LaneRule {
+        let counts = |alive: bool| (0..=8).filter(|&n| board.rule.applies(alive, n)).collect();
+
+        LaneRule {
+            birth: counts(false),
+            survival: counts(true),
+        }
+    }
+
+    // Writes 1 for every lane alive in the next generation and 0 otherwise,
+    // comparing all the lanes against one count at a time
+    fn apply(&self, sums: &[u8], alive: &[u8], states: &mut [u8]) {
+        states.fill(0);
+        for &count in &self.birth {
+            for ((state, &sum), &alive) in states.iter_mut().zip(sums).zip(alive) {
+                *state |= (sum == count) as u8 & (alive ^ 1);
+            }
+        }
+        for &count in &self.survival {
+            for ((state, &sum), &alive) in states.iter_mut().zip(sums).zip(alive) {
+                *state |= (sum == count) as u8 & alive;
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::cycle::RunOutcome;
+use crate::rule::Rule;
+use crate::{DensityError, LifeBoard};
+
+/// Settings for `run_soups`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoupConfig {
+    /// Number of soups to run.
+    pub count: usize,
+    pub width: usize,
+    pub height: usize,
+    pub density: f64,
+    pub rule: Rule,
+    /// Seed of the first soup. Soup `i` is seeded with `seed + i`.
+    pub seed: u64,
+    /// Generations after which a soup that has not settled is given up on.
+    pub max_generations: usize,
+}
+
+impl Default for SoupConfig {
+    fn default() -> SoupConfig {
+        SoupConfig {
+            count: 100,
+            width: 32,
+            height: 32,
+            density: 0.5,
+            rule: Rule::conway(),
+            seed: 0,
+            max_generations: 2000,
+        }
+    }
+}
+
+impl SoupConfig {
+    /// First generation of the soup with the given seed. The binary builds the
+    /// same board from `--seed`, so any soup can be replayed there.
+    pub fn soup(&self, seed: u64) -> Result<LifeBoard, DensityError> {
+        let mut board = LifeBoard::new(self.width, self.height);
+        board.randomize(self.density, &mut SmallRng::seed_from_u64(seed))?;
+        board.set_rule(self.rule);
+
+        Ok(board)
+    }
+}
+
+/// How one soup of a search ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoupResult {
+    pub seed: u64,
+    pub outcome: RunOutcome,
+    /// Generation at which the soup settled into a still life or oscillator,
+    /// or the generation limit if it never did.
+    pub lifetime: u64,
+    /// Number of live cells once the run stopped.
+    pub population: usize,
+}
+
+/// Results of a soup search, one per soup in seed order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoupReport {
+    pub results: Vec<SoupResult>,
+}
+
+impl SoupReport {
+    /// Number of soups that died out completely.
+    pub fn empty(&self) -> usize {
+        self.results.iter().filter(|r| r.population == 0).count()
+    }
+
+    /// Number of soups that had not settled by the generation limit.
+    pub fn maxed_out(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.outcome == RunOutcome::MaxedOut)
+            .count()
+    }
+
+    /// Soup that took longest to settle, the first one on ties.
+    pub fn longest_lived(&self) -> Option<&SoupResult> {
+        self.results.iter().rev().max_by_key(|r| r.lifetime)
+    }
+}
+
+impl std::fmt::Display for SoupReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "soups              {:>8}", self.results.len())?;
+        writeln!(f, "ended empty        {:>8}", self.empty())?;
+        writeln!(f, "never settled      {:>8}", self.maxed_out())?;
+
+        let rows = [
+            (
+                "final population",
+                summary(self.results.iter().map(|r| r.population as u64).collect()),
+            ),
+            (
+                "settled after",
+                summary(self.results.iter().map(|r| r.lifetime).collect()),
+            ),
+        ];
+        writeln!(
+            f,
+            "{:<18} {:>8} {:>8} {:>8} {:>10}",
+            "", "min", "median", "max", "mean"
+        )?;
+        for (name, stats) in rows.iter() {
+            if let Some((min, median, max, mean)) = stats {
+                writeln!(
+                    f,
+                    "{:<18} {:>8} {:>8} {:>8} {:>10.1}",
+                    name, min, median, max, mean
+                )?;
+            }
+        }
+
+        match self.longest_lived() {
+            Some(longest) => write!(
+                f,
+                "longest lived      seed {} ({} generations)",
+                longest.seed, longest.lifetime
+            ),
+            None => write!(f, "longest lived      none"),
+        }
+    }
+}
+
+/// Runs `config.count` random soups until each settles into a still life or
+/// oscillator or reaches the generation limit, and collects how they ended.
+pub fn run_soups(config: &SoupConfig) -> Result<SoupReport, DensityError> {
+    let mut results = Vec::with_capacity(config.count);
+
+    for i in 0..config.count {
+        let seed = config.seed.wrapping_add(i as u64);
+        let mut board = config.soup(seed)?;
+        let outcome = board.run_until_cycle(config.max_generations);
+
+        let lifetime = match outcome {
+            RunOutcome::Stable { start } | RunOutcome::Oscillating { start, .. } => start,
+            RunOutcome::MaxedOut => board.generation(),
+        };
+        results.push(SoupResult {
+            seed,
+            outcome,
+            lifetime,
+            population: board.population(),
+        });
+    }
+
+    Ok(SoupReport { results })
+}
+
+// Minimum, median, maximum and mean of a list of values
+fn summary(mut values: Vec<u64>) -> Option<(u64, u64, u64, f64)> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let mean = values.iter().sum::<u64>() as f64 / values.len() as f64;
+
+    Some((
+        values[0],
+        values[values.len() / 2],
+        values[values.len() - 1],
+        mean,
+    ))
+}
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::pattern::Pattern;
+use crate::rule::Rule;
+use crate::LifeBoard;
+
+const NEIGHBOURS: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Unbounded board that only stores its live cells.
+///
+/// Stepping visits the live cells and their neighbours rather than the whole
+/// board, so the cost follows the population instead of the area. This suits
+/// large universes that are mostly dead. Coordinates are signed (row, column)
+/// pairs and the board has no edges. Rules with birth on zero neighbours (B0)
+/// would fill the infinite plane and are not supported.
+#[derive(Debug, Clone, Default)]
+pub struct SparseLifeBoard {
+    rule: Rule,
+    generation: u64,
+    live: HashSet<(i64, i64)>,
+}
+
+impl SparseLifeBoard {
+    pub fn new() -> SparseLifeBoard {
+        SparseLifeBoard::default()
+    }
+
+    /// Copies the live cells of a dense board, keeping their coordinates.
+    pub fn from_board(board: &LifeBoard) -> SparseLifeBoard {
+        SparseLifeBoard::from_board_at(board, 0, 0)
+    }
+
+    // Copies the live cells of a dense board whose top left cell is at `row`,
+    // `col`
+    pub(crate) fn from_board_at(board: &LifeBoard, row: i64, col: i64) -> SparseLifeBoard {
+        SparseLifeBoard {
+            rule: board.rule,
+            generation: board.generation,
+            live: board
+                .live_cells()
+                .map(|(r, c)| (row + r as i64, col + c as i64))
+                .collect(),
+        }
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn population(&self) -> usize {
+        self.live.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.live.is_empty()
+    }
+
+    pub fn get(&self, row: i64, col: i64) -> bool {
+        self.live.contains(&(row, col))
+    }
+
+    pub fn set(&mut self, row: i64, col: i64, alive: bool) {
+        if alive {
+            self.live.insert((row, col));
+        } else {
+            self.live.remove(&(row, col));
+        }
+    }
+
+    /// (row, column) coordinates of the live cells, in no particular order.
+    pub fn live_cells(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.live.iter().copied()
+    }
+
+    /// Sets the cells of `pattern` alive with its top left corner at `row`, `col`.
+    pub fn stamp(&mut self, pattern: &Pattern, row: i64, col: i64) {
+        for &(r, c) in pattern.cells() {
+            self.live.insert((row + r as i64, col + c as i64));
+        }
+    }
+
+    /// Replaces the board with its next generation.
+    pub fn advance(&mut self) {
+        // Only cells next to a live cell can have a non-zero count
+        let mut counts: HashMap<(i64, i64), u8> = HashMap::with_capacity(self.live.len() * 8);
+        for &(row, col) in &self.live {
+            for (dr, dc) in NEIGHBOURS.iter() {
+                *counts.entry((row + dr, col + dc)).or_insert(0) += 1;
+            }
+        }
+
+        let mut next: HashSet<(i64, i64)> = if self.rule.is_totalistic() {
+            counts
+                .iter()
+                .filter(|&(cell, &count)| self.rule.applies(self.live.contains(cell), count))
+                .map(|(&cell, _)| cell)
+                .collect()
+        } else {
+            counts
+                .keys()
+                .filter(|&&cell| self.rule.applies_to(self.neighbourhood(cell)))
+                .copied()
+                .collect()
+        };
+
+        // Live cells with no live neighbours were never counted
+        if self.rule.applies(true, 0) {
+            next.extend(self.live.iter().filter(|cell| !counts.contains_key(cell)));
+        }
+
+        self.live = next;
+        self.generation += 1;
+    }
+
+    // States of `cell` and its neighbours laid out as for `Rule::applies_to`
+    fn neighbourhood(&self, (row, col): (i64, i64)) -> u16 {
+        (0..9)
+            .filter(|bit| self.live.contains(&(row + bit / 3 - 1, col + bit % 3 - 1)))
+            .fold(0, |neighbourhood, bit| neighbourhood | 1 << bit)
+    }
+
+    pub fn advance_by(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.advance();
+        }
+    }
+
+    /// Copies the `width` by `height` region with its top left corner at
+    /// `row`, `col` into a dense board, which can then be rendered.
+    pub fn viewport(&self, row: i64, col: i64, width: usize, height: usize) -> LifeBoard {
+        let mut board = LifeBoard::new(width, height);
+        board.rule = self.rule;
+        board.generation = self.generation;
+
+        for &(r, c) in &self.live {
+            let (r, c) = (r - row, c - col);
+            if r >= 0 && c >= 0 && (r as usize) < height && (c as usize) < width {
+                board[(r as usize, c as usize)] = true;
+            }
+        }
+
+        board
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::Path;
+
+use rand_xoshiro::Xoshiro256PlusPlus;
+use serde::{Deserialize, Serialize};
+
+use crate::rule::Rule;
+use crate::{BoundaryMode, LifeBoard};
+
+/// Version of the checkpoint format written by `SimulationState::save`.
+pub const STATE_VERSION: u32 = 1;
+
+// Start of every checkpoint file, followed by the version as a little endian
+// u32 and then the state encoded with bincode
+const MAGIC: &[u8; 8] = b"GOLSTATE";
+
+/// Reasons a checkpoint cannot be loaded or saved.
+#[derive(Debug)]
+pub enum StateError {
+    Io(std::io::Error),
+    /// The data does not start like a checkpoint.
+    NotAState,
+    /// The checkpoint was written by another version of the format.
+    UnsupportedVersion(u32),
+    /// The checkpoint is damaged or incomplete.
+    Corrupt(bincode::Error),
+}
+
+impl std::fmt::Display for StateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StateError::Io(e) => write!(f, "{}", e),
+            StateError::NotAState => f.write_str("not a simulation checkpoint"),
+            StateError::UnsupportedVersion(version) => write!(
+                f,
+                "checkpoint version {} is not supported, expected {}",
+                version, STATE_VERSION
+            ),
+            StateError::Corrupt(e) => write!(f, "damaged checkpoint: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+impl From<std::io::Error> for StateError {
+    fn from(e: std::io::Error) -> StateError {
+        StateError::Io(e)
+    }
+}
+
+/// Everything needed to resume a run exactly where it stopped: the board,
+/// the settings it steps with and the random number generator used for
+/// anything random the run does, such as re-seeding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulationState {
+    /// Cells of the board. Its generation, rule and boundary mode are kept
+    /// in the fields below.
+    pub board: LifeBoard,
+    pub generation: u64,
+    pub rule: Rule,
+    pub boundary: BoundaryMode,
+    pub rng: Xoshiro256PlusPlus,
+}
+
+impl SimulationState {
+    /// State of a run at `board` drawing random numbers from `rng`.
+    pub fn capture(board: &LifeBoard, rng: &Xoshiro256PlusPlus) -> SimulationState {
+        SimulationState {
+            board: board.clone(),
+            generation: board.generation,
+            rule: board.rule,
+            boundary: board.boundary,
+            rng: rng.clone(),
+        }
+    }
+
+    /// Board and generator to carry on the run with.
+    pub fn resume(self) -> (LifeBoard, Xoshiro256PlusPlus) {
+        let mut board = self.board;
+        board.generation = self.generation;
+        board.rule = self.rule;
+        board.set_boundary(self.boundary);
+
+        (board, self.rng)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&STATE_VERSION.to_le_bytes());
+        // Writing to memory cannot fail for these types
+        bincode::serialize_into(&mut bytes, self).unwrap();
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<SimulationState, StateError> {
+        let rest = bytes.strip_prefix(MAGIC).ok_or(StateError::NotAState)?;
+        if rest.len() < 4 {
+            return Err(StateError::NotAState);
+        }
+
+        let (version, payload) = rest.split_at(4);
+        let version = u32::from_le_bytes([version[0], version[1], version[2], version[3]]);
+        if version != STATE_VERSION {
+            return Err(StateError::UnsupportedVersion(version));
+        }
+
+        bincode::deserialize(payload).map_err(StateError::Corrupt)
+    }
+
+    /// Writes the state to a checkpoint file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), StateError> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    /// Reads a checkpoint file written by `save`.
+    pub fn load(path: impl AsRef<Path>) -> Result<SimulationState, StateError> {
+        SimulationState::from_bytes(&std::fs::read(path)?)
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{LifeBoard, Rect};
+
+/// Measurements of the live cells of a board, gathered in one pass by
+/// `LifeBoard::stats` or `LifeBoard::stats_since`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BoardStats {
+    pub population: usize,
+    /// Fraction of the board's cells that are alive, 0 for a board with no
+    /// cells at all.
+    pub density: f64,
+    /// Smallest rectangle holding the live cells, if there are any.
+    pub bounding_box: Option<Rect>,
+    /// Mean (row, column) of the live cells, if there are any.
+    pub centroid: Option<(f64, f64)>,
+    /// Cells that came alive since the previous generation, when it was
+    /// given to `stats_since`.
+    pub births: Option<usize>,
+    /// Cells that died since the previous generation, when it was given to
+    /// `stats_since`.
+    pub deaths: Option<usize>,
+}
+
+impl LifeBoard {
+    /// Population, density, bounding box and centroid of the live cells.
+    pub fn stats(&self) -> BoardStats {
+        self.gather_stats(None)
+    }
+
+    /// Like `stats`, also counting the births and deaths from `previous`,
+    /// usually the generation before this one.
+    ///
+    /// Panics if `previous` differs in size from this board.
+    pub fn stats_since(&self, previous: &LifeBoard) -> BoardStats {
+        assert!(
+            self.width == previous.width && self.height == previous.height,
+            "boards differ in size"
+        );
+
+        self.gather_stats(Some(previous))
+    }
+
+    fn gather_stats(&self, previous: Option<&LifeBoard>) -> BoardStats {
+        let mut population = 0;
+        let (mut births, mut deaths) = (0, 0);
+        let (mut top, mut bottom, mut left, mut right) = (usize::MAX, 0, usize::MAX, 0);
+        let (mut row_sum, mut col_sum) = (0.0, 0.0);
+
+        for (index, &alive) in self.cells.iter().enumerate() {
+            if let Some(previous) = previous {
+                let was = previous.cells[index];
+                births += (!was && alive) as usize;
+                deaths += (was && !alive) as usize;
+            }
+            if !alive {
+                continue;
+            }
+
+            let (row, col) = (index / self.width, index % self.width);
+            population += 1;
+            top = top.min(row);
+            bottom = bottom.max(row);
+            left = left.min(col);
+            right = right.max(col);
+            row_sum += row as f64;
+            col_sum += col as f64;
+        }
+
+        let (bounding_box, centroid) = if population > 0 {
+            let rect = Rect {
+                row: top,
+                col: left,
+                width: right - left + 1,
+                height: bottom - top + 1,
+            };
+            let count = population as f64;
+            (Some(rect), Some((row_sum / count, col_sum / count)))
+        } else {
+            (None, None)
+        };
+
+        BoardStats {
+            population,
+            density: if self.cells.is_empty() {
+                0.0
+            } else {
+                population as f64 / self.cells.len() as f64
+            },
+            bounding_box,
+            centroid,
+            births: previous.map(|_| births),
+            deaths: previous.map(|_| deaths),
+        }
+    }
+}
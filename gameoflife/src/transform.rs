@@ -0,0 +1,305 @@
+use rand::Rng;
+
+use crate::pattern::Pattern;
+use crate::{DensityError, LifeBoard};
+
+// Symmetries of a rectangle of cells
+#[derive(Debug, Clone, Copy)]
+enum Transform {
+    Rotate90,
+    Rotate180,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+}
+
+impl Transform {
+    // Width and height after the transform
+    fn size(self, width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Transform::Rotate90 | Transform::Transpose => (height, width),
+            Transform::Rotate180 | Transform::FlipHorizontal | Transform::FlipVertical => {
+                (width, height)
+            }
+        }
+    }
+
+    // Where the cell at `row`, `col` of a `width` by `height` rectangle ends up
+    fn apply(self, row: usize, col: usize, width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Transform::Rotate90 => (col, height - 1 - row),
+            Transform::Rotate180 => (height - 1 - row, width - 1 - col),
+            Transform::FlipHorizontal => (row, width - 1 - col),
+            Transform::FlipVertical => (height - 1 - row, col),
+            Transform::Transpose => (col, row),
+        }
+    }
+}
+
+/// Symmetry of a random soup made by `LifeBoard::randomize_symmetric`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Unchanged by half a turn.
+    C2,
+    /// Unchanged by a quarter turn. Only square boards have it.
+    C4,
+    /// Unchanged when mirrored left to right.
+    D2Horizontal,
+    /// Unchanged when mirrored top to bottom.
+    D2Vertical,
+    /// Unchanged when mirrored left to right or top to bottom, and so by
+    /// half a turn.
+    D4,
+}
+
+impl Symmetry {
+    // Cells the cell at `row`, `col` of a `width` by `height` board is
+    // carried to by the symmetry, other than itself
+    fn images(self, row: usize, col: usize, width: usize, height: usize) -> Vec<(usize, usize)> {
+        let transforms: &[Transform] = match self {
+            Symmetry::C2 => &[Transform::Rotate180],
+            Symmetry::C4 => &[Transform::Rotate90; 3],
+            Symmetry::D2Horizontal => &[Transform::FlipHorizontal],
+            Symmetry::D2Vertical => &[Transform::FlipVertical],
+            Symmetry::D4 => &[
+                Transform::FlipHorizontal,
+                Transform::FlipVertical,
+                Transform::FlipHorizontal,
+            ],
+        };
+
+        // Each transform applies to the cell the one before carried it to
+        let mut cell = (row, col);
+        let mut images = Vec::with_capacity(transforms.len());
+        for transform in transforms {
+            cell = transform.apply(cell.0, cell.1, width, height);
+            images.push(cell);
+        }
+
+        images
+    }
+}
+
+impl LifeBoard {
+    /// Fills the board with a random soup that has `symmetry`. The cells of
+    /// one fundamental domain, the first in row order of each set of cells
+    /// the symmetry carries into each other, are set alive with probability
+    /// `density` and copied to the rest. The middle row or column of a board
+    /// with an odd side is split the same way, and a cell the symmetry leaves
+    /// where it is gets a value of its own.
+    ///
+    /// Panics if `symmetry` is `C4` and the board is not square.
+    pub fn randomize_symmetric(
+        &mut self,
+        density: f64,
+        rng: &mut impl Rng,
+        symmetry: Symmetry,
+    ) -> Result<(), DensityError> {
+        assert!(
+            symmetry != Symmetry::C4 || self.width == self.height,
+            "only a square board can have C4 symmetry"
+        );
+        if !(0.0..=1.0).contains(&density) {
+            return Err(DensityError(density));
+        }
+        self.cells_edited();
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let images = symmetry.images(row, col, self.width, self.height);
+                let index = row * self.width + col;
+                if images.iter().any(|&(r, c)| r * self.width + c < index) {
+                    continue;
+                }
+
+                let alive = rng.gen_bool(density);
+                self.cells[index] = alive;
+                for (r, c) in images {
+                    self.cells[r * self.width + c] = alive;
+                }
+            }
+        }
+        if let Some(ages) = &mut self.ages {
+            ages.fill(0);
+        }
+
+        Ok(())
+    }
+
+    /// Copy of the board turned a quarter turn clockwise. The width and
+    /// height swap.
+    pub fn rotate90(&self) -> LifeBoard {
+        self.transformed(Transform::Rotate90)
+    }
+
+    /// Copy of the board turned half a turn.
+    pub fn rotate180(&self) -> LifeBoard {
+        self.transformed(Transform::Rotate180)
+    }
+
+    /// Copy of the board mirrored left to right.
+    pub fn flip_horizontal(&self) -> LifeBoard {
+        self.transformed(Transform::FlipHorizontal)
+    }
+
+    /// Copy of the board mirrored top to bottom.
+    pub fn flip_vertical(&self) -> LifeBoard {
+        self.transformed(Transform::FlipVertical)
+    }
+
+    /// Copy of the board mirrored along its main diagonal, so rows become
+    /// columns. The width and height swap.
+    pub fn transpose(&self) -> LifeBoard {
+        self.transformed(Transform::Transpose)
+    }
+
+    /// Moves every cell `d_row` rows down and `d_col` columns right, in place.
+    /// Cells pushed off one edge come back in at the opposite one.
+    pub fn shift(&mut self, d_row: isize, d_col: isize) {
+        self.cells_edited();
+        if self.cells.is_empty() {
+            return;
+        }
+
+        let rows = d_row.rem_euclid(self.height as isize) as usize;
+        let cols = d_col.rem_euclid(self.width as isize) as usize;
+        rotate(&mut self.cells, self.width, rows, cols);
+        if let Some(ages) = &mut self.ages {
+            rotate(ages, self.width, rows, cols);
+        }
+        if let Some(heat) = &mut self.heat {
+            rotate(heat, self.width, rows, cols);
+        }
+    }
+
+    /// Moves every cell `d_row` rows down and `d_col` columns right, in place,
+    /// like `shift`, except that cells pushed off an edge are dropped and the
+    /// cells left behind are dead.
+    pub fn shift_clipped(&mut self, d_row: isize, d_col: isize) {
+        self.shift(d_row, d_col);
+
+        // Rows and columns that came in from the opposite edge
+        let wrapped = |delta: isize, len: usize| {
+            let count = delta.unsigned_abs().min(len);
+            if delta >= 0 {
+                0..count
+            } else {
+                len - count..len
+            }
+        };
+        let rows = wrapped(d_row, self.height);
+        let cols = wrapped(d_col, self.width);
+
+        for (row, cells) in self.cells.chunks_mut(self.width.max(1)).enumerate() {
+            if rows.contains(&row) {
+                cells.fill(false);
+            } else {
+                cells[cols.clone()].fill(false);
+            }
+        }
+        if let Some(heat) = &mut self.heat {
+            for (row, counts) in heat.chunks_mut(self.width.max(1)).enumerate() {
+                if rows.contains(&row) {
+                    counts.fill(0);
+                } else {
+                    counts[cols.clone()].fill(0);
+                }
+            }
+        }
+        if let Some(ages) = &mut self.ages {
+            for (age, &alive) in ages.iter_mut().zip(&self.cells) {
+                if !alive {
+                    *age = 0;
+                }
+            }
+        }
+    }
+
+    // Keeps the boundary mode, rule and generation, and moves the ages and
+    // heat of each cell along with it, as `crop` does
+    fn transformed(&self, transform: Transform) -> LifeBoard {
+        let (width, height) = transform.size(self.width, self.height);
+        let mut board = LifeBoard::new(width, height);
+        board.set_boundary(self.boundary);
+        board.rule = self.rule;
+        board.generation = self.generation;
+
+        for (row, col) in self.live_cells() {
+            let (row, col) = transform.apply(row, col, self.width, self.height);
+            board.cells[row * width + col] = true;
+        }
+        if let Some(ages) = &self.ages {
+            board.ages = Some(self.transform_values(ages, transform));
+            board.max_age = self.max_age;
+        }
+        if let Some(heat) = &self.heat {
+            board.heat = Some(self.transform_values(heat, transform));
+        }
+
+        board
+    }
+
+    // Values kept for each cell in row order, moved to where `transform`
+    // takes their cells
+    fn transform_values<T: Copy + Default>(&self, values: &[T], transform: Transform) -> Vec<T> {
+        let (width, _) = transform.size(self.width, self.height);
+        let mut moved = vec![T::default(); values.len()];
+        for (index, &value) in values.iter().enumerate() {
+            let (row, col) = transform.apply(
+                index / self.width,
+                index % self.width,
+                self.width,
+                self.height,
+            );
+            moved[row * width + col] = value;
+        }
+
+        moved
+    }
+}
+
+// Rotates a grid of rows `width` long down by `rows` and right by `cols`, a
+// whole-row rotation followed by a rotation within each row
+fn rotate<T>(cells: &mut [T], width: usize, rows: usize, cols: usize) {
+    cells.rotate_right(rows * width);
+    for row in cells.chunks_mut(width) {
+        row.rotate_right(cols);
+    }
+}
+
+impl Pattern {
+    /// Pattern turned a quarter turn clockwise. A glider heading down and to
+    /// the right heads down and to the left afterwards.
+    pub fn rotate90(&self) -> Pattern {
+        self.transformed(Transform::Rotate90)
+    }
+
+    pub fn rotate180(&self) -> Pattern {
+        self.transformed(Transform::Rotate180)
+    }
+
+    pub fn flip_horizontal(&self) -> Pattern {
+        self.transformed(Transform::FlipHorizontal)
+    }
+
+    pub fn flip_vertical(&self) -> Pattern {
+        self.transformed(Transform::FlipVertical)
+    }
+
+    pub fn transpose(&self) -> Pattern {
+        self.transformed(Transform::Transpose)
+    }
+
+    fn transformed(&self, transform: Transform) -> Pattern {
+        let (width, height) = (self.width(), self.height());
+        let mut cells: Vec<(usize, usize)> = self
+            .cells()
+            .iter()
+            .map(|&(row, col)| transform.apply(row, col, width, height))
+            .collect();
+        cells.sort_unstable();
+
+        let (width, height) = transform.size(width, height);
+        Pattern::with_size(width, height, cells)
+    }
+}
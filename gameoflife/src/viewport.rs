@@ -0,0 +1,278 @@
+use crate::{BoundaryMode, LifeBoard};
+
+/// Part of a board shown on a display too small for all of it: `height` rows
+/// and `width` columns from the cell at `row`, `col`.
+///
+/// A view pans across the edges a board joins plainly, so on a wrapping
+/// board it can straddle an edge and show the cells on both sides of it
+/// next to each other. Those are both pairs of edges of `Wrap` and the top
+/// and bottom edges of `ShiftedTorus` and `KleinBottle`, whose left and
+/// right edges are joined with a shift or a flip. Other edges stop it. A
+/// view is never bigger than its board.
+///
+/// The methods that move a view take the size and boundary mode of the board
+/// it is on and do nothing else, so display loops only have to pass keys
+/// and frames through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub row: usize,
+    pub col: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    /// View of `width` by `height` cells from the top left corner.
+    pub fn new(width: usize, height: usize) -> Viewport {
+        Viewport {
+            row: 0,
+            col: 0,
+            width,
+            height,
+        }
+    }
+
+    /// This view cut down to a `board_width` by `board_height` board with
+    /// `boundary`, with its corner wrapped onto the board or moved back in
+    /// far enough for the whole view to fit.
+    pub fn fit(self, board_width: usize, board_height: usize, boundary: BoundaryMode) -> Viewport {
+        self.placed(
+            self.row as isize,
+            self.col as isize,
+            board_width,
+            board_height,
+            boundary,
+        )
+    }
+
+    /// This view moved `d_row` rows down and `d_col` columns right, as far as
+    /// the board's edges let it.
+    pub fn pan(
+        self,
+        d_row: isize,
+        d_col: isize,
+        board_width: usize,
+        board_height: usize,
+        boundary: BoundaryMode,
+    ) -> Viewport {
+        let fitted = self.fit(board_width, board_height, boundary);
+        fitted.placed(
+            fitted.row as isize + d_row,
+            fitted.col as isize + d_col,
+            board_width,
+            board_height,
+            boundary,
+        )
+    }
+
+    /// Where the cell at `row`, `col` of the board appears in the view, as
+    /// (row, column) from its top left corner, or `None` if it is out of
+    /// view.
+    pub fn position_of(
+        self,
+        row: usize,
+        col: usize,
+        board_width: usize,
+        board_height: usize,
+    ) -> Option<(usize, usize)> {
+        let row = distance(self.row, row, board_height)?;
+        let col = distance(self.col, col, board_width)?;
+        if row < self.height && col < self.width {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// This view moved as little as it takes to bring the cell at `row`,
+    /// `col` into it, going across a joined edge if that is shorter.
+    pub fn scroll_to(
+        self,
+        row: usize,
+        col: usize,
+        board_width: usize,
+        board_height: usize,
+        boundary: BoundaryMode,
+    ) -> Viewport {
+        let fitted = self.fit(board_width, board_height, boundary);
+        let (wrap_rows, wrap_cols) = wraps(boundary);
+        fitted.placed(
+            scroll_axis(fitted.row, fitted.height, row, board_height, wrap_rows),
+            scroll_axis(fitted.col, fitted.width, col, board_width, wrap_cols),
+            board_width,
+            board_height,
+            boundary,
+        )
+    }
+
+    /// This view centred on the smallest rectangle holding every live cell
+    /// of `board`, unchanged but for fitting if there are none. Across a
+    /// joined edge the rectangle may straddle it, so a pattern crossing the
+    /// edge of a torus stays in one piece.
+    pub fn follow(self, board: &LifeBoard) -> Viewport {
+        let fitted = self.fit(board.width, board.height, board.boundary);
+        let mut rows = vec![false; board.height];
+        let mut cols = vec![false; board.width];
+        for (row, col) in board.live_cells() {
+            rows[row] = true;
+            cols[col] = true;
+        }
+
+        let (wrap_rows, wrap_cols) = wraps(board.boundary);
+        let (row_span, col_span) = match (
+            occupied_span(&rows, wrap_rows),
+            occupied_span(&cols, wrap_cols),
+        ) {
+            (Some(rows), Some(cols)) => (rows, cols),
+            _ => return fitted,
+        };
+        fitted.placed(
+            centre_axis(row_span, fitted.height),
+            centre_axis(col_span, fitted.width),
+            board.width,
+            board.height,
+            board.boundary,
+        )
+    }
+
+    // This view with its corner at `row`, `col`, wrapped or clamped onto
+    // the board and cut down to it
+    fn placed(
+        self,
+        row: isize,
+        col: isize,
+        board_width: usize,
+        board_height: usize,
+        boundary: BoundaryMode,
+    ) -> Viewport {
+        let (width, height) = (self.width.min(board_width), self.height.min(board_height));
+        let (wrap_rows, wrap_cols) = wraps(boundary);
+
+        Viewport {
+            row: place(row, height, board_height, wrap_rows),
+            col: place(col, width, board_width, wrap_cols),
+            width,
+            height,
+        }
+    }
+}
+
+impl LifeBoard {
+    /// Copy of the cells in `viewport`, fitted to this board first, as a
+    /// board of the view's size. Like `crop` it keeps the boundary mode,
+    /// rule, generation and ages, while a view straddling a joined edge
+    /// brings the cells on its far side round next to the rest.
+    pub fn window(&self, viewport: Viewport) -> LifeBoard {
+        let viewport = viewport.fit(self.width, self.height, self.boundary);
+        let mut board = LifeBoard::new(viewport.width, viewport.height);
+        board.set_boundary(self.boundary);
+        board.rule = self.rule;
+        board.generation = self.generation;
+
+        let source = |index: usize| {
+            let row = (viewport.row + index / viewport.width) % self.height;
+            let col = (viewport.col + index % viewport.width) % self.width;
+            row * self.width + col
+        };
+        for (index, cell) in board.cells.iter_mut().enumerate() {
+            *cell = self.cells[source(index)];
+        }
+        if let Some(ages) = &self.ages {
+            board.ages = Some(
+                (0..board.cells.len())
+                    .map(|index| ages[source(index)])
+                    .collect(),
+            );
+        }
+
+        board
+    }
+}
+
+// Whether views pan across the top and bottom edges and across the left
+// and right ones
+fn wraps(boundary: BoundaryMode) -> (bool, bool) {
+    match boundary {
+        BoundaryMode::Wrap => (true, true),
+        BoundaryMode::ShiftedTorus { .. } | BoundaryMode::KleinBottle => (true, false),
+        BoundaryMode::Dead | BoundaryMode::Mirror => (false, false),
+    }
+}
+
+// Start of a view `size` long on an axis `len` long, asked to start at
+// `start`
+fn place(start: isize, size: usize, len: usize, wraps: bool) -> usize {
+    if len == 0 {
+        0
+    } else if wraps {
+        start.rem_euclid(len as isize) as usize
+    } else {
+        start.clamp(0, (len - size) as isize) as usize
+    }
+}
+
+// Steps forward from `start` to `index` on an axis `len` long, going round
+// the end if need be
+fn distance(start: usize, index: usize, len: usize) -> Option<usize> {
+    if index < len {
+        Some((index + len - start) % len)
+    } else {
+        None
+    }
+}
+
+// Start of a view `size` long at `start` moved as little as possible to
+// hold `index`
+fn scroll_axis(start: usize, size: usize, index: usize, len: usize, wraps: bool) -> isize {
+    let (start, index) = (start as isize, index.min(len.saturating_sub(1)) as isize);
+    if !wraps {
+        return if index < start {
+            index
+        } else {
+            start.max(index + 1 - size as isize)
+        };
+    }
+
+    let ahead = (index - start).rem_euclid(len as isize);
+    if ahead < size as isize {
+        start
+    } else if ahead + 1 - (size as isize) <= len as isize - ahead {
+        start + ahead + 1 - size as isize
+    } else {
+        index
+    }
+}
+
+// Start of a view `size` long centred on the span `span` of an axis
+fn centre_axis((start, len): (usize, usize), size: usize) -> isize {
+    (start + len / 2) as isize - (size / 2) as isize
+}
+
+// First index and length of the shortest span holding every set index of
+// `occupied`, which may go round the end if `wraps`
+fn occupied_span(occupied: &[bool], wraps: bool) -> Option<(usize, usize)> {
+    let first = occupied.iter().position(|&set| set)?;
+    let last = occupied.iter().rposition(|&set| set)?;
+    if !wraps {
+        return Some((first, last - first + 1));
+    }
+
+    // The span starts just after the longest run of unset indices, counting
+    // the run that goes round the end
+    let len = occupied.len();
+    let (mut gap_end, mut gap) = (first, first + len - last - 1);
+    let mut run = 0;
+    for (index, &set) in occupied.iter().enumerate().take(last + 1).skip(first) {
+        if set {
+            if run > gap {
+                gap = run;
+                gap_end = index;
+            }
+            run = 0;
+        } else {
+            run += 1;
+        }
+    }
+
+    Some((gap_end, len - gap))
+}
@@ -0,0 +1,159 @@
+use crate::interactive::{Command, Session};
+
+/// Where a board sits in a window of pixels that draws every cell as a
+/// `zoom` by `zoom` square.
+///
+/// The board is centred in the window. When it is bigger than the window its
+/// top left corner is kept in view and the rest is cut off. All the
+/// arithmetic between window pixels and board cells lives here, so a window
+/// loop only has to pass frames and mouse positions through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowLayout {
+    board_width: usize,
+    board_height: usize,
+    window_width: usize,
+    window_height: usize,
+    zoom: usize,
+}
+
+impl WindowLayout {
+    /// Layout of a `board_width` by `board_height` board in a window of
+    /// `window_width` by `window_height` pixels.
+    ///
+    /// Panics if `zoom` is zero.
+    pub fn new(
+        board_width: usize,
+        board_height: usize,
+        window_width: usize,
+        window_height: usize,
+        zoom: usize,
+    ) -> WindowLayout {
+        assert!(zoom > 0, "zoom must be at least one pixel per cell");
+
+        WindowLayout {
+            board_width,
+            board_height,
+            window_width,
+            window_height,
+            zoom,
+        }
+    }
+
+    /// Layout with the largest zoom at which the whole board fits in the
+    /// window, or a zoom of one if it does not fit at all.
+    pub fn fit(
+        board_width: usize,
+        board_height: usize,
+        window_width: usize,
+        window_height: usize,
+    ) -> WindowLayout {
+        let zoom = match (board_width, board_height) {
+            (0, 0) => 1,
+            (0, height) => window_height / height,
+            (width, 0) => window_width / width,
+            (width, height) => (window_width / width).min(window_height / height),
+        };
+
+        WindowLayout::new(
+            board_width,
+            board_height,
+            window_width,
+            window_height,
+            zoom.max(1),
+        )
+    }
+
+    pub fn zoom(&self) -> usize {
+        self.zoom
+    }
+
+    /// Size of the window as (width, height) in pixels.
+    pub fn window_size(&self) -> (usize, usize) {
+        (self.window_width, self.window_height)
+    }
+
+    /// Pixel at which the top left cell of the board is drawn, as (x, y).
+    pub fn origin(&self) -> (usize, usize) {
+        (
+            self.window_width
+                .saturating_sub(self.board_width * self.zoom)
+                / 2,
+            self.window_height
+                .saturating_sub(self.board_height * self.zoom)
+                / 2,
+        )
+    }
+
+    /// Cell drawn at pixel (`x`, `y`) of the window as (row, col), or `None`
+    /// if the pixel is outside the window or shows no cell.
+    pub fn cell_at(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        if x >= self.window_width || y >= self.window_height {
+            return None;
+        }
+
+        let (left, top) = self.origin();
+        let col = x.checked_sub(left)? / self.zoom;
+        let row = y.checked_sub(top)? / self.zoom;
+        if row < self.board_height && col < self.board_width {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Applies a click at pixel (`x`, `y`) of the window to `session`,
+    /// returning the new session and the command to carry out, as
+    /// `Session::handle_key` does for keys. The cursor moves to the cell
+    /// drawn there, which is toggled. Clicks that land on no cell are
+    /// ignored.
+    pub fn click(&self, session: Session, x: usize, y: usize) -> (Session, Option<Command>) {
+        match self.cell_at(x, y) {
+            Some((row, col)) => (
+                Session {
+                    cursor_row: row,
+                    cursor_col: col,
+                    ..session
+                },
+                Some(Command::Toggle { row, col }),
+            ),
+            None => (session, None),
+        }
+    }
+
+    /// Fills the window's RGBA pixels `buf` from `frame`, the board's RGBA
+    /// pixels as written by `LifeBoard::write_rgba`, scaled up by the zoom.
+    /// Pixels that show no cell are set to `background`.
+    ///
+    /// Panics if either buffer does not match its size.
+    pub fn draw(&self, frame: &[u8], buf: &mut [u8], background: [u8; 4]) {
+        assert_eq!(
+            frame.len(),
+            self.board_width * self.board_height * 4,
+            "frame does not fit a {}x{} board",
+            self.board_width,
+            self.board_height
+        );
+        assert_eq!(
+            buf.len(),
+            self.window_width * self.window_height * 4,
+            "buffer does not fit a {}x{} window",
+            self.window_width,
+            self.window_height
+        );
+        if self.window_width == 0 {
+            return;
+        }
+
+        for (y, row) in buf.chunks_exact_mut(self.window_width * 4).enumerate() {
+            for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+                match self.cell_at(x, y) {
+                    Some((row, col)) => {
+                        let index = (row * self.board_width + col) * 4;
+                        pixel.copy_from_slice(&frame[index..index + 4]);
+                    }
+                    None => pixel.copy_from_slice(&background),
+                }
+            }
+        }
+    }
+}
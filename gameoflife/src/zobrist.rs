@@ -0,0 +1,49 @@
+use crate::LifeBoard;
+
+/// Seed of the Zobrist keys behind `LifeBoard::zobrist_hash`. Changing it
+/// changes every hash.
+pub const ZOBRIST_SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+impl LifeBoard {
+    /// Zobrist hash of the cells: the exclusive or of the keys of the live
+    /// cells, the key of each cell being a random 64-bit value fixed by its
+    /// index in row order and `ZOBRIST_SEED`. Boards of the same size with
+    /// the same cells have the same hash, in any run of any build.
+    ///
+    /// This hashes every live cell, unless the board keeps its hash as
+    /// `keep_zobrist_hash` sets it to.
+    pub fn zobrist_hash(&self) -> u64 {
+        self.hash.unwrap_or_else(|| hash_cells(&self.cells))
+    }
+
+    /// Zobrist hash of the cells, as from `zobrist_hash`, which the board
+    /// then keeps up to date as it steps by flipping in the keys of the
+    /// cells that changed. Cycle detection asks for this itself.
+    ///
+    /// Boards do not keep their hash unless asked, since keeping it costs
+    /// every step a look at each cell. The hash is dropped again when cells
+    /// are changed any other way than by stepping or noise.
+    pub fn keep_zobrist_hash(&mut self) -> u64 {
+        match self.hash {
+            Some(hash) => hash,
+            None => *self.hash.insert(hash_cells(&self.cells)),
+        }
+    }
+}
+
+// Key of the cell at `index`, the output of SplitMix64 seeded with
+// `ZOBRIST_SEED` after `index + 1` steps
+pub(crate) fn zobrist_key(index: usize) -> u64 {
+    let mut z = ZOBRIST_SEED.wrapping_add((index as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+pub(crate) fn hash_cells(cells: &[bool]) -> u64 {
+    cells
+        .iter()
+        .enumerate()
+        .filter(|(_, &alive)| alive)
+        .fold(0, |hash, (index, _)| hash ^ zobrist_key(index))
+}
@@ -0,0 +1,91 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{patterns, BoundaryMode, LifeBoard, Rule};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+// Next generation found one cell at a time from the neighbours the board
+// reports for it
+fn naive_step(board: &LifeBoard) -> LifeBoard {
+    let rule = board.rule();
+    let mut next = board.clone();
+    for row in 0..board.height() {
+        for col in 0..board.width() {
+            let alive = board[(row, col)];
+            next[(row, col)] = rule.applies(alive, board.live_neighbour_count(row, col));
+        }
+    }
+    next
+}
+
+#[test]
+fn active_stepping_matches_naive_stepping_as_soups_settle() {
+    let boundaries = [BoundaryMode::Wrap, BoundaryMode::Dead, BoundaryMode::Mirror];
+    let rules = ["B3/S23", "B36/S23", "B3/S012345678"];
+    let mut rng = SmallRng::seed_from_u64(693);
+
+    for &boundary in &boundaries {
+        for rule in rules {
+            let mut board = LifeBoard::new(37, 29);
+            board.set_boundary(boundary);
+            board.set_rule(Rule::parse(rule).unwrap());
+            board.randomize(0.35, &mut rng).unwrap();
+            let mut expected = board.clone();
+
+            for generation in 1..=400 {
+                expected = naive_step(&expected);
+                board.advance_active();
+                assert_eq!(board, expected, "{:?} {} at {}", boundary, rule, generation);
+            }
+            assert_eq!(board.generation(), 400);
+        }
+    }
+}
+
+#[test]
+fn edits_between_active_steps_are_picked_up() {
+    let mut rng = SmallRng::seed_from_u64(1693);
+    let mut board = LifeBoard::new_square(40);
+    board.randomize(0.3, &mut rng).unwrap();
+    let mut expected = board.clone();
+
+    for generation in 1..=300 {
+        if generation % 25 == 0 {
+            // A glider dropped somewhere, or a single cell flipped
+            let (row, col) = (rng.gen_range(0..37), rng.gen_range(0..37));
+            if generation % 50 == 0 {
+                board.stamp(&patterns::glider(), row, col).unwrap();
+                expected.stamp(&patterns::glider(), row, col).unwrap();
+            } else {
+                let alive = !board[(row, col)];
+                board.set(row, col, alive).unwrap();
+                expected.set(row, col, alive).unwrap();
+            }
+        }
+
+        expected = naive_step(&expected);
+        board.advance_active();
+        assert_eq!(board, expected, "generation {}", generation);
+    }
+}
+
+#[test]
+fn still_lifes_and_an_oscillator_keep_stepping() {
+    // Once the first full step is done only the blinker's cells change
+    let mut board = LifeBoard::new_square(30);
+    board.stamp(&patterns::block(), 2, 2).unwrap();
+    board.stamp(&patterns::beehive(), 20, 5).unwrap();
+    board.stamp(&patterns::blinker(), 12, 20).unwrap();
+    let start = board.clone();
+
+    for generation in 1..=10 {
+        board.advance_active();
+        assert_eq!(
+            board == start,
+            generation % 2 == 0,
+            "generation {}",
+            generation
+        );
+    }
+}
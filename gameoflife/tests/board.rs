@@ -0,0 +1,345 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{patterns, Anchor, DensityError, LifeBoard, OutOfBounds, Rect};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+fn live(board: &LifeBoard) -> Vec<(usize, usize)> {
+    board.live_cells().collect()
+}
+
+#[test]
+fn non_square_board_keeps_its_dimensions() {
+    let mut board = LifeBoard::new(7, 3);
+    assert_eq!((board.width(), board.height()), (7, 3));
+    assert_eq!(board.iter().count(), 21);
+    assert_eq!(board[2].len(), 7);
+
+    // The far corner is in range only the right way round
+    board.set(2, 6, true).unwrap();
+    assert!(board[(2, 6)]);
+    assert!(board.set(6, 2, true).is_err());
+    assert_eq!(board.get(6, 2), None);
+
+    // A blinker lying along the long side turns upright and back
+    let mut board = LifeBoard::new(9, 5);
+    for col in 5..8 {
+        board.set(2, col, true).unwrap();
+    }
+    board.advance();
+    assert_eq!(live(&board), vec![(1, 6), (2, 6), (3, 6)]);
+    board.advance();
+    assert_eq!(live(&board), vec![(2, 5), (2, 6), (2, 7)]);
+    assert_eq!((board.width(), board.height()), (9, 5));
+}
+
+#[test]
+fn top_row_neighbours_wrap_to_the_bottom_row() {
+    let mut board = LifeBoard::new(10, 10);
+    board.set(9, 4, true).unwrap();
+    board.set(9, 5, true).unwrap();
+    board.set(9, 6, true).unwrap();
+
+    let above: Vec<_> = board
+        .neighbours(0, 5)
+        .filter(|&(row, _, _)| row == 9)
+        .collect();
+    assert_eq!(above, vec![(9, 4, true), (9, 5, true), (9, 6, true)]);
+    assert_eq!(board.live_neighbour_count(0, 5), 3);
+
+    // So the blinker across the seam turns upright through row 0
+    board.advance();
+    assert_eq!(live(&board), vec![(0, 5), (8, 5), (9, 5)]);
+}
+
+#[test]
+fn glider_returns_to_its_start_on_a_torus() {
+    let mut board = LifeBoard::new_square(10);
+    board.stamp(&patterns::glider(), 1, 1).unwrap();
+    let start = live(&board);
+
+    // A quarter cell a generation, across both seams and back
+    for generation in 1..40 {
+        board.advance();
+        assert_eq!(board.population(), 5);
+        assert_ne!(live(&board), start, "back early at {}", generation);
+    }
+    board.advance();
+    assert_eq!(live(&board), start);
+}
+
+#[test]
+fn blinker_keeps_its_population_as_generations_count_up() {
+    let mut board = LifeBoard::new_square(8);
+    board.stamp(&patterns::blinker(), 3, 2).unwrap();
+    assert_eq!(board.generation(), 0);
+
+    for generation in 1..=10 {
+        board.advance();
+        assert_eq!(board.generation(), generation);
+        assert_eq!(board.population(), 3);
+    }
+
+    let mut next = LifeBoard::new_square(8);
+    board.step(&mut next).unwrap();
+    assert_eq!(next.generation(), 11);
+    assert_eq!(next.population(), 3);
+}
+
+#[test]
+fn lone_cell_dies() {
+    let mut board = LifeBoard::new_square(8);
+    board.set(4, 4, true).unwrap();
+    assert_eq!(board.population(), 1);
+    assert!(!board.is_empty());
+
+    board.advance();
+    assert_eq!(board.population(), 0);
+    assert!(board.is_empty());
+    assert_eq!(board.generation(), 1);
+}
+
+fn soup(width: usize, height: usize, density: f64, seed: u64) -> LifeBoard {
+    let mut board = LifeBoard::new(width, height);
+    board
+        .randomize(density, &mut SmallRng::seed_from_u64(seed))
+        .unwrap();
+    board
+}
+
+#[test]
+fn same_seed_gives_the_same_soup() {
+    assert_eq!(soup(50, 30, 0.4, 7), soup(50, 30, 0.4, 7));
+    assert_ne!(soup(50, 30, 0.4, 7), soup(50, 30, 0.4, 8));
+}
+
+#[test]
+fn soup_density_is_close_to_the_one_asked_for() {
+    // 250000 cells put the standard deviation of the fraction alive below
+    // 0.001, so these bounds hold for any seed
+    for &density in &[0.0, 0.1, 0.5, 0.9, 1.0] {
+        let board = soup(500, 500, density, 3);
+        let fraction = board.population() as f64 / 250_000.0;
+        assert!(
+            (fraction - density).abs() < 0.01,
+            "{} for {}",
+            fraction,
+            density
+        );
+    }
+
+    let mut board = LifeBoard::new(4, 4);
+    let mut rng = SmallRng::seed_from_u64(0);
+    assert_eq!(board.randomize(1.5, &mut rng), Err(DensityError(1.5)));
+    assert_eq!(board.randomize(-0.1, &mut rng), Err(DensityError(-0.1)));
+    assert!(board.is_empty());
+}
+
+#[test]
+fn checked_accessors_cover_every_corner() {
+    let mut board = LifeBoard::new(5, 3);
+    let corners = [(0, 0), (0, 4), (2, 0), (2, 4)];
+
+    for &(row, col) in &corners {
+        assert_eq!(board.get(row, col), Some(false));
+        assert_eq!(board.set(row, col, true), Ok(()));
+        assert_eq!(board.get(row, col), Some(true));
+        assert!(board[(row, col)]);
+        assert!(board[row][col]);
+    }
+    assert_eq!(board.population(), 4);
+
+    board[(1, 2)] = true;
+    assert_eq!(board.get(1, 2), Some(true));
+    assert_eq!(board.toggle(1, 2), Ok(false));
+    assert_eq!(board.get(1, 2), Some(false));
+
+    // Just past each edge, and far past both
+    for &(row, col) in &[(3, 0), (0, 5), (3, 5), (usize::MAX, usize::MAX)] {
+        assert_eq!(board.get(row, col), None);
+        assert_eq!(board.set(row, col, true), Err(OutOfBounds { row, col }));
+        assert_eq!(board.toggle(row, col), Err(OutOfBounds { row, col }));
+    }
+    assert_eq!(board.population(), 4);
+    assert_eq!(
+        OutOfBounds { row: 3, col: 5 }.to_string(),
+        "cell (3, 5) is outside the board"
+    );
+}
+
+#[test]
+#[should_panic(expected = "column 5 is out of range for a board of width 5")]
+fn tuple_index_past_the_last_column_panics() {
+    let board = LifeBoard::new(5, 3);
+    let _ = board[(0, 5)];
+}
+
+#[test]
+#[should_panic(expected = "row 3 is out of range for a board of height 3")]
+fn tuple_index_past_the_last_row_panics() {
+    let mut board = LifeBoard::new(5, 3);
+    board[(3, 0)] = true;
+}
+
+#[test]
+fn live_cells_count_the_population() {
+    let mut rng = SmallRng::seed_from_u64(688);
+    for &(width, height) in &[(0, 0), (1, 1), (7, 3), (40, 25)] {
+        let mut board = LifeBoard::new(width, height);
+        board.randomize(0.3, &mut rng).unwrap();
+
+        let cells = board.live_cells();
+        assert_eq!(cells.size_hint(), (0, Some(width * height)));
+        assert_eq!(cells.count(), board.population());
+    }
+}
+
+#[test]
+fn live_cells_round_trip_through_set() {
+    let mut board = LifeBoard::new(31, 17);
+    board
+        .randomize(0.4, &mut SmallRng::seed_from_u64(17))
+        .unwrap();
+
+    let mut copy = LifeBoard::new(31, 17);
+    for (row, col) in board.live_cells() {
+        copy.set(row, col, true).unwrap();
+    }
+    assert_eq!(copy, board);
+}
+
+#[test]
+fn live_cells_in_matches_filtering_every_live_cell() {
+    let mut board = LifeBoard::new(23, 19);
+    board
+        .randomize(0.5, &mut SmallRng::seed_from_u64(19))
+        .unwrap();
+
+    let rects = [
+        Rect {
+            row: 0,
+            col: 0,
+            width: 23,
+            height: 19,
+        },
+        Rect {
+            row: 3,
+            col: 5,
+            width: 9,
+            height: 4,
+        },
+        Rect {
+            row: 18,
+            col: 22,
+            width: 1,
+            height: 1,
+        },
+        // Hanging off the bottom right, so clipped
+        Rect {
+            row: 10,
+            col: 15,
+            width: 30,
+            height: 30,
+        },
+        Rect {
+            row: 4,
+            col: 4,
+            width: 0,
+            height: 6,
+        },
+        Rect {
+            row: 40,
+            col: 0,
+            width: 5,
+            height: 5,
+        },
+    ];
+    for rect in rects {
+        let expected: Vec<_> = board
+            .live_cells()
+            .filter(|&(row, col)| {
+                (rect.row..rect.row + rect.height).contains(&row)
+                    && (rect.col..rect.col + rect.width).contains(&col)
+            })
+            .collect();
+        assert_eq!(
+            board.live_cells_in(rect).collect::<Vec<_>>(),
+            expected,
+            "{:?}",
+            rect
+        );
+    }
+}
+
+#[test]
+fn growing_then_shrinking_recovers_the_board() {
+    let mut original = LifeBoard::new(13, 9);
+    original
+        .randomize(0.5, &mut SmallRng::seed_from_u64(689))
+        .unwrap();
+
+    for anchor in [Anchor::TopLeft, Anchor::Center, Anchor::BottomRight] {
+        for &(grow_width, grow_height) in &[(1, 1), (4, 7), (11, 0), (0, 6)] {
+            let mut board = original.clone();
+            board.resize(13 + grow_width, 9 + grow_height, anchor);
+            assert_eq!(board.population(), original.population());
+
+            board.resize(13, 9, anchor);
+            assert_eq!(
+                board, original,
+                "{:?} by {}x{}",
+                anchor, grow_width, grow_height
+            );
+        }
+    }
+}
+
+#[test]
+fn centred_growth_keeps_a_glider_about_the_centre() {
+    // A glider just above and left of the middle of a 10x10 board stays there
+    // as the board grows to 20x20, moving 5 cells each way
+    let mut board = LifeBoard::new_square(10);
+    board.stamp(&patterns::glider(), 3, 3).unwrap();
+    let before = live(&board);
+
+    board.resize(20, 20, Anchor::Center);
+    let shifted: Vec<_> = before
+        .iter()
+        .map(|&(row, col)| (row + 5, col + 5))
+        .collect();
+    assert_eq!(live(&board), shifted);
+
+    // and it still flies as a glider
+    board.advance_by(4);
+    let flown: Vec<_> = shifted
+        .iter()
+        .map(|&(row, col)| (row + 1, col + 1))
+        .collect();
+    assert_eq!(live(&board), flown);
+}
+
+#[test]
+fn population_is_kept_when_nothing_is_cropped() {
+    let mut board = LifeBoard::new(12, 10);
+    board.stamp(&patterns::glider(), 4, 4).unwrap();
+
+    for &(width, height, anchor) in &[
+        (30, 20, Anchor::TopLeft),
+        (13, 40, Anchor::Center),
+        (25, 11, Anchor::BottomRight),
+        // Shrinking only loses empty edges
+        (8, 8, Anchor::TopLeft),
+        (7, 7, Anchor::Center),
+        (9, 9, Anchor::BottomRight),
+    ] {
+        let mut resized = board.clone();
+        resized.resize(width, height, anchor);
+        assert_eq!((resized.width(), resized.height()), (width, height));
+        assert_eq!(resized.population(), 5, "{}x{} {:?}", width, height, anchor);
+    }
+
+    // Cropping the glider away does lose it
+    board.resize(4, 4, Anchor::TopLeft);
+    assert_eq!(board.population(), 0);
+}
@@ -0,0 +1,110 @@
+extern crate gameoflife;
+
+use gameoflife::{patterns, LibraryEntry, LifeBoard, Pattern, PatternLibrary, Rect};
+
+fn board_with(objects: &[(Pattern, usize, usize)]) -> LifeBoard {
+    let mut board = LifeBoard::new(20, 16);
+    for (pattern, row, col) in objects {
+        board.stamp(pattern, *row, *col).unwrap();
+    }
+    board
+}
+
+#[test]
+fn two_blocks_and_a_blinker_in_any_phase_or_place() {
+    let vertical = patterns::blinker().rotate90();
+    let layouts = [
+        [(1, 1), (1, 10), (10, 4)],
+        [(12, 15), (3, 2), (6, 9)],
+        [(0, 0), (13, 17), (7, 7)],
+    ];
+
+    for layout in &layouts {
+        for blinker in &[patterns::blinker(), vertical.clone()] {
+            let board = board_with(&[
+                (patterns::block(), layout[0].0, layout[0].1),
+                (patterns::block(), layout[1].0, layout[1].1),
+                (blinker.clone(), layout[2].0, layout[2].1),
+            ]);
+
+            let census = board.census();
+            assert_eq!(census.count("block"), 2);
+            assert_eq!(census.count("blinker"), 1);
+            assert_eq!(census.counts.len(), 2);
+            assert_eq!(census.unidentified, 0);
+            assert_eq!(census.total(), 3);
+        }
+    }
+}
+
+#[test]
+fn rotated_and_reflected_objects_are_identified() {
+    let glider = patterns::glider();
+    let board = board_with(&[
+        (glider.rotate90(), 1, 1),
+        (glider.flip_horizontal(), 1, 8),
+        (patterns::boat().rotate180(), 8, 1),
+        (patterns::loaf().transpose(), 8, 8),
+    ]);
+
+    let census = board.census();
+    assert_eq!(census.count("glider"), 2);
+    assert_eq!(census.count("boat"), 1);
+    assert_eq!(census.count("loaf"), 1);
+    assert_eq!(census.unidentified, 0);
+}
+
+#[test]
+fn unknown_objects_are_unidentified() {
+    let pair = Pattern::from_cells(vec![(0, 0), (0, 1)]);
+    let board = board_with(&[(pair, 2, 2), (patterns::beehive(), 8, 8)]);
+
+    let census = board.census();
+    assert_eq!(census.count("beehive"), 1);
+    assert_eq!(census.unidentified, 1);
+
+    let mut library = PatternLibrary::new();
+    library.insert(LibraryEntry::new("beehive", patterns::beehive(), 1, (0, 0)));
+    assert_eq!(board.census_with(&PatternLibrary::new()).unidentified, 2);
+    assert_eq!(board.census_with(&library), census);
+}
+
+#[test]
+fn components_carry_cells_bounds_and_population() {
+    let board = board_with(&[(patterns::glider(), 2, 3), (patterns::block(), 10, 10)]);
+
+    let components = board.components();
+    assert_eq!(components.len(), 2);
+
+    let glider = &components[0];
+    assert_eq!(glider.population(), 5);
+    assert_eq!(
+        glider.bounding_box,
+        Rect {
+            row: 2,
+            col: 3,
+            width: 3,
+            height: 3,
+        }
+    );
+    let mut cells: Vec<_> = board.live_cells().filter(|&(row, _)| row < 10).collect();
+    cells.sort_unstable();
+    assert_eq!(glider.cells, cells);
+
+    assert_eq!(
+        components[1].cells,
+        vec![(10, 10), (10, 11), (11, 10), (11, 11)]
+    );
+    assert_eq!(components[1].shape(), vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+    assert!(LifeBoard::new(5, 5).components().is_empty());
+}
+
+#[test]
+fn diagonal_neighbours_are_connected() {
+    let diagonal = Pattern::from_cells(vec![(0, 0), (1, 1), (2, 2)]);
+    let board = board_with(&[(diagonal, 0, 0), (patterns::block(), 0, 4)]);
+
+    let components = board.components();
+    assert_eq!(components.len(), 2);
+    assert_eq!(components[0].population(), 3);
+}
@@ -0,0 +1,181 @@
+extern crate gameoflife;
+extern crate rand;
+
+#[cfg(feature = "native")]
+use gameoflife::options::{Options, OptionsError};
+use gameoflife::{
+    divergence, fit_side_by_side, patterns, side_by_side, BoundaryMode, Comparison, LifeBoard,
+    RenderStyle, Rule,
+};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+fn soup() -> LifeBoard {
+    let mut board = LifeBoard::new(24, 16);
+    board
+        .randomize(0.4, &mut SmallRng::seed_from_u64(7))
+        .unwrap();
+    board
+}
+
+#[test]
+fn same_rule_never_diverges() {
+    let mut comparison = Comparison::new("left", soup());
+    comparison.add_side("right", Rule::conway(), BoundaryMode::Wrap);
+
+    for _ in 0..50 {
+        assert_eq!(comparison.divergences(), vec![0]);
+        comparison.advance();
+    }
+    assert_eq!(comparison.sides()[1].board.generation(), 50);
+}
+
+#[test]
+fn divergence_counts_cells_alive_on_one_side_only() {
+    let mut a = LifeBoard::new(4, 3);
+    let mut b = LifeBoard::new(4, 3);
+    a.set(0, 0, true).unwrap();
+    a.set(1, 1, true).unwrap();
+    b.set(1, 1, true).unwrap();
+    b.set(2, 3, true).unwrap();
+
+    assert_eq!(divergence(&a, &b), 2);
+    assert_eq!(divergence(&a, &a), 0);
+}
+
+#[test]
+#[should_panic(expected = "different sizes")]
+fn boards_of_different_sizes_cannot_be_compared() {
+    divergence(&LifeBoard::new(4, 3), &LifeBoard::new(3, 4));
+}
+
+#[test]
+fn rules_and_boundaries_drift_apart() {
+    // A glider crossing the edge survives on a torus and becomes a block
+    // against a dead edge
+    let mut board = LifeBoard::new(6, 6);
+    board.stamp(&patterns::glider(), 3, 3).unwrap();
+    let mut comparison = Comparison::new("wrap", board);
+    comparison.add_side("dead", Rule::conway(), BoundaryMode::Dead);
+    comparison.add_side(
+        "highlife",
+        Rule::parse("B36/S23").unwrap(),
+        BoundaryMode::Wrap,
+    );
+
+    assert_eq!(comparison.divergences(), vec![0, 0]);
+    for _ in 0..12 {
+        comparison.advance();
+    }
+    let sides = comparison.sides();
+    assert_eq!(sides[0].board.population(), 5);
+    assert_eq!(sides[1].board.population(), 4);
+    assert_eq!(
+        comparison.divergences(),
+        vec![divergence(&sides[0].board, &sides[1].board), 0]
+    );
+    assert!(comparison.divergences()[0] > 0);
+}
+
+#[test]
+fn frames_are_laid_out_in_columns() {
+    let wide = "+----+\n| ## |\n+----+";
+    let tall = "ab\ncd\nef\ngh";
+
+    let expected = [
+        "first    second rule",
+        "+----+   ab",
+        "| ## |   cd",
+        "+----+   ef",
+        "         gh",
+    ];
+    assert_eq!(
+        side_by_side(&[("first", wide), ("second rule", tall)], 3),
+        expected.join("\n") + "\n"
+    );
+
+    // Color codes take no room
+    let colored = "\x1b[32m#\x1b[0m.\n..";
+    assert_eq!(
+        side_by_side(&[("a", colored), ("b", "xy")], 1),
+        "a  b\n\x1b[32m#\x1b[0m. xy\n..\n"
+    );
+}
+
+#[test]
+fn compared_frames_show_divergence() {
+    let mut board = LifeBoard::new(3, 3);
+    board.set(1, 1, true).unwrap();
+    let mut comparison = Comparison::new("life", board);
+    comparison.add_side("seeds", Rule::parse("B2/S").unwrap(), BoundaryMode::Dead);
+    comparison.advance();
+
+    assert_eq!(
+        comparison.compose_frame(RenderStyle::Ascii, false),
+        "life        seeds\n\
+         +--------+  +--------+\n\
+         |        |  |        |\n\
+         |        |  |        |\n\
+         |        |  |        |\n\
+         +--------+  +--------+\n\
+         generation 1, seeds: 0 differ\n"
+    );
+}
+
+#[test]
+fn side_by_side_boards_fit_the_terminal() {
+    // Two frames of 39 columns with a gap of 2, under a label line
+    assert_eq!(fit_side_by_side(RenderStyle::Ascii, 2, 2, 80, 24), (17, 20));
+    assert_eq!(fit_side_by_side(RenderStyle::Ascii, 1, 2, 80, 24), (38, 20));
+    assert_eq!(
+        fit_side_by_side(RenderStyle::Braille, 3, 2, 80, 24),
+        (42, 80)
+    );
+    assert_eq!(fit_side_by_side(RenderStyle::Ascii, 4, 2, 10, 3), (1, 1));
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn compare_options() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    let mut options = Options::parse(args(&[
+        "--rule",
+        "B3/S23",
+        "--compare",
+        "B36/S23",
+        "--compare",
+        ":dead",
+        "--compare",
+        "B2/S:klein",
+    ]))
+    .unwrap();
+    options.fit_to_terminal(80, 24);
+    assert_eq!((options.width, options.height), (7, 20));
+
+    let board = options.build_board().unwrap();
+    let comparison = options.comparison(board);
+    let labels: Vec<&str> = comparison
+        .sides()
+        .iter()
+        .map(|side| side.label.as_str())
+        .collect();
+    assert_eq!(
+        labels,
+        vec!["B3/S23 wrap", "B36/S23 wrap", "B3/S23 dead", "B2/S klein"]
+    );
+
+    for bad in &[":", "B3/S23:torus", "nonsense"] {
+        assert!(
+            Options::parse(args(&["--compare", bad])).is_err(),
+            "{}",
+            bad
+        );
+    }
+    for mode in &["--headless", "--interactive", "--window"] {
+        assert!(matches!(
+            Options::parse(args(&["--compare", ":dead", mode])),
+            Err(OptionsError::CompareWithoutDrawing)
+        ));
+    }
+}
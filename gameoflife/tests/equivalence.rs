@@ -0,0 +1,403 @@
+//! Differential tests checking every way of stepping a board against a
+//! deliberately simple reference stepper, one generation at a time.
+
+extern crate gameoflife;
+extern crate proptest;
+
+use gameoflife::SparseLifeBoard;
+use gameoflife::{BoundaryMode, CellChange, HashLifeUniverse, LifeBoard, PackedLifeBoard, Rule};
+use proptest::prelude::*;
+
+// Generations each board is stepped through
+const GENERATIONS: usize = 8;
+
+// Board as a grid of rows, stepped by looking at every neighbour of every
+// cell. Slow, but small enough to check by eye.
+#[derive(Debug, Clone, PartialEq)]
+struct Reference {
+    width: usize,
+    height: usize,
+    boundary: BoundaryMode,
+    rule: Rule,
+    rows: Vec<Vec<bool>>,
+}
+
+impl Reference {
+    fn neighbour(&self, row: usize, col: usize, delta_row: isize, delta_col: isize) -> bool {
+        let row = row as isize + delta_row;
+        let col = col as isize + delta_col;
+        match resolve(self.boundary, row, col, self.width, self.height) {
+            Some((row, col)) => self.rows[row][col],
+            None => false,
+        }
+    }
+
+    fn step(&self) -> Reference {
+        let mut next = self.clone();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                // The cell and its neighbours, bit 0 top left and bit 8 bottom
+                // right
+                let mut neighbourhood = 0;
+                for delta_row in -1..=1 {
+                    for delta_col in -1..=1 {
+                        let alive = if (delta_row, delta_col) == (0, 0) {
+                            self.rows[row][col]
+                        } else {
+                            self.neighbour(row, col, delta_row, delta_col)
+                        };
+                        let bit = 3 * (delta_row + 1) + delta_col + 1;
+                        neighbourhood |= (alive as u16) << bit;
+                    }
+                }
+                next.rows[row][col] = self.rule.applies_to(neighbourhood);
+            }
+        }
+
+        next
+    }
+
+    fn live_cells(&self) -> Vec<(usize, usize)> {
+        let mut live = Vec::new();
+        for (row, cells) in self.rows.iter().enumerate() {
+            for (col, &alive) in cells.iter().enumerate() {
+                if alive {
+                    live.push((row, col));
+                }
+            }
+        }
+
+        live
+    }
+
+    fn to_board(&self) -> LifeBoard {
+        let mut board = LifeBoard::new(self.width, self.height);
+        board.set_boundary(self.boundary);
+        board.set_rule(self.rule);
+        for (row, col) in self.live_cells() {
+            board.set(row, col, true).unwrap();
+        }
+
+        board
+    }
+
+    // Copy with `margin` dead cells on every side and dead edges, so for as
+    // many generations as the margin is wide it behaves like the cells on an
+    // unbounded plane
+    fn padded(&self, margin: usize) -> Reference {
+        let mut padded = Reference {
+            width: self.width + 2 * margin,
+            height: self.height + 2 * margin,
+            boundary: BoundaryMode::Dead,
+            rule: self.rule,
+            rows: vec![vec![false; self.width + 2 * margin]; self.height + 2 * margin],
+        };
+        for (row, col) in self.live_cells() {
+            padded.rows[row + margin][col + margin] = true;
+        }
+
+        padded
+    }
+}
+
+// Position of the cell at `row`, `col`, which may be one step past any edge
+// of a `width` by `height` board
+fn resolve(
+    boundary: BoundaryMode,
+    row: isize,
+    col: isize,
+    width: usize,
+    height: usize,
+) -> Option<(usize, usize)> {
+    let (width, height) = (width as isize, height as isize);
+    let (mut row, mut col) = (row, col);
+
+    // Across the left or right edge first, as that can move the row
+    if col < 0 || col >= width {
+        let right = col >= width;
+        match boundary {
+            BoundaryMode::Dead => return None,
+            BoundaryMode::Mirror => col = col.clamp(0, width - 1),
+            BoundaryMode::Wrap => col = col.rem_euclid(width),
+            BoundaryMode::ShiftedTorus { offset } => {
+                row += if right { offset } else { -offset };
+                col = col.rem_euclid(width);
+            }
+            BoundaryMode::KleinBottle => {
+                row = height - 1 - row;
+                col = col.rem_euclid(width);
+            }
+        }
+    }
+
+    if row < 0 || row >= height {
+        match boundary {
+            BoundaryMode::Dead => return None,
+            BoundaryMode::Mirror => row = row.clamp(0, height - 1),
+            _ => row = row.rem_euclid(height),
+        }
+    }
+
+    Some((row as usize, col as usize))
+}
+
+fn live(board: &LifeBoard) -> Vec<(usize, usize)> {
+    board.live_cells().collect()
+}
+
+fn rule_string(birth: &[bool], survival: &[bool]) -> String {
+    let counts = |counts: &[bool]| -> String {
+        (0..9)
+            .filter(|&n| counts[n])
+            .map(|n| char::from(b'0' + n as u8))
+            .collect()
+    };
+
+    format!("B{}/S{}", counts(birth), counts(survival))
+}
+
+// Hensel notation letters for each neighbour count
+const LETTERS: [&str; 9] = [
+    "",
+    "ce",
+    "cekain",
+    "cekainyqjr",
+    "cekainyqjrtwz",
+    "cekainyqjr",
+    "cekain",
+    "ce",
+    "",
+];
+
+// One half of an isotropic rulestring, with the arrangements of each count
+// picked by the bits of its entry in `picks`
+fn hensel_string(picks: &[u16]) -> String {
+    let mut half = String::new();
+    for (count, (&pick, letters)) in picks.iter().zip(LETTERS.iter()).enumerate() {
+        let picked: String = letters
+            .chars()
+            .enumerate()
+            .filter(|&(bit, _)| pick & 1 << bit != 0)
+            .map(|(_, letter)| letter)
+            .collect();
+        if !picked.is_empty() || (letters.is_empty() && pick & 1 != 0) {
+            half.push(char::from(b'0' + count as u8));
+            half.push_str(&picked);
+        }
+    }
+
+    half
+}
+
+fn rules() -> impl Strategy<Value = Rule> {
+    prop_oneof![
+        Just(Rule::conway()),
+        (
+            prop::array::uniform9(any::<bool>()),
+            prop::array::uniform9(any::<bool>())
+        )
+            .prop_map(|(birth, survival)| Rule::parse(&rule_string(&birth, &survival)).unwrap()),
+        (
+            prop::array::uniform9(any::<u16>()),
+            prop::array::uniform9(any::<u16>())
+        )
+            .prop_map(|(birth, survival)| {
+                let rule = format!("B{}/S{}", hensel_string(&birth), hensel_string(&survival));
+                Rule::parse(&rule).unwrap()
+            }),
+    ]
+}
+
+// Rules that never bring a cell with no live neighbours to life, which the
+// unbounded boards require
+fn rules_without_b0() -> impl Strategy<Value = Rule> {
+    rules().prop_filter("B0 rules fill an unbounded plane", |rule| {
+        !rule.applies(false, 0)
+    })
+}
+
+fn boundaries() -> impl Strategy<Value = BoundaryMode> {
+    prop_oneof![
+        Just(BoundaryMode::Wrap),
+        Just(BoundaryMode::Dead),
+        Just(BoundaryMode::Mirror),
+        (-30isize..=30).prop_map(|offset| BoundaryMode::ShiftedTorus { offset }),
+        Just(BoundaryMode::KleinBottle),
+    ]
+}
+
+// Widths reach past 64 so packed boards span more than one word per row.
+// Shrinking removes live cells and narrows the board towards a single cell.
+fn references(rules: impl Strategy<Value = Rule>) -> impl Strategy<Value = Reference> {
+    (1usize..=70, 1usize..=24, 0.0f64..=1.0, boundaries(), rules).prop_flat_map(
+        |(width, height, density, boundary, rule)| {
+            let row = prop::collection::vec(prop::bool::weighted(density), width);
+            prop::collection::vec(row, height).prop_map(move |rows| Reference {
+                width,
+                height,
+                boundary,
+                rule,
+                rows,
+            })
+        },
+    )
+}
+
+proptest! {
+    #[test]
+    fn advance_matches_reference(reference in references(rules())) {
+        let mut reference = reference;
+        let mut board = reference.to_board();
+        for generation in 1..=GENERATIONS {
+            reference = reference.step();
+            board.advance();
+            prop_assert_eq!(live(&board), reference.live_cells(), "generation {}", generation);
+        }
+    }
+
+    #[test]
+    fn step_matches_reference(reference in references(rules())) {
+        let mut reference = reference;
+        let mut board = reference.to_board();
+        let mut next = LifeBoard::new(board.width(), board.height());
+        for generation in 1..=GENERATIONS {
+            reference = reference.step();
+            board.step(&mut next).unwrap();
+            std::mem::swap(&mut board, &mut next);
+            prop_assert_eq!(live(&board), reference.live_cells(), "generation {}", generation);
+        }
+    }
+
+    #[test]
+    fn step_diff_matches_reference(reference in references(rules())) {
+        let mut reference = reference;
+        let mut board = reference.to_board();
+        let mut next = LifeBoard::new(board.width(), board.height());
+        for generation in 1..=GENERATIONS {
+            let previous = reference.clone();
+            reference = reference.step();
+            let changes = board.step_diff(&mut next).unwrap();
+            std::mem::swap(&mut board, &mut next);
+            prop_assert_eq!(live(&board), reference.live_cells(), "generation {}", generation);
+
+            let mut expected = Vec::new();
+            for row in 0..reference.height {
+                for col in 0..reference.width {
+                    let alive = reference.rows[row][col];
+                    if alive != previous.rows[row][col] {
+                        expected.push(CellChange { row, col, alive });
+                    }
+                }
+            }
+            prop_assert_eq!(changes, expected, "generation {}", generation);
+        }
+    }
+
+    #[test]
+    fn advance_active_matches_reference(reference in references(rules())) {
+        let mut reference = reference;
+        let mut board = reference.to_board();
+        for generation in 1..=GENERATIONS {
+            reference = reference.step();
+            board.advance_active();
+            prop_assert_eq!(live(&board), reference.live_cells(), "generation {}", generation);
+        }
+    }
+
+    #[test]
+    fn packed_matches_reference(reference in references(rules())) {
+        let mut reference = reference;
+        let mut packed = PackedLifeBoard::from_board(&reference.to_board());
+        for generation in 1..=GENERATIONS {
+            reference = reference.step();
+            packed.advance();
+            prop_assert_eq!(
+                live(&packed.to_board()),
+                reference.live_cells(),
+                "generation {}",
+                generation
+            );
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_advance_matches_reference(reference in references(rules())) {
+        let mut reference = reference;
+        let mut board = reference.to_board();
+        for generation in 1..=GENERATIONS {
+            reference = reference.step();
+            board.par_advance();
+            prop_assert_eq!(live(&board), reference.live_cells(), "generation {}", generation);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_step_matches_reference(reference in references(rules())) {
+        let mut reference = reference;
+        let mut board = reference.to_board();
+        let mut next = LifeBoard::new(board.width(), board.height());
+        for generation in 1..=GENERATIONS {
+            reference = reference.step();
+            board.par_step(&mut next).unwrap();
+            std::mem::swap(&mut board, &mut next);
+            prop_assert_eq!(live(&board), reference.live_cells(), "generation {}", generation);
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_advance_matches_reference(reference in references(rules())) {
+        let mut reference = reference;
+        let mut board = reference.to_board();
+        for generation in 1..=GENERATIONS {
+            reference = reference.step();
+            board.simd_advance();
+            prop_assert_eq!(live(&board), reference.live_cells(), "generation {}", generation);
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_step_matches_scalar_step(reference in references(rules())) {
+        let mut board = reference.to_board();
+        let mut scalar = LifeBoard::new(board.width(), board.height());
+        let mut simd = LifeBoard::new(board.width(), board.height());
+        for generation in 1..=GENERATIONS {
+            board.step(&mut scalar).unwrap();
+            board.simd_step(&mut simd).unwrap();
+            prop_assert_eq!(live(&simd), live(&scalar), "generation {}", generation);
+            std::mem::swap(&mut board, &mut scalar);
+        }
+    }
+
+    #[test]
+    fn sparse_matches_reference(reference in references(rules_without_b0())) {
+        // A pattern grows by at most a cell a generation in every direction
+        let margin = GENERATIONS + 1;
+        let mut sparse = SparseLifeBoard::from_board(&reference.to_board());
+        let mut reference = reference.padded(margin);
+        let origin = -(margin as i64);
+        for generation in 1..=GENERATIONS {
+            reference = reference.step();
+            sparse.advance();
+            let view = sparse.viewport(origin, origin, reference.width, reference.height);
+            prop_assert_eq!(live(&view), reference.live_cells(), "generation {}", generation);
+        }
+    }
+
+    #[test]
+    fn hashlife_matches_reference(reference in references(Just(Rule::conway()))) {
+        let margin = GENERATIONS + 1;
+        let mut universe = HashLifeUniverse::from_board(&reference.to_board()).unwrap();
+        let mut reference = reference.padded(margin);
+        let origin = -(margin as i64);
+        for generation in 1..=GENERATIONS {
+            reference = reference.step();
+            universe.advance(0).unwrap();
+            let view = universe.to_board(origin, origin, reference.width, reference.height);
+            prop_assert_eq!(live(&view), reference.live_cells(), "generation {}", generation);
+        }
+    }
+}
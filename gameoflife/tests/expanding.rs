@@ -0,0 +1,162 @@
+extern crate gameoflife;
+
+use gameoflife::{patterns, BirthOnZero, BoundaryMode, ExpandingLifeBoard, LifeBoard, Rule};
+
+fn sorted(board: &ExpandingLifeBoard) -> Vec<(i64, i64)> {
+    let mut cells: Vec<_> = board.live_cells().collect();
+    cells.sort_unstable();
+    cells
+}
+
+// Smallest region holding every live cell, as a dense board
+fn occupied(board: &ExpandingLifeBoard) -> LifeBoard {
+    let cells = sorted(board);
+    let top = cells[0].0;
+    let bottom = cells[cells.len() - 1].0;
+    let left = cells.iter().map(|&(_, col)| col).min().unwrap();
+    let right = cells.iter().map(|&(_, col)| col).max().unwrap();
+
+    board.viewport(
+        top,
+        left,
+        (right - left + 1) as usize,
+        (bottom - top + 1) as usize,
+    )
+}
+
+#[test]
+fn gosper_gun_fires_a_glider_every_30_generations() {
+    let mut board = ExpandingLifeBoard::new();
+    board.stamp(&patterns::gosper_gun(), 0, 0);
+
+    // The same gun on a fixed board large enough that its gliders never
+    // reach the dead edges
+    let mut fixed = LifeBoard::new_square(200);
+    fixed.set_boundary(BoundaryMode::Dead);
+    fixed.stamp(&patterns::gosper_gun(), 20, 20).unwrap();
+
+    for generation in 0..=500u64 {
+        // The first glider breaks away from the gun at generation 15
+        let gliders = if generation < 15 {
+            0
+        } else {
+            (generation - 15) / 30 + 1
+        };
+        let census = occupied(&board).census();
+        assert_eq!(
+            census.count("glider") as u64,
+            gliders,
+            "generation {}",
+            generation
+        );
+
+        if generation % 50 == 0 {
+            assert_eq!(
+                board.viewport(-20, -20, 200, 200),
+                fixed,
+                "generation {}",
+                generation
+            );
+        }
+        board.advance();
+        fixed.advance();
+    }
+    assert_eq!(board.generation(), 501);
+}
+
+#[test]
+fn settled_patterns_never_grow_the_board() {
+    let mut board = ExpandingLifeBoard::new();
+    board.stamp(&patterns::block(), -4, -4);
+    board.stamp(&patterns::blinker(), 2, 1);
+    let (width, height) = (board.width(), board.height());
+
+    board.advance_by(1000);
+    assert_eq!((board.width(), board.height()), (width, height));
+    assert!(!board.is_sparse());
+    assert_eq!(board.population(), 7);
+}
+
+#[test]
+fn spreading_patterns_switch_to_live_cells_only() {
+    // A lone glider flying away would otherwise have the board double
+    // forever; it grows while still a reasonable size, then only the five
+    // cells are kept
+    let mut board = ExpandingLifeBoard::new();
+    board.stamp(&patterns::glider(), 0, 0);
+    let start = sorted(&board);
+
+    let mut largest = board.width() * board.height();
+    for _ in 0..1000 {
+        board.advance();
+        largest = largest.max(board.width() * board.height());
+    }
+
+    assert!(board.is_sparse());
+    assert!(largest <= 64 * 64, "grew to {} cells", largest);
+    assert_eq!((board.width(), board.height()), (0, 0));
+    let moved: Vec<_> = start
+        .iter()
+        .map(|&(row, col)| (row + 250, col + 250))
+        .collect();
+    assert_eq!(sorted(&board), moved);
+
+    // Cells can still be set and stamped anywhere
+    board.set(-5000, 7, true);
+    board.stamp(&patterns::block(), 9000, -9000);
+    assert!(board.get(-5000, 7));
+    assert!(board.get(9001, -8999));
+    assert_eq!(board.population(), 10);
+}
+
+#[test]
+fn growth_keeps_cells_at_their_coordinates() {
+    let mut board = ExpandingLifeBoard::new();
+    board.stamp(&patterns::glider(), 3, -2);
+    let glider = sorted(&board);
+    let (width, height) = (board.width(), board.height());
+
+    // Setting a cell far away grows the board around what is there
+    board.set(-100, 90, true);
+    assert!(board.width() > width && board.height() > height);
+    assert!(!board.is_sparse());
+    let mut expected = glider.clone();
+    expected.insert(0, (-100, 90));
+    assert_eq!(sorted(&board), expected);
+    assert!(board.get(-100, 90));
+
+    let view = board.viewport(3, -2, 3, 3);
+    let mut stamped = LifeBoard::new_square(3);
+    stamped.stamp(&patterns::glider(), 0, 0).unwrap();
+    assert_eq!(view, stamped);
+}
+
+#[test]
+fn birth_on_zero_rules_are_rejected() {
+    let b0 = Rule::parse("B0123/S012345678").unwrap();
+
+    let mut board = ExpandingLifeBoard::new();
+    board.stamp(&patterns::glider(), 0, 0);
+    assert_eq!(board.set_rule(b0), Err(BirthOnZero(b0)));
+    assert_eq!(board.rule(), Rule::conway());
+    assert_eq!(
+        BirthOnZero(b0).to_string(),
+        format!("rule {} gives birth on zero neighbours", b0)
+    );
+
+    let highlife = Rule::parse("B36/S23").unwrap();
+    assert_eq!(board.set_rule(highlife), Ok(()));
+    assert_eq!(board.rule(), highlife);
+
+    let mut dense = LifeBoard::new_square(8);
+    dense.stamp(&patterns::glider(), 2, 2).unwrap();
+    dense.set_rule(b0);
+    assert_eq!(
+        ExpandingLifeBoard::from_board(&dense).unwrap_err(),
+        BirthOnZero(b0)
+    );
+    dense.set_rule(highlife);
+    let expanding = ExpandingLifeBoard::from_board(&dense).unwrap();
+    assert_eq!(expanding.rule(), highlife);
+    assert_eq!(expanding.population(), 5);
+}
@@ -0,0 +1,177 @@
+extern crate gameoflife;
+#[cfg(feature = "gif")]
+extern crate gif;
+#[cfg(feature = "image")]
+extern crate image;
+extern crate rand;
+
+use gameoflife::LifeBoard;
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+fn soup(width: usize, height: usize, seed: u64) -> LifeBoard {
+    let mut board = LifeBoard::new(width, height);
+    board
+        .randomize(0.5, &mut SmallRng::seed_from_u64(seed))
+        .unwrap();
+    board
+}
+
+fn cells(board: &LifeBoard) -> Vec<Vec<bool>> {
+    (0..board.height()).map(|row| board[row].to_vec()).collect()
+}
+
+// Reads back a P4 image written by `to_pbm`, as rows of cells
+fn decode_pbm(pbm: &[u8]) -> Vec<Vec<bool>> {
+    let header_end = pbm
+        .iter()
+        .enumerate()
+        .filter(|&(_, &byte)| byte == b'\n')
+        .nth(1)
+        .unwrap()
+        .0;
+    let header = std::str::from_utf8(&pbm[..header_end]).unwrap();
+    let mut fields = header.split_whitespace();
+    assert_eq!(fields.next(), Some("P4"));
+    let width: usize = fields.next().unwrap().parse().unwrap();
+    let height: usize = fields.next().unwrap().parse().unwrap();
+
+    let stride = width.div_ceil(8);
+    let data = &pbm[header_end + 1..];
+    assert_eq!(data.len(), stride * height);
+    data.chunks(stride.max(1))
+        .take(height)
+        .map(|row| {
+            (0..width)
+                .map(|col| row[col / 8] & (0x80 >> (col % 8)) != 0)
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn pbm_header_and_length_fit_any_width() {
+    for &(width, height) in &[(1, 1), (3, 2), (7, 5), (8, 4), (9, 3), (17, 1), (64, 2)] {
+        let pbm = LifeBoard::new(width, height).to_pbm();
+        let header = format!("P4\n{} {}\n", width, height);
+        assert!(pbm.starts_with(header.as_bytes()));
+        assert_eq!(
+            pbm.len(),
+            header.len() + width.div_ceil(8) * height,
+            "{}x{}",
+            width,
+            height
+        );
+    }
+}
+
+#[test]
+fn pbm_of_a_glider() {
+    let mut board = LifeBoard::new_square(3);
+    for &(row, col) in &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+        board.set(row, col, true).unwrap();
+    }
+
+    let mut expected = b"P4\n3 3\n".to_vec();
+    expected.extend([0b0100_0000, 0b0010_0000, 0b1110_0000]);
+    assert_eq!(board.to_pbm(), expected);
+}
+
+#[test]
+fn pbm_decodes_to_the_cells() {
+    for &(width, height) in &[(1, 1), (5, 3), (8, 8), (13, 7), (33, 20)] {
+        let board = soup(width, height, (width * height) as u64);
+        assert_eq!(
+            decode_pbm(&board.to_pbm()),
+            cells(&board),
+            "{}x{}",
+            width,
+            height
+        );
+    }
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn png_decodes_to_the_cells_scaled_up() {
+    for scale in [1, 3] {
+        let board = soup(13, 7, 690);
+        let png = board.to_png(scale).unwrap();
+        let image = image::load_from_memory_with_format(&png, image::ImageFormat::Png)
+            .unwrap()
+            .to_luma8();
+        assert_eq!(image.dimensions(), (13 * scale, 7 * scale));
+
+        for (x, y, pixel) in image.enumerate_pixels() {
+            let alive = board[(y / scale) as usize][(x / scale) as usize];
+            assert_eq!(pixel.0, [if alive { 0 } else { 255 }], "({}, {})", x, y);
+        }
+
+        // Reading the image back one pixel per cell gives the same board
+        if scale == 1 {
+            let decoded = LifeBoard::from_image_bytes(&png, 128, None).unwrap();
+            assert_eq!(cells(&decoded), cells(&board));
+        }
+    }
+}
+
+#[cfg(feature = "gif")]
+#[test]
+fn gif_of_a_blinker_decodes_frame_by_frame() {
+    use gameoflife::{export_gif, patterns};
+
+    let mut blinker = LifeBoard::new(5, 4);
+    blinker.stamp(&patterns::blinker(), 1, 1).unwrap();
+    let path = std::env::temp_dir().join(format!("gameoflife-export-{}.gif", std::process::id()));
+
+    let mut board = blinker.clone();
+    let exported = export_gif(&mut board, 10, 3, 7, false, &path);
+    let file = std::fs::read(&path);
+    std::fs::remove_file(&path).unwrap();
+    exported.unwrap();
+    let file = file.unwrap();
+    assert_eq!(board.generation(), 10);
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::Indexed);
+    let mut decoder = options.read_info(&file[..]).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (15, 12));
+
+    let mut frames = 0;
+    let mut expected = blinker;
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!((frame.width, frame.height), (15, 12));
+        assert_eq!(frame.delay, 7);
+        for (i, &pixel) in frame.buffer.iter().enumerate() {
+            let (y, x) = (i / 15, i % 15);
+            assert_eq!(pixel, expected[y / 3][x / 3] as u8, "frame {}", frames);
+        }
+        expected.advance();
+        frames += 1;
+    }
+    assert_eq!(frames, 10);
+}
+
+#[cfg(feature = "gif")]
+#[test]
+fn gif_export_stops_when_the_run_cycles() {
+    use gameoflife::{export_gif, patterns};
+
+    let mut board = LifeBoard::new(5, 4);
+    board.stamp(&patterns::blinker(), 1, 1).unwrap();
+    let path = std::env::temp_dir().join(format!("gameoflife-cycle-{}.gif", std::process::id()));
+
+    let exported = export_gif(&mut board, 10, 1, 10, true, &path);
+    let file = std::fs::read(&path);
+    std::fs::remove_file(&path).unwrap();
+    exported.unwrap();
+    let file = file.unwrap();
+
+    let mut decoder = gif::DecodeOptions::new().read_info(&file[..]).unwrap();
+    let mut frames = 0;
+    while decoder.read_next_frame().unwrap().is_some() {
+        frames += 1;
+    }
+    // Both phases of the blinker, and not the repeat of the first
+    assert_eq!(frames, 2);
+}
@@ -0,0 +1,192 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{patterns, FormatError, LifeBoard, Rect, Rule, SparseLifeBoard, MAX_PATTERN_SIDE};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+fn live(board: &LifeBoard) -> Vec<(usize, usize)> {
+    board.live_cells().collect()
+}
+
+#[test]
+fn life106_round_trips() {
+    let mut board = LifeBoard::new_square(20);
+    board.stamp(&patterns::glider(), 3, 7).unwrap();
+    board.stamp(&patterns::blinker(), 12, 2).unwrap();
+
+    let text = board.to_life106();
+    assert!(text.starts_with("#Life 1.06\n"));
+    let loaded = LifeBoard::from_life106(&text).unwrap();
+    assert_eq!(live(&loaded), live(&board));
+}
+
+#[test]
+fn sparse_life106_keeps_signed_coordinates() {
+    let text = "#Life 1.06\n-3 -1\n0 0\n5 -2\n";
+    let board = SparseLifeBoard::from_life106(text).unwrap();
+    assert!(board.get(-1, -3) && board.get(0, 0) && board.get(-2, 5));
+    assert_eq!(board.population(), 3);
+
+    assert_eq!(board.to_life106(), "#Life 1.06\n5 -2\n-3 -1\n0 0\n");
+    let reloaded = SparseLifeBoard::from_life106(&board.to_life106()).unwrap();
+    assert_eq!(reloaded.to_life106(), board.to_life106());
+}
+
+#[test]
+fn life106_shifts_negative_coordinates_onto_the_board() {
+    let board = LifeBoard::from_life106("#Life 1.06\n-1 0\n1 -2\n").unwrap();
+    assert_eq!((board.width(), board.height()), (3, 3));
+    assert_eq!(live(&board), vec![(0, 2), (2, 0)]);
+}
+
+#[test]
+fn life106_tolerates_crlf_and_blank_lines() {
+    let text = "\r\n#Life 1.06\r\n\r\n0 0\r\n#D a comment\r\n  1 0  \r\n\r\n2 0\r\n";
+    let board = LifeBoard::from_life106(text).unwrap();
+    assert_eq!(live(&board), vec![(0, 0), (0, 1), (0, 2)]);
+}
+
+#[test]
+fn life106_reports_malformed_lines() {
+    assert_eq!(
+        LifeBoard::from_life106("#Life 1.06\n0 0\n\n1 x\n").unwrap_err(),
+        FormatError::InvalidCoordinates(4)
+    );
+    assert_eq!(
+        LifeBoard::from_life106("#Life 1.06\r\n0 0 0\r\n").unwrap_err(),
+        FormatError::InvalidCoordinates(2)
+    );
+    assert_eq!(
+        LifeBoard::from_life106("0 0\n").unwrap_err(),
+        FormatError::InvalidHeader
+    );
+}
+
+#[test]
+fn rle_and_life106_load_the_same_board() {
+    let rle = "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+    let life106 = "#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2\n";
+
+    let from_rle = LifeBoard::from_rle(rle).unwrap();
+    let from_life106 = LifeBoard::from_life106(life106).unwrap();
+    assert_eq!(from_rle, from_life106);
+    assert_eq!(
+        LifeBoard::from_life106(&from_rle.to_life106()).unwrap(),
+        from_rle
+    );
+}
+
+// Boards with long runs, lines past the RLE line width, blank rows and an
+// empty border, under two rules
+fn sample_boards() -> Vec<LifeBoard> {
+    let mut rng = SmallRng::seed_from_u64(21);
+    let mut boards = Vec::new();
+    for &(density, rule) in &[(0.3, "B3/S23"), (0.9, "B36/S23"), (0.02, "B2/S")] {
+        let mut board = LifeBoard::new(120, 40);
+        board.set_rule(Rule::parse(rule).unwrap());
+        board
+            .randomize_region(
+                Rect {
+                    row: 3,
+                    col: 5,
+                    width: 110,
+                    height: 30,
+                },
+                density,
+                &mut rng,
+            )
+            .unwrap();
+        boards.push(board);
+    }
+    let mut glider = LifeBoard::new_square(20);
+    glider.stamp(&patterns::glider(), 0, 0).unwrap();
+    boards.push(glider);
+
+    boards
+}
+
+#[test]
+fn rle_round_trips() {
+    for board in sample_boards() {
+        let rle = board.to_rle();
+        assert!(rle.lines().all(|line| line.len() <= 70), "{}", rle);
+
+        let loaded = LifeBoard::from_rle(&rle).unwrap();
+        assert_eq!(live(&loaded), live(&board));
+        assert_eq!(loaded.rule(), board.rule());
+        assert_eq!(loaded.to_rle(), rle);
+    }
+
+    let empty = LifeBoard::new(5, 5);
+    assert!(LifeBoard::from_rle(&empty.to_rle()).unwrap().is_empty());
+}
+
+#[test]
+fn plaintext_round_trips() {
+    for board in sample_boards() {
+        let text = board.to_plaintext();
+        let loaded = LifeBoard::from_plaintext(&text).unwrap();
+        assert_eq!(live(&loaded), live(&board));
+        assert_eq!(loaded.to_plaintext(), text);
+    }
+}
+
+#[test]
+fn overflowing_run_counts_are_rejected() {
+    let digits = "9".repeat(40);
+    for body in [
+        format!("{}o!", digits),
+        format!("{}b!", digits),
+        format!("{}$o!", digits),
+        format!("{}b{}bo!", usize::MAX, usize::MAX),
+    ] {
+        let rle = format!("x = 3, y = 3\n{}\n", body);
+        assert_eq!(
+            LifeBoard::from_rle(&rle),
+            Err(FormatError::InvalidRunCount),
+            "{}",
+            body
+        );
+    }
+
+    // A run that fits in a usize but not on the board
+    let rle = format!("x = 3, y = 3\n{}o!\n", usize::MAX);
+    assert_eq!(LifeBoard::from_rle(&rle), Err(FormatError::OutOfBounds));
+}
+
+#[test]
+fn oversized_patterns_are_rejected_before_allocating() {
+    let side = MAX_PATTERN_SIDE;
+    assert!(LifeBoard::from_rle(&format!("x = {}, y = 1\no!", side)).is_ok());
+
+    let too_large = |width, height| Err(FormatError::TooLarge { width, height });
+    assert_eq!(
+        LifeBoard::from_rle(&format!("x = {}, y = 1\no!", side + 1)),
+        too_large(side + 1, 1)
+    );
+    assert_eq!(
+        LifeBoard::from_rle("x = 1000000000, y = 1000000000\no!"),
+        too_large(1_000_000_000, 1_000_000_000)
+    );
+    assert_eq!(
+        LifeBoard::from_rle(&format!("#R 0 {}\nx = 1, y = 1\no!", usize::MAX)),
+        too_large(1, usize::MAX)
+    );
+    assert_eq!(
+        LifeBoard::from_plaintext(&format!("!Origin: {} 0\nO\n", side)),
+        too_large(side + 1, 1)
+    );
+    assert_eq!(
+        LifeBoard::from_life106("#Life 1.06\n0 0\n5000 -2\n"),
+        too_large(5001, 3)
+    );
+    assert_eq!(
+        FormatError::TooLarge {
+            width: 5001,
+            height: 3
+        }
+        .to_string(),
+        "5001x3 pattern is larger than 4096 cells on a side"
+    );
+}
@@ -0,0 +1,187 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{BoundaryMode, GenerationsBoard, GenerationsRule, LifeBoard, Rule};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+// Board drawn as rows of '.' for dead, 'O' for alive and '*' for decaying
+fn from_rows(rows: &[&str], rule: GenerationsRule) -> GenerationsBoard {
+    let mut board = GenerationsBoard::new(rows[0].len(), rows.len(), rule);
+    board.set_boundary(BoundaryMode::Dead);
+    for (row, line) in rows.iter().enumerate() {
+        for (col, cell) in line.chars().enumerate() {
+            let state = match cell {
+                'O' => 1,
+                '*' => 2,
+                _ => 0,
+            };
+            board.set(row, col, state).unwrap();
+        }
+    }
+    board
+}
+
+fn to_rows(board: &GenerationsBoard) -> Vec<String> {
+    (0..board.height())
+        .map(|row| {
+            (0..board.width())
+                .map(|col| match board.get(row, col).unwrap() {
+                    0 => '.',
+                    1 => 'O',
+                    _ => '*',
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn brians_brain_domino_splits_and_spreads() {
+    // Each live cell decays for a generation, and only dead cells with
+    // exactly two live neighbours are born. The domino fires a ship each way
+    // along its long side and grows a ring of cells behind them.
+    #[rustfmt::skip]
+    let generations: [[&str; 11]; 5] = [
+        [
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            "...OO....",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+        ],
+        [
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            "...OO....",
+            "...**....",
+            "...OO....",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+        ],
+        [
+            ".........",
+            ".........",
+            ".........",
+            "...OO....",
+            "...**....",
+            "..O..O...",
+            "...**....",
+            "...OO....",
+            ".........",
+            ".........",
+            ".........",
+        ],
+        [
+            ".........",
+            ".........",
+            "...OO....",
+            "...**....",
+            "..O..O...",
+            "..*..*...",
+            "..O..O...",
+            "...**....",
+            "...OO....",
+            ".........",
+            ".........",
+        ],
+        [
+            ".........",
+            "...OO....",
+            "...**....",
+            "..O..O...",
+            "..*..*...",
+            ".O.OO.O..",
+            "..*..*...",
+            "..O..O...",
+            "...**....",
+            "...OO....",
+            ".........",
+        ],
+    ];
+
+    let mut board = from_rows(&generations[0], GenerationsRule::brians_brain());
+    for (generation, expected) in generations.iter().enumerate() {
+        assert_eq!(to_rows(&board), expected, "generation {}", generation);
+        assert_eq!(board.generation(), generation as u64);
+        board.advance();
+    }
+}
+
+#[test]
+fn brians_brain_ship_flies_at_light_speed() {
+    // Two live cells with their decaying trail behind them, the rule's most
+    // common spaceship, move a cell every generation
+    let mut board = from_rows(
+        &[
+            "......", "......", "......", "......", "..OO..", "..**..", "......", "......",
+        ],
+        GenerationsRule::brians_brain(),
+    );
+    board.set_boundary(BoundaryMode::Wrap);
+    let start = board.clone();
+
+    for generation in 1..=8 {
+        board.advance();
+        let front = (4 + 8 - generation) % 8;
+        let trail = (front + 1) % 8;
+        for col in 0..6 {
+            let expected = |row| match (row, col) {
+                (r, 2..=3) if r == front => 1,
+                (r, 2..=3) if r == trail => 2,
+                _ => 0,
+            };
+            for row in 0..8 {
+                assert_eq!(board.get(row, col), Some(expected(row)), "{}", generation);
+            }
+        }
+        assert_eq!(board.population(), 2);
+    }
+
+    // Round the torus and back where it started
+    assert_eq!(to_rows(&board), to_rows(&start));
+}
+
+#[test]
+fn two_state_generations_rules_match_the_boolean_engine() {
+    let mut rng = SmallRng::seed_from_u64(695);
+    let rules = [
+        ("23/3/2", "B3/S23"),
+        ("23/36/2", "B36/S23"),
+        ("/2/2", "B2/S"),
+    ];
+
+    for (generations_rule, rule) in rules {
+        let rule = Rule::parse(rule).unwrap();
+        let generations_rule = GenerationsRule::parse(generations_rule).unwrap();
+        assert_eq!(generations_rule, GenerationsRule::from_rule(rule, 2));
+
+        for boundary in [BoundaryMode::Wrap, BoundaryMode::Dead, BoundaryMode::Mirror] {
+            let mut board = LifeBoard::new(27, 19);
+            board.set_boundary(boundary);
+            board.set_rule(rule);
+            board.randomize(0.4, &mut rng).unwrap();
+            let mut multi = GenerationsBoard::from_board(&board, generations_rule);
+
+            for generation in 1..=40 {
+                board.advance();
+                multi.advance();
+                let mut stepped = multi.to_board();
+                stepped.set_rule(rule);
+                assert_eq!(stepped, board, "{} {:?} at {}", rule, boundary, generation);
+                assert_eq!(multi.population(), board.population());
+                assert!((0..27).all(|col| (0..19).all(|row| multi.get(row, col) < Some(2))));
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{patterns, BoundaryMode, HashLifeError, HashLifeUniverse, LifeBoard, Rect, Rule};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+fn glider_universe() -> HashLifeUniverse {
+    let mut universe = HashLifeUniverse::new();
+    for &(row, col) in patterns::glider().cells() {
+        universe.set_cell(row as i64, col as i64, true).unwrap();
+    }
+    universe
+}
+
+// Whether the universe holds exactly the glider moved `offset` cells down
+// and right
+fn is_glider_at(universe: &HashLifeUniverse, offset: i64) -> bool {
+    universe.population() == 5
+        && patterns::glider()
+            .cells()
+            .iter()
+            .all(|&(row, col)| universe.get_cell(row as i64 + offset, col as i64 + offset))
+}
+
+#[test]
+fn hashlife_matches_a_dense_board_for_hundreds_of_generations() {
+    // Nothing can spread further than a cell a generation, so a dense board
+    // with dead edges that far away behaves as an unbounded one
+    const GENERATIONS: u64 = 200;
+    const MARGIN: usize = GENERATIONS as usize + 1;
+
+    for seed in 0..2 {
+        let mut dense = LifeBoard::new_square(16 + 2 * MARGIN);
+        dense.set_boundary(BoundaryMode::Dead);
+        let soup = Rect {
+            row: MARGIN,
+            col: MARGIN,
+            width: 16,
+            height: 16,
+        };
+        dense
+            .randomize_region(soup, 0.4, &mut SmallRng::seed_from_u64(seed))
+            .unwrap();
+        let mut universe = HashLifeUniverse::from_board(&dense).unwrap();
+
+        // Steps of 1, 2, 4 and 8 generations in turn
+        let mut log2 = 0;
+        while universe.generation() < GENERATIONS {
+            universe.advance(log2).unwrap();
+            dense.advance_by(1 << log2);
+            log2 = (log2 + 1) % 4;
+
+            let generation = universe.generation();
+            assert_eq!(dense.generation(), generation);
+            assert_eq!(
+                universe.population(),
+                dense.population() as u64,
+                "{}",
+                generation
+            );
+            for (row, col) in dense.live_cells() {
+                assert!(universe.get_cell(row as i64, col as i64), "{}", generation);
+            }
+        }
+    }
+}
+
+#[test]
+fn glider_travels_2_pow_18_cells_in_2_pow_20_generations() {
+    // A glider moves one cell down and right every 4 generations
+    let mut universe = glider_universe();
+    universe.advance(20).unwrap();
+    assert_eq!(universe.generation(), 1 << 20);
+    assert!(is_glider_at(&universe, 1 << 18));
+
+    // and the same taken in smaller steps
+    let mut universe = glider_universe();
+    for log2 in (0..20).rev() {
+        universe.advance(log2).unwrap();
+    }
+    universe.advance(0).unwrap();
+    assert_eq!(universe.generation(), 1 << 20);
+    assert!(is_glider_at(&universe, 1 << 18));
+
+    let board = universe.to_board((1 << 18) - 1, (1 << 18) - 1, 5, 5);
+    let mut expected = LifeBoard::new_square(5);
+    expected.stamp(&patterns::glider(), 1, 1).unwrap();
+    assert_eq!(board, expected);
+}
+
+#[test]
+fn advancing_too_far_is_rejected() {
+    let mut universe = glider_universe();
+    for log2 in [61, 64, 200, 255] {
+        assert_eq!(
+            universe.advance(log2),
+            Err(HashLifeError::TooFar {
+                log2_generations: log2
+            })
+        );
+        assert_eq!(universe.generation(), 0);
+        assert!(is_glider_at(&universe, 0));
+    }
+
+    // The glider gets 2^58 cells further out with every 2^60 generations,
+    // until it would pass 2^62 cells
+    let mut steps = 0;
+    let error = loop {
+        match universe.advance(60) {
+            Ok(()) => steps += 1,
+            Err(error) => break error,
+        }
+    };
+    assert_eq!(
+        error,
+        HashLifeError::TooFar {
+            log2_generations: 60
+        }
+    );
+    assert!(steps >= 1);
+    assert_eq!(universe.generation(), steps << 60);
+    assert!(is_glider_at(&universe, (steps << 58) as i64));
+    assert_eq!(
+        error.to_string(),
+        "cannot advance 2^60 generations from here"
+    );
+}
+
+#[test]
+fn cells_from_2_pow_62_out_are_rejected() {
+    let edge = 1i64 << 62;
+    let mut universe = HashLifeUniverse::new();
+    for (row, col) in [(edge - 1, 0), (-edge, edge - 1), (0, -edge)] {
+        universe.set_cell(row, col, true).unwrap();
+        assert!(universe.get_cell(row, col));
+    }
+    assert_eq!(universe.population(), 3);
+
+    for (row, col) in [(edge, 0), (0, -edge - 1), (i64::MAX, i64::MIN)] {
+        let error = universe.set_cell(row, col, true).unwrap_err();
+        assert_eq!(error, HashLifeError::OutOfRange { row, col });
+        assert!(!universe.get_cell(row, col));
+    }
+    assert_eq!(universe.population(), 3);
+    assert_eq!(
+        HashLifeError::OutOfRange { row: edge, col: 0 }.to_string(),
+        "cell (4611686018427387904, 0) is out of range"
+    );
+
+    // Regions running past i64::MAX stop there
+    let board = universe.to_board(edge - 2, i64::MAX - 1, 4, 4);
+    assert_eq!(board.live_cells().count(), 0);
+    let board = universe.to_board(edge - 2, -2, 4, 4);
+    assert_eq!(board.live_cells().collect::<Vec<_>>(), vec![(1, 2)]);
+}
+
+#[test]
+fn boards_with_other_rules_are_rejected() {
+    let mut board = LifeBoard::new_square(8);
+    board.stamp(&patterns::glider(), 2, 2).unwrap();
+    let highlife = Rule::parse("B36/S23").unwrap();
+    board.set_rule(highlife);
+
+    let error = HashLifeUniverse::from_board(&board).unwrap_err();
+    assert_eq!(error, HashLifeError::UnsupportedRule(highlife));
+    assert_eq!(
+        error.to_string(),
+        "rule B36/S23 is not supported, only B3/S23"
+    );
+
+    board.set_rule(Rule::parse("B3/S23").unwrap());
+    let universe = HashLifeUniverse::from_board(&board).unwrap();
+    assert_eq!(universe.population(), 5);
+}
@@ -0,0 +1,153 @@
+extern crate gameoflife;
+
+use gameoflife::{patterns, Anchor, LifeBoard, Rect};
+
+// A blinker with a block well away from it, tracking heat
+fn board() -> LifeBoard {
+    let mut board = LifeBoard::new(12, 5);
+    board.stamp(&patterns::blinker(), 2, 1).unwrap();
+    board.stamp(&patterns::block(), 1, 7).unwrap();
+    board.enable_heat();
+    board
+}
+
+// Heat of every cell with a count, as (row, column, count)
+fn hot_cells(board: &LifeBoard) -> Vec<(usize, usize, u32)> {
+    board
+        .heat()
+        .iter()
+        .enumerate()
+        .filter(|&(_, &count)| count > 0)
+        .map(|(index, &count)| (index / board.width(), index % board.width(), count))
+        .collect()
+}
+
+// The four cells a blinker at rows 1 to 3, column 2 flips every generation
+fn blinker_heat(generations: u32) -> Vec<(usize, usize, u32)> {
+    vec![
+        (1, 2, generations),
+        (2, 1, generations),
+        (2, 3, generations),
+        (3, 2, generations),
+    ]
+}
+
+#[test]
+fn blinker_heats_its_four_changing_cells() {
+    let mut board = board();
+    assert_eq!(board.heat(), &[0; 60][..]);
+
+    for generation in 1..=6 {
+        board.advance();
+        assert_eq!(hot_cells(&board), blinker_heat(generation));
+    }
+
+    // The block never changes
+    for &(row, col) in &[(1, 7), (1, 8), (2, 7), (2, 8)] {
+        assert_eq!(board.heat()[row * 12 + col], 0);
+    }
+}
+
+#[test]
+fn every_stepper_counts_the_same() {
+    let mut advanced = board();
+    advanced.advance_by(5);
+
+    let mut active = board();
+    let mut stepped = board();
+    let mut next = LifeBoard::new(12, 5);
+    for _ in 0..5 {
+        active.advance_active();
+        stepped.step(&mut next).unwrap();
+        std::mem::swap(&mut stepped, &mut next);
+    }
+    assert_eq!(active.heat(), advanced.heat());
+    assert_eq!(stepped.heat(), advanced.heat());
+
+    #[cfg(feature = "simd")]
+    {
+        let mut simd = board();
+        for _ in 0..5 {
+            simd.simd_advance();
+        }
+        assert_eq!(simd.heat(), advanced.heat());
+    }
+    #[cfg(feature = "rayon")]
+    {
+        let mut parallel = board();
+        for _ in 0..5 {
+            parallel.par_advance();
+        }
+        assert_eq!(parallel.heat(), advanced.heat());
+    }
+}
+
+#[test]
+fn heat_does_not_change_the_run() {
+    let mut tracked = board();
+    let mut untracked = board();
+    untracked.disable_heat();
+    tracked.advance_by(7);
+    untracked.advance_by(7);
+
+    assert_eq!(tracked, untracked);
+    assert!(untracked.heat().is_empty());
+}
+
+#[test]
+fn reset_starts_counting_again() {
+    let mut board = board();
+    board.advance_by(3);
+    board.reset_heat();
+    assert!(hot_cells(&board).is_empty());
+
+    board.advance_by(2);
+    assert_eq!(hot_cells(&board), blinker_heat(2));
+}
+
+#[test]
+fn heat_follows_cells_when_the_board_changes_shape() {
+    let mut board = board();
+    board.advance_by(2);
+
+    let cropped = board.crop(Rect {
+        row: 1,
+        col: 1,
+        width: 3,
+        height: 3,
+    });
+    assert_eq!(cropped.heat(), &[0, 2, 0, 2, 0, 2, 0, 2, 0][..]);
+
+    board.resize(14, 7, Anchor::Center);
+    let moved: Vec<_> = blinker_heat(2)
+        .into_iter()
+        .map(|(row, col, count)| (row + 1, col + 1, count))
+        .collect();
+    assert_eq!(hot_cells(&board), moved);
+}
+
+#[test]
+fn heat_exports() {
+    let mut board = board();
+    board.advance_by(2);
+
+    let mut pgm = b"P5\n12 5\n255\n".to_vec();
+    let mut levels = vec![0u8; 60];
+    for (row, col, _) in blinker_heat(2) {
+        levels[row * 12 + col] = 255;
+    }
+    pgm.extend(levels);
+    assert_eq!(board.heat_to_pgm(), pgm);
+
+    assert_eq!(
+        board.heat_to_csv(),
+        "0,0,0,0,0,0,0,0,0,0,0,0\n\
+         0,0,2,0,0,0,0,0,0,0,0,0\n\
+         0,2,0,2,0,0,0,0,0,0,0,0\n\
+         0,0,2,0,0,0,0,0,0,0,0,0\n\
+         0,0,0,0,0,0,0,0,0,0,0,0\n"
+    );
+
+    board.disable_heat();
+    assert_eq!(board.heat_to_csv(), "0,0,0,0,0,0,0,0,0,0,0,0\n".repeat(5));
+}
@@ -0,0 +1,134 @@
+extern crate gameoflife;
+
+use gameoflife::{patterns, sparkline, LifeBoard, PopulationHistory, PopulationSample};
+
+fn blinker() -> LifeBoard {
+    let mut board = LifeBoard::new(5, 5);
+    board.stamp(&patterns::blinker(), 1, 2).unwrap();
+    board
+}
+
+#[test]
+fn blinker_history_is_constant() {
+    // Observed until the blinker repeats
+    let mut observed = PopulationHistory::new();
+    blinker().run_observed(10, &mut observed);
+    assert_eq!(
+        observed.samples(),
+        &[
+            PopulationSample {
+                generation: 0,
+                population: 3,
+                births: 0,
+                deaths: 0
+            },
+            PopulationSample {
+                generation: 1,
+                population: 3,
+                births: 2,
+                deaths: 2
+            },
+            PopulationSample {
+                generation: 2,
+                population: 3,
+                births: 2,
+                deaths: 2
+            },
+        ][..]
+    );
+
+    // Recorded by hand for longer
+    let mut board = blinker();
+    let mut history = PopulationHistory::new();
+    history.record(0, board.population(), 0, 0);
+    for _ in 0..20 {
+        let previous = board.clone();
+        board.advance();
+        let stats = board.stats_since(&previous);
+        history.record(
+            board.generation(),
+            stats.population,
+            stats.births.unwrap(),
+            stats.deaths.unwrap(),
+        );
+    }
+    assert_eq!(history.samples().len(), 21);
+    assert!(history
+        .samples()
+        .iter()
+        .all(|sample| sample.population == 3));
+    assert_eq!(history.sparkline(80), "█".repeat(21));
+}
+
+#[test]
+fn csv_reads_back() {
+    let mut history = PopulationHistory::new();
+    blinker().run_observed(10, &mut history);
+    let mut csv = Vec::new();
+    history.to_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("generation,population,births,deaths"));
+    let samples: Vec<PopulationSample> = lines
+        .map(|line| {
+            let fields: Vec<u64> = line
+                .split(',')
+                .map(|field| field.parse().unwrap())
+                .collect();
+            PopulationSample {
+                generation: fields[0],
+                population: fields[1] as usize,
+                births: fields[2] as usize,
+                deaths: fields[3] as usize,
+            }
+        })
+        .collect();
+    assert_eq!(samples, history.samples());
+}
+
+#[test]
+fn sparkline_scales_to_the_largest_value() {
+    let rising: Vec<f64> = (0..8).map(f64::from).collect();
+    assert_eq!(sparkline(&rising), "▁▂▃▄▅▆▇█");
+    assert_eq!(sparkline(&[2.0, 4.0, 0.0, 1.0]), "▅█▁▃");
+    assert_eq!(sparkline(&[3.0, 3.0]), "██");
+    assert_eq!(sparkline(&[0.0, 0.0]), "▁▁");
+    assert_eq!(sparkline(&[]), "");
+}
+
+#[test]
+fn long_histories_are_averaged_into_the_width() {
+    let mut history = PopulationHistory::new();
+    for (generation, &population) in [0, 0, 7, 7, 14, 14].iter().enumerate() {
+        history.record(generation as u64, population, 0, 0);
+    }
+
+    assert_eq!(history.sparkline(3), "▁▅█");
+    assert_eq!(history.sparkline(6), "▁▁▅▅██");
+    assert_eq!(history.sparkline(60), "▁▁▅▅██");
+}
+
+#[test]
+fn limited_history_keeps_every_other_sample() {
+    let mut history = PopulationHistory::with_limit(4);
+    for generation in 0..10 {
+        history.record(generation, generation as usize, 1, 0);
+    }
+
+    // Halved at generations 4 and 8. Births of dropped generations go to the
+    // next sample kept, and generation 9's wait for the next one.
+    let kept: Vec<(u64, usize)> = history
+        .samples()
+        .iter()
+        .map(|sample| (sample.generation, sample.births))
+        .collect();
+    assert_eq!(kept, vec![(0, 1), (4, 4), (8, 4)]);
+    assert_eq!(history.stride(), 4);
+}
+
+#[test]
+#[should_panic(expected = "at least two samples")]
+fn history_must_keep_two_samples() {
+    PopulationHistory::with_limit(1);
+}
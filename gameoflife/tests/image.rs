@@ -0,0 +1,129 @@
+#![cfg(feature = "image")]
+
+extern crate gameoflife;
+extern crate image;
+
+use gameoflife::{ImageError, LifeBoard};
+use image::{DynamicImage, GrayImage, ImageFormat, Luma, Rgba, RgbaImage};
+
+fn png(image: impl Into<DynamicImage>) -> Vec<u8> {
+    let mut png = std::io::Cursor::new(Vec::new());
+    image.into().write_to(&mut png, ImageFormat::Png).unwrap();
+    png.into_inner()
+}
+
+fn live_cells(board: &LifeBoard) -> Vec<(usize, usize)> {
+    board.live_cells().collect()
+}
+
+#[test]
+fn pixels_darker_than_the_threshold_are_alive() {
+    #[rustfmt::skip]
+    let levels = [
+        0, 64, 128, 255,
+        255, 127, 128, 10,
+        200, 0, 0, 200,
+        129, 128, 127, 126,
+    ];
+    let image = GrayImage::from_fn(4, 4, |x, y| Luma([levels[(y * 4 + x) as usize]]));
+
+    let board = LifeBoard::from_image_bytes(&png(image), 128, None).unwrap();
+    assert_eq!((board.width(), board.height()), (4, 4));
+    assert_eq!(
+        live_cells(&board),
+        vec![
+            (0, 0),
+            (0, 1),
+            (1, 1),
+            (1, 3),
+            (2, 1),
+            (2, 2),
+            (3, 2),
+            (3, 3)
+        ]
+    );
+}
+
+#[test]
+fn color_and_transparency_go_through_gray() {
+    // Dark red, pale yellow, transparent black and half transparent black
+    let pixels = [
+        Rgba([120, 0, 0, 255]),
+        Rgba([250, 250, 200, 255]),
+        Rgba([0, 0, 0, 0]),
+        Rgba([0, 0, 0, 128]),
+    ];
+    let image = RgbaImage::from_fn(4, 1, |x, _| pixels[x as usize]);
+
+    // Half transparent black over white is a gray of 127
+    let board = LifeBoard::from_image_bytes(&png(image.clone()), 128, None).unwrap();
+    assert_eq!(live_cells(&board), vec![(0, 0), (0, 3)]);
+    let board = LifeBoard::from_image_bytes(&png(image), 127, None).unwrap();
+    assert_eq!(live_cells(&board), vec![(0, 0)]);
+}
+
+#[test]
+fn images_scale_down_to_the_board() {
+    // Blocks of two by two pixels, dark on the diagonal
+    let image = GrayImage::from_fn(4, 4, |x, y| Luma([if x / 2 == y / 2 { 0 } else { 255 }]));
+
+    let board = LifeBoard::from_image_bytes(&png(image), 128, Some((2, 2))).unwrap();
+    assert_eq!((board.width(), board.height()), (2, 2));
+    assert_eq!(live_cells(&board), vec![(0, 0), (1, 1)]);
+}
+
+#[test]
+fn files_are_read() {
+    let path = std::env::temp_dir().join(format!("gameoflife-{}.png", std::process::id()));
+    std::fs::write(
+        &path,
+        png(GrayImage::from_fn(3, 2, |x, _| Luma([x as u8 * 100]))),
+    )
+    .unwrap();
+
+    let board = LifeBoard::from_image(&path, 150);
+    let scaled = LifeBoard::from_image_scaled(&path, 150, 1, 1);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        live_cells(&board.unwrap()),
+        vec![(0, 0), (0, 1), (1, 0), (1, 1)]
+    );
+    assert_eq!(live_cells(&scaled.unwrap()), vec![(0, 0)]);
+}
+
+#[test]
+fn bad_images_and_settings_are_rejected() {
+    let image = png(GrayImage::new(4, 4));
+
+    assert!(matches!(
+        LifeBoard::from_image_bytes(&image, 0, None),
+        Err(ImageError::InvalidThreshold)
+    ));
+    assert!(matches!(
+        LifeBoard::from_image_bytes(&image, 128, Some((0, 4))),
+        Err(ImageError::InvalidSize {
+            width: 0,
+            height: 4
+        })
+    ));
+    assert!(matches!(
+        LifeBoard::from_image_bytes(&image, 128, Some((4, 5000))),
+        Err(ImageError::InvalidSize { .. })
+    ));
+    assert!(matches!(
+        LifeBoard::from_image_bytes(b"not an image", 128, None),
+        Err(ImageError::Decode(_))
+    ));
+    assert!(matches!(
+        LifeBoard::from_image_bytes(&png(GrayImage::new(4097, 1)), 128, Some((2, 1))),
+        Err(ImageError::TooLarge {
+            width: 4097,
+            height: 1
+        })
+    ));
+    assert!(matches!(
+        LifeBoard::from_image("no/such/image.png", 128),
+        Err(ImageError::Io(_))
+    ));
+}
@@ -0,0 +1,93 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{BoundaryMode, CharSet, ImmigrationBoard, ImmigrationCell, LifeBoard, Rect};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ImmigrationCell::{Dead, A, B};
+
+#[test]
+fn pure_a_board_steps_like_life() {
+    for (seed, &boundary) in [BoundaryMode::Wrap, BoundaryMode::Dead, BoundaryMode::Mirror]
+        .iter()
+        .enumerate()
+    {
+        let mut life = LifeBoard::new(30, 20);
+        life.set_boundary(boundary);
+        life.randomize(0.4, &mut StdRng::seed_from_u64(seed as u64))
+            .unwrap();
+        let mut immigration = ImmigrationBoard::from_board(&life, A);
+
+        for _ in 0..60 {
+            life.advance();
+            immigration.advance();
+            assert_eq!(immigration, ImmigrationBoard::from_board(&life, A));
+            assert_eq!(immigration.populations(), (life.population(), 0));
+        }
+    }
+}
+
+#[test]
+fn newborn_takes_the_majority_species() {
+    // Parents A, A, B around the dead centre cell
+    let mut board = ImmigrationBoard::new(5, 5);
+    board.set(1, 1, A).unwrap();
+    board.set(1, 3, A).unwrap();
+    board.set(3, 2, B).unwrap();
+
+    board.advance();
+    assert_eq!(board.get(2, 2), Some(A));
+
+    // The same parents with the species swapped
+    let mut board = ImmigrationBoard::new(5, 5);
+    board.set(1, 1, B).unwrap();
+    board.set(1, 3, B).unwrap();
+    board.set(3, 2, A).unwrap();
+
+    board.advance();
+    assert_eq!(board.get(2, 2), Some(B));
+}
+
+#[test]
+fn survivors_keep_their_species() {
+    // Block of mixed species, a still life
+    let mut board = ImmigrationBoard::new(4, 4);
+    board.set(1, 1, A).unwrap();
+    board.set(1, 2, B).unwrap();
+    board.set(2, 1, B).unwrap();
+    board.set(2, 2, A).unwrap();
+    let start = board.clone();
+
+    board.advance_by(5);
+    assert_eq!(board.to_board(), start.to_board());
+    assert_eq!(board.get(1, 2), Some(B));
+    assert_eq!(board.get(2, 2), Some(A));
+    assert_eq!(board.populations(), (2, 2));
+    assert_eq!(board.get(0, 0), Some(Dead));
+}
+
+#[test]
+fn seeded_soups_and_rendering() {
+    let mut board = ImmigrationBoard::new(3, 2);
+    let mut rng = StdRng::seed_from_u64(1);
+    let left = Rect {
+        row: 0,
+        col: 0,
+        width: 2,
+        height: 2,
+    };
+    let right = Rect { col: 2, ..left };
+    board.randomize_region(left, 1.0, A, &mut rng).unwrap();
+    board.randomize_region(right, 1.0, B, &mut rng).unwrap();
+    assert!(board.randomize_region(right, 2.0, B, &mut rng).is_err());
+
+    assert_eq!(board.populations(), (4, 2));
+    assert_eq!(
+        board.to_string(),
+        "+--------+\n| # # o  |\n| # # o  |\n+--------+"
+    );
+    let mut out = String::new();
+    board.render_to(&mut out, &CharSet::ASCII_COLOR).unwrap();
+    assert!(out.contains(CharSet::ASCII_COLOR.rival));
+}
@@ -0,0 +1,157 @@
+extern crate gameoflife;
+
+use gameoflife::interactive::{Command, Key, Session};
+
+// Feeds `keys` through a fresh session on a `width` by `height` board,
+// returning the final session and every command produced
+fn press(keys: &[Key], width: usize, height: usize) -> (Session, Vec<Command>) {
+    let mut session = Session::default();
+    let mut commands = Vec::new();
+    for &key in keys {
+        let (next, command) = session.handle_key(key, width, height);
+        session = next;
+        commands.extend(command);
+    }
+    (session, commands)
+}
+
+#[test]
+fn space_pauses_and_resumes() {
+    let (session, commands) = press(&[Key::Char(' ')], 10, 10);
+    assert!(session.paused);
+    assert!(commands.is_empty());
+
+    let (session, _) = press(&[Key::Char(' '), Key::Char(' ')], 10, 10);
+    assert!(!session.paused);
+}
+
+#[test]
+fn n_steps_only_while_paused() {
+    let (_, commands) = press(&[Key::Char('n')], 10, 10);
+    assert!(commands.is_empty());
+
+    let (session, commands) = press(&[Key::Char(' '), Key::Char('n'), Key::Char('n')], 10, 10);
+    assert!(session.paused);
+    assert_eq!(commands, vec![Command::Step, Command::Step]);
+
+    // Resuming stops single steps again
+    let (_, commands) = press(&[Key::Char(' '), Key::Char(' '), Key::Char('n')], 10, 10);
+    assert!(commands.is_empty());
+}
+
+#[test]
+fn arrows_move_the_cursor_within_the_board() {
+    let (session, commands) = press(&[Key::Down, Key::Down, Key::Right], 4, 3);
+    assert_eq!((session.cursor_row, session.cursor_col), (2, 1));
+    assert!(commands.is_empty());
+
+    // Held against every edge in turn
+    let (session, _) = press(&[Key::Up, Key::Left], 4, 3);
+    assert_eq!((session.cursor_row, session.cursor_col), (0, 0));
+    let (session, _) = press(&[Key::Down; 5], 4, 3);
+    assert_eq!(session.cursor_row, 2);
+    let (session, _) = press(&[Key::Right; 9], 4, 3);
+    assert_eq!(session.cursor_col, 3);
+
+    let (session, _) = press(
+        &[Key::Down, Key::Right, Key::Up, Key::Left, Key::Right],
+        4,
+        3,
+    );
+    assert_eq!((session.cursor_row, session.cursor_col), (0, 1));
+}
+
+#[test]
+fn enter_toggles_the_cell_under_the_cursor() {
+    let (_, commands) = press(&[Key::Enter], 4, 3);
+    assert_eq!(commands, vec![Command::Toggle { row: 0, col: 0 }]);
+
+    let (_, commands) = press(
+        &[Key::Right, Key::Right, Key::Down, Key::Enter, Key::Enter],
+        4,
+        3,
+    );
+    assert_eq!(
+        commands,
+        vec![
+            Command::Toggle { row: 1, col: 2 },
+            Command::Toggle { row: 1, col: 2 }
+        ]
+    );
+
+    // A cursor left outside a board that has since shrunk toggles nothing
+    let session = Session {
+        cursor_row: 5,
+        cursor_col: 1,
+        ..Session::default()
+    };
+    assert_eq!(session.handle_key(Key::Enter, 4, 3), (session, None));
+    // and moving it brings it back onto the board
+    let (session, _) = session.handle_key(Key::Down, 4, 3);
+    assert_eq!(session.cursor_row, 2);
+}
+
+#[test]
+fn r_c_and_q_work_paused_or_not() {
+    for paused in [false, true] {
+        let session = Session {
+            paused,
+            ..Session::default()
+        };
+        for &(key, command) in &[
+            ('r', Command::Randomize),
+            ('c', Command::Clear),
+            ('q', Command::Quit),
+        ] {
+            assert_eq!(
+                session.handle_key(Key::Char(key), 10, 10),
+                (session, Some(command))
+            );
+        }
+    }
+}
+
+#[test]
+fn other_keys_are_ignored() {
+    let session = Session {
+        paused: true,
+        cursor_row: 3,
+        cursor_col: 4,
+    };
+    for key in ['x', 'Q', 'N', '1', '\t', 'é'] {
+        assert_eq!(session.handle_key(Key::Char(key), 10, 10), (session, None));
+    }
+}
+
+#[test]
+fn a_session_edits_a_paused_board() {
+    let keys = [
+        Key::Char(' '),
+        Key::Down,
+        Key::Right,
+        Key::Enter,
+        Key::Right,
+        Key::Enter,
+        Key::Char('n'),
+        Key::Char('c'),
+        Key::Char(' '),
+        Key::Char('n'),
+        Key::Char('r'),
+        Key::Char('q'),
+    ];
+    let (session, commands) = press(&keys, 8, 8);
+
+    assert_eq!(
+        commands,
+        vec![
+            Command::Toggle { row: 1, col: 1 },
+            Command::Toggle { row: 1, col: 2 },
+            Command::Step,
+            Command::Clear,
+            Command::Randomize,
+            Command::Quit,
+        ]
+    );
+    assert!(!session.paused);
+    assert_eq!((session.cursor_row, session.cursor_col), (1, 2));
+}
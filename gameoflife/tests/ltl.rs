@@ -0,0 +1,143 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{BoundaryMode, LifeBoard, LtlRule};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+const BOUNDARIES: [BoundaryMode; 5] = [
+    BoundaryMode::Wrap,
+    BoundaryMode::Dead,
+    BoundaryMode::Mirror,
+    BoundaryMode::ShiftedTorus { offset: 2 },
+    BoundaryMode::KleinBottle,
+];
+
+fn random_board(rng: &mut SmallRng, boundary: BoundaryMode) -> LifeBoard {
+    let mut board = LifeBoard::new(rng.gen_range(1..30), rng.gen_range(1..30));
+    board.set_boundary(boundary);
+    board.randomize(rng.gen_range(0.1..0.6), rng).unwrap();
+    board
+}
+
+// Counts the square around every cell one cell at a time
+fn brute_force_counts(board: &LifeBoard, radius: isize) -> Vec<u32> {
+    let (width, height) = (board.width() as isize, board.height() as isize);
+    let mut counts = Vec::new();
+    for row in 0..height {
+        for col in 0..width {
+            let mut count = 0;
+            for dr in -radius..=radius {
+                for dc in -radius..=radius {
+                    let (r, c) = (row + dr, col + dc);
+                    let alive = match board.boundary() {
+                        BoundaryMode::Wrap => {
+                            board[r.rem_euclid(height) as usize][c.rem_euclid(width) as usize]
+                        }
+                        _ => {
+                            r >= 0 && r < height && c >= 0 && c < width && {
+                                board[r as usize][c as usize]
+                            }
+                        }
+                    };
+                    count += ((dr, dc) != (0, 0) && alive) as u32;
+                }
+            }
+            counts.push(count);
+        }
+    }
+    counts
+}
+
+#[test]
+fn radius_one_life_matches_the_engine() {
+    let mut rng = SmallRng::seed_from_u64(21);
+    for &boundary in BOUNDARIES.iter().cycle().take(20) {
+        let mut board = random_board(&mut rng, boundary);
+        let mut ltl = board.clone();
+        for _ in 0..10 {
+            board.advance();
+            ltl.advance_ltl(&LtlRule::life());
+            assert_eq!(ltl, board, "{:?}", boundary);
+        }
+    }
+}
+
+#[test]
+fn radius_one_counts_are_the_neighbour_counts() {
+    let mut rng = SmallRng::seed_from_u64(4);
+    for &boundary in &BOUNDARIES {
+        let board = random_board(&mut rng, boundary);
+        let counts: Vec<u32> = (0..board.height())
+            .flat_map(|row| (0..board.width()).map(move |col| (row, col)))
+            .map(|(row, col)| board.live_neighbour_count(row, col) as u32)
+            .collect();
+        assert_eq!(board.neighbour_counts_within(1), counts, "{:?}", boundary);
+    }
+}
+
+#[test]
+fn box_sums_match_brute_force_at_radius_three() {
+    let mut rng = SmallRng::seed_from_u64(8);
+    for seed in 0..20 {
+        let boundary = [BoundaryMode::Wrap, BoundaryMode::Dead][seed % 2];
+        let board = random_board(&mut rng, boundary);
+        assert_eq!(
+            board.neighbour_counts_within(3),
+            brute_force_counts(&board, 3),
+            "{}x{} {:?}",
+            board.width(),
+            board.height(),
+            boundary
+        );
+    }
+
+    // A torus smaller than the square counts cells more than once
+    let mut board = LifeBoard::new(2, 2);
+    board.set(0, 0, true).unwrap();
+    assert_eq!(
+        board.neighbour_counts_within(3),
+        brute_force_counts(&board, 3)
+    );
+    // Three rows and three columns of the square land on (0, 0)
+    assert_eq!(board.neighbour_counts_within(3)[0], 8);
+}
+
+#[test]
+fn radius_zero_counts_nothing() {
+    let mut board = LifeBoard::new(3, 3);
+    board.set(1, 1, true).unwrap();
+    assert_eq!(board.neighbour_counts_within(0), vec![0; 9]);
+}
+
+#[test]
+fn bugs_counts_the_cell_itself_in_survival() {
+    let bugs = LtlRule::bugs();
+    assert_eq!(bugs.radius, 5);
+    // Survival from 34 to 58 counting the cell is 33 to 57 around it
+    assert!(!bugs.applies(true, 32));
+    assert!(bugs.applies(true, 33));
+    assert!(bugs.applies(true, 57));
+    assert!(!bugs.applies(true, 58));
+    assert!(!bugs.applies(false, 33));
+    assert!(bugs.applies(false, 34));
+    assert!(bugs.applies(false, 45));
+    assert!(!bugs.applies(false, 46));
+}
+
+#[test]
+fn stepping_keeps_the_board_rule() {
+    let mut rng = SmallRng::seed_from_u64(2);
+    let board = random_board(&mut rng, BoundaryMode::Dead);
+    let mut next = LifeBoard::new(board.width(), board.height());
+    board.step_ltl(&LtlRule::bugs(), &mut next).unwrap();
+
+    let mut advanced = board.clone();
+    advanced.advance_ltl(&LtlRule::bugs());
+    assert_eq!(next, advanced);
+    assert_eq!(next.generation(), 1);
+    assert_eq!(next.rule(), board.rule());
+
+    let mut wrong = LifeBoard::new(board.width() + 1, board.height());
+    assert!(board.step_ltl(&LtlRule::life(), &mut wrong).is_err());
+}
@@ -0,0 +1,136 @@
+extern crate gameoflife;
+
+use gameoflife::{patterns, FormatError, HashLifeUniverse, Rule, SparseLifeBoard};
+
+const GLIDER_AND_BLOCK: &str = "[M2] (golly 4.2)
+#R B3/S23
+#C a glider and a block
+.*$..*$***$
+**$**$
+4 0 0 0 1
+4 2 0 0 0
+5 4 0 0 3
+";
+
+fn sorted(board: &SparseLifeBoard) -> Vec<(i64, i64)> {
+    let mut cells: Vec<_> = board.live_cells().collect();
+    cells.sort_unstable();
+    cells
+}
+
+#[test]
+fn hand_written_file_decodes_to_its_cells() {
+    let expected = vec![
+        (-16, -16),
+        (-16, -15),
+        (-15, -16),
+        (-15, -15),
+        (8, 9),
+        (9, 10),
+        (10, 8),
+        (10, 9),
+        (10, 10),
+    ];
+
+    let board = SparseLifeBoard::from_macrocell(GLIDER_AND_BLOCK).unwrap();
+    assert_eq!(sorted(&board), expected);
+    assert_eq!(board.rule(), Rule::conway());
+
+    let universe = HashLifeUniverse::from_macrocell(GLIDER_AND_BLOCK).unwrap();
+    assert_eq!(universe.population(), 9);
+    for &(row, col) in &expected {
+        assert!(universe.get_cell(row, col));
+    }
+}
+
+#[test]
+fn dangling_reference_names_the_line() {
+    let text = "[M2] (golly 4.2)\n#R B3/S23\n.*$..*$***$\n4 0 0 0 2\n";
+    let error = HashLifeUniverse::from_macrocell(text).unwrap_err();
+    assert_eq!(error, FormatError::UndefinedNode { line: 4, node: 2 });
+    assert_eq!(
+        error.to_string(),
+        "line 4: node 2 is not defined on an earlier line"
+    );
+    assert_eq!(SparseLifeBoard::from_macrocell(text).unwrap_err(), error);
+}
+
+#[test]
+fn malformed_files_are_rejected() {
+    let cases = [
+        ("4 0 0 0 1\n", FormatError::InvalidHeader),
+        (
+            "[M2]\n.*$\n5 1 0 0 0\n",
+            FormatError::LevelMismatch { line: 3, node: 1 },
+        ),
+        ("[M2]\n\n.*.x$\n", FormatError::InvalidNode(3)),
+        ("[M2]\n.........*$\n", FormatError::InvalidNode(2)),
+        ("[M2]\n.*$\n4 1 0 0\n", FormatError::InvalidNode(3)),
+        ("[M2]\n.*$\n3 1 0 0 0\n", FormatError::InvalidNode(3)),
+    ];
+    for (text, expected) in cases.iter() {
+        assert_eq!(
+            &SparseLifeBoard::from_macrocell(text).unwrap_err(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn other_rules_load_sparse_only() {
+    let text = "[M2]\n#R B36/S23\n.*$\n";
+    let board = SparseLifeBoard::from_macrocell(text).unwrap();
+    assert_eq!(board.rule(), Rule::parse("B36/S23").unwrap());
+    assert_eq!(sorted(&board), vec![(-4, -3)]);
+
+    assert_eq!(
+        HashLifeUniverse::from_macrocell(text).unwrap_err(),
+        FormatError::UnsupportedRule(Rule::parse("B36/S23").unwrap())
+    );
+}
+
+#[test]
+fn round_trip_preserves_the_pattern() {
+    let mut universe = HashLifeUniverse::new();
+    let mut sparse = SparseLifeBoard::new();
+    for &(row, col) in &[(-300, 40), (0, 0), (17, -90), (1000, 1000)] {
+        for &(r, c) in patterns::glider().cells() {
+            universe
+                .set_cell(row + r as i64, col + c as i64, true)
+                .unwrap();
+        }
+        sparse.stamp(&patterns::glider(), row, col);
+    }
+    universe.advance(4).unwrap();
+    sparse.advance_by(16);
+
+    let text = universe.to_macrocell();
+    assert!(text.starts_with("[M2]"));
+    let loaded = HashLifeUniverse::from_macrocell(&text).unwrap();
+    assert_eq!(loaded.population(), universe.population());
+    for (row, col) in sparse.live_cells() {
+        assert!(loaded.get_cell(row, col));
+    }
+    assert_eq!(
+        sorted(&SparseLifeBoard::from_macrocell(&text).unwrap()),
+        sorted(&sparse)
+    );
+    assert_eq!(loaded.to_macrocell(), text);
+}
+
+#[test]
+fn repeated_squares_are_written_once() {
+    let mut universe = HashLifeUniverse::new();
+    for copy in 0..64 {
+        universe.set_cell(0, copy * 8, true).unwrap();
+    }
+
+    let text = universe.to_macrocell();
+    assert_eq!(text.lines().filter(|line| line.starts_with('*')).count(), 1);
+    assert_eq!(
+        HashLifeUniverse::from_macrocell(&text)
+            .unwrap()
+            .population(),
+        64
+    );
+}
@@ -0,0 +1,102 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{patterns, BoundaryMode, LifeBoard, Rule};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn block_board(max_age: Option<u16>) -> LifeBoard {
+    let mut board = LifeBoard::new_square(6);
+    board.set_boundary(BoundaryMode::Dead);
+    board.stamp(&patterns::block(), 2, 2).unwrap();
+    board.set_max_age(max_age);
+    board
+}
+
+#[test]
+fn block_dies_the_generation_after_reaching_max_age() {
+    for max_age in 0..5 {
+        let mut board = block_board(Some(max_age));
+        assert!(board.tracks_ages());
+
+        for generation in 1..=max_age {
+            board.advance();
+            assert_eq!(board.population(), 4, "generation {}", generation);
+            assert_eq!(board.age(2, 2), Some(generation));
+        }
+        board.advance();
+        assert_eq!(board.population(), 0);
+        assert_eq!(board.generation(), max_age as u64 + 1);
+    }
+}
+
+#[test]
+fn every_stepper_applies_max_age() {
+    let mut advanced = block_board(Some(3));
+    let mut active = advanced.clone();
+    let mut stepped = advanced.clone();
+    let mut next = LifeBoard::new_square(6);
+
+    for _ in 0..6 {
+        advanced.advance();
+        active.advance_active();
+        let changes = stepped.step_diff(&mut next).unwrap();
+        std::mem::swap(&mut stepped, &mut next);
+
+        assert_eq!(active, advanced);
+        assert_eq!(stepped, advanced);
+        assert_eq!(stepped.max_age(), Some(3));
+        if stepped.generation() == 4 {
+            assert_eq!(changes.len(), 4);
+            assert!(changes.iter().all(|change| !change.alive));
+        }
+    }
+    assert_eq!(advanced.population(), 0);
+}
+
+#[test]
+fn max_age_composes_with_any_rule() {
+    for seed in 0..10 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut board = LifeBoard::new(rng.gen_range(1..30), rng.gen_range(1..30));
+        board.set_rule(
+            Rule::parse(["B3/S23", "B36/S23", "B3678/S34678"][seed as usize % 3]).unwrap(),
+        );
+        board.randomize(0.4, &mut rng).unwrap();
+        board.set_max_age(Some(rng.gen_range(0..4)));
+
+        let mut active = board.clone();
+        for _ in 0..20 {
+            board.advance();
+            active.advance_active();
+            assert_eq!(active, board);
+            for row in 0..board.height() {
+                for col in 0..board.width() {
+                    assert!(board
+                        .age(row, col)
+                        .is_none_or(|age| Some(age) <= board.max_age()));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn no_max_age_matches_unmodified_engine() {
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut board = LifeBoard::new(rng.gen_range(1..40), rng.gen_range(1..40));
+        board.set_boundary([BoundaryMode::Wrap, BoundaryMode::Dead][seed as usize % 2]);
+        board.randomize(0.4, &mut rng).unwrap();
+
+        let mut modified = board.clone();
+        modified.enable_ages();
+        modified.set_max_age(Some(2));
+        modified.set_max_age(None);
+        for _ in 0..30 {
+            board.advance();
+            modified.advance();
+            assert_eq!(modified, board);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{patterns, BoundaryMode, LifeBoard, Noise, ProbabilityError, Rule};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn zero_noise_matches_advance() {
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut board = LifeBoard::new(rng.gen_range(1..40), rng.gen_range(1..40));
+        board.set_boundary([BoundaryMode::Wrap, BoundaryMode::Dead][seed as usize % 2]);
+        board.set_rule(Rule::parse(["B3/S23", "B36/S23", "B2/S"][seed as usize % 3]).unwrap());
+        board.randomize(0.4, &mut rng).unwrap();
+        board.enable_ages();
+
+        let mut noisy = board.clone();
+        let mut noise_rng = StdRng::seed_from_u64(seed);
+        for _ in 0..20 {
+            board.advance();
+            noisy.advance_noisy(Noise::default(), &mut noise_rng);
+            assert_eq!(noisy, board);
+            assert_eq!(noisy.generation(), board.generation());
+        }
+        // No random numbers were drawn
+        assert_eq!(noise_rng, StdRng::seed_from_u64(seed));
+    }
+}
+
+#[test]
+fn seeded_noise_is_reproducible() {
+    let mut board = LifeBoard::new_square(5);
+    board.set_boundary(BoundaryMode::Dead);
+    board.stamp(&patterns::blinker(), 2, 1).unwrap();
+    let noise = Noise::flip(0.1).unwrap();
+
+    let mut expected = board.clone();
+    expected.advance();
+
+    let mut rng = StdRng::seed_from_u64(7);
+    board.advance_noisy(noise, &mut rng);
+    // The vertical blinker, with (0, 0) and (2, 1) flipped alive and the
+    // centre (2, 2) flipped dead
+    assert_eq!(
+        expected.live_cells().collect::<Vec<_>>(),
+        vec![(1, 2), (2, 2), (3, 2)]
+    );
+    assert_eq!(
+        board.live_cells().collect::<Vec<_>>(),
+        vec![(0, 0), (1, 2), (2, 1), (3, 2)]
+    );
+    assert_eq!(board.generation(), 1);
+}
+
+#[test]
+fn certain_rain_fills_the_board() {
+    let mut board = LifeBoard::new(9, 7);
+    board.stamp(&patterns::glider(), 1, 1).unwrap();
+    let mut rng = StdRng::seed_from_u64(1);
+
+    board.advance_noisy(Noise::rain(1.0).unwrap(), &mut rng);
+    assert_eq!(board.population(), 63);
+    assert_eq!(board.generation(), 1);
+}
+
+#[test]
+fn rain_never_kills() {
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut board = LifeBoard::new(30, 20);
+    board.randomize(0.3, &mut rng).unwrap();
+    let rain = Noise::rain(0.05).unwrap();
+    assert_eq!((rain.birth(), rain.death()), (0.05, 0.0));
+
+    for _ in 0..10 {
+        let mut plain = board.clone();
+        plain.advance();
+        board.advance_noisy(rain, &mut rng);
+        assert!(plain.live_cells().all(|(row, col)| board[row][col]));
+    }
+}
+
+#[test]
+fn probabilities_are_checked() {
+    assert!(Noise::new(0.0, 1.0).is_ok());
+    assert_eq!(Noise::new(1.5, 0.0), Err(ProbabilityError(1.5)));
+    assert_eq!(Noise::flip(-0.1), Err(ProbabilityError(-0.1)));
+    assert!(Noise::flip(f64::NAN).is_err());
+    assert_eq!(Noise::rain(2.0), Err(ProbabilityError(2.0)));
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn rain_and_decay_options() {
+    use gameoflife::options::Options;
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    let options = Options::parse(args(&[])).unwrap();
+    assert!(options.noise.is_none());
+
+    let options = Options::parse(args(&["--rain", "0.01", "--decay", "0.5"])).unwrap();
+    assert_eq!(options.noise, Noise::new(0.01, 0.5).unwrap());
+    assert!(Options::parse(args(&["--rain", "1.5"])).is_err());
+    assert!(Options::parse(args(&["--decay", "-1"])).is_err());
+
+    // Seeded noise repeats, but not the soup's numbers
+    let seeded = Options::parse(args(&["--seed", "9"])).unwrap();
+    let draws = |mut rng: rand::rngs::SmallRng| (0..4).map(|_| rng.gen()).collect::<Vec<u64>>();
+    assert_eq!(draws(seeded.noise_rng()), draws(seeded.noise_rng()));
+    assert_ne!(draws(seeded.noise_rng()), draws(seeded.rng()));
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "native")]
+
+extern crate gameoflife;
+
+use std::path::PathBuf;
+
+use gameoflife::options::{Options, OptionsError, Start};
+use gameoflife::{patterns, LifeBoard, Rule};
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+fn parse(arguments: &[&str]) -> Result<Options, OptionsError> {
+    Options::parse(args(arguments))
+}
+
+#[test]
+fn defaults() {
+    let options = parse(&[]).unwrap();
+    assert_eq!(options, Options::default());
+    assert_eq!((options.width, options.height), (32, 32));
+    assert!(options.fit_terminal);
+    assert_eq!(options.start, Start::Soup { density: 0.5 });
+    assert_eq!(options.generations, 2000);
+    assert_eq!(options.delay_ms, 100);
+    assert_eq!(options.seed, None);
+    assert_eq!(options.rule, None);
+}
+
+#[test]
+fn values_are_parsed() {
+    let options = parse(&[
+        "--width",
+        "40",
+        "--height",
+        "12",
+        "--density",
+        "0.25",
+        "--generations",
+        "7",
+        "--delay-ms",
+        "5",
+        "--seed",
+        "99",
+        "--rule",
+        "B36/S23",
+        "--color",
+    ])
+    .unwrap();
+
+    assert_eq!((options.width, options.height), (40, 12));
+    assert!(!options.fit_terminal);
+    assert_eq!(options.start, Start::Soup { density: 0.25 });
+    assert_eq!(options.generations, 7);
+    assert_eq!(options.delay_ms, 5);
+    assert_eq!(options.seed, Some(99));
+    assert_eq!(options.rule, Some(Rule::parse("B36/S23").unwrap()));
+    assert!(options.color);
+
+    let options = parse(&["--pattern", "glider.rle"]).unwrap();
+    assert_eq!(options.start, Start::Pattern(PathBuf::from("glider.rle")));
+}
+
+#[test]
+fn bad_arguments_are_rejected() {
+    let rejected = |arguments: &[&str]| match parse(arguments) {
+        Err(e) => e.to_string(),
+        Ok(options) => panic!("{:?} parsed as {:?}", arguments, options),
+    };
+
+    assert_eq!(rejected(&["--bogus"]), "unknown option '--bogus'");
+    assert_eq!(rejected(&["--width"]), "missing value for '--width'");
+    assert_eq!(
+        rejected(&["--width", "wide"]),
+        "invalid value 'wide' for '--width'"
+    );
+    assert_eq!(
+        rejected(&["--density", "1.5"]),
+        "invalid value '1.5' for '--density'"
+    );
+    assert_eq!(
+        rejected(&["--seed", "-1"]),
+        "invalid value '-1' for '--seed'"
+    );
+    assert!(rejected(&["--rule", "B3"]).starts_with("invalid rule"));
+    assert!(matches!(parse(&["--help"]), Err(OptionsError::Help)));
+}
+
+#[test]
+fn pattern_and_density_are_mutually_exclusive() {
+    for arguments in [
+        ["--pattern", "glider.rle", "--density", "0.3"],
+        ["--density", "0.3", "--pattern", "glider.rle"],
+    ] {
+        assert!(matches!(
+            parse(&arguments),
+            Err(OptionsError::PatternWithDensity)
+        ));
+    }
+    assert_eq!(
+        OptionsError::PatternWithDensity.to_string(),
+        "'--pattern' and '--density' cannot be used together"
+    );
+}
+
+#[test]
+fn same_seed_builds_the_same_soup() {
+    let build = |seed: &str| {
+        parse(&["--width", "30", "--height", "20", "--seed", seed])
+            .unwrap()
+            .build_board()
+            .unwrap()
+    };
+
+    let board = build("42");
+    assert_eq!(build("42"), board);
+    assert_ne!(build("43"), board);
+    assert_eq!((board.width(), board.height()), (30, 20));
+}
+
+#[test]
+fn pattern_file_is_loaded_into_the_corner() {
+    let mut glider = LifeBoard::new_square(3);
+    glider.stamp(&patterns::glider(), 0, 0).unwrap();
+    let path = std::env::temp_dir().join(format!("gameoflife-options-{}.rle", std::process::id()));
+    std::fs::write(&path, glider.to_rle()).unwrap();
+
+    let options = parse(&[
+        "--pattern",
+        path.to_str().unwrap(),
+        "--width",
+        "10",
+        "--height",
+        "8",
+    ])
+    .unwrap();
+    let board = options.build_board();
+    std::fs::remove_file(&path).unwrap();
+
+    let board = board.unwrap();
+    assert_eq!((board.width(), board.height()), (10, 8));
+    assert_eq!(
+        board.live_cells().collect::<Vec<_>>(),
+        glider.live_cells().collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,57 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{BoundaryMode, LifeBoard, PackedLifeBoard, Rule};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+// Next generation found one cell at a time from the neighbours the board
+// reports for it
+fn naive_step(board: &LifeBoard) -> LifeBoard {
+    let rule = board.rule();
+    let mut next = board.clone();
+    for row in 0..board.height() {
+        for col in 0..board.width() {
+            let alive = board[(row, col)];
+            next[(row, col)] = rule.applies(alive, board.live_neighbour_count(row, col));
+        }
+    }
+    next
+}
+
+#[test]
+fn packed_matches_naive_stepping_on_random_boards() {
+    // Widths either side of the 64 cell word, and spanning several words
+    let sizes = [(1, 3), (63, 9), (64, 11), (65, 10), (130, 7), (200, 70)];
+    let boundaries = [BoundaryMode::Wrap, BoundaryMode::Dead, BoundaryMode::Mirror];
+    let rules = ["B3/S23", "B36/S23", "B2/S"];
+    let mut rng = SmallRng::seed_from_u64(64);
+
+    for &(width, height) in &sizes {
+        for &boundary in &boundaries {
+            for rule in rules {
+                let mut board = LifeBoard::new(width, height);
+                board.set_boundary(boundary);
+                board.set_rule(Rule::parse(rule).unwrap());
+                board.randomize(0.45, &mut rng).unwrap();
+                let mut packed = PackedLifeBoard::from_board(&board);
+
+                for generation in 1..=12 {
+                    board = naive_step(&board);
+                    packed.advance();
+                    assert_eq!(
+                        packed.to_board(),
+                        board,
+                        "{}x{} {:?} {} at {}",
+                        width,
+                        height,
+                        boundary,
+                        rule,
+                        generation
+                    );
+                    assert_eq!(packed.population(), board.population());
+                }
+            }
+        }
+    }
+}
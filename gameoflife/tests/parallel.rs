@@ -0,0 +1,47 @@
+#![cfg(feature = "rayon")]
+
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{BoundaryMode, LifeBoard};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+#[test]
+fn parallel_matches_serial_on_a_512_board_for_50_generations() {
+    for boundary in [BoundaryMode::Wrap, BoundaryMode::Dead] {
+        let mut serial = LifeBoard::new_square(512);
+        serial.set_boundary(boundary);
+        serial
+            .randomize(0.5, &mut SmallRng::seed_from_u64(512))
+            .unwrap();
+        let mut parallel = serial.clone();
+        let mut serial_next = serial.clone();
+        let mut parallel_next = serial.clone();
+
+        for generation in 1..=50 {
+            serial.step(&mut serial_next).unwrap();
+            parallel.par_step(&mut parallel_next).unwrap();
+            std::mem::swap(&mut serial, &mut serial_next);
+            std::mem::swap(&mut parallel, &mut parallel_next);
+            assert_eq!(parallel, serial, "{:?} step {}", boundary, generation);
+            assert_eq!(parallel.zobrist_hash(), serial.zobrist_hash());
+        }
+        assert_eq!(serial.generation(), 50);
+    }
+}
+
+#[test]
+fn par_advance_matches_advance() {
+    let mut serial = LifeBoard::new(300, 77);
+    serial
+        .randomize(0.4, &mut SmallRng::seed_from_u64(77))
+        .unwrap();
+    let mut parallel = serial.clone();
+
+    for generation in 1..=50 {
+        serial.advance();
+        parallel.par_advance();
+        assert_eq!(parallel, serial, "generation {}", generation);
+    }
+}
@@ -0,0 +1,137 @@
+extern crate gameoflife;
+
+use gameoflife::{patterns, BoundaryMode, LifeBoard, Pattern, PatternLibrary, RunOutcome};
+
+fn board_with(pattern: &Pattern, size: usize, row: usize, col: usize) -> LifeBoard {
+    let mut board = LifeBoard::new_square(size);
+    board.set_boundary(BoundaryMode::Dead);
+    board.stamp(pattern, row, col).unwrap();
+    board
+}
+
+fn live(board: &LifeBoard) -> Vec<(usize, usize)> {
+    board.live_cells().collect()
+}
+
+fn assert_period(pattern: &Pattern, period: u64) {
+    let mut board = board_with(pattern, 24, 5, 5);
+    let start = live(&board);
+
+    for generation in 1..period {
+        board.advance();
+        assert_ne!(live(&board), start, "repeated early at {}", generation);
+    }
+    board.advance();
+    assert_eq!(live(&board), start);
+
+    let mut board = board_with(pattern, 24, 5, 5);
+    let expected = match period {
+        1 => RunOutcome::Stable { start: 0 },
+        period => RunOutcome::Oscillating { period, start: 0 },
+    };
+    assert_eq!(board.run_until_cycle(100), expected);
+}
+
+#[test]
+fn block_is_stable() {
+    assert_period(&patterns::block(), 1);
+}
+
+#[test]
+fn blinker_has_period_two() {
+    assert_period(&patterns::blinker(), 2);
+}
+
+#[test]
+fn toad_has_period_two() {
+    assert_period(&Pattern::from_rows(&[".OOO", "OOO."]), 2);
+}
+
+#[test]
+fn pulsar_has_period_three() {
+    assert_period(&patterns::pulsar(), 3);
+}
+
+#[test]
+fn glider_moves_one_cell_diagonally_every_four_generations() {
+    let mut board = board_with(&patterns::glider(), 40, 2, 2);
+    let start = live(&board);
+
+    for moves in 1..=20 {
+        board.advance_by(4);
+        let expected: Vec<_> = start
+            .iter()
+            .map(|&(row, col)| (row + moves, col + moves))
+            .collect();
+        assert_eq!(live(&board), expected);
+    }
+}
+
+#[test]
+fn r_pentomino_population() {
+    let expected = [
+        5, 6, 7, 9, 8, 9, 12, 11, 18, 11, 11, 10, 13, 16, 19, 19, 23, 25, 35, 25, 32,
+    ];
+    let r_pentomino = Pattern::from_rows(&[".OO", "OO.", ".O."]);
+    let mut board = board_with(&r_pentomino, 64, 30, 30);
+
+    for &population in expected.iter() {
+        assert_eq!(board.population(), population, "{}", board.generation());
+        board.advance();
+    }
+}
+
+#[test]
+fn library_agrees_with_engine() {
+    let library = PatternLibrary::classic();
+    assert!(library.len() >= 10);
+
+    for entry in &library {
+        assert_eq!(entry.verify(), Ok(()), "{}", entry.name);
+    }
+}
+
+#[test]
+fn stamped_glider_goes_through_its_four_phases() {
+    let mut board = board_with(&patterns::glider(), 10, 2, 3);
+    let phases = [
+        vec![(2, 4), (3, 5), (4, 3), (4, 4), (4, 5)],
+        vec![(3, 3), (3, 5), (4, 4), (4, 5), (5, 4)],
+        vec![(3, 5), (4, 3), (4, 5), (5, 4), (5, 5)],
+        vec![(3, 4), (4, 5), (4, 6), (5, 4), (5, 5)],
+        // The first phase again, a cell down and to the right
+        vec![(3, 5), (4, 6), (5, 4), (5, 5), (5, 6)],
+    ];
+
+    for (generation, phase) in phases.iter().enumerate() {
+        assert_eq!(&live(&board), phase, "generation {}", generation);
+        board.advance();
+    }
+}
+
+#[test]
+fn empty_board_is_stable_from_the_start() {
+    let mut board = LifeBoard::new_square(12);
+    assert_eq!(board.run_until_cycle(10), RunOutcome::Stable { start: 0 });
+    assert_eq!(board.generation(), 1);
+}
+
+#[test]
+fn cycles_start_after_a_transient() {
+    // A lone cell dies, after which the board is empty for good
+    let mut board = LifeBoard::new_square(12);
+    board.set(5, 5, true).unwrap();
+    assert_eq!(board.run_until_cycle(10), RunOutcome::Stable { start: 1 });
+
+    // Three cells in an L become a block
+    let mut board = board_with(&Pattern::from_rows(&["OO", "O."]), 12, 4, 4);
+    assert_eq!(board.run_until_cycle(10), RunOutcome::Stable { start: 1 });
+    assert_eq!(board.population(), 4);
+}
+
+#[test]
+fn glider_on_an_open_board_maxes_out() {
+    let mut board = board_with(&patterns::glider(), 40, 2, 2);
+    assert_eq!(board.run_until_cycle(20), RunOutcome::MaxedOut);
+    assert_eq!(board.generation(), 20);
+}
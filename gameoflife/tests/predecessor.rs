@@ -0,0 +1,97 @@
+extern crate gameoflife;
+
+use gameoflife::{find_predecessors, is_garden_of_eden, patterns, BoundaryMode, LifeBoard};
+
+fn dead_edged(width: usize, height: usize) -> LifeBoard {
+    let mut board = LifeBoard::new(width, height);
+    board.set_boundary(BoundaryMode::Dead);
+    board
+}
+
+fn next(board: &LifeBoard) -> LifeBoard {
+    let mut next = board.clone();
+    next.advance();
+    next
+}
+
+#[test]
+fn block_has_predecessors() {
+    let mut block = dead_edged(4, 4);
+    block.stamp(&patterns::block(), 1, 1).unwrap();
+
+    let predecessors = find_predecessors(&block, usize::MAX);
+    assert!(predecessors.contains(&block));
+    // Three cells of the block bring the fourth to life
+    let mut three = block.clone();
+    three.set(1, 1, false).unwrap();
+    assert!(predecessors.contains(&three));
+
+    for predecessor in &predecessors {
+        assert_eq!(next(predecessor), block, "\n{}", predecessor);
+    }
+    for (index, predecessor) in predecessors.iter().enumerate() {
+        assert!(!predecessors[..index].contains(predecessor));
+    }
+}
+
+#[test]
+fn limit_caps_the_results() {
+    let mut block = dead_edged(4, 4);
+    block.stamp(&patterns::block(), 1, 1).unwrap();
+    let all = find_predecessors(&block, usize::MAX);
+    assert!(all.len() > 3);
+
+    assert_eq!(find_predecessors(&block, 3), all[..3]);
+    assert!(find_predecessors(&block, 0).is_empty());
+}
+
+#[test]
+fn matches_stepping_every_board() {
+    // Every 3x3 board, as the successors of all 512 starting boards
+    let boards: Vec<LifeBoard> = (0..512u32)
+        .map(|bits| {
+            let mut board = dead_edged(3, 3);
+            for cell in 0..9 {
+                board
+                    .set(cell / 3, cell % 3, bits & (1 << cell) != 0)
+                    .unwrap();
+            }
+            board
+        })
+        .collect();
+
+    for target in &boards {
+        let expected: Vec<&LifeBoard> = boards.iter().filter(|b| next(b) == *target).collect();
+        let found = find_predecessors(target, usize::MAX);
+
+        assert_eq!(found.len(), expected.len(), "\n{}", target);
+        assert!(found.iter().all(|board| expected.contains(&board)));
+        assert_eq!(is_garden_of_eden(target), expected.is_empty());
+    }
+}
+
+#[test]
+fn lone_cell_on_one_cell_board_is_a_garden_of_eden() {
+    let mut cell = dead_edged(1, 1);
+    cell.set(0, 0, true).unwrap();
+    assert!(is_garden_of_eden(&cell));
+    assert!(find_predecessors(&cell, 10).is_empty());
+
+    let mut empty = dead_edged(1, 1);
+    assert_eq!(find_predecessors(&empty, 10).len(), 2);
+    empty.set_boundary(BoundaryMode::Wrap);
+    // A lone cell on a wrapping board is its own eight neighbours
+    assert_eq!(find_predecessors(&empty, 10).len(), 2);
+}
+
+#[test]
+fn wrapping_predecessors_step_to_the_target() {
+    let mut blinker = LifeBoard::new(5, 5);
+    blinker.stamp(&patterns::blinker(), 2, 1).unwrap();
+
+    let predecessors = find_predecessors(&blinker, 50);
+    assert_eq!(predecessors.len(), 50);
+    for predecessor in &predecessors {
+        assert_eq!(next(predecessor), blinker, "\n{}", predecessor);
+    }
+}
@@ -0,0 +1,119 @@
+extern crate gameoflife;
+
+use gameoflife::{patterns, BoundaryMode, CellProbes, LifeBoard, ProbeTransition};
+
+// Horizontal blinker through the middle of a 5 by 5 board
+fn blinker() -> LifeBoard {
+    let mut board = LifeBoard::new_square(5);
+    board.set_boundary(BoundaryMode::Dead);
+    board.stamp(&patterns::blinker(), 2, 1).unwrap();
+    board
+}
+
+fn transition(generation: u64, label: &str, row: usize, col: usize, is: bool) -> ProbeTransition {
+    ProbeTransition {
+        generation,
+        label: label.to_string(),
+        row,
+        col,
+        was: !is,
+        is,
+    }
+}
+
+#[test]
+fn blinker_probes_alternate() {
+    let mut board = blinker();
+    let mut probes = CellProbes::new();
+    probes.add_probe(1, 2, "top");
+    probes.add_probe(2, 1, "left");
+    probes.add_probe(2, 2, "centre");
+    probes.add_probe(0, 0, "corner");
+
+    probes.observe(&board);
+    for _ in 0..6 {
+        board.advance();
+        probes.observe(&board);
+    }
+
+    let mut expected = Vec::new();
+    for generation in 1..=6 {
+        let vertical = generation % 2 == 1;
+        expected.push(transition(generation, "top", 1, 2, vertical));
+        expected.push(transition(generation, "left", 2, 1, !vertical));
+    }
+    assert_eq!(probes.transitions(), &expected[..]);
+}
+
+#[test]
+fn unprobed_cells_log_nothing() {
+    let mut board = blinker();
+    let mut probes = CellProbes::new();
+    probes.observe(&board);
+    for _ in 0..6 {
+        board.advance();
+        probes.observe(&board);
+    }
+    assert!(probes.is_empty());
+    assert!(probes.transitions().is_empty());
+
+    // Cells off the board count as dead and so never change
+    probes.add_probe(9, 9, "outside");
+    board.advance();
+    probes.observe(&board);
+    assert!(probes.transitions().is_empty());
+}
+
+#[test]
+fn probes_observe_runs() {
+    let mut board = blinker();
+    let mut probes = CellProbes::new();
+    probes.add_probe(3, 2, "bottom");
+    board.run_observed(10, &mut probes);
+
+    // The run stops as soon as the blinker repeats
+    assert_eq!(
+        probes.take_transitions(),
+        vec![
+            transition(1, "bottom", 3, 2, true),
+            transition(2, "bottom", 3, 2, false),
+        ]
+    );
+    assert!(probes.transitions().is_empty());
+}
+
+#[test]
+fn streamed_transitions_are_written_as_lines() {
+    let mut out = Vec::new();
+    let mut board = blinker();
+    let mut probes = CellProbes::streaming(&mut out);
+    probes.add_probe(2, 3, "right");
+    probes.observe(&board);
+    board.advance_by(2);
+    probes.observe(&board);
+    board.advance();
+    probes.observe(&board);
+
+    assert!(probes.transitions().is_empty());
+    assert!(probes.take_error().is_none());
+    drop(probes);
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "generation 3: right (2, 3) alive -> dead\n"
+    );
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn probe_option() {
+    use gameoflife::options::Options;
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    let options = Options::parse(args(&["--probe", "3,4", "--probe", "0,12:gun"])).unwrap();
+    assert_eq!(
+        options.probes,
+        vec![(3, 4, "3,4".to_string()), (0, 12, "gun".to_string())]
+    );
+    assert!(Options::parse(args(&["--probe", "3"])).is_err());
+    assert!(Options::parse(args(&["--probe", "3,x:label"])).is_err());
+}
@@ -0,0 +1,171 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{
+    BoundaryMode, LifeBoard, Pattern, Recorder, Recording, RecordingError, RecordingHeader, Rule,
+    RECORDING_VERSION,
+};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+const SEED: u64 = 2024;
+
+fn soup() -> LifeBoard {
+    let mut board = LifeBoard::new(40, 30);
+    board.set_rule(Rule::parse("B36/S23").unwrap());
+    board.set_boundary(BoundaryMode::Mirror);
+    board
+        .randomize(0.35, &mut SmallRng::seed_from_u64(SEED))
+        .unwrap();
+    board
+}
+
+// Recording of the first `generations` steps from `board`, its first
+// generation included
+fn record_from(mut board: LifeBoard, generations: usize, keyframe_every: u64) -> Vec<u8> {
+    let mut recorder = Recorder::new(Vec::new(), &board, Some(SEED), keyframe_every).unwrap();
+    recorder.record(&board).unwrap();
+    for _ in 0..generations {
+        board.advance();
+        recorder.record(&board).unwrap();
+    }
+
+    recorder.finish().unwrap()
+}
+
+fn record(generations: usize, keyframe_every: u64) -> Vec<u8> {
+    record_from(soup(), generations, keyframe_every)
+}
+
+#[test]
+fn replay_matches_a_fresh_run() {
+    let recording = Recording::from_bytes(&record(100, 16)).unwrap();
+    assert_eq!(
+        recording.header(),
+        &RecordingHeader {
+            width: 40,
+            height: 30,
+            boundary: BoundaryMode::Mirror,
+            rule: Rule::parse("B36/S23").unwrap(),
+            seed: Some(SEED),
+            keyframe_every: 16,
+        }
+    );
+    assert_eq!(recording.len(), 101);
+    assert!(recording.generations().eq(0..=100));
+
+    let mut fresh = soup();
+    for board in recording.boards_from(0) {
+        assert_eq!(board, fresh, "generation {}", fresh.generation());
+        fresh.advance();
+    }
+}
+
+#[test]
+fn seeking_lands_on_the_exact_board() {
+    let recording = Recording::from_bytes(&record(100, 16)).unwrap();
+    let mut expected = soup();
+    expected.advance_by(57);
+
+    assert_eq!(recording.board_at(57), Some(expected.clone()));
+    let mut playback = recording.boards_from(57);
+    assert_eq!(playback.next(), Some(expected.clone()));
+    expected.advance();
+    assert_eq!(playback.next(), Some(expected));
+
+    assert_eq!(recording.board_at(101), None);
+    assert_eq!(recording.boards_from(101).count(), 0);
+}
+
+#[test]
+fn diffs_keep_recordings_small() {
+    let mut board = LifeBoard::new_square(64);
+    board
+        .stamp(&Pattern::from_rows(&[".OO", "OO.", ".O."]), 30, 30)
+        .unwrap();
+
+    let keyframes_only = record_from(board.clone(), 100, 1);
+    let with_diffs = record_from(board, 100, 64);
+    assert!(with_diffs.len() * 4 < keyframes_only.len());
+
+    let keyframes_only = Recording::from_bytes(&keyframes_only).unwrap();
+    let with_diffs = Recording::from_bytes(&with_diffs).unwrap();
+    assert!(keyframes_only.boards_from(0).eq(with_diffs.boards_from(0)));
+}
+
+#[test]
+fn damaged_recordings_are_rejected() {
+    let bytes = record(10, 4);
+
+    assert!(matches!(
+        Recording::from_bytes(b"GOLSTATE"),
+        Err(RecordingError::NotARecording)
+    ));
+    let mut newer = bytes.clone();
+    newer[8..12].copy_from_slice(&(RECORDING_VERSION + 1).to_le_bytes());
+    assert!(matches!(
+        Recording::from_bytes(&newer),
+        Err(RecordingError::UnsupportedVersion(version)) if version == RECORDING_VERSION + 1
+    ));
+    assert!(matches!(
+        Recording::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(RecordingError::Corrupt)
+    ));
+}
+
+#[test]
+fn boards_of_another_size_are_refused() {
+    let board = soup();
+    let mut recorder = Recorder::new(Vec::new(), &board, None, 8).unwrap();
+    assert!(recorder.record(&LifeBoard::new(4, 4)).is_err());
+    recorder.record(&board).unwrap();
+
+    let recording = Recording::from_bytes(&recorder.finish().unwrap()).unwrap();
+    assert_eq!(recording.len(), 1);
+    assert_eq!(recording.header().seed, None);
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn play_renders_from_the_seek_point() {
+    use gameoflife::Renderer;
+
+    #[derive(Default)]
+    struct Generations(Vec<u64>, bool);
+
+    impl Renderer for Generations {
+        fn render(&mut self, board: &LifeBoard, generation: u64) -> std::io::Result<()> {
+            assert_eq!(board.generation(), generation);
+            self.0.push(generation);
+            Ok(())
+        }
+
+        fn finish(&mut self) {
+            self.1 = true;
+        }
+    }
+
+    let recording = Recording::from_bytes(&record(100, 16)).unwrap();
+    let mut renderer = Generations::default();
+    recording
+        .play(90, std::time::Duration::ZERO, &mut renderer)
+        .unwrap();
+    assert_eq!(renderer.0, (90..=100).collect::<Vec<_>>());
+    assert!(renderer.1);
+}
+
+#[test]
+fn twisted_topologies_are_kept() {
+    for &boundary in &[
+        BoundaryMode::ShiftedTorus { offset: -3 },
+        BoundaryMode::KleinBottle,
+    ] {
+        let mut board = soup();
+        board.set_boundary(boundary);
+        let recording = Recording::from_bytes(&record_from(board.clone(), 20, 8)).unwrap();
+
+        assert_eq!(recording.header().boundary, boundary);
+        board.advance_by(20);
+        assert_eq!(recording.board_at(20), Some(board));
+    }
+}
@@ -0,0 +1,277 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{patterns, DensityError, LifeBoard, Rect};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+fn glider_board() -> LifeBoard {
+    let mut board = LifeBoard::new_square(16);
+    board.stamp(&patterns::glider(), 5, 9).unwrap();
+    board
+}
+
+#[test]
+fn crop_extracts_glider() {
+    let board = glider_board();
+    let cropped = board.crop(board.bounding_box().unwrap());
+
+    assert_eq!((cropped.width(), cropped.height()), (3, 3));
+    let mut expected = LifeBoard::new_square(3);
+    expected.stamp(&patterns::glider(), 0, 0).unwrap();
+    assert_eq!(cropped, expected);
+}
+
+#[test]
+fn copy_from_restores_cropped_region() {
+    let board = glider_board();
+    let rect = board.bounding_box().unwrap();
+    let cropped = board.crop(rect);
+
+    let mut rebuilt = LifeBoard::new_square(16);
+    rebuilt.copy_from(
+        &cropped,
+        cropped.bounding_box().unwrap(),
+        (rect.row, rect.col),
+    );
+    assert_eq!(rebuilt, board);
+}
+
+#[test]
+fn copy_from_overwrites_dead_cells_too() {
+    let mut board = LifeBoard::new_square(4);
+    board.randomize(1.0, &mut rand::thread_rng()).unwrap();
+    board.copy_from(
+        &LifeBoard::new_square(2),
+        Rect {
+            row: 0,
+            col: 0,
+            width: 2,
+            height: 2,
+        },
+        (1, 1),
+    );
+
+    assert_eq!(board.population(), 12);
+    assert_eq!(board.get(1, 1), Some(false));
+    assert_eq!(board.get(2, 2), Some(false));
+}
+
+#[test]
+fn regions_are_clipped_without_panicking() {
+    let board = glider_board();
+    let far = Rect {
+        row: 100,
+        col: 100,
+        width: 5,
+        height: 5,
+    };
+    let empty = Rect {
+        row: 6,
+        col: 9,
+        width: 0,
+        height: 0,
+    };
+    let huge = Rect {
+        row: 0,
+        col: 0,
+        width: usize::MAX,
+        height: usize::MAX,
+    };
+
+    assert_eq!(board.crop(far).width(), 0);
+    assert_eq!(board.crop(empty).height(), 0);
+    assert_eq!(board.crop(huge), board);
+    // Only the corner that lands on the board is kept
+    let corner = board.crop(Rect {
+        row: 14,
+        col: 14,
+        width: 10,
+        height: 10,
+    });
+    assert_eq!((corner.width(), corner.height()), (2, 2));
+
+    let mut target = LifeBoard::new_square(4);
+    target.copy_from(&board, far, (0, 0));
+    target.copy_from(&board, huge, (usize::MAX, usize::MAX));
+    target.copy_from(&board, empty, (2, 2));
+    assert!(target.is_empty());
+
+    // The glider's top row lands on the last row of the target
+    target.copy_from(&board, board.bounding_box().unwrap(), (3, 1));
+    assert_eq!(target.live_cells().collect::<Vec<_>>(), vec![(3, 2)]);
+}
+
+#[test]
+fn shifting_by_the_board_size_is_the_identity() {
+    let mut board = LifeBoard::new(16, 11);
+    board.randomize(0.4, &mut rand::thread_rng()).unwrap();
+    let original = board.clone();
+
+    board.shift(11, 16);
+    assert_eq!(board, original);
+    board.shift(-22, 48);
+    assert_eq!(board, original);
+    board.shift(3, -5);
+    assert_ne!(board, original);
+    board.shift(-3, 5);
+    assert_eq!(board, original);
+}
+
+#[test]
+fn shift_commutes_with_stepping() {
+    let mut shifted_first = glider_board();
+    let mut stepped_first = glider_board();
+
+    shifted_first.shift(1, 1);
+    shifted_first.advance_by(4);
+    stepped_first.advance_by(4);
+    stepped_first.shift(1, 1);
+    assert_eq!(shifted_first, stepped_first);
+
+    // On a torus this holds even across the edges
+    shifted_first.shift(-9, 12);
+    shifted_first.advance_by(30);
+    stepped_first.advance_by(30);
+    stepped_first.shift(-9, 12);
+    assert_eq!(shifted_first, stepped_first);
+}
+
+#[test]
+fn shift_clipped_drops_cells_at_the_edges() {
+    // Glider at rows 5 to 7 and columns 9 to 11 of a 16 by 16 board
+    let mut board = glider_board();
+    board.shift_clipped(9, 5);
+    assert_eq!(board.live_cells().collect::<Vec<_>>(), vec![(14, 15)]);
+
+    // Only the bottom row is left after moving up two rows past the top
+    let mut board = glider_board();
+    board.shift_clipped(-7, -1);
+    assert_eq!(
+        board.live_cells().collect::<Vec<_>>(),
+        vec![(0, 8), (0, 9), (0, 10)]
+    );
+
+    let mut board = glider_board();
+    board.shift_clipped(0, -100);
+    assert!(board.is_empty());
+    board.shift_clipped(isize::MIN, isize::MAX);
+    assert!(board.is_empty());
+}
+
+// Cells outside `rect` on two boards of the same size that differ
+fn differences_outside(a: &LifeBoard, b: &LifeBoard, rect: Rect) -> usize {
+    let inside = |row: usize, col: usize| {
+        (rect.row..rect.row + rect.height).contains(&row)
+            && (rect.col..rect.col + rect.width).contains(&col)
+    };
+    (0..a.height())
+        .flat_map(|row| (0..a.width()).map(move |col| (row, col)))
+        .filter(|&(row, col)| !inside(row, col) && a[(row, col)] != b[(row, col)])
+        .count()
+}
+
+#[test]
+fn randomize_region_leaves_the_rest_alone() {
+    let mut rng = SmallRng::seed_from_u64(699);
+    let rect = Rect {
+        row: 4,
+        col: 7,
+        width: 12,
+        height: 9,
+    };
+
+    // Over both an empty and a full board
+    for density in [0.0, 1.0] {
+        let mut before = LifeBoard::new(30, 20);
+        before.randomize(density, &mut rng).unwrap();
+        let mut after = before.clone();
+        after.randomize_region(rect, 0.5, &mut rng).unwrap();
+        assert_eq!(differences_outside(&before, &after, rect), 0);
+        assert_ne!(after, before);
+
+        let mut cleared = before.clone();
+        cleared.clear_region(rect);
+        assert_eq!(differences_outside(&before, &cleared, rect), 0);
+        assert_eq!(cleared.live_cells_in(rect).count(), 0);
+    }
+}
+
+#[test]
+fn randomize_region_fills_to_the_density() {
+    let rect = Rect {
+        row: 50,
+        col: 20,
+        width: 100,
+        height: 80,
+    };
+    for density in [0.1, 0.37, 0.5, 0.9] {
+        let mut board = LifeBoard::new(200, 200);
+        board
+            .randomize_region(rect, density, &mut SmallRng::seed_from_u64(99))
+            .unwrap();
+
+        // 8000 cells, so the realised density is well within 0.02
+        let realised = board.population() as f64 / 8000.0;
+        assert!(
+            (realised - density).abs() < 0.02,
+            "{} gave {}",
+            density,
+            realised
+        );
+        assert_eq!(board.live_cells_in(rect).count(), board.population());
+    }
+
+    // Rects hanging off the board are clipped to it
+    let mut board = LifeBoard::new(10, 10);
+    let hanging = Rect {
+        row: 6,
+        col: 6,
+        width: 100,
+        height: 100,
+    };
+    board
+        .randomize_region(hanging, 1.0, &mut SmallRng::seed_from_u64(1))
+        .unwrap();
+    assert_eq!(board.population(), 16);
+}
+
+#[test]
+fn zero_area_regions_are_no_ops() {
+    let mut board = LifeBoard::new(12, 8);
+    board
+        .randomize(0.5, &mut SmallRng::seed_from_u64(3))
+        .unwrap();
+    let before = board.clone();
+    let mut rng = SmallRng::seed_from_u64(4);
+
+    for (width, height) in [(0, 0), (0, 5), (5, 0)] {
+        for (row, col) in [(0, 0), (3, 4), (8, 12), (50, 50)] {
+            let rect = Rect {
+                row,
+                col,
+                width,
+                height,
+            };
+            board.randomize_region(rect, 1.0, &mut rng).unwrap();
+            board.clear_region(rect);
+            assert_eq!(board, before, "{:?}", rect);
+        }
+    }
+
+    // Beyond the board there is nothing to fill
+    let outside = Rect {
+        row: 8,
+        col: 0,
+        width: 12,
+        height: 3,
+    };
+    board.randomize_region(outside, 1.0, &mut rng).unwrap();
+    assert_eq!(board, before);
+
+    // An invalid density is rejected whatever the rect, and changes nothing
+    assert_eq!(
+        board.randomize_region(outside, 1.5, &mut rng),
+        Err(DensityError(1.5))
+    );
+}
@@ -0,0 +1,195 @@
+extern crate gameoflife;
+
+use gameoflife::{
+    braille_glyph, density_glyph, fit_zoomed_out, half_block_glyph, patterns, zoom_to_fit,
+    LifeBoard, RenderStyle,
+};
+
+fn styled(board: &LifeBoard, style: RenderStyle) -> String {
+    let mut out = String::new();
+    board.render_styled_to(&mut out, style, false).unwrap();
+    out
+}
+
+#[test]
+fn half_blocks() {
+    assert_eq!(half_block_glyph(false, false), ' ');
+    assert_eq!(half_block_glyph(true, false), '▀');
+    assert_eq!(half_block_glyph(false, true), '▄');
+    assert_eq!(half_block_glyph(true, true), '█');
+}
+
+#[test]
+fn braille_dots() {
+    let mut cells = [[false; 2]; 4];
+    assert_eq!(braille_glyph(cells), '\u{2800}');
+
+    cells[0][0] = true;
+    assert_eq!(braille_glyph(cells), '⠁');
+    cells[3][1] = true;
+    assert_eq!(braille_glyph(cells), '⢁');
+    assert_eq!(braille_glyph([[true; 2]; 4]), '⣿');
+    // Left column only: dots 1, 2, 3 and 7
+    assert_eq!(braille_glyph([[true, false]; 4]), '⡇');
+    // Right column only: dots 4, 5, 6 and 8
+    assert_eq!(braille_glyph([[false, true]; 4]), '⢸');
+}
+
+#[test]
+fn glider_in_half_blocks() {
+    // .O.
+    // ..O
+    // OOO
+    let mut board = LifeBoard::new(3, 3);
+    board.stamp(&patterns::glider(), 0, 0).unwrap();
+
+    // The third row is paired with a row of dead cells past the edge
+    assert_eq!(
+        styled(&board, RenderStyle::HalfBlock),
+        "┌─────┐\n│  ▀▄ │\n│ ▀▀▀ │\n└─────┘"
+    );
+}
+
+#[test]
+fn glider_in_braille() {
+    let mut board = LifeBoard::new(3, 3);
+    board.stamp(&patterns::glider(), 0, 0).unwrap();
+
+    // Dots 4, 3 and 6 in the first block and dots 2 and 3 in the second
+    assert_eq!(
+        styled(&board, RenderStyle::Braille),
+        "┌────┐\n│ ⠬⠆ │\n└────┘"
+    );
+}
+
+#[test]
+fn ascii_style_matches_display() {
+    let mut board = LifeBoard::new(4, 2);
+    board.set(1, 2, true).unwrap();
+
+    assert_eq!(styled(&board, RenderStyle::Ascii), board.to_string());
+}
+
+#[test]
+fn styles_fit_more_cells() {
+    assert_eq!(RenderStyle::Ascii.fit_to_terminal(80, 24), (38, 21));
+    assert_eq!(RenderStyle::HalfBlock.fit_to_terminal(80, 24), (76, 42));
+    assert_eq!(RenderStyle::Braille.fit_to_terminal(80, 24), (152, 84));
+}
+
+#[test]
+fn rgba_buffer_has_a_pixel_per_cell() {
+    // 3x2 board with live cells at opposite corners
+    let mut board = LifeBoard::new(3, 2);
+    board.set(0, 0, true).unwrap();
+    board.set(1, 2, true).unwrap();
+
+    let live = [0, 255, 0, 255];
+    let dead = [16, 16, 16, 255];
+    let mut buf = vec![0; 3 * 2 * 4];
+    board.write_rgba(&mut buf, live, dead);
+
+    #[rustfmt::skip]
+    let expected = vec![
+        0, 255, 0, 255,    16, 16, 16, 255,   16, 16, 16, 255,
+        16, 16, 16, 255,   16, 16, 16, 255,   0, 255, 0, 255,
+    ];
+    assert_eq!(buf, expected);
+}
+
+#[test]
+#[should_panic(expected = "RGBA buffer does not fit a 3x2 board")]
+fn rgba_buffer_of_the_wrong_size_panics() {
+    let board = LifeBoard::new(3, 2);
+    board.write_rgba(&mut [0; 20], [0; 4], [0; 4]);
+}
+
+// 5x3 board whose 2x2 blocks are cut short at the right and bottom edges:
+//
+// OO..O
+// O..O.
+// .O..O
+fn uneven_board() -> LifeBoard {
+    let mut board = LifeBoard::new(5, 3);
+    for &(row, col) in &[(0, 0), (0, 1), (0, 4), (1, 0), (1, 3), (2, 1), (2, 4)] {
+        board.set(row, col, true).unwrap();
+    }
+    board
+}
+
+#[test]
+fn downsampling_counts_partial_blocks_by_the_cells_they_cover() {
+    let board = uneven_board();
+
+    // The last column of blocks is one cell wide and the last row one cell
+    // high, so the bottom right block is a single live cell
+    assert_eq!(board.downsample(2), vec![0.75, 0.25, 0.5, 0.5, 0.0, 1.0]);
+    assert_eq!(board.downsample(5), vec![7.0 / 15.0]);
+    assert_eq!(board.downsample(8), vec![7.0 / 15.0]);
+
+    let cells: Vec<f32> = (0..3)
+        .flat_map(|row| (0..5).map(move |col| (row, col)))
+        .map(|(row, col)| board[row][col] as u8 as f32)
+        .collect();
+    assert_eq!(board.downsample(1), cells);
+}
+
+#[test]
+#[should_panic(expected = "at least one cell across")]
+fn downsampling_by_zero_panics() {
+    LifeBoard::new(4, 4).downsample(0);
+}
+
+#[test]
+fn density_ramp() {
+    assert_eq!(density_glyph(0.0), ' ');
+    assert_eq!(density_glyph(0.01), '.');
+    assert_eq!(density_glyph(0.5), '+');
+    assert_eq!(density_glyph(0.99), '@');
+    assert_eq!(density_glyph(1.0), '@');
+}
+
+#[test]
+fn zoomed_out_board_is_shaded_by_block() {
+    let mut out = String::new();
+    uneven_board().render_zoomed_to(&mut out, 2, false).unwrap();
+
+    assert_eq!(out, "+--------+\n| ##--++ |\n| ++  @@ |\n+--------+");
+}
+
+#[test]
+fn zoom_fits_big_boards_to_the_terminal() {
+    // An 80x24 terminal fits 38x21 blocks
+    assert_eq!(fit_zoomed_out(1, 80, 24), (38, 21));
+    assert_eq!(fit_zoomed_out(3, 80, 24), (114, 63));
+
+    assert_eq!(zoom_to_fit(38, 21, 80, 24), 1);
+    assert_eq!(zoom_to_fit(39, 21, 80, 24), 2);
+    assert_eq!(zoom_to_fit(100, 50, 80, 24), 3);
+    assert_eq!(zoom_to_fit(0, 0, 80, 24), 1);
+}
+
+#[test]
+fn empty_board_display() {
+    let board = LifeBoard::new(3, 3);
+    assert_eq!(
+        board.to_string(),
+        "+--------+\n|        |\n|        |\n|        |\n+--------+"
+    );
+}
+
+#[test]
+fn glider_display() {
+    let mut board = LifeBoard::new(4, 3);
+    board.stamp(&patterns::glider(), 0, 0).unwrap();
+    assert_eq!(
+        board.to_string(),
+        concat!(
+            "+----------+\n",
+            "|   #      |\n",
+            "|     #    |\n",
+            "| # # #    |\n",
+            "+----------+",
+        )
+    );
+}
@@ -0,0 +1,189 @@
+#![cfg(feature = "native")]
+
+extern crate gameoflife;
+extern crate rand;
+
+use std::io;
+use std::time::Duration;
+
+use gameoflife::{
+    patterns, run_rendered, run_rendered_with, AnsiRenderer, FramePolicy, LifeBoard, Noise,
+    NullRenderer, RenderStyle, Renderer,
+};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+// Renderer remembering what it was asked to draw, failing once it has drawn
+// `fail_after` frames
+#[derive(Default)]
+struct Recorder {
+    generations: Vec<u64>,
+    populations: Vec<usize>,
+    finished: usize,
+    fail_after: Option<usize>,
+}
+
+impl Renderer for Recorder {
+    fn render(&mut self, board: &LifeBoard, generation: u64) -> io::Result<()> {
+        if Some(self.generations.len()) == self.fail_after {
+            return Err(io::Error::other("screen went away"));
+        }
+        self.generations.push(generation);
+        self.populations.push(board.population());
+        Ok(())
+    }
+
+    fn finish(&mut self) {
+        self.finished += 1;
+    }
+}
+
+fn glider_board() -> LifeBoard {
+    let mut board = LifeBoard::new_square(12);
+    board.stamp(&patterns::glider(), 1, 1).unwrap();
+    board
+}
+
+#[test]
+fn every_generation_is_rendered_in_order() {
+    let mut board = glider_board();
+    let mut recorder = Recorder::default();
+    run_rendered(
+        &mut board,
+        10,
+        FramePolicy::EveryGeneration,
+        Duration::ZERO,
+        &mut recorder,
+    )
+    .unwrap();
+
+    assert_eq!(recorder.generations, (0..=10).collect::<Vec<_>>());
+    assert_eq!(recorder.populations, vec![5; 11]);
+    assert_eq!(recorder.finished, 1);
+    assert_eq!(board.generation(), 10);
+}
+
+#[test]
+fn latest_only_ends_on_the_last_generation() {
+    let mut board = glider_board();
+    let mut recorder = Recorder::default();
+    run_rendered(
+        &mut board,
+        50,
+        FramePolicy::LatestOnly,
+        Duration::ZERO,
+        &mut recorder,
+    )
+    .unwrap();
+
+    assert_eq!(recorder.generations.last(), Some(&50));
+    assert!(recorder
+        .generations
+        .windows(2)
+        .all(|pair| pair[0] < pair[1]));
+    assert_eq!(recorder.finished, 1);
+}
+
+#[test]
+fn a_failing_renderer_stops_the_run() {
+    let mut board = glider_board();
+    let mut recorder = Recorder {
+        fail_after: Some(3),
+        ..Recorder::default()
+    };
+    let error = run_rendered(
+        &mut board,
+        100,
+        FramePolicy::EveryGeneration,
+        Duration::ZERO,
+        &mut recorder,
+    )
+    .unwrap_err();
+
+    assert_eq!(error.to_string(), "screen went away");
+    assert_eq!(recorder.generations, vec![0, 1, 2]);
+    assert_eq!(recorder.finished, 1);
+    assert!(board.generation() < 100);
+}
+
+#[test]
+fn null_renderer_draws_nothing() {
+    let mut board = glider_board();
+    run_rendered(
+        &mut board,
+        20,
+        FramePolicy::EveryGeneration,
+        Duration::ZERO,
+        &mut NullRenderer,
+    )
+    .unwrap();
+    assert_eq!(board.generation(), 20);
+}
+
+#[test]
+fn steps_can_add_rain() {
+    let mut board = glider_board();
+    let mut recorder = Recorder::default();
+    let rain = Noise::rain(1.0).unwrap();
+    let mut rng = SmallRng::seed_from_u64(3);
+    run_rendered_with(
+        &mut board,
+        2,
+        FramePolicy::EveryGeneration,
+        Duration::ZERO,
+        |board| board.advance_noisy(rain, &mut rng),
+        &mut recorder,
+    )
+    .unwrap();
+
+    assert_eq!(recorder.generations, vec![0, 1, 2]);
+    assert_eq!(recorder.populations, vec![5, 144, 144]);
+}
+
+#[test]
+fn ansi_renderer_redraws_in_place() {
+    let mut board = LifeBoard::new(2, 2);
+    board.set(0, 0, true).unwrap();
+    let mut out = Vec::new();
+
+    let mut renderer = AnsiRenderer::with_writer(&mut out, RenderStyle::Ascii, false).unwrap();
+    renderer.render(&board, 0).unwrap();
+    board.advance();
+    renderer.render(&board, 1).unwrap();
+    drop(renderer);
+
+    // Hide the cursor and clear the screen, draw each frame from the top
+    // left corner, then reset colors and show the cursor again
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        concat!(
+            "\x1b[?25l\x1b[2J",
+            "\x1b[H+------+\n| #    |\n|      |\n+------+\n",
+            "\x1b[H+------+\n|      |\n|      |\n+------+\n",
+            "\x1b[0m\x1b[?25h",
+        )
+    );
+}
+
+#[test]
+fn ansi_renderer_colors_live_cells() {
+    let mut board = LifeBoard::new(2, 1);
+    board.set(0, 1, true).unwrap();
+    let mut out = Vec::new();
+
+    let mut renderer = AnsiRenderer::with_writer(&mut out, RenderStyle::Ascii, true).unwrap();
+    renderer.render(&board, 0).unwrap();
+    renderer.finish();
+    // Finishing twice, or dropping after finishing, writes nothing more
+    renderer.finish();
+    drop(renderer);
+
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        concat!(
+            "\x1b[?25l\x1b[2J",
+            "\x1b[H+------+\n|   \x1b[32m# \x1b[0m |\n+------+\n",
+            "\x1b[0m\x1b[?25h",
+        )
+    );
+}
@@ -0,0 +1,130 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{HistoryBuffer, LifeBoard, Noise};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn soup() -> LifeBoard {
+    let mut board = LifeBoard::new(24, 18);
+    board.randomize(0.4, &mut StdRng::seed_from_u64(5)).unwrap();
+    board
+}
+
+// Steps `board` `generations` times, pushing it to `history` before each step
+fn run(board: &mut LifeBoard, history: &mut HistoryBuffer, generations: usize) {
+    for _ in 0..generations {
+        history.push(board);
+        board.advance();
+    }
+}
+
+#[test]
+fn rewinding_restores_recorded_generation() {
+    let mut board = soup();
+    let mut history = HistoryBuffer::new(16);
+    run(&mut board, &mut history, 10);
+    assert_eq!(history.history_len(), 10);
+
+    for _ in 0..3 {
+        assert!(history.step_back(&mut board));
+    }
+    let mut expected = soup();
+    expected.advance_by(7);
+    assert_eq!(board, expected);
+    assert_eq!(board.generation(), 7);
+    assert_eq!(history.history_len(), 7);
+}
+
+#[test]
+fn deterministic_run_replays_after_rewinding() {
+    let mut board = soup();
+    let mut history = HistoryBuffer::new(16);
+    run(&mut board, &mut history, 10);
+    let abandoned = board.clone();
+
+    for _ in 0..3 {
+        history.step_back(&mut board);
+    }
+    run(&mut board, &mut history, 3);
+    assert_eq!(board, abandoned);
+    assert_eq!(board.generation(), 10);
+    assert_eq!(history.history_len(), 10);
+}
+
+#[test]
+fn noisy_run_diverges_after_rewinding() {
+    let noise = Noise::flip(0.05).unwrap();
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut board = soup();
+    let mut history = HistoryBuffer::new(16);
+    for _ in 0..10 {
+        history.push(&board);
+        board.advance_noisy(noise, &mut rng);
+    }
+    let abandoned = board.clone();
+
+    for _ in 0..3 {
+        history.step_back(&mut board);
+    }
+    for _ in 0..3 {
+        history.push(&board);
+        board.advance_noisy(noise, &mut rng);
+    }
+    assert_eq!(board.generation(), 10);
+    assert_ne!(board, abandoned);
+}
+
+#[test]
+fn full_buffer_drops_oldest_generations() {
+    let mut board = soup();
+    let mut history = HistoryBuffer::new(4);
+    run(&mut board, &mut history, 10);
+    assert_eq!(history.history_len(), 4);
+
+    while history.step_back(&mut board) {}
+    assert_eq!(board.generation(), 6);
+    let mut expected = soup();
+    expected.advance_by(6);
+    assert_eq!(board, expected);
+
+    // With nothing left to go back to the board is left alone
+    assert!(!history.step_back(&mut board));
+    assert_eq!(board.generation(), 6);
+}
+
+#[test]
+fn rewinding_restores_ages() {
+    let mut board = soup();
+    board.enable_ages();
+    let mut history = HistoryBuffer::new(8);
+    run(&mut board, &mut history, 5);
+    let ages_at_4: Vec<_> = {
+        let mut expected = soup();
+        expected.enable_ages();
+        expected.advance_by(4);
+        expected
+            .live_cells()
+            .map(|(row, col)| expected.age(row, col))
+            .collect()
+    };
+
+    history.step_back(&mut board);
+    let ages: Vec<_> = board
+        .live_cells()
+        .map(|(row, col)| board.age(row, col))
+        .collect();
+    assert_eq!(ages, ages_at_4);
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn rewind_option() {
+    use gameoflife::options::Options;
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(Options::parse(args(&[])).unwrap().rewind, 0);
+    let options = Options::parse(args(&["--interactive", "--rewind", "50"])).unwrap();
+    assert_eq!(options.rewind, 50);
+    assert!(Options::parse(args(&["--rewind", "-3"])).is_err());
+}
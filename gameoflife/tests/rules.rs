@@ -0,0 +1,319 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{
+    next_state, BoundaryMode, GenerationsRule, LifeBoard, Pattern, Rule, RuleParseError,
+};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+// Neighbour offsets as (row, column) of the bits of a neighbourhood
+fn live_neighbours(neighbourhood: u16) -> Vec<(i32, i32)> {
+    (0..9)
+        .filter(|&bit| bit != 4 && neighbourhood & 1 << bit != 0)
+        .map(|bit| (bit / 3 - 1, bit % 3 - 1))
+        .collect()
+}
+
+fn alive(neighbourhood: u16) -> bool {
+    neighbourhood & 1 << 4 != 0
+}
+
+#[test]
+fn conway_table_matches_counting() {
+    let rule = Rule::conway();
+    assert!(rule.is_totalistic());
+    for neighbourhood in 0..512 {
+        let count = live_neighbours(neighbourhood).len() as u8;
+        assert_eq!(
+            rule.applies_to(neighbourhood),
+            next_state(alive(neighbourhood), count),
+            "{:09b}",
+            neighbourhood
+        );
+    }
+}
+
+#[test]
+fn conway_table_steps_random_boards_like_counting() {
+    // `advance` counts neighbours for a totalistic rule; look every cell up
+    // in the table instead
+    let rule = Rule::conway();
+    let mut board = LifeBoard::new(37, 23);
+    board
+        .randomize(0.4, &mut SmallRng::seed_from_u64(11))
+        .unwrap();
+    let (width, height) = (board.width(), board.height());
+
+    for _ in 0..5 {
+        let mut next = board.clone();
+        next.advance();
+        for row in 0..height {
+            for col in 0..width {
+                let neighbourhood = (0..9).fold(0, |neighbourhood, bit| {
+                    let r = (row + height + bit / 3 - 1) % height;
+                    let c = (col + width + bit % 3 - 1) % width;
+                    neighbourhood | (board[r][c] as u16) << bit
+                });
+                assert_eq!(next[row][col], rule.applies_to(neighbourhood));
+            }
+        }
+        board = next;
+    }
+}
+
+#[test]
+fn letters_spelling_out_every_arrangement_give_the_totalistic_rule() {
+    let spelled = Rule::parse("B3cekainyqjr/S2cekain3-").unwrap();
+    assert_eq!(spelled, Rule::conway());
+    assert!(spelled.is_totalistic());
+    assert_eq!(spelled.to_string(), "B3/S23");
+}
+
+#[test]
+fn just_friends_births_need_friends_that_do_not_touch() {
+    let rule = Rule::parse("B2-a/S12").unwrap();
+    assert!(!rule.is_totalistic());
+    assert_eq!(rule.to_string(), "B2-a/S12");
+
+    for neighbourhood in 0..512 {
+        let neighbours = live_neighbours(neighbourhood);
+        let expected = if alive(neighbourhood) {
+            neighbours.len() == 1 || neighbours.len() == 2
+        } else {
+            match neighbours[..] {
+                // Born unless the two are side by side or one above the other
+                [(r1, c1), (r2, c2)] => (r1 - r2).abs() + (c1 - c2).abs() != 1,
+                _ => false,
+            }
+        };
+        assert_eq!(
+            rule.applies_to(neighbourhood),
+            expected,
+            "{:09b}",
+            neighbourhood
+        );
+    }
+}
+
+#[test]
+fn tlife_survival_excludes_opposite_sides() {
+    let rule = Rule::parse("B3/S2-i34q").unwrap();
+    assert_eq!(rule.to_string(), "B3/S2-i34q");
+
+    for neighbourhood in (0..512).filter(|&n| alive(n)) {
+        if let [(r1, c1), (r2, c2)] = live_neighbours(neighbourhood)[..] {
+            let opposite_sides = (r1, c1) == (-r2, -c2) && (r1 == 0 || c1 == 0);
+            assert_eq!(rule.applies_to(neighbourhood), !opposite_sides);
+        }
+    }
+}
+
+#[test]
+fn four_corners_and_four_sides_are_named_by_their_letters() {
+    let corners = 1 | 1 << 2 | 1 << 6 | 1 << 8;
+    let sides = 1 << 1 | 1 << 3 | 1 << 5 | 1 << 7;
+    let rule = Rule::parse("B4c/S4e").unwrap();
+
+    assert!(rule.applies_to(corners));
+    assert!(!rule.applies_to(sides));
+    assert!(rule.applies_to(sides | 1 << 4));
+    assert!(!rule.applies_to(corners | 1 << 4));
+}
+
+#[test]
+fn just_friends_diagonal_pair_fills_a_block_then_dies() {
+    let mut board = LifeBoard::new(8, 8);
+    board.set_rule(Rule::parse("B2-a/S12").unwrap());
+    board.set(3, 3, true).unwrap();
+    board.set(4, 4, true).unwrap();
+
+    // Each cell has one neighbour and survives. The two cells beside both
+    // see them at right angles, so are born; everything else sees at most
+    // one.
+    board.advance();
+    let live: Vec<_> = board.live_cells().collect();
+    assert_eq!(live, vec![(3, 3), (3, 4), (4, 3), (4, 4)]);
+
+    // Three neighbours each is too many, and every cell beside the block
+    // sees two that touch
+    board.advance();
+    assert_eq!(board.population(), 0);
+
+    // With plain B2 the cells beside the block are born instead
+    let mut board = LifeBoard::new(8, 8);
+    board.set_rule(Rule::parse("B2/S12").unwrap());
+    board.set(3, 3, true).unwrap();
+    board.set(4, 4, true).unwrap();
+    board.advance_by(2);
+    assert_eq!(board.population(), 8);
+}
+
+#[test]
+fn hensel_letters_are_checked() {
+    assert_eq!(
+        Rule::parse("B2x/S23"),
+        Err(RuleParseError::InvalidLetter(2, 'x'))
+    );
+    assert_eq!(
+        Rule::parse("B3/S0c"),
+        Err(RuleParseError::InvalidLetter(0, 'c'))
+    );
+    assert_eq!(
+        Rule::parse("B1z/S"),
+        Err(RuleParseError::InvalidLetter(1, 'z'))
+    );
+    assert_eq!(
+        Rule::parse("B2x/S23").unwrap_err().to_string(),
+        "no arrangement 'x' of 2 neighbours"
+    );
+}
+
+#[test]
+fn canonical_spelling_round_trips() {
+    for spelling in [
+        "B2ak/S",
+        "B36/S2-i34q",
+        "B2-a/S12",
+        "B0/S8",
+        "B4-ckz5y/S1e7",
+    ] {
+        let rule = Rule::parse(spelling).unwrap();
+        assert_eq!(
+            Rule::parse(&rule.to_string()).unwrap(),
+            rule,
+            "{}",
+            spelling
+        );
+    }
+    assert_eq!(Rule::parse("B2ak/S").unwrap().to_string(), "B2ka/S");
+}
+
+#[test]
+#[should_panic(expected = "only counts neighbours")]
+fn generations_rules_must_be_totalistic() {
+    GenerationsRule::from_rule(Rule::parse("B2-a/S12").unwrap(), 3);
+}
+
+fn stamped(rows: &[&str], rule: &str) -> LifeBoard {
+    let mut board = LifeBoard::new_square(9);
+    board.set_boundary(BoundaryMode::Dead);
+    board.set_rule(Rule::parse(rule).unwrap());
+    board.stamp(&Pattern::from_rows(rows), 3, 3).unwrap();
+    board
+}
+
+fn live(board: &LifeBoard) -> Vec<(usize, usize)> {
+    board.live_cells().collect()
+}
+
+#[test]
+fn parsed_conway_rule_is_conway() {
+    for spelling in ["B3/S23", "b3/s23", " B3/S32 "] {
+        let rule = Rule::parse(spelling).unwrap();
+        assert_eq!(rule, Rule::conway(), "{}", spelling);
+        for count in 0..=8 {
+            assert_eq!(rule.applies(false, count), next_state(false, count));
+            assert_eq!(rule.applies(true, count), next_state(true, count));
+        }
+    }
+
+    let mut default = LifeBoard::new(31, 17);
+    default
+        .randomize(0.35, &mut SmallRng::seed_from_u64(5))
+        .unwrap();
+    let mut parsed = default.clone();
+    parsed.set_rule(Rule::parse("B3/S23").unwrap());
+    for _ in 0..10 {
+        default.advance();
+        parsed.advance();
+        assert_eq!(default, parsed);
+    }
+}
+
+#[test]
+fn seeds_domino_jumps_to_either_side() {
+    // Nothing survives and a cell with exactly two neighbours is born
+    let mut board = stamped(&["OO"], "B2/S");
+    board.advance();
+    assert_eq!(live(&board), vec![(2, 3), (2, 4), (4, 3), (4, 4)]);
+
+    let mut board = stamped(&["O"], "B2/S");
+    board.advance();
+    assert!(board.is_empty());
+}
+
+#[test]
+fn highlife_births_with_six_neighbours() {
+    // The middle cell has six live neighbours, the only difference from
+    // Conway's rule here
+    let rows = ["OOO", "...", "OOO"];
+    let mut highlife = stamped(&rows, "B36/S23");
+    let mut conway = stamped(&rows, "B3/S23");
+    highlife.advance();
+    conway.advance();
+
+    assert_eq!(live(&conway), vec![(2, 4), (3, 4), (5, 4), (6, 4)]);
+    assert_eq!(
+        live(&highlife),
+        vec![(2, 4), (3, 4), (4, 4), (5, 4), (6, 4)]
+    );
+}
+
+#[test]
+fn malformed_rulestrings_are_rejected() {
+    for malformed in ["", "B3", "B3S23", "B3/S23/", "S23/B3", "3/23"] {
+        assert_eq!(
+            Rule::parse(malformed),
+            Err(RuleParseError::MissingPrefix),
+            "{:?}",
+            malformed
+        );
+    }
+    assert_eq!(
+        Rule::parse("B39/S23"),
+        Err(RuleParseError::InvalidCount('9'))
+    );
+    assert_eq!(
+        Rule::parse("B3/Sx23"),
+        Err(RuleParseError::InvalidCount('x'))
+    );
+    assert_eq!(
+        Rule::parse("B3S23").unwrap_err().to_string(),
+        "expected a rule of the form B3/S23"
+    );
+}
+
+#[test]
+fn next_state_truth_table() {
+    // Next state for 0 to 8 live neighbours, for a dead and a live cell
+    let dead = [false, false, false, true, false, false, false, false, false];
+    let alive = [false, false, true, true, false, false, false, false, false];
+
+    for count in 0..=8u8 {
+        assert_eq!(next_state(false, count), dead[count as usize], "{}", count);
+        assert_eq!(next_state(true, count), alive[count as usize], "{}", count);
+
+        let conway = Rule::conway();
+        assert_eq!(conway.applies(false, count), dead[count as usize]);
+        assert_eq!(conway.applies(true, count), alive[count as usize]);
+    }
+}
+
+#[test]
+fn rules_built_from_functions_match_their_rulestrings() {
+    assert_eq!(Rule::conway(), Rule::parse("B3/S23").unwrap());
+    assert_eq!(Rule::from_fn(next_state).to_string(), "B3/S23");
+    assert_eq!(
+        Rule::from_fn(|alive, count| match alive {
+            true => count == 2 || count == 3,
+            false => count == 3 || count == 6,
+        }),
+        Rule::parse("B36/S23").unwrap()
+    );
+    assert_eq!(
+        Rule::from_fn(|alive, count| !alive && count == 2),
+        Rule::parse("B2/S").unwrap()
+    );
+    assert!(Rule::from_fn(|_, _| true).is_totalistic());
+}
@@ -0,0 +1,51 @@
+extern crate gameoflife;
+
+use gameoflife::{exhaustive_search, Pattern, Rect, Rule, RunOutcome};
+
+// 3x3 region with a margin wide enough for the R-pentomino to play out
+const REGION: Rect = Rect {
+    row: 20,
+    col: 20,
+    width: 3,
+    height: 3,
+};
+
+#[test]
+fn every_configuration_is_evaluated() {
+    let report = exhaustive_search(REGION, None, Rule::conway(), 10);
+    assert_eq!(report.evaluated, 512);
+
+    let counts = [(0, 1), (3, 84), (5, 126), (9, 1), (10, 0)];
+    for &(live, count) in &counts {
+        let report = exhaustive_search(REGION, Some(live), Rule::conway(), 10);
+        assert_eq!(report.evaluated, count, "{} live cells", live);
+    }
+}
+
+#[test]
+fn r_pentomino_lives_longest() {
+    let report = exhaustive_search(REGION, Some(5), Rule::conway(), 2000);
+
+    // One result for each rotation and reflection
+    assert_eq!(report.longest_lived.len(), 8);
+    for result in &report.longest_lived {
+        assert_eq!(result.lifetime, 1025);
+        assert_eq!(
+            result.outcome,
+            RunOutcome::Oscillating {
+                period: 2,
+                start: 1025
+            }
+        );
+    }
+
+    let r_pentomino = Pattern::from_rows(&[".OO", "OO.", ".O."]);
+    assert!(report
+        .longest_lived
+        .iter()
+        .any(|result| result.cells == r_pentomino.cells()));
+    assert_eq!(
+        report.to_string().lines().nth(1),
+        Some("longest lived          1025 generations, 8 found")
+    );
+}
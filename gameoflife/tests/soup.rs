@@ -0,0 +1,103 @@
+extern crate gameoflife;
+
+use gameoflife::{run_soups, RunOutcome, SoupConfig};
+
+fn tiny() -> SoupConfig {
+    SoupConfig {
+        count: 40,
+        width: 10,
+        height: 8,
+        density: 0.4,
+        seed: 700,
+        max_generations: 300,
+        ..SoupConfig::default()
+    }
+}
+
+#[test]
+fn same_seed_gives_the_same_report() {
+    let report = run_soups(&tiny()).unwrap();
+    assert_eq!(run_soups(&tiny()).unwrap(), report);
+    assert_eq!(run_soups(&tiny()).unwrap().to_string(), report.to_string());
+
+    let seeds: Vec<_> = report.results.iter().map(|result| result.seed).collect();
+    assert_eq!(seeds, (700..740).collect::<Vec<_>>());
+
+    let moved = SoupConfig {
+        seed: 701,
+        ..tiny()
+    };
+    let shifted = run_soups(&moved).unwrap();
+    assert_eq!(shifted.results[..39], report.results[1..]);
+    assert_ne!(shifted, report);
+}
+
+#[test]
+fn longest_lived_seed_replays_to_its_lifetime() {
+    let config = tiny();
+    let report = run_soups(&config).unwrap();
+    let best = *report.longest_lived().unwrap();
+    assert!(report.results.iter().all(|r| r.lifetime <= best.lifetime));
+
+    // The whole run replays to the same outcome
+    let mut board = config.soup(best.seed).unwrap();
+    assert_eq!(board.run_until_cycle(config.max_generations), best.outcome);
+    assert_eq!(board.population(), best.population);
+
+    // and stepping by hand the board first repeats at the recorded lifetime
+    let period = match best.outcome {
+        RunOutcome::Stable { start } => {
+            assert_eq!(start, best.lifetime);
+            1
+        }
+        RunOutcome::Oscillating { start, period } => {
+            assert_eq!(start, best.lifetime);
+            period
+        }
+        RunOutcome::MaxedOut => panic!("seed {} never settled", best.seed),
+    };
+    let mut board = config.soup(best.seed).unwrap();
+    board.advance_by(best.lifetime as usize - 1);
+    let before = board.clone();
+    board.advance();
+    let settled = board.clone();
+
+    let mut later = settled.clone();
+    later.advance_by(period as usize);
+    assert_eq!(later, settled);
+    let mut later = before.clone();
+    later.advance_by(period as usize);
+    assert_ne!(later, before);
+}
+
+#[test]
+fn totals_agree_with_the_results() {
+    let config = SoupConfig {
+        max_generations: 40,
+        ..tiny()
+    };
+    let report = run_soups(&config).unwrap();
+
+    let empty = report.results.iter().filter(|r| r.population == 0).count();
+    assert_eq!(report.empty(), empty);
+    let maxed: Vec<_> = report
+        .results
+        .iter()
+        .filter(|r| r.outcome == RunOutcome::MaxedOut)
+        .collect();
+    assert_eq!(report.maxed_out(), maxed.len());
+    assert!(maxed.iter().all(|r| r.lifetime == 40));
+
+    let summary = report.to_string();
+    assert!(summary.contains(&format!("ended empty        {:>8}", empty)));
+    assert!(summary.contains(&format!("never settled      {:>8}", maxed.len())));
+
+    let none = run_soups(&SoupConfig { count: 0, ..tiny() }).unwrap();
+    assert_eq!(none.longest_lived(), None);
+    assert!(none.to_string().ends_with("longest lived      none"));
+    assert!(run_soups(&SoupConfig {
+        density: -0.1,
+        ..tiny()
+    })
+    .is_err());
+}
@@ -0,0 +1,64 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{patterns, BoundaryMode, LifeBoard, Rect, SparseLifeBoard};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+fn sorted(board: &SparseLifeBoard) -> Vec<(i64, i64)> {
+    let mut cells: Vec<_> = board.live_cells().collect();
+    cells.sort_unstable();
+    cells
+}
+
+#[test]
+fn glider_travels_250_cells_in_1000_generations() {
+    // One heading down and right and one, flipped both ways, up and left
+    // into negative coordinates
+    for (pattern, step) in [
+        (patterns::glider(), 1),
+        (patterns::glider().flip_vertical().flip_horizontal(), -1),
+    ] {
+        let mut board = SparseLifeBoard::new();
+        board.stamp(&pattern, 0, 0);
+        let start = sorted(&board);
+
+        board.advance_by(1000);
+        assert_eq!(board.generation(), 1000);
+        assert_eq!(board.population(), 5);
+        let expected: Vec<_> = start
+            .iter()
+            .map(|&(row, col)| (row + 250 * step, col + 250 * step))
+            .collect();
+        assert_eq!(sorted(&board), expected);
+    }
+}
+
+#[test]
+fn sparse_matches_a_dense_board_with_room_to_grow() {
+    // Nothing can spread further than a cell a generation, so a dense board
+    // with dead edges that far away behaves as an unbounded one
+    const GENERATIONS: usize = 40;
+    const MARGIN: usize = GENERATIONS + 1;
+
+    let mut dense = LifeBoard::new_square(30 + 2 * MARGIN);
+    dense.set_boundary(BoundaryMode::Dead);
+    let soup = Rect {
+        row: MARGIN,
+        col: MARGIN,
+        width: 30,
+        height: 30,
+    };
+    dense
+        .randomize_region(soup, 0.4, &mut SmallRng::seed_from_u64(17))
+        .unwrap();
+    let mut sparse = SparseLifeBoard::from_board(&dense);
+
+    for generation in 1..=GENERATIONS {
+        dense.advance();
+        sparse.advance();
+        let view = sparse.viewport(0, 0, dense.width(), dense.height());
+        assert_eq!(view, dense, "generation {}", generation);
+        assert_eq!(sparse.population(), dense.population());
+    }
+}
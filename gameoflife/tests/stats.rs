@@ -0,0 +1,78 @@
+extern crate gameoflife;
+
+use gameoflife::{patterns, BoardStats, LifeBoard, Rect};
+
+#[test]
+fn empty_board() {
+    let stats = LifeBoard::new(4, 5).stats();
+    assert_eq!(
+        stats,
+        BoardStats {
+            population: 0,
+            density: 0.0,
+            bounding_box: None,
+            centroid: None,
+            births: None,
+            deaths: None,
+        }
+    );
+    assert_eq!(LifeBoard::new(0, 0).stats().density, 0.0);
+}
+
+#[test]
+fn single_cell() {
+    let mut board = LifeBoard::new(4, 5);
+    board.set(3, 1, true).unwrap();
+
+    let stats = board.stats();
+    assert_eq!(stats.population, 1);
+    assert_eq!(stats.density, 0.05);
+    assert_eq!(
+        stats.bounding_box,
+        Some(Rect {
+            row: 3,
+            col: 1,
+            width: 1,
+            height: 1
+        })
+    );
+    assert_eq!(stats.centroid, Some((3.0, 1.0)));
+}
+
+#[test]
+fn glider() {
+    // .O.
+    // ..O
+    // OOO
+    let mut board = LifeBoard::new_square(10);
+    board.stamp(&patterns::glider(), 2, 4).unwrap();
+
+    let stats = board.stats();
+    assert_eq!(stats.population, 5);
+    assert_eq!(stats.density, 0.05);
+    assert_eq!(
+        stats.bounding_box,
+        Some(Rect {
+            row: 2,
+            col: 4,
+            width: 3,
+            height: 3
+        })
+    );
+    // Rows 2, 3, 4, 4, 4 and columns 5, 6, 4, 5, 6
+    assert_eq!(stats.centroid, Some((17.0 / 5.0, 26.0 / 5.0)));
+    assert_eq!((stats.births, stats.deaths), (None, None));
+}
+
+#[test]
+fn births_and_deaths_since_previous_generation() {
+    let mut board = LifeBoard::new_square(5);
+    board.stamp(&patterns::blinker(), 2, 1).unwrap();
+    let previous = board.clone();
+    board.advance();
+
+    let stats = board.stats_since(&previous);
+    assert_eq!((stats.births, stats.deaths), (Some(2), Some(2)));
+    assert_eq!(stats.population, 3);
+    assert_eq!(stats.centroid, Some((2.0, 2.0)));
+}
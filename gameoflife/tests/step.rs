@@ -0,0 +1,435 @@
+extern crate gameoflife;
+extern crate rand;
+
+use std::ops::ControlFlow;
+
+use gameoflife::{
+    next_state, patterns, Anchor, BoardMismatch, BoundaryMode, CellChange, LifeBoard, Rule,
+};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+#[test]
+fn mismatched_boards_are_rejected() {
+    let mut board = LifeBoard::new(8, 6);
+    board.stamp(&patterns::glider(), 1, 1).unwrap();
+    let mut next = LifeBoard::new(8, 5);
+    next.set(4, 7, true).unwrap();
+    let untouched = next.clone();
+
+    let mismatch = BoardMismatch {
+        expected: (8, 6),
+        found: (8, 5),
+    };
+    assert_eq!(board.step(&mut next), Err(mismatch));
+    assert_eq!(board.step_diff(&mut next), Err(mismatch));
+    #[cfg(feature = "rayon")]
+    assert_eq!(board.par_step(&mut next), Err(mismatch));
+    #[cfg(feature = "simd")]
+    assert_eq!(board.simd_step(&mut next), Err(mismatch));
+
+    assert_eq!(next, untouched);
+    assert_eq!(next.generation(), 0);
+    assert_eq!(
+        mismatch.to_string(),
+        "next generation board is 8x5, expected 8x6"
+    );
+}
+
+#[test]
+fn advance_after_resize() {
+    let mut board = LifeBoard::new_square(8);
+    board.stamp(&patterns::blinker(), 3, 2).unwrap();
+    board.advance();
+
+    for &(width, height) in &[(20, 12), (5, 5), (9, 30)] {
+        board.resize(width, height, Anchor::TopLeft);
+        let mut expected = LifeBoard::new(width, height);
+        board.step(&mut expected).unwrap();
+
+        board.advance();
+        assert_eq!(board, expected);
+        board.advance_active();
+        expected.advance();
+        assert_eq!(board, expected);
+    }
+}
+
+// Next generation worked out one cell at a time from its neighbour count
+fn cell_by_cell(board: &LifeBoard) -> Vec<(usize, usize)> {
+    let rule = board.rule();
+    let mut live = Vec::new();
+    for row in 0..board.height() {
+        for col in 0..board.width() {
+            let alive = board.get(row, col).unwrap();
+            if rule.applies(alive, board.live_neighbour_count(row, col)) {
+                live.push((row, col));
+            }
+        }
+    }
+
+    live
+}
+
+#[test]
+fn step_matches_cell_by_cell_counts() {
+    let sizes = [(1, 1), (1, 7), (2, 3), (3, 2), (64, 64), (257, 131)];
+    let boundaries = [BoundaryMode::Wrap, BoundaryMode::Dead, BoundaryMode::Mirror];
+    let mut rng = SmallRng::seed_from_u64(3);
+
+    for &(width, height) in &sizes {
+        for &boundary in &boundaries {
+            let mut board = LifeBoard::new(width, height);
+            board.set_boundary(boundary);
+            board.randomize(0.4, &mut rng).unwrap();
+            let mut next = LifeBoard::new(width, height);
+
+            for _ in 0..4 {
+                let expected = cell_by_cell(&board);
+                board.step(&mut next).unwrap();
+                assert_eq!(
+                    next.live_cells().collect::<Vec<_>>(),
+                    expected,
+                    "{}x{} {:?}",
+                    width,
+                    height,
+                    boundary
+                );
+                std::mem::swap(&mut board, &mut next);
+            }
+        }
+    }
+}
+
+// Xorshift generator, so the boards below do not depend on the rand crate's
+// choice of algorithms
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+// Next generation as the board worked it out before stepping was rewritten:
+// one cell at a time, wrapping each neighbour around the torus by hand
+fn reference_step(cells: &[bool], width: usize, height: usize) -> Vec<bool> {
+    let mut next = vec![false; cells.len()];
+    for row in 0..height {
+        for col in 0..width {
+            let mut count = 0;
+            for delta_row in -1..=1 {
+                for delta_col in -1..=1 {
+                    if (delta_row, delta_col) == (0, 0) {
+                        continue;
+                    }
+                    let r = (row as isize + delta_row).rem_euclid(height as isize) as usize;
+                    let c = (col as isize + delta_col).rem_euclid(width as isize) as usize;
+                    count += cells[r * width + c] as u8;
+                }
+            }
+            next[row * width + col] = next_state(cells[row * width + col], count);
+        }
+    }
+    next
+}
+
+#[test]
+fn random_boards_step_as_before_the_rewrite() {
+    let mut state = 0x2f6b_1d3c_95a7_e801;
+    let mut populations = Vec::new();
+
+    for &(width, height) in &[(32, 32), (17, 40), (3, 3), (1, 5)] {
+        let mut board = LifeBoard::new(width, height);
+        for cell in board.iter_mut() {
+            *cell.state = xorshift(&mut state) & 1 == 0;
+        }
+        let mut expected: Vec<bool> = board.iter().map(|cell| *cell.state).collect();
+        let mut next = LifeBoard::new(width, height);
+
+        for generation in 1..=20 {
+            expected = reference_step(&expected, width, height);
+            board.step(&mut next).unwrap();
+            board.advance();
+            for stepped in [&board, &next] {
+                let cells: Vec<bool> = stepped.iter().map(|cell| *cell.state).collect();
+                assert_eq!(cells, expected, "{}x{} at {}", width, height, generation);
+            }
+        }
+        populations.push(board.population());
+    }
+
+    // Pinned, so a change to the reference is noticed too
+    assert_eq!(populations, vec![109, 133, 0, 0]);
+}
+
+// Neighbour offsets of the step that went through `Index` one neighbour at
+// a time
+const OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+// A coordinate of one axis moved onto the board the way that step did
+fn resolve_axis(boundary: BoundaryMode, index: isize, len: usize) -> Option<usize> {
+    let len = len as isize;
+    match boundary {
+        BoundaryMode::Wrap => Some(index.rem_euclid(len) as usize),
+        BoundaryMode::Dead if index < 0 || index >= len => None,
+        BoundaryMode::Mirror => Some(index.clamp(0, len - 1) as usize),
+        _ => Some(index as usize),
+    }
+}
+
+// Next generation as it was found before whole rows were stepped at once:
+// every neighbour of every cell resolved and read through `Index` in turn
+fn per_neighbour_step(board: &LifeBoard) -> Vec<bool> {
+    let mut next = Vec::new();
+    for row in 0..board.height() {
+        for col in 0..board.width() {
+            let mut count = 0;
+            for &(delta_row, delta_col) in &OFFSETS {
+                let r = resolve_axis(board.boundary(), row as isize + delta_row, board.height());
+                let c = resolve_axis(board.boundary(), col as isize + delta_col, board.width());
+                if let (Some(r), Some(c)) = (r, c) {
+                    count += board[r][c] as u8;
+                }
+            }
+            next.push(board.rule().applies(board[row][col], count));
+        }
+    }
+    next
+}
+
+#[test]
+fn rows_step_as_the_per_neighbour_step_did() {
+    let mut rng = SmallRng::seed_from_u64(701);
+    let shapes = [(1, 1), (1, 6), (6, 1), (2, 2), (3, 17), (31, 12), (64, 40)];
+    let rules = ["B3/S23", "B36/S23", "B0/S8", "B2/S", "B1357/S1357"];
+
+    for &(width, height) in &shapes {
+        for boundary in [BoundaryMode::Wrap, BoundaryMode::Dead, BoundaryMode::Mirror] {
+            for rule in rules {
+                let mut board = LifeBoard::new(width, height);
+                board.set_boundary(boundary);
+                board.set_rule(Rule::parse(rule).unwrap());
+                board.randomize(0.4, &mut rng).unwrap();
+                let mut next = board.clone();
+
+                for generation in 1..=8 {
+                    let expected = per_neighbour_step(&board);
+                    board.step(&mut next).unwrap();
+                    #[cfg(feature = "rayon")]
+                    {
+                        let mut parallel = board.clone();
+                        board.par_step(&mut parallel).unwrap();
+                        assert_eq!(parallel, next);
+                    }
+                    board.advance();
+                    for stepped in [&board, &next] {
+                        let cells: Vec<bool> = stepped.iter().map(|cell| *cell.state).collect();
+                        assert_eq!(
+                            cells, expected,
+                            "{}x{} {:?} {} at {}",
+                            width, height, boundary, rule, generation
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn advance_matches_step_as_its_buffer_is_reused() {
+    let mut rng = SmallRng::seed_from_u64(8);
+    let mut board = LifeBoard::new(23, 19);
+    board.randomize(0.4, &mut rng).unwrap();
+    let mut next = LifeBoard::new(23, 19);
+
+    for generation in 1..=60 {
+        board.step(&mut next).unwrap();
+        board.advance();
+        assert_eq!(board, next, "generation {}", generation);
+        assert_eq!(board.generation(), next.generation());
+
+        // The buffer now holds the previous generation, which must not leak
+        // into the next one whatever is changed in between
+        match generation % 20 {
+            5 => board.clear_region(board.bounding_box().unwrap()),
+            10 => board.randomize(0.6, &mut rng).unwrap(),
+            15 => {
+                board.toggle(0, 0).unwrap();
+            }
+            _ => {}
+        }
+        next = LifeBoard::new(23, 19);
+    }
+
+    // And after the board, and with it the buffer, changes size
+    for &(width, height) in &[(40, 7), (5, 5), (23, 19)] {
+        board.resize(width, height, Anchor::Center);
+        board.randomize(0.5, &mut rng).unwrap();
+        for _ in 0..5 {
+            let mut next = LifeBoard::new(width, height);
+            board.step(&mut next).unwrap();
+            board.advance();
+            assert_eq!(board, next, "{}x{}", width, height);
+        }
+    }
+}
+
+fn soup(seed: u64) -> LifeBoard {
+    let mut board = LifeBoard::new(24, 18);
+    board
+        .randomize(0.4, &mut SmallRng::seed_from_u64(seed))
+        .unwrap();
+    board
+}
+
+#[test]
+fn run_calls_back_once_a_generation() {
+    let mut board = soup(87);
+    let mut stepped = board.clone();
+    let mut seen = Vec::new();
+
+    board.run(20, |board, generation| {
+        stepped.advance();
+        assert_eq!(board, &stepped);
+        seen.push(generation);
+        ControlFlow::Continue(())
+    });
+
+    assert_eq!(seen, (1..=20).collect::<Vec<u64>>());
+    assert_eq!(board.generation(), 20);
+}
+
+#[test]
+fn run_stops_when_the_callback_breaks() {
+    let mut board = soup(88);
+    let mut calls = 0;
+
+    board.run(20, |_, generation| {
+        calls += 1;
+        if generation == 7 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+
+    assert_eq!(calls, 7);
+    assert_eq!(board.generation(), 7);
+    let mut expected = soup(88);
+    expected.advance_by(7);
+    assert_eq!(board, expected);
+}
+
+#[test]
+fn run_and_advance_by_match_manual_steps() {
+    let mut manual = soup(89);
+    let mut next = manual.clone();
+    for _ in 0..33 {
+        manual.step(&mut next).unwrap();
+        std::mem::swap(&mut manual, &mut next);
+    }
+
+    let mut run = soup(89);
+    run.run(33, |_, _| ControlFlow::Continue(()));
+    let mut advanced = soup(89);
+    advanced.advance_by(33);
+
+    for board in [&run, &advanced] {
+        assert_eq!(board, &manual);
+        assert_eq!(board.generation(), 33);
+    }
+
+    // Nothing happens for zero generations
+    let mut idle = soup(89);
+    idle.run(0, |_, _| panic!("no generation was stepped"));
+    idle.advance_by(0);
+    assert_eq!(idle, soup(89));
+    assert_eq!(idle.generation(), 0);
+}
+
+#[test]
+fn applying_the_diff_reproduces_the_next_generation() {
+    let mut rng = SmallRng::seed_from_u64(698);
+    for rule in ["B3/S23", "B36/S23", "B2-a/S12"] {
+        for boundary in [BoundaryMode::Wrap, BoundaryMode::Dead, BoundaryMode::Mirror] {
+            let mut board = LifeBoard::new(29, 21);
+            board.set_boundary(boundary);
+            board.set_rule(Rule::parse(rule).unwrap());
+            board.randomize(0.4, &mut rng).unwrap();
+            let mut next = board.clone();
+
+            for generation in 1..=30 {
+                let changes = board.step_diff(&mut next).unwrap();
+                assert!(changes
+                    .windows(2)
+                    .all(|pair| (pair[0].row, pair[0].col) < (pair[1].row, pair[1].col)));
+
+                let mut patched = board.clone();
+                for change in &changes {
+                    assert_ne!(patched[(change.row, change.col)], change.alive);
+                    patched[(change.row, change.col)] = change.alive;
+                }
+                assert_eq!(patched, next, "{} {:?} at {}", rule, boundary, generation);
+                std::mem::swap(&mut board, &mut next);
+            }
+        }
+    }
+}
+
+#[test]
+fn still_lifes_have_an_empty_diff() {
+    let mut board = LifeBoard::new_square(16);
+    board.stamp(&patterns::block(), 1, 1).unwrap();
+    board.stamp(&patterns::beehive(), 8, 3).unwrap();
+    board.stamp(&patterns::loaf(), 3, 9).unwrap();
+    let mut next = board.clone();
+
+    for _ in 0..5 {
+        assert_eq!(board.step_diff(&mut next).unwrap(), vec![]);
+        assert_eq!(next, board);
+        std::mem::swap(&mut board, &mut next);
+    }
+}
+
+#[test]
+fn blinker_diff_has_two_births_and_two_deaths() {
+    let mut board = LifeBoard::new_square(5);
+    board.stamp(&patterns::blinker(), 2, 1).unwrap();
+    let mut next = board.clone();
+
+    let change = |row, col, alive| CellChange { row, col, alive };
+    let flips = [
+        vec![
+            change(1, 2, true),
+            change(2, 1, false),
+            change(2, 3, false),
+            change(3, 2, true),
+        ],
+        vec![
+            change(1, 2, false),
+            change(2, 1, true),
+            change(2, 3, true),
+            change(3, 2, false),
+        ],
+    ];
+    for generation in 0..6 {
+        let changes = board.step_diff(&mut next).unwrap();
+        assert_eq!(
+            changes,
+            flips[generation % 2],
+            "generation {}",
+            generation + 1
+        );
+        std::mem::swap(&mut board, &mut next);
+    }
+}
@@ -0,0 +1,93 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{DensityError, LifeBoard, Symmetry};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn soup(width: usize, height: usize, density: f64, symmetry: Symmetry, seed: u64) -> LifeBoard {
+    let mut board = LifeBoard::new(width, height);
+    board
+        .randomize_symmetric(density, &mut StdRng::seed_from_u64(seed), symmetry)
+        .unwrap();
+    board
+}
+
+#[test]
+fn soups_are_invariant_under_their_symmetry() {
+    for (seed, (width, height)) in [(8, 6), (7, 5), (9, 4), (1, 1), (2, 3)].iter().enumerate() {
+        let (width, height) = (*width, *height);
+        let seed = seed as u64;
+
+        let board = soup(width, height, 0.5, Symmetry::C2, seed);
+        assert_eq!(board.rotate180(), board);
+        let board = soup(width, height, 0.5, Symmetry::D2Horizontal, seed);
+        assert_eq!(board.flip_horizontal(), board);
+        let board = soup(width, height, 0.5, Symmetry::D2Vertical, seed);
+        assert_eq!(board.flip_vertical(), board);
+        let board = soup(width, height, 0.5, Symmetry::D4, seed);
+        assert_eq!(board.flip_horizontal(), board);
+        assert_eq!(board.flip_vertical(), board);
+    }
+
+    for side in 1..10 {
+        let board = soup(side, side, 0.5, Symmetry::C4, side as u64);
+        assert_eq!(board.rotate90(), board);
+    }
+}
+
+#[test]
+fn soups_are_not_more_symmetric_than_asked() {
+    let board = soup(16, 16, 0.5, Symmetry::C2, 1);
+    assert_ne!(board.flip_horizontal(), board);
+    let board = soup(16, 16, 0.5, Symmetry::D2Horizontal, 1);
+    assert_ne!(board.flip_vertical(), board);
+    let board = soup(16, 16, 0.5, Symmetry::D4, 1);
+    assert_ne!(board.rotate90(), board);
+}
+
+#[test]
+fn fundamental_domain_has_requested_density() {
+    // Each domain includes the middle row and column of the odd board
+    let domains = [
+        (Symmetry::C2, 101, 201),
+        (Symmetry::D2Horizontal, 201, 101),
+        (Symmetry::D2Vertical, 101, 201),
+        (Symmetry::D4, 101, 101),
+        (Symmetry::C4, 101, 101),
+    ];
+    for &(symmetry, rows, cols) in &domains {
+        let board = soup(201, 201, 0.3, symmetry, 7);
+        let live = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .filter(|&(row, col)| board[row][col])
+            .count();
+        let density = live as f64 / (rows * cols) as f64;
+        assert!((density - 0.3).abs() < 0.01, "{:?}: {}", symmetry, density);
+    }
+}
+
+#[test]
+fn same_seed_gives_same_soup() {
+    assert_eq!(
+        soup(20, 15, 0.4, Symmetry::D4, 3),
+        soup(20, 15, 0.4, Symmetry::D4, 3)
+    );
+}
+
+#[test]
+fn density_out_of_range_is_rejected() {
+    let mut board = LifeBoard::new_square(4);
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(
+        board.randomize_symmetric(1.5, &mut rng, Symmetry::C2),
+        Err(DensityError(1.5))
+    );
+    assert!(board.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn c4_needs_a_square_board() {
+    soup(5, 4, 0.5, Symmetry::C4, 0);
+}
@@ -0,0 +1,70 @@
+extern crate gameoflife;
+
+use gameoflife::fit_to_terminal;
+#[cfg(feature = "native")]
+use gameoflife::options::{Options, ZoomOut};
+
+#[test]
+fn fits_standard_terminals() {
+    // Each cell is two columns, plus two columns of border and padding on
+    // either side; three rows go to the borders and the line below
+    assert_eq!(fit_to_terminal(80, 24), (38, 21));
+    assert_eq!(fit_to_terminal(81, 24), (38, 21));
+    assert_eq!(fit_to_terminal(82, 24), (39, 21));
+    assert_eq!(fit_to_terminal(200, 60), (98, 57));
+}
+
+#[test]
+fn tiny_terminals_still_get_a_cell() {
+    assert_eq!(fit_to_terminal(0, 0), (1, 1));
+    assert_eq!(fit_to_terminal(5, 3), (1, 1));
+    assert_eq!(fit_to_terminal(6, 4), (1, 1));
+    assert_eq!(fit_to_terminal(8, 5), (2, 2));
+}
+
+#[test]
+fn fitted_board_draws_within_terminal() {
+    let (width, height) = fit_to_terminal(37, 19);
+    let frame = gameoflife::LifeBoard::new(width, height).to_string();
+    let lines: Vec<&str> = frame.lines().collect();
+
+    assert!(lines.len() < 19);
+    assert!(lines.iter().all(|line| line.chars().count() <= 37));
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn explicit_size_is_kept() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    let mut options = Options::parse(args(&[])).unwrap();
+    options.fit_to_terminal(80, 24);
+    assert_eq!((options.width, options.height), (38, 21));
+
+    let mut options = Options::parse(args(&["--width", "100"])).unwrap();
+    options.fit_to_terminal(80, 24);
+    assert_eq!((options.width, options.height), (100, 32));
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn zooming_out_fits_bigger_boards() {
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    let mut options = Options::parse(args(&["--zoom-out", "4"])).unwrap();
+    assert_eq!(options.zoom_out, Some(ZoomOut::By(4)));
+    options.fit_to_terminal(80, 24);
+    assert_eq!((options.width, options.height), (152, 84));
+    assert_eq!(options.zoom_out_for(152, 84, None), 4);
+
+    // Only boards too big for the style are zoomed out
+    let options = Options::parse(args(&["--zoom-out", "auto"])).unwrap();
+    assert_eq!(options.zoom_out_for(38, 21, Some((80, 24))), 1);
+    assert_eq!(options.zoom_out_for(100, 50, Some((80, 24))), 3);
+    assert_eq!(options.zoom_out_for(100, 50, None), 1);
+    let braille = Options::parse(args(&["--zoom-out", "auto", "--style", "braille"])).unwrap();
+    assert_eq!(braille.zoom_out_for(100, 50, Some((80, 24))), 1);
+
+    assert!(Options::parse(args(&["--zoom-out", "0"])).is_err());
+    assert!(Options::parse(args(&["--zoom-out", "far"])).is_err());
+}
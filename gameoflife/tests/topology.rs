@@ -0,0 +1,331 @@
+extern crate gameoflife;
+extern crate rand;
+
+use gameoflife::{patterns, Anchor, BoundaryMode, LifeBoard};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+fn board(boundary: BoundaryMode, row: usize, col: usize) -> LifeBoard {
+    let mut board = LifeBoard::new(6, 8);
+    board.set_boundary(boundary);
+    board.stamp(&patterns::glider(), row, col).unwrap();
+    board
+}
+
+fn live(board: &LifeBoard) -> Vec<(usize, usize)> {
+    board.live_cells().collect()
+}
+
+fn neighbours(board: &LifeBoard, row: usize, col: usize) -> Vec<(usize, usize)> {
+    let mut cells: Vec<_> = board
+        .neighbours(row, col)
+        .map(|(row, col, _)| (row, col))
+        .collect();
+    cells.sort_unstable();
+    cells
+}
+
+#[test]
+fn shifted_torus_neighbours_across_the_seam() {
+    let mut board = LifeBoard::new(3, 4);
+    board.set_boundary(BoundaryMode::ShiftedTorus { offset: 1 });
+
+    // Right of row r is row r + 1 of the left edge, and left of row r is
+    // row r - 1 of the right edge
+    assert_eq!(
+        neighbours(&board, 0, 2),
+        vec![
+            (0, 0),
+            (0, 1),
+            (1, 0),
+            (1, 1),
+            (1, 2),
+            (2, 0),
+            (3, 1),
+            (3, 2)
+        ]
+    );
+    assert_eq!(
+        neighbours(&board, 0, 0),
+        vec![
+            (0, 1),
+            (0, 2),
+            (1, 0),
+            (1, 1),
+            (2, 2),
+            (3, 0),
+            (3, 1),
+            (3, 2)
+        ]
+    );
+}
+
+#[test]
+fn klein_bottle_neighbours_across_the_seam() {
+    let mut board = LifeBoard::new(3, 4);
+    board.set_boundary(BoundaryMode::KleinBottle);
+
+    // Across the left or right edge row r becomes row 3 - r
+    assert_eq!(
+        neighbours(&board, 1, 2),
+        vec![
+            (0, 1),
+            (0, 2),
+            (1, 0),
+            (1, 1),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (3, 0)
+        ]
+    );
+    assert_eq!(
+        neighbours(&board, 0, 0),
+        vec![
+            (0, 1),
+            (0, 2),
+            (1, 0),
+            (1, 1),
+            (2, 2),
+            (3, 0),
+            (3, 1),
+            (3, 2)
+        ]
+    );
+}
+
+#[test]
+fn glider_lands_offset_across_shifted_torus_seam() {
+    let mut board = board(BoundaryMode::ShiftedTorus { offset: 3 }, 0, 3);
+
+    // Halfway across: the column past the right edge is three rows down on
+    // the left edge
+    board.advance_by(4);
+    assert_eq!(live(&board), vec![(1, 5), (3, 4), (3, 5), (5, 0), (6, 0)]);
+
+    // Wholly across, a glider again three rows below where it would be on a
+    // plain torus
+    board.advance_by(8);
+    assert_eq!(live(&board), vec![(0, 0), (0, 1), (0, 2), (6, 1), (7, 2)]);
+}
+
+#[test]
+fn glider_crossing_klein_bottle_seam_is_mirrored() {
+    let mut board = board(BoundaryMode::KleinBottle, 2, 3);
+
+    // Halfway across, with the cells past the right edge upside down
+    board.advance_by(4);
+    assert_eq!(live(&board), vec![(2, 0), (3, 0), (3, 5), (5, 4), (5, 5)]);
+
+    // Wholly across it is a glider flipped top to bottom
+    board.advance_by(8);
+    let mut expected = LifeBoard::new(6, 8);
+    expected.set_boundary(BoundaryMode::KleinBottle);
+    expected
+        .stamp(&patterns::glider().flip_vertical(), 0, 0)
+        .unwrap();
+    assert_eq!(live(&board), live(&expected));
+    assert_eq!(live(&board), vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 1)]);
+
+    // Which now travels up and to the right, over the top edge
+    board.advance_by(4);
+    assert_eq!(live(&board), vec![(0, 3), (1, 2), (7, 1), (7, 2), (7, 3)]);
+}
+
+// Glider three cells from the bottom right corner of an 8 by 8 board,
+// heading for it
+fn glider_near_corner(boundary: BoundaryMode) -> LifeBoard {
+    let mut board = LifeBoard::new_square(8);
+    board.set_boundary(boundary);
+    board.stamp(&patterns::glider(), 3, 3).unwrap();
+    board
+}
+
+#[test]
+fn glider_meeting_dead_edges_settles_into_a_block() {
+    let mut board = glider_near_corner(BoundaryMode::Dead);
+
+    board.advance_by(8);
+    assert_eq!(live(&board), vec![(5, 6), (6, 7), (7, 5), (7, 6), (7, 7)]);
+    board.advance_by(3);
+    let block = vec![(6, 6), (6, 7), (7, 6), (7, 7)];
+    assert_eq!(live(&board), block);
+    board.advance_by(10);
+    assert_eq!(live(&board), block);
+}
+
+#[test]
+fn glider_meeting_mirrored_edges_meets_its_reflections() {
+    let mut board = glider_near_corner(BoundaryMode::Mirror);
+
+    // A mirrored board behaves as one quarter of a torus twice its size
+    // holding the board reflected about its right and bottom edges
+    let mut torus = LifeBoard::new_square(16);
+    for (row, col) in board.live_cells() {
+        for &(r, c) in &[
+            (row, col),
+            (15 - row, col),
+            (row, 15 - col),
+            (15 - row, 15 - col),
+        ] {
+            torus.set(r, c, true).unwrap();
+        }
+    }
+    let quarter = |torus: &LifeBoard| -> Vec<(usize, usize)> {
+        torus
+            .live_cells()
+            .filter(|&(row, col)| row < 8 && col < 8)
+            .collect()
+    };
+
+    for generation in 1..=16 {
+        board.advance();
+        torus.advance();
+        assert_eq!(live(&board), quarter(&torus), "generation {}", generation);
+    }
+    assert_eq!(live(&board), vec![(5, 4), (5, 5), (6, 4), (6, 5)]);
+}
+
+#[test]
+fn glider_crossing_wrapped_edges_comes_back_unchanged() {
+    let mut board = glider_near_corner(BoundaryMode::Wrap);
+    let start = live(&board);
+
+    // Straddling the corner, split over all four sides of the board
+    board.advance_by(12);
+    assert_eq!(live(&board), vec![(0, 0), (0, 6), (0, 7), (6, 7), (7, 0)]);
+
+    board.advance_by(20);
+    assert_eq!(live(&board), start);
+}
+
+// Neighbour of (row, col) by wrapping its coordinates with `%` as each mode
+// describes, the way neighbours were found before the boards kept tables
+fn modulo_neighbour(
+    boundary: BoundaryMode,
+    row: usize,
+    col: usize,
+    delta_row: isize,
+    delta_col: isize,
+    width: usize,
+    height: usize,
+) -> Option<(usize, usize)> {
+    let (width, height) = (width as isize, height as isize);
+    let (row, col) = (row as isize + delta_row, col as isize + delta_col);
+    let wrap = |index: isize, len: isize| (index % len + len) % len;
+    let crossing = if col < 0 {
+        -1
+    } else if col >= width {
+        1
+    } else {
+        0
+    };
+
+    let (row, col) = match boundary {
+        BoundaryMode::Wrap => (wrap(row, height), wrap(col, width)),
+        BoundaryMode::Dead if row < 0 || row >= height || col < 0 || col >= width => {
+            return None
+        }
+        BoundaryMode::Dead => (row, col),
+        BoundaryMode::Mirror => (row.clamp(0, height - 1), col.clamp(0, width - 1)),
+        BoundaryMode::ShiftedTorus { offset } => {
+            (wrap(row + crossing * offset, height), wrap(col, width))
+        }
+        BoundaryMode::KleinBottle if crossing != 0 => {
+            (wrap(height - 1 - row, height), wrap(col, width))
+        }
+        BoundaryMode::KleinBottle => (wrap(row, height), wrap(col, width)),
+    };
+    Some((row as usize, col as usize))
+}
+
+fn modulo_step(board: &LifeBoard) -> Vec<bool> {
+    let (width, height) = (board.width(), board.height());
+    let mut next = Vec::new();
+    for row in 0..height {
+        for col in 0..width {
+            let mut count = 0;
+            for delta_row in -1..=1 {
+                for delta_col in -1..=1 {
+                    if (delta_row, delta_col) == (0, 0) {
+                        continue;
+                    }
+                    let cell = modulo_neighbour(
+                        board.boundary(),
+                        row,
+                        col,
+                        delta_row,
+                        delta_col,
+                        width,
+                        height,
+                    );
+                    if let Some((r, c)) = cell {
+                        count += board[r][c] as u8;
+                    }
+                }
+            }
+            next.push(board.rule().applies(board[row][col], count));
+        }
+    }
+    next
+}
+
+fn cells(board: &LifeBoard) -> Vec<bool> {
+    board.iter().map(|cell| *cell.state).collect()
+}
+
+#[test]
+fn neighbour_tables_step_as_modulo_wrapping_does() {
+    let mut rng = SmallRng::seed_from_u64(702);
+    let modes = [
+        BoundaryMode::Wrap,
+        BoundaryMode::Dead,
+        BoundaryMode::Mirror,
+        BoundaryMode::ShiftedTorus { offset: 3 },
+        BoundaryMode::ShiftedTorus { offset: -5 },
+        BoundaryMode::KleinBottle,
+    ];
+
+    for (index, &boundary) in modes.iter().enumerate() {
+        for &(width, height) in &[(1, 1), (2, 7), (13, 5), (23, 17)] {
+            let mut board = LifeBoard::new(width, height);
+            board.set_boundary(boundary);
+            board.randomize(0.45, &mut rng).unwrap();
+            let mut active = board.clone();
+
+            for generation in 1..=30 {
+                // Partway through, the tables have to follow a new mode and
+                // then a new size
+                if generation == 11 {
+                    let next_mode = modes[(index + 1) % modes.len()];
+                    board.set_boundary(next_mode);
+                    active.set_boundary(next_mode);
+                }
+                if generation == 21 {
+                    board.resize(width + 4, height + 3, Anchor::Center);
+                    active.resize(width + 4, height + 3, Anchor::Center);
+                }
+                let expected = modulo_step(&board);
+                let context = format!("{:?} {}x{} at {}", boundary, width, height, generation);
+
+                let mut next = LifeBoard::new(board.width(), board.height());
+                board.step(&mut next).unwrap();
+                assert_eq!(cells(&next), expected, "step {}", context);
+                let mut diffed = LifeBoard::new(board.width(), board.height());
+                board.step_diff(&mut diffed).unwrap();
+                assert_eq!(cells(&diffed), expected, "step_diff {}", context);
+                #[cfg(feature = "rayon")]
+                {
+                    let mut parallel = LifeBoard::new(board.width(), board.height());
+                    board.par_step(&mut parallel).unwrap();
+                    assert_eq!(cells(&parallel), expected, "par_step {}", context);
+                }
+
+                board.advance();
+                active.advance_active();
+                assert_eq!(cells(&board), expected, "advance {}", context);
+                assert_eq!(cells(&active), expected, "advance_active {}", context);
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+extern crate gameoflife;
+
+use gameoflife::{patterns, LifeBoard};
+
+// A blinker next to a block on a board twice as wide as it is high, three
+// generations on, so the blinker's heat and the block's ages both vary
+fn aged_board() -> LifeBoard {
+    let mut board = LifeBoard::new(10, 5);
+    board.stamp(&patterns::blinker(), 2, 1).unwrap();
+    board.set_max_age(Some(40));
+    board.enable_heat();
+    for _ in 0..3 {
+        board.advance();
+    }
+    board.stamp(&patterns::block(), 1, 6).unwrap();
+    board.advance();
+    board
+}
+
+#[test]
+fn rotating_moves_ages_and_heat_with_their_cells() {
+    let board = aged_board();
+    let rotated = board.rotate90();
+    assert_eq!((rotated.width(), rotated.height()), (5, 10));
+    assert_eq!(rotated.max_age(), Some(40));
+
+    let (width, height) = (board.width(), board.height());
+    for row in 0..height {
+        for col in 0..width {
+            // A quarter turn clockwise takes row, col to col, height - 1 - row
+            let (to_row, to_col) = (col, height - 1 - row);
+            assert_eq!(rotated.get(to_row, to_col), board.get(row, col));
+            assert_eq!(rotated.age(to_row, to_col), board.age(row, col));
+            assert_eq!(
+                rotated.heat()[to_row * height + to_col],
+                board.heat()[row * width + col]
+            );
+        }
+    }
+    let mut ages: Vec<_> = board
+        .live_cells()
+        .map(|(row, col)| board.age(row, col))
+        .collect();
+    ages.sort();
+    ages.dedup();
+    assert!(ages.len() > 1);
+    assert!(board.heat().iter().any(|&count| count > 0));
+
+    let turned = rotated.rotate90().rotate90().rotate90();
+    let ages = |board: &LifeBoard| {
+        board
+            .live_cells()
+            .map(|(row, col)| board.age(row, col))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ages(&turned), ages(&board));
+    assert_eq!(turned.heat(), board.heat());
+}
+
+#[test]
+fn flips_and_transpose_keep_ages_and_heat() {
+    let board = aged_board();
+    for flipped in [
+        board.flip_horizontal().flip_horizontal(),
+        board.flip_vertical().flip_vertical(),
+        board.rotate180().rotate180(),
+        board.transpose().transpose(),
+    ] {
+        assert!(flipped.tracks_ages() && flipped.tracks_heat());
+        assert_eq!(flipped.max_age(), Some(40));
+        for (row, col) in board.live_cells() {
+            assert_eq!(flipped.age(row, col), board.age(row, col));
+        }
+        assert_eq!(flipped.heat(), board.heat());
+    }
+}
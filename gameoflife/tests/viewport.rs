@@ -0,0 +1,174 @@
+extern crate gameoflife;
+
+use gameoflife::{patterns, BoundaryMode, LifeBoard, Viewport};
+
+fn view(row: usize, col: usize, width: usize, height: usize) -> Viewport {
+    Viewport {
+        row,
+        col,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn view_larger_than_board_is_cut_down() {
+    for &boundary in &[BoundaryMode::Dead, BoundaryMode::Mirror] {
+        assert_eq!(view(3, 4, 50, 40).fit(10, 8, boundary), view(0, 0, 10, 8));
+        assert_eq!(
+            view(0, 0, 50, 40).pan(5, -5, 10, 8, boundary),
+            view(0, 0, 10, 8)
+        );
+    }
+    // A wrapping view the size of the board still turns around it
+    assert_eq!(
+        view(3, 4, 50, 40).fit(10, 8, BoundaryMode::Wrap),
+        view(3, 4, 10, 8)
+    );
+    assert_eq!(
+        view(0, 0, 50, 40).pan(-1, -1, 10, 8, BoundaryMode::Wrap),
+        view(7, 9, 10, 8)
+    );
+}
+
+#[test]
+fn dead_edges_stop_the_view() {
+    let start = view(0, 0, 4, 3);
+    assert_eq!(start.pan(-1, -1, 10, 8, BoundaryMode::Dead), start);
+    assert_eq!(
+        start.pan(100, 100, 10, 8, BoundaryMode::Dead),
+        view(5, 6, 4, 3)
+    );
+    assert_eq!(
+        view(9, 9, 4, 3).fit(10, 8, BoundaryMode::Dead),
+        view(5, 6, 4, 3)
+    );
+}
+
+#[test]
+fn joined_edges_wrap_the_view() {
+    let start = view(0, 0, 4, 3);
+    assert_eq!(
+        start.pan(-1, -1, 10, 8, BoundaryMode::Wrap),
+        view(7, 9, 4, 3)
+    );
+    assert_eq!(
+        start.pan(17, 23, 10, 8, BoundaryMode::Wrap),
+        view(1, 3, 4, 3)
+    );
+
+    // Only the plainly joined top and bottom edges of a Klein bottle wrap
+    assert_eq!(
+        start.pan(-1, -1, 10, 8, BoundaryMode::KleinBottle),
+        view(7, 0, 4, 3)
+    );
+    assert_eq!(
+        start.pan(-1, -1, 10, 8, BoundaryMode::ShiftedTorus { offset: 2 }),
+        view(7, 0, 4, 3)
+    );
+}
+
+#[test]
+fn window_across_the_seam_of_a_torus() {
+    let mut board = LifeBoard::new(10, 6);
+    board[(5, 9)] = true;
+    board[(0, 0)] = true;
+    board.enable_ages();
+
+    let window = board.window(view(4, 8, 4, 3));
+    assert_eq!((window.width(), window.height()), (4, 3));
+    assert_eq!(
+        window.live_cells().collect::<Vec<_>>(),
+        vec![(1, 1), (2, 2)]
+    );
+    assert_eq!(window.age(2, 2), Some(0));
+
+    // Without joined edges the view is moved back onto the board
+    board.set_boundary(BoundaryMode::Dead);
+    let window = board.window(view(4, 8, 4, 3));
+    assert_eq!(window.live_cells().collect::<Vec<_>>(), vec![(2, 3)]);
+}
+
+#[test]
+fn positions_in_view_go_round_joined_edges() {
+    let straddling = view(4, 8, 4, 3);
+    assert_eq!(straddling.position_of(5, 9, 10, 6), Some((1, 1)));
+    assert_eq!(straddling.position_of(0, 1, 10, 6), Some((2, 3)));
+    assert_eq!(straddling.position_of(1, 1, 10, 6), None);
+    assert_eq!(straddling.position_of(0, 2, 10, 6), None);
+    assert_eq!(straddling.position_of(6, 0, 10, 6), None);
+}
+
+#[test]
+fn scrolling_moves_the_view_as_little_as_possible() {
+    let start = view(2, 2, 4, 3);
+    assert_eq!(start.scroll_to(3, 4, 20, 10, BoundaryMode::Dead), start);
+    assert_eq!(
+        start.scroll_to(8, 10, 20, 10, BoundaryMode::Dead),
+        view(6, 7, 4, 3)
+    );
+    assert_eq!(
+        start.scroll_to(0, 0, 20, 10, BoundaryMode::Dead),
+        view(0, 0, 4, 3)
+    );
+
+    // Across the seam is the shorter way from the top left to the far corner
+    assert_eq!(
+        view(0, 0, 4, 3).scroll_to(9, 19, 20, 10, BoundaryMode::Wrap),
+        view(9, 19, 4, 3)
+    );
+    assert_eq!(
+        view(0, 0, 4, 3).scroll_to(4, 6, 20, 10, BoundaryMode::Wrap),
+        view(2, 3, 4, 3)
+    );
+}
+
+#[test]
+fn follow_centres_the_live_cells() {
+    let mut board = LifeBoard::new(40, 30);
+    board.set_boundary(BoundaryMode::Dead);
+    board.stamp(&patterns::glider(), 20, 30).unwrap();
+
+    let followed = view(0, 0, 11, 9).follow(&board);
+    assert_eq!(followed, view(17, 26, 11, 9));
+    assert_eq!(board.window(followed).population(), 5);
+
+    // Near an edge the view stays on the board
+    let mut board = LifeBoard::new(40, 30);
+    board.set_boundary(BoundaryMode::Dead);
+    board.stamp(&patterns::glider(), 0, 0).unwrap();
+    assert_eq!(view(5, 5, 11, 9).follow(&board), view(0, 0, 11, 9));
+}
+
+#[test]
+fn follow_keeps_a_pattern_on_the_seam_in_one_piece() {
+    // A glider split across both seams of a torus
+    let mut board = LifeBoard::new(40, 30);
+    board.stamp(&patterns::glider(), 0, 0).unwrap();
+    board.shift(-1, -1);
+
+    let followed = view(0, 0, 5, 5).follow(&board);
+    assert_eq!(followed, view(28, 38, 5, 5));
+    let window = board.window(followed);
+    assert_eq!(window.population(), 5);
+    assert_eq!(
+        window.bounding_box().map(|rect| (rect.width, rect.height)),
+        Some((3, 3))
+    );
+}
+
+#[test]
+fn follow_leaves_an_empty_board_alone() {
+    let board = LifeBoard::new(40, 30);
+    assert_eq!(view(7, 9, 5, 5).follow(&board), view(7, 9, 5, 5));
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn follow_option() {
+    use gameoflife::options::Options;
+    let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+    assert!(!Options::parse(args(&[])).unwrap().follow);
+    assert!(Options::parse(args(&["--follow"])).unwrap().follow);
+}
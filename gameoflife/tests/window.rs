@@ -0,0 +1,138 @@
+extern crate gameoflife;
+
+use gameoflife::interactive::{Command, Key, Session};
+use gameoflife::{LifeBoard, WindowLayout};
+
+#[test]
+fn pixels_map_to_cells_through_the_zoom() {
+    // (window width, window height, zoom, origin)
+    let layouts = [
+        (40, 30, 1, (15, 12)),
+        (40, 30, 4, (0, 3)),
+        (57, 31, 3, (13, 6)),
+        (100, 24, 2, (40, 6)),
+    ];
+
+    for &(window_width, window_height, zoom, origin) in &layouts {
+        let layout = WindowLayout::new(10, 6, window_width, window_height, zoom);
+        assert_eq!(layout.origin(), origin, "zoom {}", zoom);
+
+        let (left, top) = origin;
+        for row in 0..6 {
+            for col in 0..10 {
+                // Every pixel of the cell's square, corners included
+                for &(dx, dy) in &[(0, 0), (zoom - 1, 0), (0, zoom - 1), (zoom - 1, zoom - 1)] {
+                    let x = left + col * zoom + dx;
+                    let y = top + row * zoom + dy;
+                    assert_eq!(layout.cell_at(x, y), Some((row, col)), "zoom {}", zoom);
+                }
+            }
+        }
+
+        if left > 0 {
+            assert_eq!(layout.cell_at(left - 1, top), None);
+        }
+        if top > 0 {
+            assert_eq!(layout.cell_at(left, top - 1), None);
+        }
+        assert_eq!(layout.cell_at(left + 10 * zoom, top), None);
+        assert_eq!(layout.cell_at(left, top + 6 * zoom), None);
+        assert_eq!(layout.cell_at(window_width, 0), None);
+    }
+}
+
+#[test]
+fn boards_bigger_than_the_window_are_cut_off() {
+    let layout = WindowLayout::new(10, 6, 25, 10, 3);
+    assert_eq!(layout.origin(), (0, 0));
+    assert_eq!(layout.cell_at(24, 9), Some((3, 8)));
+    assert_eq!(layout.cell_at(25, 9), None);
+}
+
+#[test]
+fn fit_picks_the_largest_zoom_that_shows_the_whole_board() {
+    assert_eq!(WindowLayout::fit(10, 6, 800, 600).zoom(), 80);
+    assert_eq!(WindowLayout::fit(100, 100, 640, 480).zoom(), 4);
+    assert_eq!(WindowLayout::fit(1000, 10, 640, 480).zoom(), 1);
+    assert_eq!(WindowLayout::fit(0, 0, 640, 480).zoom(), 1);
+}
+
+#[test]
+fn frames_are_scaled_and_centred() {
+    let mut board = LifeBoard::new(2, 1);
+    board.set(0, 1, true).unwrap();
+    let mut frame = vec![0; 2 * 4];
+    board.write_rgba(&mut frame, [1, 1, 1, 1], [2, 2, 2, 2]);
+
+    // Cells are 2x2 squares, with a pixel of background on every side
+    let layout = WindowLayout::new(2, 1, 6, 4, 2);
+    let mut buf = vec![0; 6 * 4 * 4];
+    layout.draw(&frame, &mut buf, [9, 9, 9, 9]);
+
+    let pixels: Vec<u8> = buf.chunks(4).map(|pixel| pixel[0]).collect();
+    #[rustfmt::skip]
+    let expected = vec![
+        9, 9, 9, 9, 9, 9,
+        9, 2, 2, 1, 1, 9,
+        9, 2, 2, 1, 1, 9,
+        9, 9, 9, 9, 9, 9,
+    ];
+    assert_eq!(pixels, expected);
+}
+
+#[test]
+fn plus_and_minus_change_the_speed() {
+    let session = Session::default();
+    for &(key, command) in &[
+        ('+', Command::Faster),
+        ('=', Command::Faster),
+        ('-', Command::Slower),
+    ] {
+        let (_, found) = session.handle_key(Key::Char(key), 10, 10);
+        assert_eq!(found, Some(command));
+    }
+}
+
+#[test]
+fn b_steps_back_only_while_paused() {
+    let running = Session::default();
+    assert_eq!(running.handle_key(Key::Char('b'), 10, 10).1, None);
+
+    let paused = Session {
+        paused: true,
+        ..Session::default()
+    };
+    assert_eq!(
+        paused.handle_key(Key::Char('b'), 10, 10).1,
+        Some(Command::StepBack)
+    );
+}
+
+#[test]
+fn wasd_pans_the_view() {
+    let session = Session::default();
+    for &(key, rows, cols) in &[('w', -1, 0), ('a', 0, -1), ('s', 1, 0), ('d', 0, 1)] {
+        let (next, found) = session.handle_key(Key::Char(key), 10, 10);
+        assert_eq!(found, Some(Command::Pan { rows, cols }));
+        assert_eq!(next, session);
+    }
+}
+
+#[test]
+fn clicks_toggle_the_cell_under_the_pointer() {
+    let layout = WindowLayout::new(10, 6, 57, 31, 3);
+    let session = Session {
+        paused: true,
+        ..Session::default()
+    };
+
+    // Origin (13, 6), so the cell at row 2, column 4 spans x 25..28, y 12..15
+    let (next, command) = layout.click(session, 27, 12);
+    assert_eq!(command, Some(Command::Toggle { row: 2, col: 4 }));
+    assert_eq!((next.cursor_row, next.cursor_col), (2, 4));
+    assert!(next.paused);
+
+    for &(x, y) in &[(12, 6), (13, 5), (43, 6), (13, 24), (57, 0)] {
+        assert_eq!(layout.click(session, x, y), (session, None), "{} {}", x, y);
+    }
+}
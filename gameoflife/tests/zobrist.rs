@@ -0,0 +1,172 @@
+extern crate gameoflife;
+extern crate rand;
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use gameoflife::{patterns, BoundaryMode, LifeBoard, LtlRule, Noise, Rect, Rule, RunOutcome};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Hash of a fresh board with the same cells, worked out from scratch
+fn fresh_hash(board: &LifeBoard) -> u64 {
+    let mut fresh = LifeBoard::new(board.width(), board.height());
+    for (row, col) in board.live_cells() {
+        fresh[(row, col)] = true;
+    }
+    fresh.zobrist_hash()
+}
+
+fn random_board(rng: &mut StdRng) -> LifeBoard {
+    let mut board = LifeBoard::new(rng.gen_range(1..30), rng.gen_range(1..30));
+    board.set_boundary(
+        [
+            BoundaryMode::Wrap,
+            BoundaryMode::Dead,
+            BoundaryMode::KleinBottle,
+        ][rng.gen_range(0..3)],
+    );
+    board.set_rule(Rule::parse(["B3/S23", "B36/S23", "B2/S"][rng.gen_range(0..3)]).unwrap());
+    board.randomize(0.4, rng).unwrap();
+    board
+}
+
+#[test]
+fn incremental_hash_matches_recomputation() {
+    for seed in 0..30 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut board = random_board(&mut rng);
+        let mut next = LifeBoard::new(board.width(), board.height());
+
+        for _ in 0..40 {
+            // Edits drop the hash, so it is asked for again before every move
+            assert_eq!(board.keep_zobrist_hash(), fresh_hash(&board));
+            match rng.gen_range(0..9) {
+                0 => board.advance(),
+                1 => board.advance_active(),
+                2 => {
+                    board.step(&mut next).unwrap();
+                    std::mem::swap(&mut board, &mut next);
+                }
+                3 => {
+                    board.step_diff(&mut next).unwrap();
+                    std::mem::swap(&mut board, &mut next);
+                }
+                4 => board.advance_ltl(&LtlRule::bugs()),
+                5 => board.set_max_age(Some(rng.gen_range(0..4))),
+                6 => board.advance_noisy(Noise::flip(0.05).unwrap(), &mut rng),
+                7 => {
+                    let (row, col) = (
+                        rng.gen_range(0..board.height()),
+                        rng.gen_range(0..board.width()),
+                    );
+                    board.toggle(row, col).unwrap();
+                }
+                _ => {
+                    let rect = Rect {
+                        row: 1,
+                        col: 1,
+                        width: 3,
+                        height: 3,
+                    };
+                    board.randomize_region(rect, 0.5, &mut rng).unwrap();
+                }
+            }
+            assert_eq!(board.zobrist_hash(), fresh_hash(&board));
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_stepping_keeps_hash() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut board = random_board(&mut rng);
+    board.keep_zobrist_hash();
+    for _ in 0..20 {
+        board.par_advance();
+        assert_eq!(board.zobrist_hash(), fresh_hash(&board));
+    }
+}
+
+#[cfg(feature = "simd")]
+#[test]
+fn simd_stepping_keeps_hash() {
+    let mut rng = StdRng::seed_from_u64(4);
+    let mut board = random_board(&mut rng);
+    board.set_max_age(Some(2));
+    board.keep_zobrist_hash();
+    for _ in 0..20 {
+        board.simd_advance();
+        assert_eq!(board.zobrist_hash(), fresh_hash(&board));
+    }
+}
+
+#[test]
+fn hash_depends_only_on_cells() {
+    let mut a = LifeBoard::new_square(8);
+    a.stamp(&patterns::glider(), 1, 1).unwrap();
+    let mut b = a.clone();
+    b.set_rule(Rule::parse("B36/S23").unwrap());
+    b.advance();
+    b[(0, 0)] = true;
+    b.clear_region(Rect {
+        row: 0,
+        col: 0,
+        width: 8,
+        height: 8,
+    });
+    b.stamp(&patterns::glider(), 1, 1).unwrap();
+
+    assert_eq!(a.zobrist_hash(), b.zobrist_hash());
+    assert_eq!(LifeBoard::new_square(8).zobrist_hash(), 0);
+}
+
+#[test]
+fn hashes_are_stable_across_runs() {
+    // Hashes may be kept with saved states, so a cell's key must never
+    // change. The first is the first output of SplitMix64 from the seed.
+    let mut board = LifeBoard::new_square(8);
+    board[(0, 0)] = true;
+    assert_eq!(board.zobrist_hash(), 0xc0e1_6b16_3a85_a4dc);
+    board.stamp(&patterns::glider(), 2, 2).unwrap();
+    board.advance_by(4);
+    assert_eq!(board.zobrist_hash(), fresh_hash(&board));
+}
+
+// Cycle detection as it was before Zobrist hashing, hashing every cell of
+// every generation
+fn run_until_cycle_rehashing(board: &mut LifeBoard, max_generations: usize) -> RunOutcome {
+    let mut seen = HashMap::new();
+    for steps in 0..=max_generations {
+        let mut hasher = DefaultHasher::new();
+        board.hash(&mut hasher);
+        if let Some(&start) = seen.get(&hasher.finish()) {
+            return match board.generation() - start {
+                1 => RunOutcome::Stable { start },
+                period => RunOutcome::Oscillating { period, start },
+            };
+        }
+        seen.insert(hasher.finish(), board.generation());
+        if steps == max_generations {
+            break;
+        }
+        board.advance();
+    }
+
+    RunOutcome::MaxedOut
+}
+
+#[test]
+fn cycle_detection_is_unchanged() {
+    for seed in 0..40 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut board = random_board(&mut rng);
+        let mut expected = board.clone();
+
+        let outcome = board.run_until_cycle(200);
+        assert_eq!(outcome, run_until_cycle_rehashing(&mut expected, 200));
+        assert_eq!(board, expected);
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use keyvaluemap::KeyValueMap;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+const SIZES: &[usize] = &[10_000, 100_000, 1_000_000];
+
+fn keys(size: usize, random: bool) -> Vec<u64> {
+    let mut keys: Vec<u64> = (0..size as u64).collect();
+    if random {
+        keys.shuffle(&mut StdRng::seed_from_u64(0x5eed));
+    }
+    keys
+}
+
+fn kv_map(keys: &[u64]) -> KeyValueMap<u64, u64> {
+    let mut map = KeyValueMap::new();
+    for &k in keys {
+        map.insert(k, k);
+    }
+    map
+}
+
+fn btree_map(keys: &[u64]) -> BTreeMap<u64, u64> {
+    keys.iter().map(|&k| (k, k)).collect()
+}
+
+fn insert(c: &mut Criterion) {
+    for &(name, random) in &[("insert/sequential", false), ("insert/random", true)] {
+        let mut group = c.benchmark_group(name);
+        group.sample_size(10);
+        for &size in SIZES {
+            let keys = keys(size, random);
+            group.bench_with_input(BenchmarkId::new("KeyValueMap", size), &keys, |b, keys| {
+                b.iter(|| kv_map(keys))
+            });
+            group.bench_with_input(BenchmarkId::new("BTreeMap", size), &keys, |b, keys| {
+                b.iter(|| btree_map(keys))
+            });
+        }
+        group.finish();
+    }
+}
+
+fn get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get/random");
+    group.sample_size(10);
+    for &size in SIZES {
+        let keys = keys(size, true);
+        let map = kv_map(&keys);
+        let btree = btree_map(&keys);
+        group.bench_with_input(BenchmarkId::new("KeyValueMap", size), &keys, |b, keys| {
+            b.iter(|| {
+                for k in keys {
+                    black_box(map.find(k));
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("BTreeMap", size), &keys, |b, keys| {
+            b.iter(|| {
+                for k in keys {
+                    black_box(btree.get(k));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn remove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("remove/random");
+    group.sample_size(10);
+    for &size in SIZES {
+        let keys = keys(size, true);
+        group.bench_with_input(BenchmarkId::new("KeyValueMap", size), &keys, |b, keys| {
+            b.iter_batched(
+                || kv_map(keys),
+                |mut map| {
+                    for &k in keys {
+                        map.delete(k);
+                    }
+                    map
+                },
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("BTreeMap", size), &keys, |b, keys| {
+            b.iter_batched(
+                || btree_map(keys),
+                |mut map| {
+                    for k in keys {
+                        map.remove(k);
+                    }
+                    map
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn iterate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iterate");
+    group.sample_size(10);
+    for &size in SIZES {
+        let keys = keys(size, true);
+        let map = kv_map(&keys);
+        let btree = btree_map(&keys);
+        group.bench_function(BenchmarkId::new("KeyValueMap", size), |b| {
+            b.iter(|| map.iter().map(|(_, v)| *v).sum::<u64>())
+        });
+        group.bench_function(BenchmarkId::new("BTreeMap", size), |b| {
+            b.iter(|| btree.values().sum::<u64>())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, insert, get, remove, iterate);
+criterion_main!(benches);
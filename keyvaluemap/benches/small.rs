@@ -0,0 +1,39 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use keyvaluemap::KeyValueMap;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+const SIZES: &[usize] = &[4, 16, 64];
+
+fn keys(size: usize) -> Vec<u64> {
+    let mut keys: Vec<u64> = (0..size as u64).collect();
+    keys.shuffle(&mut StdRng::seed_from_u64(0x5eed));
+    keys
+}
+
+// Compares the sorted vector store of small maps against always using the tree
+fn small(c: &mut Criterion) {
+    let mut group = c.benchmark_group("small/insert_get");
+    for &size in SIZES {
+        let keys = keys(size);
+        for &(name, capacity) in &[("vec", size), ("tree", 0)] {
+            group.bench_with_input(BenchmarkId::new(name, size), &keys, |b, keys| {
+                b.iter(|| {
+                    let mut map = KeyValueMap::with_small_capacity(capacity);
+                    for &k in keys {
+                        map.insert(k, k);
+                    }
+                    for k in keys {
+                        black_box(map.find(k));
+                    }
+                    map
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, small);
+criterion_main!(benches);
@@ -0,0 +1,102 @@
+use crate::KeyValueMap;
+
+/// One-to-one map that can be queried from either side.
+///
+/// Every pair is stored twice, once in a key to value map and once in a value
+/// to key map. All mutations go through both maps so each forward entry always
+/// has a matching reverse entry.
+#[derive(Debug)]
+pub struct BiMap<
+    K: std::cmp::PartialOrd + std::cmp::PartialEq,
+    V: std::cmp::PartialOrd + std::cmp::PartialEq,
+> {
+    forward: KeyValueMap<K, V>,
+    reverse: KeyValueMap<V, K>,
+}
+
+impl<K, V> BiMap<K, V>
+where
+    K: std::cmp::PartialOrd + std::cmp::PartialEq + Clone,
+    V: std::cmp::PartialOrd + std::cmp::PartialEq + Clone,
+{
+    pub fn new() -> BiMap<K, V> {
+        BiMap {
+            forward: KeyValueMap::new(),
+            reverse: KeyValueMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.forward.is_empty()
+    }
+
+    /// Inserts the pair, first removing any existing pair that shares its key
+    /// or its value. The removed pairs are returned.
+    pub fn insert(&mut self, key: K, value: V) -> Vec<(K, V)> {
+        let mut removed = Vec::new();
+
+        if self.get_by_key(&key) == Some(&value) {
+            return removed;
+        }
+
+        if let Some(pair) = self.remove_by_key(key.clone()) {
+            removed.push(pair);
+        }
+        if let Some(pair) = self.remove_by_value(value.clone()) {
+            removed.push(pair);
+        }
+
+        self.forward.insert(key.clone(), value.clone());
+        self.reverse.insert(value, key);
+
+        removed
+    }
+
+    pub fn get_by_key(&self, key: &K) -> Option<&V> {
+        self.forward.find(key).and_then(|pair| pair.value)
+    }
+
+    pub fn get_by_value(&self, value: &V) -> Option<&K> {
+        self.reverse.find(value).and_then(|pair| pair.value)
+    }
+
+    pub fn remove_by_key(&mut self, key: K) -> Option<(K, V)> {
+        let pair = self.forward.delete(key)?;
+        let value = pair.value.unwrap();
+        self.reverse.delete(value.clone());
+
+        Some((pair.key, value))
+    }
+
+    pub fn remove_by_value(&mut self, value: V) -> Option<(K, V)> {
+        let pair = self.reverse.delete(value)?;
+        let key = pair.value.unwrap();
+        self.forward.delete(key.clone());
+
+        Some((key, pair.key))
+    }
+
+    /// Iterates over all pairs in ascending key order.
+    pub fn iter_by_key(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.forward.iter()
+    }
+
+    /// Iterates over all pairs in ascending value order.
+    pub fn iter_by_value(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.reverse.iter().map(|(value, key)| (key, value))
+    }
+}
+
+impl<K, V> Default for BiMap<K, V>
+where
+    K: std::cmp::PartialOrd + std::cmp::PartialEq + Clone,
+    V: std::cmp::PartialOrd + std::cmp::PartialEq + Clone,
+{
+    fn default() -> BiMap<K, V> {
+        BiMap::new()
+    }
+}
@@ -0,0 +1,65 @@
+use crate::KeyValueMap;
+
+/// Position in a map's key order that does not borrow the map, so the map can
+/// be changed between calls to `next`.
+///
+/// The cursor remembers the last key it yielded, along with the path an
+/// in-order walk of the map has left to take. While the map is unchanged it
+/// carries on along that path, so stepping is amortised O(1) like an iterator.
+/// Once the map's generation moves on it re-seeks to the smallest key greater
+/// than the last one yielded. Because of that:
+///
+/// - entries inserted behind the cursor are not visited,
+/// - entries inserted ahead of the cursor are visited,
+/// - entries removed before the cursor reaches them are skipped,
+/// - no key is ever yielded twice, even if it is removed and inserted again.
+///
+/// Any mutable access to the map counts as a change, including `find_mut` and
+/// inserts or deletes that fail. Handing the cursor a different map makes it
+/// re-seek by its last key in that map.
+#[derive(Debug, Clone)]
+pub struct StableCursor<K> {
+    last: Option<K>,
+    // Map and generation the path was taken from
+    map: u64,
+    generation: u64,
+    // Rank of the next entry to yield
+    rank: usize,
+    path: Vec<usize>,
+}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq + Clone> StableCursor<K> {
+    /// Creates a cursor positioned before the first entry.
+    pub fn new() -> StableCursor<K> {
+        StableCursor {
+            last: None,
+            map: 0,
+            generation: 0,
+            rank: 0,
+            path: Vec::new(),
+        }
+    }
+
+    /// Yields the entry following the last one yielded, or `None` once the
+    /// cursor has passed the end of the map.
+    pub fn next<'a, V>(&mut self, map: &'a KeyValueMap<K, V>) -> Option<(&'a K, &'a V)> {
+        if self.map != map.id() || self.generation != map.generation() {
+            self.rank = map.seek_path(self.last.as_ref(), &mut self.path);
+            self.map = map.id();
+            self.generation = map.generation();
+        }
+
+        // SAFETY: the path was taken from this map at its current generation
+        let (key, value) = unsafe { map.next_on_path(self.rank, &mut self.path) }?;
+        self.last = Some(key.clone());
+        self.rank += 1;
+
+        Some((key, value))
+    }
+}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq + Clone> Default for StableCursor<K> {
+    fn default() -> StableCursor<K> {
+        StableCursor::new()
+    }
+}
@@ -0,0 +1,1078 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Write};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+use crate::observer::Observer;
+
+mod bimap;
+mod cursor;
+mod lru;
+mod merge_join;
+mod observer;
+mod range_map;
+mod sharded;
+
+pub use crate::bimap::BiMap;
+pub use crate::cursor::StableCursor;
+pub use crate::lru::LruKeyValueCache;
+pub use crate::merge_join::{merge_join, EitherOrBoth, MergeJoin};
+pub use crate::observer::MapEvent;
+pub use crate::range_map::{RangeMap, RangeMapError};
+pub use crate::sharded::ShardedKeyValueMap;
+
+#[derive(Debug)]
+struct TreeNode<T> {
+    value: T,
+    left: Option<Box<TreeNode<T>>>,
+    right: Option<Box<TreeNode<T>>>,
+    level: usize,
+    // Number of nodes in the subtree, for order statistics
+    size: usize,
+}
+
+impl<T> TreeNode<T> {
+    fn leaf(value: T) -> Box<TreeNode<T>> {
+        Box::new(TreeNode {
+            value,
+            left: None,
+            right: None,
+            level: 1,
+            size: 1,
+        })
+    }
+}
+
+fn size<T>(node: &Option<Box<TreeNode<T>>>) -> usize {
+    node.as_ref().map_or(0, |n| n.size)
+}
+
+fn update_size<T>(t: &mut TreeNode<T>) {
+    t.size = 1 + size(&t.left) + size(&t.right);
+}
+
+// Source: https://en.wikipedia.org/wiki/AA_tree
+// function skew is
+//     input: T, a node representing an AA tree that needs to be rebalanced.
+//     output: Another node representing the rebalanced AA tree.
+//
+//     if nil(T) then
+//         return Nil
+//     else if nil(left(T)) then
+//         return T
+//     else if level(left(T)) == level(T) then
+//         Swap the pointers of horizontal left links.
+//         L = left(T)
+//         left(T) := right(L)
+//         right(L) := T
+//         return L
+//     else
+//         return T
+//     end if
+// end function
+
+fn skew<T>(root: Option<Box<TreeNode<T>>>) -> Option<Box<TreeNode<T>>> {
+    root.map(|mut t| {
+        skew_node(&mut t);
+        t
+    })
+}
+
+// Rotates in place so the common case of no rotation leaves the links
+// untouched. Returns whether it rotated.
+fn skew_node<T>(t: &mut Box<TreeNode<T>>) -> bool {
+    match &t.left {
+        Some(l) if l.level == t.level => {}
+        _ => return false,
+    }
+
+    let mut l = t.left.take().unwrap();
+    t.left = l.right.take();
+    update_size(t);
+    std::mem::swap(t, &mut l);
+    t.right = Some(l);
+    update_size(t);
+    true
+}
+
+// function split is
+//     input: T, a node representing an AA tree that needs to be rebalanced.
+//     output: Another node representing the rebalanced AA tree.
+//
+//     if nil(T) then
+//         return Nil
+//     else if nil(right(T)) or  nil(right(right(T))) then
+//         return T
+//     else if level(T) == level(right(right(T))) then
+//         We have two horizontal right links.  Take the middle node, elevate it, and return it.
+//         R = right(T)
+//         right(T) := left(R)
+//         left(R) := T
+//         level(R) := level(R) + 1
+//         return R
+//     else
+//         return T
+//     end if
+// end function
+
+fn split<T>(root: Option<Box<TreeNode<T>>>) -> Option<Box<TreeNode<T>>> {
+    root.map(|mut t| {
+        split_node(&mut t);
+        t
+    })
+}
+
+// The right child's level is checked first, as it bounds its own right
+// child's and is usually enough to tell nothing needs to change
+fn split_node<T>(t: &mut Box<TreeNode<T>>) {
+    match &t.right {
+        Some(r) if r.level == t.level => {}
+        _ => return,
+    }
+    match &t.right.as_ref().unwrap().right {
+        Some(rr) if rr.level == t.level => {}
+        _ => return,
+    }
+
+    let mut r = t.right.take().unwrap();
+    t.right = r.left.take();
+    update_size(t);
+    r.level += 1;
+    std::mem::swap(t, &mut r);
+    t.left = Some(r);
+    update_size(t);
+}
+
+// function insert is
+//     input: X, the value to be inserted, and T, the root of the tree to insert it into.
+//     output: A balanced version T including X.
+
+//     Do the normal binary tree insertion procedure. Set the result of the
+//     recursive call to the correct child in case a new node was created or the
+//     root of the subtree changes.
+//     if nil(T) then
+//         Create a new leaf node with X.
+//         return node(X, 1, Nil, Nil)
+//     else if X < value(T) then
+//         left(T) := insert(X, left(T))
+//     else if X > value(T) then
+//         right(T) := insert(X, right(T))
+//     end if
+//     Note that the case of X == value(T) is unspecified. As given, an insert
+//     will have no effect. The implementor may desire different behavior.
+
+//     Perform skew and then split. The conditionals that determine whether or
+//     not a rotation will occur or not are inside of the procedures, as given
+//     above.
+//     T := skew(T)
+//     T := split(T)
+
+//     return T
+// end function
+
+fn insert<T: std::cmp::PartialEq + std::cmp::PartialOrd>(
+    root: Option<Box<TreeNode<T>>>,
+    x: Box<TreeNode<T>>,
+) -> (bool, Option<Box<TreeNode<T>>>) {
+    let mut t = match root {
+        None => return (true, Some(x)),
+        Some(t) => t,
+    };
+
+    // Only the side that was inserted into can have broken the levels at T,
+    // so the checks that would read the other, untouched side are skipped.
+    // Going left, the right side only needs splitting if skew rotated the
+    // left child up; going right, the left child is still below T's level.
+    let res = match x.value.partial_cmp(&t.value) {
+        Some(Ordering::Less) => {
+            let (res, sub) = insert(t.left, x);
+            t.left = sub;
+            t.size += res as usize;
+            if skew_node(&mut t) {
+                split_node(&mut t);
+            }
+            res
+        }
+        Some(Ordering::Greater) => {
+            let (res, sub) = insert(t.right, x);
+            t.right = sub;
+            t.size += res as usize;
+            split_node(&mut t);
+            res
+        }
+        _ => false,
+    };
+
+    (res, Some(t))
+}
+
+// Retrieving a predecessor is simply a matter of following one left link and then all of the remaining right links.
+// We implement predecessor as a fused delete operation
+
+fn predecessor<T>(mut t: Box<TreeNode<T>>) -> (Option<Box<TreeNode<T>>>, Box<TreeNode<T>>) {
+    let (mut tree, deleted) = if t.right.is_none() {
+        let mut res = None;
+        std::mem::swap(&mut res, &mut t.left);
+        return (res, t);
+    } else {
+        let (sub, succ) = predecessor(t.right.unwrap());
+        t.right = sub;
+        (t, succ)
+    };
+
+    // Rebalance subtree
+    update_size(&mut tree);
+    tree = skew(decrease_level(Some(tree))).unwrap();
+    tree.right = skew(tree.right);
+    if let Some(mut r) = tree.right {
+        r.right = skew(r.right);
+        tree.right = Some(r);
+    }
+    tree = split(Some(tree)).unwrap();
+    tree.right = split(tree.right);
+
+    (Some(tree), deleted)
+}
+
+// Retrieving a successor is simply a matter of following one right link and then all of the remaining left links.
+// We implement sucessor as a fused delete operation
+
+fn successor<T>(mut t: Box<TreeNode<T>>) -> (Option<Box<TreeNode<T>>>, Box<TreeNode<T>>) {
+    let (mut tree, deleted) = if t.left.is_none() {
+        let mut res = None;
+        std::mem::swap(&mut res, &mut t.right);
+        return (res, t);
+    } else {
+        let (sub, succ) = successor(t.left.unwrap());
+        t.left = sub;
+        (t, succ)
+    };
+
+    // Rebalance subtree
+    update_size(&mut tree);
+    tree = skew(decrease_level(Some(tree))).unwrap();
+    tree.right = skew(tree.right);
+    if let Some(mut r) = tree.right {
+        r.right = skew(r.right);
+        tree.right = Some(r);
+    }
+    tree = split(Some(tree)).unwrap();
+    tree.right = split(tree.right);
+
+    (Some(tree), deleted)
+}
+
+// function decrease_level is
+//     input: T, a tree for which we want to remove links that skip levels.
+//     output: T with its level decreased.
+
+//     should_be = min(level(left(T)), level(right(T))) + 1
+//     if should_be < level(T) then
+//         level(T) := should_be
+//         if should_be < level(right(T)) then
+//             level(right(T)) := should_be
+//         end if
+//     end if
+//     return T
+// end function
+fn decrease_level<T>(root: Option<Box<TreeNode<T>>>) -> Option<Box<TreeNode<T>>> {
+    let mut t = root.unwrap();
+
+    let level = |node: &Option<Box<TreeNode<T>>>| match node {
+        None => 0,
+        Some(n) => n.level,
+    };
+
+    let new_level = std::cmp::min(level(&t.left), level(&t.right)) + 1;
+    if new_level < t.level {
+        t.level = new_level;
+        if let Some(mut r) = t.right {
+            if new_level < r.level {
+                r.level = new_level;
+            }
+            t.right = Some(r);
+        }
+    }
+
+    Some(t)
+}
+
+// function delete is
+//     input: X, the value to delete, and T, the root of the tree from which it should be deleted.
+//     output: T, balanced, without the value X.
+
+//     if nil(T) then
+//         return T
+//     else if X > value(T) then
+//         right(T) := delete(X, right(T))
+//     else if X < value(T) then
+//         left(T) := delete(X, left(T))
+//     else
+//         If we're a leaf, easy, otherwise reduce to leaf case.
+//         if leaf(T) then
+//             return Nil
+//         else if nil(left(T)) then
+//             L := successor(T)
+//             right(T) := delete(value(L), right(T))
+//             value(T) := value(L)
+//         else
+//             L := predecessor(T)
+//             left(T) := delete(value(L), left(T))
+//             value(T) := value(L)
+//         end if
+//     end if
+
+//     Rebalance the tree. Decrease the level of all nodes in this level if
+//     necessary, and then skew and split all nodes in the new level.
+//     T := decrease_level(T)
+//     T := skew(T)
+//     right(T) := skew(right(T))
+//     if not nil(right(T))
+//         right(right(T)) := skew(right(right(T)))
+//     end if
+//     T := split(T)
+//     right(T) := split(right(T))
+//     return T
+// end function
+
+#[allow(clippy::type_complexity)]
+fn delete<T: std::cmp::PartialEq + std::cmp::PartialOrd>(
+    root: Option<Box<TreeNode<T>>>,
+    x: &T,
+) -> (Option<Box<TreeNode<T>>>, Option<Box<TreeNode<T>>>) {
+    let (mut tree, deleted) = match root {
+        None => {
+            return (None, None);
+        }
+        Some(mut t) => {
+            if *x < t.value {
+                let (sub, deleted) = delete(t.left, x);
+                t.left = sub;
+                (t, deleted)
+            } else if *x > t.value {
+                let (sub, deleted) = delete(t.right, x);
+                t.right = sub;
+                (t, deleted)
+            } else if t.left.is_none() && t.right.is_none() {
+                return (None, Some(t));
+            } else if t.left.is_none() {
+                // Find next largest item for replacement
+                let r = t.right;
+                let (sub, mut succ) = successor(r.unwrap());
+                t.right = sub;
+
+                std::mem::swap(&mut t.value, &mut succ.value);
+
+                (t, Some(succ))
+            } else {
+                // Find next smallest item for replacement
+                let l = t.left;
+                let (sub, mut pre) = predecessor(l.unwrap());
+                t.left = sub;
+
+                std::mem::swap(&mut t.value, &mut pre.value);
+
+                (t, Some(pre))
+            }
+        }
+    };
+
+    // Rebalance subtree
+    update_size(&mut tree);
+    tree = skew(decrease_level(Some(tree))).unwrap();
+    tree.right = skew(tree.right);
+    if let Some(mut r) = tree.right {
+        r.right = skew(r.right);
+        tree.right = Some(r);
+    }
+    tree = split(Some(tree)).unwrap();
+    tree.right = split(tree.right);
+
+    (Some(tree), deleted)
+}
+
+#[derive(Debug)]
+pub struct KeyValuePair<K: std::cmp::PartialOrd + std::cmp::PartialEq, V> {
+    pub key: K,
+    pub value: Option<V>,
+}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq, V> std::cmp::PartialOrd for KeyValuePair<K, V> {
+    fn partial_cmp(&self, other: &KeyValuePair<K, V>) -> Option<Ordering> {
+        if self.key == other.key {
+            Some(Ordering::Equal)
+        } else if self.key < other.key {
+            Some(Ordering::Less)
+        } else {
+            Some(Ordering::Greater)
+        }
+    }
+}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq, V> PartialEq for KeyValuePair<K, V> {
+    fn eq(&self, other: &KeyValuePair<K, V>) -> bool {
+        self.key == other.key
+    }
+}
+
+/// Number of entries a map holds in its sorted vector before being promoted to a tree.
+pub const DEFAULT_SMALL_CAPACITY: usize = 16;
+
+#[derive(Debug)]
+enum Store<K: std::cmp::PartialOrd + std::cmp::PartialEq, V> {
+    Small(Vec<KeyValuePair<K, V>>),
+    Tree(Option<Box<TreeNode<KeyValuePair<K, V>>>>),
+}
+
+// Binary search over the sorted small store, mirroring the comparisons used by the tree
+fn search<K: std::cmp::PartialOrd + std::cmp::PartialEq, V>(
+    entries: &[KeyValuePair<K, V>],
+    key: &K,
+) -> Result<usize, usize> {
+    entries.binary_search_by(|pair| {
+        if pair.key < *key {
+            Ordering::Less
+        } else if pair.key > *key {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    })
+}
+
+/// Ordered map backed by an AA tree.
+///
+/// Small maps keep their entries in a sorted vector instead, which avoids the
+/// pointer chasing of the tree for the handful of entries most maps hold.
+/// Once the map grows past its small capacity it is promoted to a tree. The
+/// promotion is one way: a tree is never converted back into a vector, even
+/// if entries are later removed.
+#[derive(Debug)]
+pub struct KeyValueMap<K: std::cmp::PartialOrd + std::cmp::PartialEq, V> {
+    count: usize,
+    // Never shared by two maps, so a cursor can tell it is handed the map it
+    // walked before. Maps are not Clone, which would break this.
+    id: u64,
+    // Bumped on every operation that may change, move or mutably borrow the
+    // entries, so cursors can tell their position went stale
+    generation: u64,
+    small_capacity: usize,
+    store: Store<K, V>,
+    observer: Option<Observer<K, V>>,
+}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq, V> KeyValueMap<K, V> {
+    pub fn new() -> KeyValueMap<K, V> {
+        KeyValueMap::with_small_capacity(DEFAULT_SMALL_CAPACITY)
+    }
+
+    /// Creates a map that is promoted to a tree once it holds more than
+    /// `small_capacity` entries. A capacity of zero always uses the tree.
+    pub fn with_small_capacity(small_capacity: usize) -> KeyValueMap<K, V> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+        KeyValueMap {
+            count: 0,
+            id: NEXT_ID.fetch_add(1, AtomicOrdering::Relaxed),
+            generation: 0,
+            small_capacity,
+            store: if small_capacity == 0 {
+                Store::Tree(None)
+            } else {
+                Store::Small(Vec::new())
+            },
+            observer: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    fn promote(&mut self) {
+        if let Store::Small(entries) = &mut self.store {
+            self.store = Store::Tree(build_tree(entries.drain(..)));
+        }
+    }
+
+    /// Registers a closure called synchronously with every change made to the
+    /// map, replacing any previous observer.
+    ///
+    /// Events are only emitted for operations that change the map, once the
+    /// change has been made. The observer is handed borrowed keys and values
+    /// while the map is mutably borrowed, so it cannot call back into the map;
+    /// reaching the map through shared ownership (a `RefCell` or `Mutex`) from
+    /// the observer panics or deadlocks.
+    ///
+    /// As the observer may hold state tied to one thread, such as an `Rc`, a
+    /// map is neither `Send` nor `Sync`. Use `ShardedKeyValueMap` to share
+    /// entries between threads.
+    pub fn set_observer(&mut self, observer: impl FnMut(MapEvent<&K, &V>) + 'static) {
+        self.observer = Some(Observer::new(observer));
+    }
+
+    pub fn remove_observer(&mut self) {
+        self.observer = None;
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> bool {
+        // Even an insert that fails relinks the nodes on its path
+        self.generation += 1;
+
+        if let Store::Small(entries) = &mut self.store {
+            match search(entries, &key) {
+                Ok(_) => return false,
+                Err(i) if entries.len() < self.small_capacity => {
+                    entries.insert(i, KeyValuePair { key, value: Some(value) });
+                    self.count += 1;
+
+                    if let Some(observer) = &mut self.observer {
+                        observer.notify(MapEvent::Inserted {
+                            key: &entries[i].key,
+                            value: entries[i].value.as_ref().unwrap(),
+                        });
+                    }
+                    return true;
+                }
+                Err(_) => self.promote(),
+            }
+        }
+
+        // Only an observer needs the new entry back. Its rank is the number of
+        // keys below it, which rebalancing leaves alone, so it is looked up by
+        // rank once linked.
+        let rank = self
+            .observer
+            .is_some()
+            .then(|| self.seek_path(Some(&key), &mut Vec::new()));
+
+        let root = match &mut self.store {
+            Store::Tree(root) => root,
+            Store::Small(_) => unreachable!(),
+        };
+
+        let leaf = TreeNode::leaf(KeyValuePair { key, value: Some(value) });
+        let (res, tree) = insert(root.take(), leaf);
+
+        *root = tree;
+        if !res {
+            return false;
+        }
+
+        self.count += 1;
+
+        if let Some(rank) = rank {
+            let mut observer = self.observer.take();
+            if let (Some(observer), Some((key, value))) = (&mut observer, self.select(rank)) {
+                observer.notify(MapEvent::Inserted { key, value });
+            }
+            self.observer = observer;
+        }
+
+        true
+    }
+
+    pub fn delete(&mut self, key: K) -> Option<KeyValuePair<K, V>> {
+        self.generation += 1;
+
+        let deleted = match &mut self.store {
+            Store::Small(entries) => match search(entries, &key) {
+                Ok(i) => Some(entries.remove(i)),
+                Err(_) => None,
+            },
+            Store::Tree(root) => {
+                let (tree, deleted) = delete(root.take(), &KeyValuePair { key, value: None });
+                *root = tree;
+                deleted.map(|node| node.value)
+            }
+        };
+
+        if let Some(pair) = &deleted {
+            self.count -= 1;
+
+            if let Some(observer) = &mut self.observer {
+                observer.notify(MapEvent::Removed {
+                    key: &pair.key,
+                    value: pair.value.as_ref().unwrap(),
+                });
+            }
+        }
+
+        deleted
+    }
+
+    /// Inserts `key`, replacing the value of an existing entry. Returns the old value.
+    pub fn replace(&mut self, key: K, value: V) -> Option<V> {
+        // The observer is taken out so it can be handed the replaced value in
+        // place, without looking the key up again
+        let mut observer = self.observer.take();
+        let replaced = match self.find_mut(&key) {
+            Some(existing) => {
+                let old = std::mem::replace(existing, value);
+                if let Some(observer) = &mut observer {
+                    observer.notify(MapEvent::Updated {
+                        key: &key,
+                        old: &old,
+                        new: existing,
+                    });
+                }
+                Ok(old)
+            }
+            None => Err(value),
+        };
+        self.observer = observer;
+
+        match replaced {
+            Ok(old) => Some(old),
+            Err(value) => {
+                self.insert(key, value);
+                None
+            }
+        }
+    }
+
+    // Takes every entry out of the map in ascending order, keeping the kind of store
+    fn take_entries(&mut self) -> Vec<KeyValuePair<K, V>> {
+        self.count = 0;
+        self.generation += 1;
+
+        match &mut self.store {
+            Store::Small(entries) => std::mem::take(entries),
+            Store::Tree(root) => {
+                let mut entries = Vec::new();
+                drain_tree(root.take(), &mut entries);
+                entries
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        let removed = self.take_entries();
+
+        if let Some(observer) = &mut self.observer {
+            for pair in &removed {
+                observer.notify(MapEvent::Removed {
+                    key: &pair.key,
+                    value: pair.value.as_ref().unwrap(),
+                });
+            }
+        }
+    }
+
+    /// Keeps only the entries for which `f` returns true.
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        let (kept, removed): (Vec<_>, Vec<_>) = self
+            .take_entries()
+            .into_iter()
+            .map(|mut pair| {
+                let keep = f(&pair.key, pair.value.as_mut().unwrap());
+                (pair, keep)
+            })
+            .partition(|(_, keep)| *keep);
+
+        self.count = kept.len();
+        let kept = kept.into_iter().map(|(pair, _)| pair);
+        match &mut self.store {
+            Store::Small(entries) => entries.extend(kept),
+            Store::Tree(root) => *root = build_tree(kept),
+        }
+
+        if let Some(observer) = &mut self.observer {
+            for (pair, _) in &removed {
+                observer.notify(MapEvent::Removed {
+                    key: &pair.key,
+                    value: pair.value.as_ref().unwrap(),
+                });
+            }
+        }
+    }
+
+    pub fn find(&self, key: &K) -> Option<KeyValuePair<&K, &V>> {
+        let mut cursor = match &self.store {
+            Store::Small(entries) => {
+                return search(entries, key).ok().map(|i| KeyValuePair {
+                    key: &entries[i].key,
+                    value: entries[i].value.as_ref(),
+                })
+            }
+            Store::Tree(root) => root,
+        };
+
+        loop {
+            match &cursor {
+                None => break None,
+                Some(t) => {
+                    if *key < t.value.key {
+                        cursor = &t.left;
+                    } else if *key > t.value.key {
+                        cursor = &t.right;
+                    } else if *key == t.value.key {
+                        break Some(KeyValuePair {
+                            key: &t.value.key,
+                            value: Some(t.value.value.as_ref().unwrap()),
+                        });
+                    } else {
+                        break None;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn find_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.generation += 1;
+
+        let mut cursor = match &mut self.store {
+            Store::Small(entries) => {
+                return match search(entries, key) {
+                    Ok(i) => entries[i].value.as_mut(),
+                    Err(_) => None,
+                }
+            }
+            Store::Tree(root) => root,
+        };
+
+        while let Some(t) = cursor {
+            if *key < t.value.key {
+                cursor = &mut t.left;
+            } else if *key > t.value.key {
+                cursor = &mut t.right;
+            } else {
+                return t.value.value.as_mut();
+            }
+        }
+
+        None
+    }
+
+    /// Finds the entry with the smallest key.
+    pub fn first(&self) -> Option<(&K, &V)> {
+        let mut t = match &self.store {
+            Store::Small(entries) => return entries.first().map(pair_ref),
+            Store::Tree(root) => root.as_ref()?,
+        };
+        while let Some(l) = &t.left {
+            t = l;
+        }
+
+        Some(pair_ref(&t.value))
+    }
+
+    /// Finds the entry with the greatest key less than or equal to `key`.
+    pub fn floor(&self, key: &K) -> Option<(&K, &V)> {
+        let mut cursor = match &self.store {
+            Store::Small(entries) => {
+                return match search(entries, key) {
+                    Ok(i) => Some(pair_ref(&entries[i])),
+                    Err(0) => None,
+                    Err(i) => Some(pair_ref(&entries[i - 1])),
+                }
+            }
+            Store::Tree(root) => root,
+        };
+        let mut best = None;
+
+        while let Some(t) = cursor {
+            if *key < t.value.key {
+                cursor = &t.left;
+            } else {
+                best = Some(t);
+                if t.value.key == *key {
+                    break;
+                }
+                cursor = &t.right;
+            }
+        }
+
+        best.map(|t| pair_ref(&t.value))
+    }
+
+    /// Finds the entry with the smallest key greater than or equal to `key`.
+    pub fn ceiling(&self, key: &K) -> Option<(&K, &V)> {
+        let mut cursor = match &self.store {
+            Store::Small(entries) => {
+                return match search(entries, key) {
+                    Ok(i) | Err(i) => entries.get(i).map(pair_ref),
+                }
+            }
+            Store::Tree(root) => root,
+        };
+        let mut best = None;
+
+        while let Some(t) = cursor {
+            if *key > t.value.key {
+                cursor = &t.right;
+            } else {
+                best = Some(t);
+                if t.value.key == *key {
+                    break;
+                }
+                cursor = &t.left;
+            }
+        }
+
+        best.map(|t| pair_ref(&t.value))
+    }
+
+    /// Finds the entry at position `rank` in ascending key order.
+    pub fn select(&self, rank: usize) -> Option<(&K, &V)> {
+        self.iter_from(rank).next()
+    }
+
+    /// Yields up to `limit` entries in ascending key order, starting from the
+    /// entry at position `offset`. Seeking to the offset is O(log n).
+    pub fn page(&self, offset: usize, limit: usize) -> std::iter::Take<Iter<'_, K, V>> {
+        self.iter_from(offset).take(limit)
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    // Positions an in-order walk at the first entry with a key strictly
+    // greater than `key`, or at the first entry without one, and returns the
+    // rank of that entry. For a tree, `path` is left holding the addresses of
+    // the nodes the walk has still to visit, nearest last.
+    pub(crate) fn seek_path(&self, key: Option<&K>, path: &mut Vec<usize>) -> usize {
+        path.clear();
+        let mut cursor = match &self.store {
+            Store::Small(entries) => {
+                return match key.map(|key| search(entries, key)) {
+                    None => 0,
+                    Some(Ok(i)) => i + 1,
+                    Some(Err(i)) => i,
+                };
+            }
+            Store::Tree(root) => root,
+        };
+        let mut rank = 0;
+
+        while let Some(t) = cursor {
+            if key.is_none_or(|key| t.value.key > *key) {
+                path.push(&**t as *const TreeNode<KeyValuePair<K, V>> as usize);
+                cursor = &t.left;
+            } else {
+                rank += size(&t.left) + 1;
+                cursor = &t.right;
+            }
+        }
+
+        rank
+    }
+
+    // Takes the entry at `rank` from a walk positioned by `seek_path`, moving
+    // `path` on past it. Nothing is allocated once `path` has grown to the
+    // height of the tree.
+    //
+    // SAFETY: `path` must have been filled by `seek_path` or this function
+    // on this same map, with `generation` unchanged since. Every operation
+    // that could free, move or mutably borrow a node bumps the generation, so
+    // the addresses are still those of live nodes that are only read.
+    pub(crate) unsafe fn next_on_path(
+        &self,
+        rank: usize,
+        path: &mut Vec<usize>,
+    ) -> Option<(&K, &V)> {
+        if let Store::Small(entries) = &self.store {
+            return entries.get(rank).map(pair_ref);
+        }
+
+        let t = &*(path.pop()? as *const TreeNode<KeyValuePair<K, V>>);
+        let mut cursor = &t.right;
+        while let Some(child) = cursor {
+            path.push(&**child as *const TreeNode<KeyValuePair<K, V>> as usize);
+            cursor = &child.left;
+        }
+
+        Some(pair_ref(&t.value))
+    }
+
+    // Builds an in-order iterator positioned at `rank`, using the subtree sizes
+    // to descend straight to it
+    fn iter_from(&self, mut rank: usize) -> Iter<'_, K, V> {
+        let mut iter = Iter {
+            small: [].iter(),
+            stack: Vec::new(),
+        };
+
+        let mut cursor = match &self.store {
+            Store::Small(entries) => {
+                iter.small = entries[rank.min(entries.len())..].iter();
+                return iter;
+            }
+            Store::Tree(root) => root,
+        };
+
+        while let Some(t) = cursor {
+            let left = size(&t.left);
+            if rank < left {
+                iter.stack.push(t);
+                cursor = &t.left;
+            } else if rank == left {
+                iter.stack.push(t);
+                break;
+            } else {
+                rank -= left + 1;
+                cursor = &t.right;
+            }
+        }
+
+        iter
+    }
+
+    /// Iterates over all entries in ascending key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        match &self.store {
+            Store::Small(entries) => Iter {
+                small: entries.iter(),
+                stack: Vec::new(),
+            },
+            Store::Tree(root) => {
+                let mut iter = Iter {
+                    small: [].iter(),
+                    stack: Vec::new(),
+                };
+                iter.push_left(root);
+                iter
+            }
+        }
+    }
+}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq + Debug, V> KeyValueMap<K, V> {
+    /// Renders the structure of the map as indented ASCII art, one pseudo-node per line.
+    ///
+    /// A right child on the same level as its parent is a horizontal link in
+    /// the AA tree, so it is drawn on its parent's line joined by `═`. A tree
+    /// holding the keys 1 to 6 renders as:
+    ///
+    /// ```text
+    /// 2 [lvl 2] ═ 4 [lvl 2]
+    /// ├─ 1 [lvl 1]
+    /// ├─ 3 [lvl 1]
+    /// └─ 5 [lvl 1] ═ 6 [lvl 1]
+    /// ```
+    ///
+    /// Maps still held in the small sorted vector are listed flat.
+    pub fn print_tree(&self) -> String {
+        let mut out = String::new();
+
+        match &self.store {
+            Store::Small(entries) if entries.is_empty() => out.push_str("(empty)\n"),
+            Store::Small(entries) => {
+                writeln!(out, "sorted vec [{} entries]", entries.len()).unwrap();
+                for (i, pair) in entries.iter().enumerate() {
+                    let connector = if i + 1 == entries.len() { "└─ " } else { "├─ " };
+                    writeln!(out, "{}{:?}", connector, pair.key).unwrap();
+                }
+            }
+            Store::Tree(None) => out.push_str("(empty)\n"),
+            Store::Tree(Some(root)) => print_node(root, "", "", "", &mut out),
+        }
+
+        out
+    }
+}
+
+fn print_node<K: std::cmp::PartialOrd + std::cmp::PartialEq + Debug, V>(
+    t: &TreeNode<KeyValuePair<K, V>>,
+    prefix: &str,
+    connector: &str,
+    child_prefix: &str,
+    out: &mut String,
+) {
+    write!(out, "{}{}{:?} [lvl {}]", prefix, connector, t.value.key, t.level).unwrap();
+
+    let mut children = vec![&t.left];
+    match &t.right {
+        Some(r) if r.level == t.level => {
+            write!(out, " ═ {:?} [lvl {}]", r.value.key, r.level).unwrap();
+            children.push(&r.left);
+            children.push(&r.right);
+        }
+        right => children.push(right),
+    }
+    out.push('\n');
+
+    let children: Vec<_> = children.into_iter().flatten().collect();
+    for (i, child) in children.iter().enumerate() {
+        let (connector, indent) = if i + 1 == children.len() {
+            ("└─ ", "   ")
+        } else {
+            ("├─ ", "│  ")
+        };
+        let prefix = format!("{}{}", prefix, child_prefix);
+        print_node(child, &prefix, connector, indent, out);
+    }
+}
+
+fn build_tree<T: std::cmp::PartialEq + std::cmp::PartialOrd>(
+    values: impl IntoIterator<Item = T>,
+) -> Option<Box<TreeNode<T>>> {
+    let mut root = None;
+    for value in values {
+        root = insert(root, TreeNode::leaf(value)).1;
+    }
+
+    root
+}
+
+fn drain_tree<T>(root: Option<Box<TreeNode<T>>>, out: &mut Vec<T>) {
+    if let Some(t) = root {
+        let TreeNode {
+            value, left, right, ..
+        } = *t;
+        drain_tree(left, out);
+        out.push(value);
+        drain_tree(right, out);
+    }
+}
+
+fn pair_ref<K: std::cmp::PartialOrd + std::cmp::PartialEq, V>(
+    pair: &KeyValuePair<K, V>,
+) -> (&K, &V) {
+    (&pair.key, pair.value.as_ref().unwrap())
+}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq, V> Default for KeyValueMap<K, V> {
+    fn default() -> KeyValueMap<K, V> {
+        KeyValueMap::new()
+    }
+}
+
+/// In-order iterator over the entries of a `KeyValueMap`.
+pub struct Iter<'a, K: std::cmp::PartialOrd + std::cmp::PartialEq, V> {
+    small: std::slice::Iter<'a, KeyValuePair<K, V>>,
+    stack: Vec<&'a TreeNode<KeyValuePair<K, V>>>,
+}
+
+impl<'a, K: std::cmp::PartialOrd + std::cmp::PartialEq, V> Iter<'a, K, V> {
+    fn push_left(&mut self, mut cursor: &'a Option<Box<TreeNode<KeyValuePair<K, V>>>>) {
+        while let Some(t) = cursor {
+            self.stack.push(t);
+            cursor = &t.left;
+        }
+    }
+}
+
+impl<'a, K: std::cmp::PartialOrd + std::cmp::PartialEq, V> std::iter::Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(pair) = self.small.next() {
+            return Some(pair_ref(pair));
+        }
+
+        let t = self.stack.pop()?;
+        self.push_left(&t.right);
+
+        Some(pair_ref(&t.value))
+    }
+}
@@ -0,0 +1,98 @@
+use crate::KeyValueMap;
+
+/// Fixed capacity cache evicting the least recently used entry.
+///
+/// Entries are stored against a recency stamp, and a second map orders the
+/// keys by stamp so the eviction candidate is always the first entry of that
+/// map. Every operation is O(log n).
+#[derive(Debug)]
+pub struct LruKeyValueCache<K: std::cmp::PartialOrd + std::cmp::PartialEq, V> {
+    capacity: usize,
+    next_stamp: u64,
+    entries: KeyValueMap<K, (V, u64)>,
+    recency: KeyValueMap<u64, K>,
+}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq + Clone, V> LruKeyValueCache<K, V> {
+    pub fn new(capacity: usize) -> LruKeyValueCache<K, V> {
+        LruKeyValueCache {
+            capacity,
+            next_stamp: 0,
+            entries: KeyValueMap::new(),
+            recency: KeyValueMap::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn stamp(&mut self) -> u64 {
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+        stamp
+    }
+
+    /// Moves an existing key to the most recently used position.
+    fn refresh(&mut self, key: &K) -> Option<&mut V> {
+        let old_stamp = self.entries.find(key)?.value?.1;
+        let stamp = self.stamp();
+
+        self.recency.delete(old_stamp);
+        self.recency.insert(stamp, key.clone());
+
+        let (value, entry_stamp) = self.entries.find_mut(key).unwrap();
+        *entry_stamp = stamp;
+        Some(value)
+    }
+
+    /// Looks up `key`, marking it as the most recently used entry.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.refresh(key).map(|value| &*value)
+    }
+
+    /// Inserts or updates `key`, marking it as the most recently used entry.
+    /// Returns the evicted entry when the insert pushed the cache over capacity.
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(existing) = self.refresh(&key) {
+            *existing = value;
+            return None;
+        }
+
+        if self.capacity == 0 {
+            return Some((key, value));
+        }
+
+        let stamp = self.stamp();
+        self.recency.insert(stamp, key.clone());
+        self.entries.insert(key, (value, stamp));
+
+        if self.entries.len() > self.capacity {
+            let oldest = *self.recency.first().unwrap().0;
+            let key = self.recency.delete(oldest).unwrap().value.unwrap();
+            let evicted = self.entries.delete(key).unwrap();
+            let (value, _) = evicted.value.unwrap();
+
+            Some((evicted.key, value))
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over the entries from least to most recently used. This does
+    /// not refresh any entry.
+    pub fn iter_by_recency(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.recency.iter().map(move |(_, key)| {
+            let pair = self.entries.find(key).unwrap();
+            (pair.key, &pair.value.unwrap().0)
+        })
+    }
+}
@@ -0,0 +1,59 @@
+use std::iter::Peekable;
+
+use crate::{Iter, KeyValueMap};
+
+/// A key present in the left map, the right map, or both.
+#[derive(Debug, PartialEq)]
+pub enum EitherOrBoth<A, B> {
+    Left(A),
+    Right(B),
+    Both(A, B),
+}
+
+/// Iterator walking two maps in lockstep. See `merge_join`.
+pub struct MergeJoin<'a, K: std::cmp::PartialOrd + std::cmp::PartialEq, V1, V2> {
+    left: Peekable<Iter<'a, K, V1>>,
+    right: Peekable<Iter<'a, K, V2>>,
+}
+
+/// Joins two maps on their keys, yielding every key from either map once in
+/// ascending order along with the values found for it on each side.
+pub fn merge_join<'a, K: std::cmp::PartialOrd + std::cmp::PartialEq, V1, V2>(
+    a: &'a KeyValueMap<K, V1>,
+    b: &'a KeyValueMap<K, V2>,
+) -> MergeJoin<'a, K, V1, V2> {
+    MergeJoin {
+        left: a.iter().peekable(),
+        right: b.iter().peekable(),
+    }
+}
+
+impl<'a, K: std::cmp::PartialOrd + std::cmp::PartialEq, V1, V2> std::iter::Iterator
+    for MergeJoin<'a, K, V1, V2>
+{
+    type Item = (&'a K, EitherOrBoth<&'a V1, &'a V2>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let take_left = match (self.left.peek(), self.right.peek()) {
+            (None, None) => return None,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (Some((l, _)), Some((r, _))) if l == r => {
+                let (key, l) = self.left.next().unwrap();
+                let (_, r) = self.right.next().unwrap();
+                return Some((key, EitherOrBoth::Both(l, r)));
+            }
+            (Some((l, _)), Some((r, _))) => l < r,
+        };
+
+        if take_left {
+            self.left
+                .next()
+                .map(|(key, l)| (key, EitherOrBoth::Left(l)))
+        } else {
+            self.right
+                .next()
+                .map(|(key, r)| (key, EitherOrBoth::Right(r)))
+        }
+    }
+}
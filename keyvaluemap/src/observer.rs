@@ -0,0 +1,27 @@
+/// Change made to a `KeyValueMap`, as reported to its observer.
+#[derive(Debug, PartialEq)]
+pub enum MapEvent<K, V> {
+    Inserted { key: K, value: V },
+    Updated { key: K, old: V, new: V },
+    Removed { key: K, value: V },
+}
+
+type ObserverFn<K, V> = Box<dyn FnMut(MapEvent<&K, &V>)>;
+
+pub(crate) struct Observer<K, V>(ObserverFn<K, V>);
+
+impl<K, V> Observer<K, V> {
+    pub(crate) fn new(observer: impl FnMut(MapEvent<&K, &V>) + 'static) -> Observer<K, V> {
+        Observer(Box::new(observer))
+    }
+
+    pub(crate) fn notify(&mut self, event: MapEvent<&K, &V>) {
+        (self.0)(event)
+    }
+}
+
+impl<K, V> std::fmt::Debug for Observer<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Observer")
+    }
+}
@@ -0,0 +1,96 @@
+use std::ops::Range;
+
+use crate::KeyValueMap;
+
+/// Reason a range could not be inserted into a `RangeMap`.
+#[derive(Debug)]
+pub enum RangeMapError {
+    /// The range ends where it starts, or before.
+    EmptyRange,
+    /// The range shares points with one already in the map.
+    Overlap,
+}
+
+impl std::fmt::Display for RangeMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RangeMapError::EmptyRange => f.write_str("range is empty"),
+            RangeMapError::Overlap => f.write_str("range overlaps one already in the map"),
+        }
+    }
+}
+
+impl std::error::Error for RangeMapError {}
+
+/// Maps non-overlapping half-open ranges `[start, end)` to values.
+///
+/// Ranges are keyed by their start, so a point lookup is a floor lookup on the
+/// start followed by a check against the end. Inserting a range that overlaps
+/// an existing one is rejected rather than splitting either range; adjacent
+/// ranges (one ending where the next starts) do not overlap.
+#[derive(Debug)]
+pub struct RangeMap<K: std::cmp::PartialOrd + std::cmp::PartialEq, V> {
+    ranges: KeyValueMap<K, (Range<K>, V)>,
+}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq + Clone, V> RangeMap<K, V> {
+    pub fn new() -> RangeMap<K, V> {
+        RangeMap {
+            ranges: KeyValueMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn insert(&mut self, range: Range<K>, value: V) -> Result<(), RangeMapError> {
+        if range.start >= range.end {
+            return Err(RangeMapError::EmptyRange);
+        }
+
+        // Only the ranges either side of the new start can overlap it
+        if let Some((_, (before, _))) = self.ranges.floor(&range.start) {
+            if before.end > range.start {
+                return Err(RangeMapError::Overlap);
+            }
+        }
+        if let Some((_, (after, _))) = self.ranges.ceiling(&range.start) {
+            if after.start < range.end {
+                return Err(RangeMapError::Overlap);
+            }
+        }
+
+        self.ranges.insert(range.start.clone(), (range, value));
+
+        Ok(())
+    }
+
+    /// Removes the range starting exactly at `start`.
+    pub fn remove(&mut self, start: K) -> Option<(Range<K>, V)> {
+        self.ranges.delete(start).and_then(|pair| pair.value)
+    }
+
+    /// Finds the range containing `point`.
+    pub fn get(&self, point: &K) -> Option<(&Range<K>, &V)> {
+        match self.ranges.floor(point) {
+            Some((_, (range, value))) if *point < range.end => Some((range, value)),
+            _ => None,
+        }
+    }
+
+    /// Iterates over all ranges in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Range<K>, &V)> {
+        self.ranges.iter().map(|(_, (range, value))| (range, value))
+    }
+}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq + Clone, V> Default for RangeMap<K, V> {
+    fn default() -> RangeMap<K, V> {
+        RangeMap::new()
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::RwLock;
+
+use crate::KeyValueMap;
+
+/// Thread-safe map that spreads its keys over several independently locked
+/// `KeyValueMap` shards, routed by the hash of the key.
+///
+/// Single-key operations only ever lock the shard owning the key. Ordered
+/// iteration across shards is snapshot-based: see `collect_sorted`.
+pub struct ShardedKeyValueMap<K: std::cmp::PartialOrd + std::cmp::PartialEq, V> {
+    shards: Vec<RwLock<Shard<K, V>>>,
+}
+
+// A map that never has an observer, which is the only part of a
+// `KeyValueMap` that may be tied to one thread
+struct Shard<K: std::cmp::PartialOrd + std::cmp::PartialEq, V>(KeyValueMap<K, V>);
+
+// SAFETY: shards are only reached through `ShardedKeyValueMap`, which never
+// sets an observer, so what is sent or shared is just keys, values and the
+// boxes of the tree holding them.
+unsafe impl<K: std::cmp::PartialOrd + std::cmp::PartialEq + Send, V: Send> Send for Shard<K, V> {}
+unsafe impl<K: std::cmp::PartialOrd + std::cmp::PartialEq + Sync, V: Sync> Sync for Shard<K, V> {}
+
+impl<K: std::cmp::PartialOrd + std::cmp::PartialEq + Hash, V> ShardedKeyValueMap<K, V> {
+    /// Creates a map with `shard_count` shards. At least one shard is always created.
+    pub fn new(shard_count: usize) -> ShardedKeyValueMap<K, V> {
+        ShardedKeyValueMap {
+            shards: (0..shard_count.max(1))
+                .map(|_| RwLock::new(Shard(KeyValueMap::new())))
+                .collect(),
+        }
+    }
+
+    fn shard(&self, key: &K) -> &RwLock<Shard<K, V>> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+
+        &self.shards[(hasher.finish() % self.shards.len() as u64) as usize]
+    }
+
+    pub fn insert(&self, key: K, value: V) -> bool {
+        self.shard(&key).write().unwrap().0.insert(key, value)
+    }
+
+    pub fn remove(&self, key: K) -> Option<V> {
+        self.shard(&key)
+            .write()
+            .unwrap()
+            .0
+            .delete(key)
+            .and_then(|pair| pair.value)
+    }
+
+    /// Returns a clone of the value stored for `key`.
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.shard(key)
+            .read()
+            .unwrap()
+            .0
+            .find(key)
+            .and_then(|pair| pair.value.cloned())
+    }
+
+    /// Runs `f` against the value stored for `key` while its shard is read locked.
+    pub fn get_with<R, F: FnOnce(&V) -> R>(&self, key: &K, f: F) -> Option<R> {
+        self.shard(key)
+            .read()
+            .unwrap()
+            .0
+            .find(key)
+            .and_then(|pair| pair.value.map(f))
+    }
+
+    /// Total number of entries. Shards are counted one at a time, so the
+    /// result may be stale if other threads are mutating the map.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|s| s.read().unwrap().0.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Collects every entry in ascending key order.
+    ///
+    /// Each shard is locked in turn just long enough to copy its entries, and
+    /// the per-shard snapshots are then merge-joined. The result is not an
+    /// atomic snapshot of the whole map: writes landing in a shard after it
+    /// has been copied are not reflected.
+    pub fn collect_sorted(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        // Snapshots are stored in descending order so the merge can pop from the back
+        let mut snapshots: Vec<Vec<(K, V)>> = self
+            .shards
+            .iter()
+            .map(|shard| {
+                let shard = shard.read().unwrap();
+                let mut entries: Vec<(K, V)> = shard
+                    .0
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                entries.reverse();
+                entries
+            })
+            .collect();
+
+        // Keys are unique across shards, so the merge never has to break ties
+        let mut result = Vec::with_capacity(snapshots.iter().map(Vec::len).sum());
+        loop {
+            let mut smallest: Option<usize> = None;
+            for (i, snapshot) in snapshots.iter().enumerate() {
+                if let Some((key, _)) = snapshot.last() {
+                    match smallest {
+                        Some(j) if snapshots[j].last().unwrap().0 < *key => {}
+                        _ => smallest = Some(i),
+                    }
+                }
+            }
+
+            match smallest {
+                Some(i) => result.push(snapshots[i].pop().unwrap()),
+                None => break result,
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+extern crate keyvaluemap;
+extern crate rand;
+
+use keyvaluemap::BiMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Every forward entry has a matching reverse entry and the other way round
+fn assert_consistent(map: &BiMap<u32, u32>) {
+    let by_key: Vec<(u32, u32)> = map.iter_by_key().map(|(&k, &v)| (k, v)).collect();
+    let mut by_value: Vec<(u32, u32)> = map.iter_by_value().map(|(&k, &v)| (k, v)).collect();
+
+    assert!(by_value.windows(2).all(|pair| pair[0].1 < pair[1].1));
+    by_value.sort();
+    assert_eq!(by_key, by_value);
+    assert_eq!(map.len(), by_key.len());
+
+    for &(key, value) in &by_key {
+        assert_eq!(map.get_by_key(&key), Some(&value));
+        assert_eq!(map.get_by_value(&value), Some(&key));
+    }
+}
+
+#[test]
+fn overwriting_removes_the_conflicting_pairs() {
+    let mut map = BiMap::new();
+    assert!(map.insert(1, 10).is_empty());
+    assert!(map.insert(2, 20).is_empty());
+    assert!(map.insert(1, 10).is_empty());
+
+    // Same key, new value
+    assert_eq!(map.insert(1, 11), vec![(1, 10)]);
+    assert_eq!(map.get_by_value(&10), None);
+    // New key, same value
+    assert_eq!(map.insert(3, 20), vec![(2, 20)]);
+    assert_eq!(map.get_by_key(&2), None);
+    // Key and value each already paired elsewhere
+    assert_eq!(map.insert(1, 20), vec![(1, 11), (3, 20)]);
+    assert_consistent(&map);
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn removal_from_either_side_clears_both() {
+    let mut map = BiMap::new();
+    for key in 0..5 {
+        map.insert(key, key * 100);
+    }
+
+    assert_eq!(map.remove_by_key(2), Some((2, 200)));
+    assert_eq!(map.get_by_value(&200), None);
+    assert_eq!(map.remove_by_value(300), Some((3, 300)));
+    assert_eq!(map.get_by_key(&3), None);
+    assert_eq!(map.remove_by_key(3), None);
+    assert_eq!(map.remove_by_value(200), None);
+    assert_consistent(&map);
+    assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn random_operations_keep_both_sides_consistent() {
+    let mut rng = StdRng::seed_from_u64(0xb1);
+    let mut map = BiMap::new();
+
+    for _ in 0..3_000 {
+        let (key, value) = (rng.gen_range(0..30), rng.gen_range(0..30));
+        match rng.gen_range(0..4) {
+            0 | 1 => {
+                let (old_value, old_key) = (
+                    map.get_by_key(&key).copied(),
+                    map.get_by_value(&value).copied(),
+                );
+                let removed = map.insert(key, value);
+                if old_value == Some(value) {
+                    assert!(removed.is_empty());
+                } else {
+                    let expected: Vec<(u32, u32)> = old_value
+                        .map(|old| (key, old))
+                        .into_iter()
+                        .chain(old_key.map(|old| (old, value)))
+                        .collect();
+                    assert_eq!(removed, expected);
+                }
+                assert_eq!(map.get_by_key(&key), Some(&value));
+            }
+            2 => {
+                let expected = map.get_by_key(&key).map(|&value| (key, value));
+                assert_eq!(map.remove_by_key(key), expected);
+            }
+            _ => {
+                let expected = map.get_by_value(&value).map(|&key| (key, value));
+                assert_eq!(map.remove_by_value(value), expected);
+            }
+        }
+        assert_consistent(&map);
+    }
+}
@@ -0,0 +1,138 @@
+extern crate keyvaluemap;
+extern crate rand;
+
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use keyvaluemap::{KeyValueMap, StableCursor};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn map_of(keys: &[u32], small_capacity: usize) -> KeyValueMap<u32, u32> {
+    let mut map = KeyValueMap::with_small_capacity(small_capacity);
+    for &key in keys {
+        map.insert(key, key * 10);
+    }
+    map
+}
+
+#[test]
+fn walks_an_unchanged_map_in_order() {
+    for &small_capacity in &[0, 16] {
+        let keys: Vec<u32> = (0..12).map(|i| i * 7 % 12).collect();
+        let map = map_of(&keys, small_capacity);
+
+        let mut cursor = StableCursor::new();
+        let mut walked = Vec::new();
+        while let Some((&key, &value)) = cursor.next(&map) {
+            walked.push((key, value));
+        }
+        assert_eq!(
+            walked,
+            map.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>()
+        );
+        assert_eq!(cursor.next(&map), None);
+    }
+}
+
+#[test]
+fn documented_semantics_hold_across_mutation() {
+    for &small_capacity in &[0, 16] {
+        let mut map = map_of(&[10, 20, 30, 40, 50], small_capacity);
+        let mut cursor = StableCursor::new();
+
+        assert_eq!(cursor.next(&map), Some((&10, &100)));
+        assert_eq!(cursor.next(&map), Some((&20, &200)));
+
+        // Behind the cursor, not visited
+        map.insert(5, 50);
+        map.insert(15, 150);
+        // Ahead of the cursor, visited
+        map.insert(25, 250);
+        // Removed before the cursor reaches it, skipped
+        map.delete(30);
+        // Yielded already, not yielded again
+        map.delete(20);
+        map.insert(20, 200);
+
+        let rest: Vec<u32> = std::iter::from_fn(|| cursor.next(&map).map(|(&k, _)| k)).collect();
+        assert_eq!(rest, vec![25, 40, 50]);
+
+        // Inserted past the end after the cursor finished, still visited
+        map.insert(60, 600);
+        assert_eq!(cursor.next(&map), Some((&60, &600)));
+        assert_eq!(cursor.next(&map), None);
+    }
+}
+
+#[test]
+fn value_changes_between_pulls_are_seen() {
+    let mut map = map_of(&(0..40).collect::<Vec<_>>(), 0);
+    let mut cursor = StableCursor::new();
+
+    assert_eq!(cursor.next(&map), Some((&0, &0)));
+    *map.find_mut(&1).unwrap() = 7;
+    map.replace(2, 9);
+    assert_eq!(cursor.next(&map), Some((&1, &7)));
+    assert_eq!(cursor.next(&map), Some((&2, &9)));
+}
+
+#[test]
+fn another_map_is_sought_by_the_last_key() {
+    let first = map_of(&[1, 2, 3, 4], 0);
+    let second = map_of(&[0, 2, 5], 0);
+    let mut cursor = StableCursor::new();
+
+    assert_eq!(cursor.next(&first), Some((&1, &10)));
+    assert_eq!(cursor.next(&first), Some((&2, &20)));
+    assert_eq!(cursor.next(&second), Some((&5, &50)));
+}
+
+// Every pull yields the smallest key greater than the last one yielded, as
+// found in a BTreeMap given the same random inserts and deletes in between
+#[test]
+fn random_mutation_matches_a_btree_oracle() {
+    let mut rng = StdRng::seed_from_u64(0xc0de);
+
+    for &small_capacity in &[0, 8, 64] {
+        let mut map = KeyValueMap::with_small_capacity(small_capacity);
+        let mut oracle = BTreeMap::new();
+        for _ in 0..200 {
+            let key = rng.gen_range(0..1000u32);
+            map.insert(key, key);
+            oracle.insert(key, key);
+        }
+
+        let mut cursor = StableCursor::new();
+        let mut last = None;
+        loop {
+            // Pull a few times in a row so the cached path is followed too
+            for _ in 0..rng.gen_range(1..4) {
+                let expected = match last {
+                    None => oracle.iter().next(),
+                    Some(last) => oracle
+                        .range((Bound::Excluded(last), Bound::Unbounded))
+                        .next(),
+                };
+                let found = cursor.next(&map);
+                assert_eq!(found, expected);
+                match found {
+                    Some((&key, _)) => last = Some(key),
+                    None => break,
+                }
+            }
+            if last.is_some_and(|last| oracle.range(last + 1..).next().is_none()) {
+                break;
+            }
+
+            for _ in 0..rng.gen_range(0..4) {
+                let key = rng.gen_range(0..1000u32);
+                if rng.gen_bool(0.5) {
+                    assert_eq!(map.insert(key, key), oracle.insert(key, key).is_none());
+                } else {
+                    assert_eq!(map.delete(key).is_some(), oracle.remove(&key).is_some());
+                }
+            }
+        }
+    }
+}
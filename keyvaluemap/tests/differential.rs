@@ -0,0 +1,129 @@
+extern crate keyvaluemap;
+extern crate rand;
+
+use std::collections::BTreeMap;
+
+use keyvaluemap::KeyValueMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Depth, level and whether it has a horizontal link, of each pseudo-node
+// drawn by print_tree
+fn pseudo_nodes(map: &KeyValueMap<u32, u32>) -> Vec<(usize, usize, bool)> {
+    map.print_tree()
+        .lines()
+        .map(|line| {
+            let text = line.trim_start_matches(|c| "│├└─ ".contains(c));
+            let depth = (line.chars().count() - text.chars().count()) / 3;
+            let levels: Vec<usize> = text
+                .split("[lvl ")
+                .skip(1)
+                .map(|rest| rest[..rest.find(']').unwrap()].parse().unwrap())
+                .collect();
+            assert!(
+                levels.len() == 1 || levels == [levels[0], levels[0]],
+                "{}",
+                line
+            );
+            (depth, levels[0], levels.len() == 2)
+        })
+        .collect()
+}
+
+// The AA tree rules, read off the drawing: a pseudo-node of level 1 has no
+// children, every other one has one more child than it has nodes, and each
+// child is exactly one level down
+fn assert_balanced(map: &KeyValueMap<u32, u32>) {
+    if map.is_empty() {
+        return;
+    }
+    let nodes = pseudo_nodes(map);
+
+    for (i, &(depth, level, horizontal)) in nodes.iter().enumerate() {
+        let children: Vec<usize> = nodes[i + 1..]
+            .iter()
+            .take_while(|&&(d, _, _)| d > depth)
+            .filter(|&&(d, _, _)| d == depth + 1)
+            .map(|&(_, level, _)| level)
+            .collect();
+
+        let expected = if level == 1 {
+            0
+        } else {
+            2 + horizontal as usize
+        };
+        assert_eq!(children.len(), expected, "{}", map.print_tree());
+        assert!(
+            children.iter().all(|&child| child + 1 == level),
+            "{}",
+            map.print_tree()
+        );
+    }
+
+    // A root of level L has at least 2^L - 1 nodes below it
+    assert!(1usize << nodes[0].1 <= map.len() + 1);
+}
+
+// Random inserts, deletes and replaces on the tree, checked against a
+// BTreeMap after every step, with the tree's balance checked as well
+#[test]
+fn random_operations_match_a_btree_map() {
+    for seed in 0..8 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let key_space = [16, 256, 4096][seed as usize % 3];
+        let mut map = KeyValueMap::with_small_capacity(0);
+        let mut oracle = BTreeMap::new();
+
+        for step in 0..3_000 {
+            let key = rng.gen_range(0..key_space);
+            let value = rng.gen::<u32>();
+            match rng.gen_range(0..6) {
+                0..=2 => {
+                    let vacant = !oracle.contains_key(&key);
+                    oracle.entry(key).or_insert(value);
+                    assert_eq!(map.insert(key, value), vacant, "step {}", step);
+                }
+                3 => assert_eq!(map.replace(key, value), oracle.insert(key, value)),
+                _ => {
+                    let deleted = map.delete(key).map(|pair| (pair.key, pair.value.unwrap()));
+                    assert_eq!(deleted, oracle.remove_entry(&key));
+                }
+            }
+
+            assert_eq!(map.len(), oracle.len());
+            if step % 50 == 0 {
+                assert!(map.iter().eq(oracle.iter()));
+                assert_balanced(&map);
+            }
+        }
+
+        assert!(map.iter().eq(oracle.iter()));
+        assert_balanced(&map);
+    }
+}
+
+// Sequential and reversed runs stress the rotations the early exit skips
+#[test]
+fn ordered_inserts_stay_balanced() {
+    for keys in [
+        (0..2_000).collect::<Vec<u32>>(),
+        (0..2_000).rev().collect(),
+        (0..2_000)
+            .map(|i| if i % 2 == 0 { i } else { 4_000 - i })
+            .collect(),
+    ] {
+        let mut map = KeyValueMap::with_small_capacity(0);
+        for (i, &key) in keys.iter().enumerate() {
+            assert!(map.insert(key, key));
+            assert!(!map.insert(key, key));
+            if i % 97 == 0 {
+                assert_balanced(&map);
+            }
+        }
+        assert_balanced(&map);
+
+        let mut sorted = keys.clone();
+        sorted.sort_unstable();
+        assert!(map.iter().map(|(&k, _)| k).eq(sorted));
+    }
+}
@@ -0,0 +1,107 @@
+extern crate keyvaluemap;
+extern crate rand;
+
+use std::collections::VecDeque;
+
+use keyvaluemap::LruKeyValueCache;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Known-good LRU: entries from least to most recently used, found by scanning
+struct Model {
+    capacity: usize,
+    entries: VecDeque<(u32, u32)>,
+}
+
+impl Model {
+    fn touch(&mut self, key: u32) -> Option<&mut (u32, u32)> {
+        let i = self.entries.iter().position(|&(k, _)| k == key)?;
+        let entry = self.entries.remove(i).unwrap();
+        self.entries.push_back(entry);
+        self.entries.back_mut()
+    }
+
+    fn get(&mut self, key: u32) -> Option<u32> {
+        self.touch(key).map(|&mut (_, value)| value)
+    }
+
+    fn put(&mut self, key: u32, value: u32) -> Option<(u32, u32)> {
+        if let Some(entry) = self.touch(key) {
+            entry.1 = value;
+            return None;
+        }
+
+        self.entries.push_back((key, value));
+        if self.entries.len() > self.capacity {
+            self.entries.pop_front()
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn hand_written_trace() {
+    let mut cache = LruKeyValueCache::new(2);
+    assert_eq!(cache.put(1, "a"), None);
+    assert_eq!(cache.put(2, "b"), None);
+    assert_eq!(cache.get(&1), Some(&"a"));
+    assert_eq!(cache.put(3, "c"), Some((2, "b")));
+    assert_eq!(cache.get(&2), None);
+    assert_eq!(cache.put(1, "A"), None);
+    assert_eq!(cache.put(4, "d"), Some((3, "c")));
+    assert_eq!(
+        cache.iter_by_recency().collect::<Vec<_>>(),
+        vec![(&1, &"A"), (&4, &"d")]
+    );
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn zero_capacity_keeps_nothing() {
+    let mut cache = LruKeyValueCache::new(0);
+    assert_eq!(cache.put(1, 10), Some((1, 10)));
+    assert_eq!(cache.get(&1), None);
+    assert!(cache.is_empty());
+}
+
+// Replays random traces over a small key space, so hits, misses, updates and
+// evictions all happen often, checking every result and the recency order
+#[test]
+fn random_traces_match_the_model() {
+    let mut rng = StdRng::seed_from_u64(0x1e0);
+
+    for &capacity in &[1, 2, 3, 5, 8] {
+        let mut cache = LruKeyValueCache::new(capacity);
+        let mut model = Model {
+            capacity,
+            entries: VecDeque::new(),
+        };
+
+        for step in 0..2_000 {
+            let key = rng.gen_range(0..12);
+            if rng.gen_bool(0.5) {
+                assert_eq!(
+                    cache.get(&key).copied(),
+                    model.get(key),
+                    "get at step {}",
+                    step
+                );
+            } else {
+                let value = rng.gen();
+                assert_eq!(
+                    cache.put(key, value),
+                    model.put(key, value),
+                    "put at step {}",
+                    step
+                );
+            }
+
+            assert_eq!(cache.len(), model.entries.len());
+            assert!(cache
+                .iter_by_recency()
+                .map(|(&k, &v)| (k, v))
+                .eq(model.entries.iter().copied()));
+        }
+    }
+}
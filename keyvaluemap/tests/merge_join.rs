@@ -0,0 +1,106 @@
+extern crate keyvaluemap;
+extern crate rand;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use keyvaluemap::{merge_join, EitherOrBoth, KeyValueMap};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn map_of(keys: &[u32], tag: char) -> KeyValueMap<u32, String> {
+    let mut map = KeyValueMap::new();
+    for &key in keys {
+        map.insert(key, format!("{}{}", tag, key));
+    }
+    map
+}
+
+// Join of the collected entries of both maps, looking each key of their union
+// up in the entries of either side
+fn reference(
+    left: &KeyValueMap<u32, String>,
+    right: &KeyValueMap<u32, String>,
+) -> Vec<(u32, EitherOrBoth<String, String>)> {
+    let left: BTreeMap<u32, String> = left.iter().map(|(&k, v)| (k, v.clone())).collect();
+    let right: BTreeMap<u32, String> = right.iter().map(|(&k, v)| (k, v.clone())).collect();
+    let keys: BTreeSet<u32> = left.keys().chain(right.keys()).copied().collect();
+    keys.into_iter()
+        .map(|key| {
+            let (l, r) = (left.get(&key).cloned(), right.get(&key).cloned());
+            let joined = match (l, r) {
+                (Some(l), Some(r)) => EitherOrBoth::Both(l, r),
+                (Some(l), None) => EitherOrBoth::Left(l),
+                (None, Some(r)) => EitherOrBoth::Right(r),
+                (None, None) => unreachable!(),
+            };
+            (key, joined)
+        })
+        .collect()
+}
+
+fn joined(
+    left: &KeyValueMap<u32, String>,
+    right: &KeyValueMap<u32, String>,
+) -> Vec<(u32, EitherOrBoth<String, String>)> {
+    merge_join(left, right)
+        .map(|(&key, joined)| {
+            let joined = match joined {
+                EitherOrBoth::Both(l, r) => EitherOrBoth::Both(l.clone(), r.clone()),
+                EitherOrBoth::Left(l) => EitherOrBoth::Left(l.clone()),
+                EitherOrBoth::Right(r) => EitherOrBoth::Right(r.clone()),
+            };
+            (key, joined)
+        })
+        .collect()
+}
+
+#[test]
+fn overlapping_keys_are_joined() {
+    let (left, right) = (map_of(&[1, 2, 4], 'l'), map_of(&[2, 3, 4, 5], 'r'));
+    assert_eq!(
+        joined(&left, &right),
+        vec![
+            (1, EitherOrBoth::Left("l1".to_string())),
+            (2, EitherOrBoth::Both("l2".to_string(), "r2".to_string())),
+            (3, EitherOrBoth::Right("r3".to_string())),
+            (4, EitherOrBoth::Both("l4".to_string(), "r4".to_string())),
+            (5, EitherOrBoth::Right("r5".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn disjoint_and_empty_sides() {
+    let cases: &[(&[u32], &[u32])] = &[
+        (&[1, 3, 5], &[2, 4, 6]),
+        (&[1, 2, 3], &[7, 8, 9]),
+        (&[7, 8, 9], &[1, 2, 3]),
+        (&[1, 2, 3], &[]),
+        (&[], &[1, 2, 3]),
+        (&[], &[]),
+    ];
+    for &(l, r) in cases {
+        let (left, right) = (map_of(l, 'l'), map_of(r, 'r'));
+        assert_eq!(
+            joined(&left, &right),
+            reference(&left, &right),
+            "{:?} {:?}",
+            l,
+            r
+        );
+    }
+}
+
+#[test]
+fn random_maps_match_the_reference() {
+    let mut rng = StdRng::seed_from_u64(0x301);
+    for _ in 0..200 {
+        let keys = |rng: &mut StdRng| -> Vec<u32> {
+            let len = rng.gen_range(0..40);
+            (0..len).map(|_| rng.gen_range(0..60)).collect()
+        };
+        let (l, r) = (keys(&mut rng), keys(&mut rng));
+        let (left, right) = (map_of(&l, 'l'), map_of(&r, 'r'));
+        assert_eq!(joined(&left, &right), reference(&left, &right));
+    }
+}
@@ -0,0 +1,154 @@
+extern crate keyvaluemap;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use keyvaluemap::{KeyValueMap, MapEvent};
+
+type Events = Rc<RefCell<Vec<MapEvent<u32, &'static str>>>>;
+
+// Map whose observer records owned copies of every event
+fn observed(small_capacity: usize) -> (KeyValueMap<u32, &'static str>, Events) {
+    let events = Events::default();
+    let recorded = Rc::clone(&events);
+
+    let mut map = KeyValueMap::with_small_capacity(small_capacity);
+    map.set_observer(move |event| {
+        let event = match event {
+            MapEvent::Inserted { key, value } => MapEvent::Inserted {
+                key: *key,
+                value: *value,
+            },
+            MapEvent::Updated { key, old, new } => MapEvent::Updated {
+                key: *key,
+                old: *old,
+                new: *new,
+            },
+            MapEvent::Removed { key, value } => MapEvent::Removed {
+                key: *key,
+                value: *value,
+            },
+        };
+        recorded.borrow_mut().push(event);
+    });
+
+    (map, events)
+}
+
+fn take(events: &Events) -> Vec<MapEvent<u32, &'static str>> {
+    events.take()
+}
+
+#[test]
+fn scripted_operations_emit_their_events_in_order() {
+    // Both the small store and the tree, and the promotion between them
+    for &small_capacity in &[0, 2, 16] {
+        let (mut map, events) = observed(small_capacity);
+
+        assert!(map.insert(1, "one"));
+        assert!(map.insert(3, "three"));
+        assert!(map.insert(2, "two"));
+        assert!(!map.insert(2, "deux"));
+        assert_eq!(map.replace(3, "trois"), Some("three"));
+        assert_eq!(map.replace(4, "four"), None);
+        assert!(map.delete(1).is_some());
+        assert!(map.delete(1).is_none());
+        assert!(map.delete(9).is_none());
+
+        assert_eq!(
+            take(&events),
+            vec![
+                MapEvent::Inserted {
+                    key: 1,
+                    value: "one"
+                },
+                MapEvent::Inserted {
+                    key: 3,
+                    value: "three"
+                },
+                MapEvent::Inserted {
+                    key: 2,
+                    value: "two"
+                },
+                MapEvent::Updated {
+                    key: 3,
+                    old: "three",
+                    new: "trois"
+                },
+                MapEvent::Inserted {
+                    key: 4,
+                    value: "four"
+                },
+                MapEvent::Removed {
+                    key: 1,
+                    value: "one"
+                },
+            ],
+            "small capacity {}",
+            small_capacity
+        );
+    }
+}
+
+#[test]
+fn tree_inserts_report_the_entry_they_linked() {
+    // Enough inserts in both orders that most of them rotate nodes
+    let (mut map, events) = observed(0);
+    let keys: Vec<u32> = (0..64).chain((100..164).rev()).collect();
+    for &key in &keys {
+        assert!(map.insert(key, "v"));
+    }
+
+    let inserted: Vec<u32> = take(&events)
+        .into_iter()
+        .map(|event| match event {
+            MapEvent::Inserted { key, value: "v" } => key,
+            event => panic!("unexpected {:?}", event),
+        })
+        .collect();
+    assert_eq!(inserted, keys);
+}
+
+#[test]
+fn bulk_removals_report_each_entry() {
+    let (mut map, events) = observed(0);
+    for (key, value) in [(1, "a"), (2, "b"), (3, "c"), (4, "d")] {
+        map.insert(key, value);
+    }
+    take(&events);
+
+    map.retain(|&key, _| key % 2 == 0);
+    assert_eq!(
+        take(&events),
+        vec![
+            MapEvent::Removed { key: 1, value: "a" },
+            MapEvent::Removed { key: 3, value: "c" },
+        ]
+    );
+
+    map.retain(|_, _| true);
+    assert!(take(&events).is_empty());
+
+    map.clear();
+    assert_eq!(
+        take(&events),
+        vec![
+            MapEvent::Removed { key: 2, value: "b" },
+            MapEvent::Removed { key: 4, value: "d" },
+        ]
+    );
+
+    map.clear();
+    assert!(take(&events).is_empty());
+}
+
+#[test]
+fn removed_observer_sees_nothing() {
+    let (mut map, events) = observed(16);
+    map.remove_observer();
+
+    map.insert(1, "one");
+    map.replace(1, "uno");
+    map.delete(1);
+    assert!(take(&events).is_empty());
+}
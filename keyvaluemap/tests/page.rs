@@ -0,0 +1,75 @@
+extern crate keyvaluemap;
+extern crate rand;
+
+use keyvaluemap::KeyValueMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn random_map(len: usize, small_capacity: usize, seed: u64) -> KeyValueMap<u64, u64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut map = KeyValueMap::with_small_capacity(small_capacity);
+    while map.len() < len {
+        let key: u64 = rng.gen();
+        map.insert(key, !key);
+    }
+    map
+}
+
+#[test]
+fn every_page_of_a_large_map_matches_skip_and_take() {
+    let map = random_map(10_000, 0, 0x9a6e);
+
+    // Page sizes that divide the length, that leave a short last page, and
+    // that cover the whole map in one page or more
+    for &limit in &[7, 100, 333, 1000, 9_999, 10_000, 10_001] {
+        let mut offset = 0;
+        while offset <= map.len() + limit {
+            let page: Vec<_> = map.page(offset, limit).collect();
+            let expected: Vec<_> = map.iter().skip(offset).take(limit).collect();
+            assert_eq!(page, expected, "offset {} limit {}", offset, limit);
+            offset += limit;
+        }
+    }
+}
+
+#[test]
+fn select_matches_the_nth_entry() {
+    let map = random_map(10_000, 0, 0x5e1e);
+    let entries: Vec<_> = map.iter().collect();
+
+    for (rank, &entry) in entries.iter().enumerate() {
+        assert_eq!(map.select(rank), Some(entry), "rank {}", rank);
+        assert_eq!(map.page(rank, 1).collect::<Vec<_>>(), vec![entry]);
+    }
+    assert_eq!(map.select(entries.len()), None);
+    assert_eq!(map.select(usize::MAX), None);
+}
+
+#[test]
+fn small_store_pages_match_skip_and_take() {
+    for len in 0..=16 {
+        let map = random_map(len, 16, len as u64);
+        for offset in 0..=len + 2 {
+            for limit in 0..=len + 2 {
+                assert_eq!(
+                    map.page(offset, limit).collect::<Vec<_>>(),
+                    map.iter().skip(offset).take(limit).collect::<Vec<_>>(),
+                    "len {} offset {} limit {}",
+                    len,
+                    offset,
+                    limit
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn empty_pages() {
+    let map = random_map(100, 0, 1);
+    assert_eq!(map.page(0, 0).count(), 0);
+    assert_eq!(map.page(50, 0).count(), 0);
+    assert_eq!(map.page(100, 10).count(), 0);
+    assert_eq!(map.page(usize::MAX, usize::MAX).count(), 0);
+    assert_eq!(map.page(90, usize::MAX).count(), 10);
+}
@@ -0,0 +1,47 @@
+extern crate keyvaluemap;
+
+use keyvaluemap::KeyValueMap;
+
+fn tree_of(keys: impl IntoIterator<Item = u32>, small_capacity: usize) -> KeyValueMap<u32, ()> {
+    let mut map = KeyValueMap::with_small_capacity(small_capacity);
+    for key in keys {
+        map.insert(key, ());
+    }
+    map
+}
+
+#[test]
+fn keys_one_to_six_render_as_documented() {
+    assert_eq!(
+        tree_of(1..=6, 0).print_tree(),
+        "2 [lvl 2] ═ 4 [lvl 2]\n\
+         ├─ 1 [lvl 1]\n\
+         ├─ 3 [lvl 1]\n\
+         └─ 5 [lvl 1] ═ 6 [lvl 1]\n"
+    );
+}
+
+#[test]
+fn deeper_trees_indent_their_children() {
+    assert_eq!(
+        tree_of(1..=10, 0).print_tree(),
+        "4 [lvl 3]\n\
+         ├─ 2 [lvl 2]\n\
+         │  ├─ 1 [lvl 1]\n\
+         │  └─ 3 [lvl 1]\n\
+         └─ 6 [lvl 2] ═ 8 [lvl 2]\n   \
+            ├─ 5 [lvl 1]\n   \
+            ├─ 7 [lvl 1]\n   \
+            └─ 9 [lvl 1] ═ 10 [lvl 1]\n"
+    );
+}
+
+#[test]
+fn small_and_empty_maps() {
+    assert_eq!(
+        tree_of(vec![3, 1, 2], 16).print_tree(),
+        "sorted vec [3 entries]\n├─ 1\n├─ 2\n└─ 3\n"
+    );
+    assert_eq!(tree_of(None, 16).print_tree(), "(empty)\n");
+    assert_eq!(tree_of(None, 0).print_tree(), "(empty)\n");
+}
@@ -0,0 +1,94 @@
+extern crate keyvaluemap;
+
+use keyvaluemap::{RangeMap, RangeMapError};
+
+// Owners of a few address blocks, with a gap between 30 and 40
+fn owners() -> RangeMap<u32, &'static str> {
+    let mut map = RangeMap::new();
+    map.insert(10..20, "alice").unwrap();
+    map.insert(20..30, "bob").unwrap();
+    map.insert(40..50, "carol").unwrap();
+    map
+}
+
+#[test]
+fn adjacent_ranges_do_not_overlap() {
+    let map = owners();
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get(&19), Some((&(10..20), &"alice")));
+    assert_eq!(map.get(&20), Some((&(20..30), &"bob")));
+}
+
+#[test]
+fn start_is_inclusive_and_end_exclusive() {
+    let map = owners();
+    assert_eq!(map.get(&10), Some((&(10..20), &"alice")));
+    assert_eq!(map.get(&9), None);
+    assert_eq!(map.get(&29), Some((&(20..30), &"bob")));
+    assert_eq!(map.get(&30), None);
+    assert_eq!(map.get(&49), Some((&(40..50), &"carol")));
+    assert_eq!(map.get(&50), None);
+}
+
+#[test]
+fn points_in_gaps_find_nothing() {
+    let map = owners();
+    for point in (0..10).chain(30..40).chain(50..60) {
+        assert_eq!(map.get(&point), None, "point {}", point);
+    }
+}
+
+#[test]
+fn overlapping_ranges_are_rejected() {
+    let mut map = owners();
+    for range in [15..25, 5..11, 29..31, 35..45, 12..18, 0..100, 40..50] {
+        assert!(
+            matches!(
+                map.insert(range.clone(), "mallory"),
+                Err(RangeMapError::Overlap)
+            ),
+            "range {:?}",
+            range
+        );
+    }
+    assert!(matches!(
+        map.insert(35..35, "empty"),
+        Err(RangeMapError::EmptyRange)
+    ));
+    assert!(matches!(
+        map.insert(std::ops::Range { start: 36, end: 35 }, "reversed"),
+        Err(RangeMapError::EmptyRange)
+    ));
+
+    // Usable with `?` into a boxed error
+    let boxed: Box<dyn std::error::Error> = map.insert(12..14, "mallory").unwrap_err().into();
+    assert_eq!(boxed.to_string(), "range overlaps one already in the map");
+    assert_eq!(RangeMapError::EmptyRange.to_string(), "range is empty");
+
+    // Nothing was changed by the rejected inserts
+    let ranges: Vec<_> = map
+        .iter()
+        .map(|(range, &owner)| (range.clone(), owner))
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![(10..20, "alice"), (20..30, "bob"), (40..50, "carol")]
+    );
+
+    // The gap can still be filled exactly
+    map.insert(30..40, "dave").unwrap();
+    assert_eq!(map.get(&30), Some((&(30..40), &"dave")));
+    assert_eq!(map.get(&39), Some((&(30..40), &"dave")));
+}
+
+#[test]
+fn removed_ranges_free_their_points() {
+    let mut map = owners();
+    assert_eq!(map.remove(25), None);
+    assert_eq!(map.remove(20), Some((20..30, "bob")));
+    assert_eq!(map.get(&25), None);
+    map.insert(15..25, "erin").unwrap_err();
+    map.insert(20..25, "erin").unwrap();
+    assert_eq!(map.get(&24), Some((&(20..25), &"erin")));
+    assert_eq!(map.get(&25), None);
+}
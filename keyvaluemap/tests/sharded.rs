@@ -0,0 +1,83 @@
+extern crate keyvaluemap;
+extern crate rand;
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::thread;
+
+use keyvaluemap::ShardedKeyValueMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const THREADS: u64 = 8;
+const OPERATIONS: usize = 5_000;
+
+// Eight threads insert, remove and read keys of their own residue mod 8, so
+// every key's operations happen in one thread's order. Each operation is
+// replayed on a locked BTreeMap as it is made and must give the same result.
+#[test]
+fn mixed_operations_from_eight_threads_match_an_oracle() {
+    let map = ShardedKeyValueMap::new(4);
+    let oracle = Mutex::new(BTreeMap::new());
+
+    thread::scope(|scope| {
+        for thread in 0..THREADS {
+            let (map, oracle) = (&map, &oracle);
+            scope.spawn(move || {
+                let mut rng = StdRng::seed_from_u64(thread);
+                for _ in 0..OPERATIONS {
+                    let key = rng.gen_range(0..200u64) * THREADS + thread;
+                    match rng.gen_range(0..4) {
+                        0 | 1 => {
+                            let value = rng.gen::<u32>();
+                            let expected = match oracle.lock().unwrap().entry(key) {
+                                Entry::Vacant(entry) => {
+                                    entry.insert(value);
+                                    true
+                                }
+                                Entry::Occupied(_) => false,
+                            };
+                            assert_eq!(map.insert(key, value), expected);
+                        }
+                        2 => {
+                            let expected = oracle.lock().unwrap().remove(&key);
+                            assert_eq!(map.remove(key), expected);
+                        }
+                        _ => {
+                            let expected = oracle.lock().unwrap().get(&key).copied();
+                            assert_eq!(map.get(&key), expected);
+                            assert_eq!(
+                                map.get_with(&key, |value| value / 2),
+                                expected.map(|v| v / 2)
+                            );
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    let oracle = oracle.into_inner().unwrap();
+    assert_eq!(map.len(), oracle.len());
+    assert_eq!(map.collect_sorted(), oracle.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn collect_sorted_merges_every_shard() {
+    for &shards in &[0, 1, 3, 16] {
+        let map = ShardedKeyValueMap::new(shards);
+        assert!(map.is_empty());
+        for key in (0..100u32).rev() {
+            assert!(map.insert(key * 3 % 100, key));
+        }
+        assert!(!map.insert(5, 0));
+
+        let sorted = map.collect_sorted();
+        assert_eq!(map.len(), 100);
+        assert_eq!(
+            sorted.iter().map(|&(k, _)| k).collect::<Vec<_>>(),
+            (0..100).collect::<Vec<_>>()
+        );
+    }
+}
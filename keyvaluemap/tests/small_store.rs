@@ -0,0 +1,115 @@
+extern crate keyvaluemap;
+extern crate rand;
+
+use std::collections::BTreeMap;
+
+use keyvaluemap::KeyValueMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn is_promoted(map: &KeyValueMap<u32, u32>) -> bool {
+    !map.is_empty() && !map.print_tree().starts_with("sorted vec")
+}
+
+// Every read of the map agrees with the BTreeMap holding the same entries
+fn assert_matches(map: &KeyValueMap<u32, u32>, oracle: &BTreeMap<u32, u32>) {
+    assert_eq!(map.len(), oracle.len());
+    assert!(map.iter().eq(oracle.iter()));
+    assert_eq!(map.first(), oracle.iter().next());
+
+    for key in 0..=40 {
+        let found = map.find(&key).map(|pair| (pair.key, pair.value.unwrap()));
+        assert_eq!(found, oracle.get_key_value(&key));
+        assert_eq!(map.floor(&key), oracle.range(..=key).next_back());
+        assert_eq!(map.ceiling(&key), oracle.range(key..).next());
+    }
+    for rank in 0..=oracle.len() {
+        assert_eq!(map.select(rank), oracle.iter().nth(rank));
+        assert!(map.page(rank, 3).eq(oracle.iter().skip(rank).take(3)));
+    }
+}
+
+// Grows maps one entry at a time past their small capacity, so every read is
+// checked on both sides of the promotion
+#[test]
+fn reads_agree_on_both_sides_of_promotion() {
+    for small_capacity in 0..=6 {
+        let mut rng = StdRng::seed_from_u64(small_capacity as u64);
+        let mut map = KeyValueMap::with_small_capacity(small_capacity);
+        let mut oracle = BTreeMap::new();
+        let mut promoted_at = None;
+
+        while oracle.len() < small_capacity + 4 {
+            let key = rng.gen_range(0..40);
+            assert_eq!(
+                map.insert(key, key * 2),
+                oracle.insert(key, key * 2).is_none()
+            );
+            assert_matches(&map, &oracle);
+
+            if promoted_at.is_none() && is_promoted(&map) {
+                promoted_at = Some(map.len());
+            }
+        }
+
+        assert_eq!(
+            promoted_at,
+            Some(small_capacity + 1),
+            "small capacity {}",
+            small_capacity
+        );
+    }
+}
+
+#[test]
+fn promotion_is_one_way() {
+    let mut map = KeyValueMap::with_small_capacity(4);
+    for key in 0..5 {
+        map.insert(key, key);
+    }
+    assert!(is_promoted(&map));
+
+    for key in 0..4 {
+        map.delete(key);
+    }
+    assert!(is_promoted(&map));
+    map.insert(9, 9);
+    assert!(is_promoted(&map));
+    assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&4, &4), (&9, &9)]);
+}
+
+// Random inserts, replaces, deletes and retains around the promotion point
+#[test]
+fn random_operations_across_the_boundary() {
+    let mut rng = StdRng::seed_from_u64(0x5a11);
+
+    for &small_capacity in &[1, 4, 16] {
+        let mut map = KeyValueMap::with_small_capacity(small_capacity);
+        let mut oracle = BTreeMap::new();
+
+        for _ in 0..1_000 {
+            let key = rng.gen_range(0..40);
+            match rng.gen_range(0..10) {
+                0..=3 => {
+                    let vacant = !oracle.contains_key(&key);
+                    oracle.entry(key).or_insert(key);
+                    assert_eq!(map.insert(key, key), vacant);
+                }
+                4 | 5 => {
+                    let value = rng.gen_range(0..1000);
+                    assert_eq!(map.replace(key, value), oracle.insert(key, value));
+                }
+                6..=8 => assert_eq!(
+                    map.delete(key).and_then(|pair| pair.value),
+                    oracle.remove(&key)
+                ),
+                _ => {
+                    let bound = rng.gen_range(0..40);
+                    map.retain(|&key, _| key < bound);
+                    oracle.retain(|&key, _| key < bound);
+                }
+            }
+            assert_matches(&map, &oracle);
+        }
+    }
+}